@@ -1,5 +1,6 @@
+use crate::events::{self, SeriesIssuedEvent, SettlementFundedEvent};
 use crate::storage::Storage;
-use crate::types::{Series, SeriesStatus, SCALE};
+use crate::types::{Series, SeriesStatus, SettlementProgress, SCALE};
 use crate::validation::Validator;
 use soroban_sdk::{Address, Env};
 
@@ -61,9 +62,41 @@ impl Admin {
         // Increment next ID
         Storage::increment_series_id(env);
 
+        events::publish(
+            env,
+            "series_issued",
+            series_id,
+            SeriesIssuedEvent {
+                series_id,
+                par_value,
+                subscription_price,
+                maturity_time,
+                max_cap,
+            },
+        );
+
         series_id
     }
 
+    /// Raise caps on an open series (max_cap only upward)
+    pub fn update_series_caps(
+        env: &Env,
+        series_id: u32,
+        new_max_cap: i128,
+        new_per_user_cap: i128,
+    ) {
+        let admin = Storage::get_admin(env);
+        admin.require_auth();
+
+        let mut series = Storage::get_series(env, series_id);
+
+        Validator::validate_cap_update(env, &series, new_max_cap, new_per_user_cap);
+
+        series.max_cap = new_max_cap;
+        series.per_user_cap = new_per_user_cap;
+        Storage::set_series(env, &series);
+    }
+
     /// Verify user KYC
     pub fn verify_kyc(env: &Env, user: &Address) {
         let admin = Storage::get_admin(env);
@@ -80,7 +113,10 @@ impl Admin {
         Storage::set_kyc_verified(env, user, false);
     }
 
-    /// Settle matured series (deposit USDC for redemptions)
+    /// Fund a matured series' settlement (deposit USDC for redemptions)
+    ///
+    /// May be called repeatedly; the series is marked Settled once the
+    /// accumulated deposits reach the amount required to pay par.
     pub fn settle_series(env: &Env, series_id: u32, usdc_amount: i128, admin: &Address) {
         admin.require_auth();
 
@@ -91,18 +127,49 @@ impl Admin {
 
         let mut series = Storage::get_series(env, series_id);
 
-        // Calculate required amount
-        let required = (series.total_subscribed * series.par_value) / SCALE;
-
         // Validate
-        Validator::validate_settlement(env, &series, usdc_amount, required);
+        Validator::validate_settlement(env, &series, usdc_amount);
 
         // Transfer USDC from admin to contract
         let usdc_client = soroban_sdk::token::Client::new(env, &series.usdc_token);
         usdc_client.transfer(admin, &env.current_contract_address(), &usdc_amount);
 
-        // Update status
-        series.status = SeriesStatus::Settled;
-        Storage::set_series(env, &series);
+        // Accumulate toward the required amount
+        let funded = Storage::get_settlement_funded(env, series_id) + usdc_amount;
+        Storage::set_settlement_funded(env, series_id, funded);
+
+        // Update status once fully funded
+        let settled = funded >= Self::required_settlement(&series);
+        if settled {
+            series.status = SeriesStatus::Settled;
+            Storage::set_series(env, &series);
+        }
+
+        events::publish(
+            env,
+            "settlement_funded",
+            series_id,
+            SettlementFundedEvent {
+                series_id,
+                usdc_amount,
+                funded,
+                settled,
+            },
+        );
+    }
+
+    /// Get settlement funding progress for a series
+    pub fn get_settlement_progress(env: &Env, series_id: u32) -> SettlementProgress {
+        let series = Storage::get_series(env, series_id);
+
+        SettlementProgress {
+            funded: Storage::get_settlement_funded(env, series_id),
+            required: Self::required_settlement(&series),
+        }
+    }
+
+    /// USDC needed to redeem every subscribed share at par
+    fn required_settlement(series: &Series) -> i128 {
+        (series.total_subscribed * series.par_value) / SCALE
     }
 }
@@ -0,0 +1,57 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, IntoVal, Symbol, Val};
+
+// Same topic convention as contracts/bingo_events (schema v1). This crate
+// pins soroban-sdk 21, so it mirrors the constants instead of depending on it.
+const PROTOCOL: Symbol = symbol_short!("bingo");
+const SCHEMA_VERSION: u32 = 1;
+const SERIES: Symbol = symbol_short!("series");
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SeriesIssuedEvent {
+    pub series_id: u32,
+    pub par_value: i128,
+    pub subscription_price: i128,
+    pub maturity_time: u64,
+    pub max_cap: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SubscribedEvent {
+    pub series_id: u32,
+    pub user: Address,
+    pub usdc_amount: i128,
+    pub shares: i128,
+    pub index: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RedeemedEvent {
+    pub series_id: u32,
+    pub user: Address,
+    pub shares: i128,
+    pub redemption_value: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SettlementFundedEvent {
+    pub series_id: u32,
+    pub usdc_amount: i128,
+    pub funded: i128,
+    pub settled: bool,
+}
+
+/// Publish `("bingo", "series", event, series_id)` with `(SCHEMA_VERSION, payload)`
+pub fn publish<D>(env: &Env, event: &str, series_id: u32, payload: D)
+where
+    D: IntoVal<Env, Val>,
+{
+    let payload: Val = payload.into_val(env);
+    env.events().publish(
+        (PROTOCOL, SERIES, Symbol::new(env, event), series_id),
+        (SCHEMA_VERSION, payload),
+    );
+}
@@ -1,6 +1,7 @@
 #![no_std]
 
 mod admin;
+mod events;
 mod storage;
 mod types;
 mod user_ops;
@@ -9,10 +10,11 @@ mod yield_calc;
 
 use admin::Admin;
 use storage::Storage;
-use types::{Series, UserPosition, SCALE};
+use types::{Series, SettlementProgress, SubscriptionLot, UserPosition, SCALE};
 use user_ops::UserOps;
+use yield_calc::YieldCalculator;
 
-use soroban_sdk::{contract, contractimpl, Address, Env};
+use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
 
 #[contract]
 pub struct BingoSeries;
@@ -49,6 +51,11 @@ impl BingoSeries {
         )
     }
 
+    /// Update caps of an open series (Admin only, max_cap never decreases)
+    pub fn update_series_caps(env: Env, series_id: u32, new_max_cap: i128, new_per_user_cap: i128) {
+        Admin::update_series_caps(&env, series_id, new_max_cap, new_per_user_cap);
+    }
+
     /// Verify user KYC (Admin only)
     pub fn verify_kyc(env: Env, user: Address) {
         Admin::verify_kyc(&env, &user);
@@ -59,7 +66,7 @@ impl BingoSeries {
         Admin::revoke_kyc(&env, &user);
     }
 
-    /// Settle matured series (Admin only)
+    /// Fund settlement of a matured series, possibly in several calls (Admin only)
     pub fn settle_series(env: Env, series_id: u32, usdc_amount: i128, admin: Address) {
         Admin::settle_series(&env, series_id, usdc_amount, &admin);
     }
@@ -92,6 +99,18 @@ impl BingoSeries {
         Storage::get_series(&env, series_id)
     }
 
+    /// Get implied annualized yield (scaled by 1e7, SCALE = 100%)
+    pub fn get_series_yield(env: Env, series_id: u32) -> i128 {
+        let series = Storage::get_series(&env, series_id);
+        YieldCalculator::calculate_annualized_yield(&series)
+    }
+
+    /// Get seconds remaining until maturity (0 once matured)
+    pub fn time_to_maturity(env: Env, series_id: u32) -> u64 {
+        let series = Storage::get_series(&env, series_id);
+        YieldCalculator::calculate_time_to_maturity(&env, &series)
+    }
+
     /// Get user position
     pub fn get_user_position(env: Env, series_id: u32, user: Address) -> UserPosition {
         Storage::get_user_position(&env, series_id, &user).unwrap_or(UserPosition {
@@ -100,6 +119,16 @@ impl BingoSeries {
         })
     }
 
+    /// Get user's subscription lots, oldest first
+    pub fn get_lots(env: Env, series_id: u32, user: Address) -> Vec<SubscriptionLot> {
+        Storage::get_user_lots(&env, series_id, &user)
+    }
+
+    /// Get USDC funded toward settlement vs. required
+    pub fn get_settlement_progress(env: Env, series_id: u32) -> SettlementProgress {
+        Admin::get_settlement_progress(&env, series_id)
+    }
+
     /// Check if user is KYC verified
     pub fn is_kyc_verified(env: Env, user: Address) -> bool {
         Storage::is_kyc_verified(&env, &user)
@@ -110,6 +139,8 @@ impl BingoSeries {
 mod test {
     use super::*;
     use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::token::StellarAssetClient;
+    use types::SeriesStatus;
     use soroban_sdk::Env;
 
     #[test]
@@ -158,6 +189,155 @@ mod test {
         assert_eq!(position.shares, 0);
     }
 
+    #[test]
+    fn test_subscription_lots_recorded_per_subscribe() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BingoSeries);
+        let client = BingoSeriesClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let usdc_token = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        StellarAssetClient::new(&env, &usdc_token).mint(&user, &1_000_000);
+
+        client.initialize(&admin);
+        client.verify_kyc(&user);
+
+        let maturity = env.ledger().timestamp() + 1000;
+        let series_id = client.issue_series(
+            &1_000_000,
+            &980_000,
+            &maturity,
+            &100_000_000,
+            &10_000_000,
+            &usdc_token,
+        );
+
+        let first_shares = client.subscribe(&series_id, &98_000, &user);
+        env.ledger().with_mut(|li| li.timestamp += 500);
+        let second_shares = client.subscribe(&series_id, &99_000, &user);
+
+        let lots = client.get_lots(&series_id, &user);
+        assert_eq!(lots.len(), 2);
+
+        let first = lots.get(0).unwrap();
+        let second = lots.get(1).unwrap();
+        assert_eq!(first.usdc_amount, 98_000);
+        assert_eq!(first.shares, first_shares);
+        assert_eq!(first.index, 980_000);
+        assert_eq!(second.shares, second_shares);
+        assert_eq!(second.index, 990_000);
+        assert!(second.timestamp > first.timestamp);
+
+        // Position still aggregates all lots
+        let position = client.get_user_position(&series_id, &user);
+        assert_eq!(position.shares, first_shares + second_shares);
+        assert_eq!(position.entry_index, first.index);
+    }
+
+    #[test]
+    fn test_update_series_caps() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BingoSeries);
+        let client = BingoSeriesClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let usdc_token = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        let series_id = client.issue_series(
+            &1_000_000,
+            &980_000,
+            &(env.ledger().timestamp() + 1000),
+            &10_000_000,
+            &1_000_000,
+            &usdc_token,
+        );
+
+        client.update_series_caps(&series_id, &20_000_000, &500_000);
+
+        let series = client.get_series(&series_id);
+        assert_eq!(series.max_cap, 20_000_000);
+        assert_eq!(series.per_user_cap, 500_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Max cap can only increase")]
+    fn test_update_series_caps_rejects_lower_max_cap() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BingoSeries);
+        let client = BingoSeriesClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let usdc_token = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        let series_id = client.issue_series(
+            &1_000_000,
+            &980_000,
+            &(env.ledger().timestamp() + 1000),
+            &10_000_000,
+            &1_000_000,
+            &usdc_token,
+        );
+
+        client.update_series_caps(&series_id, &9_000_000, &1_000_000); // Should panic
+    }
+
+    #[test]
+    fn test_partial_settlements_accumulate() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, BingoSeries);
+        let client = BingoSeriesClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let usdc_token = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let usdc_admin = StellarAssetClient::new(&env, &usdc_token);
+        usdc_admin.mint(&user, &1_000_000);
+        usdc_admin.mint(&admin, &1_000_000);
+
+        client.initialize(&admin);
+        client.verify_kyc(&user);
+
+        let maturity = env.ledger().timestamp() + 1000;
+        let series_id = client.issue_series(
+            &SCALE,
+            &(98 * SCALE / 100),
+            &maturity,
+            &100_000_000,
+            &10_000_000,
+            &usdc_token,
+        );
+        client.subscribe(&series_id, &98_000, &user); // 100_000 shares
+
+        env.ledger().with_mut(|li| li.timestamp = maturity);
+
+        client.settle_series(&series_id, &60_000, &admin);
+        let progress = client.get_settlement_progress(&series_id);
+        assert_eq!(progress.funded, 60_000);
+        assert_eq!(progress.required, 100_000);
+        assert_eq!(client.get_series(&series_id).status, SeriesStatus::Active);
+
+        client.settle_series(&series_id, &40_000, &admin);
+        assert_eq!(client.get_settlement_progress(&series_id).funded, 100_000);
+        assert_eq!(client.get_series(&series_id).status, SeriesStatus::Settled);
+    }
+
     #[test]
     fn test_kyc_enforcement() {
         let env = Env::default();
@@ -1,5 +1,5 @@
-use crate::types::{DataKey, Series, UserPosition};
-use soroban_sdk::{Address, Env};
+use crate::types::{DataKey, Series, SubscriptionLot, UserPosition};
+use soroban_sdk::{Address, Env, Vec};
 
 pub struct Storage;
 
@@ -74,6 +74,42 @@ impl Storage {
             .remove(&DataKey::UserPosition(series_id, user.clone()));
     }
 
+    // Subscription lots
+    pub fn get_user_lots(env: &Env, series_id: u32, user: &Address) -> Vec<SubscriptionLot> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserLots(series_id, user.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    pub fn add_user_lot(env: &Env, series_id: u32, user: &Address, lot: &SubscriptionLot) {
+        let mut lots = Self::get_user_lots(env, series_id, user);
+        lots.push_back(lot.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::UserLots(series_id, user.clone()), &lots);
+    }
+
+    pub fn remove_user_lots(env: &Env, series_id: u32, user: &Address) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::UserLots(series_id, user.clone()));
+    }
+
+    // Settlement
+    pub fn get_settlement_funded(env: &Env, series_id: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SettlementFunded(series_id))
+            .unwrap_or(0)
+    }
+
+    pub fn set_settlement_funded(env: &Env, series_id: u32, funded: i128) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::SettlementFunded(series_id), &funded);
+    }
+
     // KYC
     pub fn is_kyc_verified(env: &Env, user: &Address) -> bool {
         env.storage()
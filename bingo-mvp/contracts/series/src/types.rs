@@ -4,15 +4,15 @@ use soroban_sdk::{contracttype, Address};
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Series {
     pub id: u32,
-    pub par_value: i128,              // Face value (e.g., 1_000_000 for $1)
-    pub subscription_price: i128,     // Initial discount price
-    pub issue_time: u64,              // Timestamp when issued
-    pub maturity_time: u64,           // Maturity timestamp
-    pub max_cap: i128,                // Maximum total subscription cap
-    pub per_user_cap: i128,           // Maximum per user
-    pub total_subscribed: i128,       // Current total subscribed
+    pub par_value: i128,          // Face value (e.g., 1_000_000 for $1)
+    pub subscription_price: i128, // Initial discount price
+    pub issue_time: u64,          // Timestamp when issued
+    pub maturity_time: u64,       // Maturity timestamp
+    pub max_cap: i128,            // Maximum total subscription cap
+    pub per_user_cap: i128,       // Maximum per user
+    pub total_subscribed: i128,   // Current total subscribed
     pub status: SeriesStatus,
-    pub usdc_token: Address,          // USDC token address for payments
+    pub usdc_token: Address, // USDC token address for payments
 }
 
 #[contracttype]
@@ -26,17 +26,36 @@ pub enum SeriesStatus {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UserPosition {
-    pub shares: i128,                 // Number of shares owned
-    pub entry_index: i128,            // Index at subscription (scaled by 1e7)
+    pub shares: i128,      // Number of shares owned
+    pub entry_index: i128, // Index at subscription (scaled by 1e7)
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionLot {
+    pub usdc_amount: i128, // USDC paid for this lot
+    pub shares: i128,      // Shares minted for this lot
+    pub index: i128,       // Index at subscription (scaled by 1e7)
+    pub timestamp: u64,    // Ledger timestamp of the subscription
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementProgress {
+    pub funded: i128,   // USDC deposited via settle_series so far
+    pub required: i128, // USDC needed to redeem all shares at par
 }
 
 #[contracttype]
 pub enum DataKey {
     Admin,
     NextSeriesId,
-    Series(u32),                      // series_id -> Series
-    UserPosition(u32, Address),       // (series_id, user) -> UserPosition
-    KYCVerified(Address),             // user -> bool
+    Series(u32),                // series_id -> Series
+    UserPosition(u32, Address), // (series_id, user) -> UserPosition
+    UserLots(u32, Address),     // (series_id, user) -> Vec<SubscriptionLot>, oldest first
+    KYCVerified(Address),       // user -> bool
+    SettlementFunded(u32),      // series_id -> USDC deposited toward settlement
 }
 
 pub const SCALE: i128 = 10_000_000; // 1e7 for precision
+pub const SECONDS_PER_YEAR: i128 = 365 * 24 * 3600;
@@ -1,5 +1,6 @@
+use crate::events::{self, RedeemedEvent, SubscribedEvent};
 use crate::storage::Storage;
-use crate::types::{UserPosition, SCALE};
+use crate::types::{SubscriptionLot, UserPosition, SCALE};
 use crate::validation::Validator;
 use crate::yield_calc::YieldCalculator;
 use soroban_sdk::{token, Address, Env};
@@ -56,6 +57,32 @@ impl UserOps {
         };
         Storage::set_user_position(env, series_id, user, &user_position);
 
+        // Record the lot for FIFO/average cost reporting
+        Storage::add_user_lot(
+            env,
+            series_id,
+            user,
+            &SubscriptionLot {
+                usdc_amount,
+                shares,
+                index: current_index,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        events::publish(
+            env,
+            "subscribed",
+            series_id,
+            SubscribedEvent {
+                series_id,
+                user: user.clone(),
+                usdc_amount,
+                shares,
+                index: current_index,
+            },
+        );
+
         shares
     }
 
@@ -88,8 +115,21 @@ impl UserOps {
         let usdc_client = token::Client::new(env, &series.usdc_token);
         usdc_client.transfer(&env.current_contract_address(), user, &redemption_value);
 
-        // Clear user position
+        // Clear user position and its lots
         Storage::remove_user_position(env, series_id, user);
+        Storage::remove_user_lots(env, series_id, user);
+
+        events::publish(
+            env,
+            "redeemed",
+            series_id,
+            RedeemedEvent {
+                series_id,
+                user: user.clone(),
+                shares: user_position.shares,
+                redemption_value,
+            },
+        );
 
         redemption_value
     }
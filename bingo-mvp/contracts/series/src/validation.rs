@@ -33,6 +33,38 @@ impl Validator {
         }
     }
 
+    pub fn validate_cap_update(
+        env: &Env,
+        series: &Series,
+        new_max_cap: i128,
+        new_per_user_cap: i128,
+    ) {
+        // Only open series can be resized
+        if series.status != SeriesStatus::Active {
+            panic!("Series not active");
+        }
+
+        if env.ledger().timestamp() >= series.maturity_time {
+            panic!("Series has matured");
+        }
+
+        if new_max_cap < series.max_cap {
+            panic!("Max cap can only increase");
+        }
+
+        if new_max_cap < series.total_subscribed {
+            panic!("Max cap below total subscribed");
+        }
+
+        if new_per_user_cap <= 0 {
+            panic!("Per user cap must be positive");
+        }
+
+        if new_per_user_cap > new_max_cap {
+            panic!("Per user cap cannot exceed max cap");
+        }
+    }
+
     pub fn validate_subscription(
         env: &Env,
         series: &Series,
@@ -66,13 +98,17 @@ impl Validator {
         }
     }
 
-    pub fn validate_settlement(env: &Env, series: &Series, usdc_amount: i128, required: i128) {
+    pub fn validate_settlement(env: &Env, series: &Series, usdc_amount: i128) {
         if env.ledger().timestamp() < series.maturity_time {
             panic!("Series not yet matured");
         }
-        
-        if usdc_amount < required {
-            panic!("Insufficient settlement amount");
+
+        if series.status == SeriesStatus::Settled {
+            panic!("Series already settled");
+        }
+
+        if usdc_amount <= 0 {
+            panic!("Settlement amount must be positive");
         }
     }
 }
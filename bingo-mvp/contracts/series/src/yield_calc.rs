@@ -1,4 +1,4 @@
-use crate::types::{Series, SCALE};
+use crate::types::{Series, SCALE, SECONDS_PER_YEAR};
 use soroban_sdk::Env;
 
 pub struct YieldCalculator;
@@ -36,6 +36,25 @@ impl YieldCalculator {
         (shares * current_index) / SCALE
     }
 
+    /// Calculate implied annualized yield (scaled by 1e7, SCALE = 100%)
+    /// Yield = (par - subscription_price) / subscription_price * year / tenor
+    pub fn calculate_annualized_yield(series: &Series) -> i128 {
+        let tenor = (series.maturity_time - series.issue_time) as i128;
+        if tenor <= 0 || series.subscription_price <= 0 {
+            return 0;
+        }
+
+        let discount = series.par_value - series.subscription_price;
+        (discount * SCALE * SECONDS_PER_YEAR) / (series.subscription_price * tenor)
+    }
+
+    /// Calculate seconds left until maturity (0 once matured)
+    pub fn calculate_time_to_maturity(env: &Env, series: &Series) -> u64 {
+        series
+            .maturity_time
+            .saturating_sub(env.ledger().timestamp())
+    }
+
     /// Calculate redemption value at maturity (always par)
     pub fn calculate_redemption_value(shares: i128, par_value: i128) -> i128 {
         (shares * par_value) / SCALE
@@ -46,7 +65,10 @@ impl YieldCalculator {
 mod tests {
     use super::*;
     use crate::types::SeriesStatus;
-    use soroban_sdk::{testutils::Address as _, Address, Env};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        Address, Env,
+    };
 
     fn create_test_series(env: &Env, issue_time: u64, maturity_time: u64) -> Series {
         Series {
@@ -68,10 +90,10 @@ mod tests {
         let env = Env::default();
         let issue_time = env.ledger().timestamp();
         let maturity_time = issue_time + 90 * 24 * 3600;
-        
+
         let series = create_test_series(&env, issue_time, maturity_time);
         let index = YieldCalculator::calculate_index(&env, &series);
-        
+
         assert_eq!(index, series.subscription_price);
     }
 
@@ -80,14 +102,14 @@ mod tests {
         let env = Env::default();
         let issue_time = 1000u64;
         let maturity_time = issue_time + 90 * 24 * 3600;
-        
+
         env.ledger().with_mut(|li| {
             li.timestamp = maturity_time;
         });
-        
+
         let series = create_test_series(&env, issue_time, maturity_time);
         let index = YieldCalculator::calculate_index(&env, &series);
-        
+
         assert_eq!(index, SCALE);
     }
 
@@ -98,25 +120,54 @@ mod tests {
         let duration = 90 * 24 * 3600u64;
         let maturity_time = issue_time + duration;
         let halfway = issue_time + duration / 2;
-        
+
         env.ledger().with_mut(|li| {
             li.timestamp = halfway;
         });
-        
+
         let series = create_test_series(&env, issue_time, maturity_time);
         let index = YieldCalculator::calculate_index(&env, &series);
-        
-        let expected = series.subscription_price + (series.par_value - series.subscription_price) / 2;
+
+        let expected =
+            series.subscription_price + (series.par_value - series.subscription_price) / 2;
         assert_eq!(index, expected);
     }
 
+    #[test]
+    fn test_annualized_yield() {
+        let env = Env::default();
+        let series = create_test_series(&env, 0, 90 * 24 * 3600);
+
+        // 20k / 980k = 2.0408% over 90 days → 8.2766% annualized
+        let apy = YieldCalculator::calculate_annualized_yield(&series);
+        assert_eq!(apy, 827_664);
+    }
+
+    #[test]
+    fn test_time_to_maturity() {
+        let env = Env::default();
+        let series = create_test_series(&env, 1000, 5000);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 4000;
+        });
+        let remaining = YieldCalculator::calculate_time_to_maturity(&env, &series);
+        assert_eq!(remaining, 1000);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 6000;
+        });
+        let remaining = YieldCalculator::calculate_time_to_maturity(&env, &series);
+        assert_eq!(remaining, 0);
+    }
+
     #[test]
     fn test_calculate_shares() {
         let usdc_amount = 980_000i128;
         let current_index = 980_000i128;
-        
+
         let shares = YieldCalculator::calculate_shares(usdc_amount, current_index);
-        
+
         // Should get SCALE shares (10_000_000)
         assert_eq!(shares, SCALE);
     }
@@ -125,9 +176,9 @@ mod tests {
     fn test_calculate_position_value() {
         let shares = SCALE;
         let current_index = 990_000i128;
-        
+
         let value = YieldCalculator::calculate_position_value(shares, current_index);
-        
+
         assert_eq!(value, 990_000);
     }
 }
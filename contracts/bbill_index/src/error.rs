@@ -0,0 +1,44 @@
+use bingo_shares::ShareError;
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // ============================================
+    // INITIALIZATION ERRORS (1-5)
+    // ============================================
+    /// Contract already initialized
+    AlreadyInitialized = 1,
+    /// Contract not initialized
+    NotInitialized = 2,
+
+    // ============================================
+    // SERIES ERRORS (20-29)
+    // ============================================
+    /// Series is not in ACTIVE status in the vault
+    SeriesNotActive = 20,
+    /// Index holds no bT-Bills of this series
+    SeriesNotHeld = 21,
+
+    // ============================================
+    // AMOUNT/BALANCE ERRORS (30-39)
+    // ============================================
+    /// Amount must be positive
+    InvalidAmount = 30,
+    /// Not enough index tokens
+    InsufficientBalance = 31,
+    /// Deposit too small to mint a whole index unit
+    ZeroShares = 32,
+    /// Index units are outstanding against a zero NAV; a deposit would dilute them
+    NavDepleted = 33,
+}
+
+impl From<ShareError> for Error {
+    fn from(error: ShareError) -> Self {
+        match error {
+            ShareError::InvalidAmount => Error::InvalidAmount,
+            ShareError::InsufficientBalance => Error::InsufficientBalance,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IndexDepositEvent {
+    pub user: Address,
+    pub series_id: u32,
+    pub par_amount: i128,
+    pub value: i128,
+    pub shares: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IndexWithdrawEvent {
+    pub user: Address,
+    pub shares: i128,
+    pub stablecoin_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IndexRolledEvent {
+    pub from_series_id: u32,
+    pub to_series_id: u32,
+    pub redeemed_par: i128,
+    pub minted_par: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IndexTransferEvent {
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+}
@@ -0,0 +1,445 @@
+#![no_std]
+
+mod error;
+mod events;
+mod storage;
+
+pub use error::Error;
+use events::*;
+use storage::{DataKey, VAULT_STATUS_ACTIVE, VAULT_STATUS_FULLY_SUBSCRIBED};
+
+use bingo_events::{publish, publish_global, INDEX};
+use bingo_shares::{
+    authorize_subscription, burn_shares, calculate_pro_rata, calculate_share_price,
+    calculate_shares_for_deposit, idle_cash, mint_shares, move_shares, set_idle_cash,
+};
+use soroban_sdk::{contract, contractimpl, token, vec, Address, Env, IntoVal, Symbol, Vec};
+
+/// bBILL index: one fungible token backed by bT-Bills from any active series
+///
+/// Deposits are valued at the vault's accreted price, so index units track
+/// the blended accretion of everything the index holds. Matured holdings are
+/// redeemed and rolled into a fresh series by the admin keeper.
+#[contract]
+pub struct BBillIndex;
+
+#[contractimpl]
+impl BBillIndex {
+    // ============================================
+    // INITIALIZATION
+    // ============================================
+
+    /// Initialize the index
+    ///
+    /// # Errors
+    /// - `AlreadyInitialized`: Contract already initialized
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        vault: Address,
+        bt_bill_token: Address,
+        stablecoin: Address,
+    ) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Initialized, &true);
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Vault, &vault);
+        env.storage()
+            .instance()
+            .set(&DataKey::BTBillToken, &bt_bill_token);
+        env.storage().instance().set(&DataKey::Stablecoin, &stablecoin);
+
+        Ok(())
+    }
+
+    // ============================================
+    // DEPOSIT / WITHDRAW
+    // ============================================
+
+    /// Deposit bT-Bills of an active series and receive index tokens
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: par_amount must be positive
+    /// - `SeriesNotActive`: Series not ACTIVE (or FULLY_SUBSCRIBED) in the vault
+    /// - `ZeroShares`: Deposit too small to mint any index tokens
+    /// - `NavDepleted`: Index units outstanding against a zero NAV
+    pub fn deposit(env: Env, user: Address, series_id: u32, par_amount: i128) -> Result<i128, Error> {
+        if par_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        user.require_auth();
+
+        let vault = Self::vault(&env)?;
+        let status: u32 = env.invoke_contract(
+            &vault,
+            &Symbol::new(&env, "get_series_status"),
+            vec![&env, series_id.into()],
+        );
+        if status != VAULT_STATUS_ACTIVE && status != VAULT_STATUS_FULLY_SUBSCRIBED {
+            return Err(Error::SeriesNotActive);
+        }
+
+        let nav_before = Self::nav(env.clone())?;
+        let value = Self::collateral_value(&env, &vault, series_id, par_amount);
+
+        let total_supply = Self::total_supply(env.clone());
+        if total_supply > 0 && nav_before <= 0 {
+            return Err(Error::NavDepleted);
+        }
+        let shares = calculate_shares_for_deposit(value, total_supply, nav_before)
+            .ok_or(Error::InvalidAmount)?;
+        if shares <= 0 {
+            return Err(Error::ZeroShares);
+        }
+
+        let bt_bill_token = Self::bt_bill_token(&env)?;
+        env.invoke_contract::<()>(
+            &bt_bill_token,
+            &Symbol::new(&env, "transfer"),
+            vec![
+                &env,
+                series_id.into(),
+                user.to_val(),
+                env.current_contract_address().to_val(),
+                par_amount.into_val(&env),
+            ],
+        );
+
+        Self::add_held_series(&env, series_id);
+        mint_shares(&env, &user, shares)?;
+
+        publish(
+            &env,
+            INDEX,
+            "index_deposit",
+            user.clone(),
+            IndexDepositEvent {
+                user,
+                series_id,
+                par_amount,
+                value,
+                shares,
+            },
+        );
+
+        Ok(shares)
+    }
+
+    /// Burn index tokens for a pro-rata slice of every holding
+    ///
+    /// Pays out bT-Bills of each held series in kind plus a slice of the idle
+    /// stablecoin the index booked. Returns the stablecoin paid.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: shares must be positive
+    /// - `InsufficientBalance`: Not enough index tokens
+    pub fn withdraw(env: Env, user: Address, shares: i128) -> Result<i128, Error> {
+        if shares <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        user.require_auth();
+
+        let total_supply = Self::total_supply(env.clone());
+        let bt_bill_token = Self::bt_bill_token(&env)?;
+        let this = env.current_contract_address();
+
+        // Burn first so the pro-rata math uses the pre-withdraw supply once
+        burn_shares(&env, &user, shares)?;
+
+        for series_id in Self::get_held_series(env.clone()).iter() {
+            let held = Self::bt_balance(&env, &bt_bill_token, series_id);
+            let out = calculate_pro_rata(held, shares, total_supply).ok_or(Error::InvalidAmount)?;
+            if out > 0 {
+                env.invoke_contract::<()>(
+                    &bt_bill_token,
+                    &Symbol::new(&env, "transfer"),
+                    vec![
+                        &env,
+                        series_id.into(),
+                        this.to_val(),
+                        user.to_val(),
+                        out.into_val(&env),
+                    ],
+                );
+            }
+        }
+
+        let idle = idle_cash(&env);
+        let stablecoin_out =
+            calculate_pro_rata(idle, shares, total_supply).ok_or(Error::InvalidAmount)?;
+        if stablecoin_out > 0 {
+            set_idle_cash(&env, idle - stablecoin_out);
+            token::Client::new(&env, &Self::stablecoin(&env)?).transfer(
+                &this,
+                &user,
+                &stablecoin_out,
+            );
+        }
+
+        publish(
+            &env,
+            INDEX,
+            "index_withdraw",
+            user.clone(),
+            IndexWithdrawEvent {
+                user,
+                shares,
+                stablecoin_out,
+            },
+        );
+
+        Ok(stablecoin_out)
+    }
+
+    // ============================================
+    // MATURITY ROLLOVER
+    // ============================================
+
+    /// Redeem a matured holding at PAR and reinvest the proceeds in another series
+    ///
+    /// Returns the PAR minted in the new series.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotHeld`: Index holds nothing in from_series_id
+    /// - `SeriesNotActive`: to_series_id not ACTIVE in the vault
+    pub fn roll(env: Env, from_series_id: u32, to_series_id: u32) -> Result<i128, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        let vault = Self::vault(&env)?;
+        let bt_bill_token = Self::bt_bill_token(&env)?;
+        let stablecoin = Self::stablecoin(&env)?;
+        let stablecoin_client = token::Client::new(&env, &stablecoin);
+        let this = env.current_contract_address();
+
+        let redeemed_par = Self::bt_balance(&env, &bt_bill_token, from_series_id);
+        if redeemed_par <= 0 {
+            return Err(Error::SeriesNotHeld);
+        }
+
+        let status: u32 = env.invoke_contract(
+            &vault,
+            &Symbol::new(&env, "get_series_status"),
+            vec![&env, to_series_id.into()],
+        );
+        if status != VAULT_STATUS_ACTIVE {
+            return Err(Error::SeriesNotActive);
+        }
+
+        let cash_before = stablecoin_client.balance(&this);
+        env.invoke_contract::<()>(
+            &vault,
+            &Symbol::new(&env, "redeem"),
+            vec![
+                &env,
+                this.to_val(),
+                from_series_id.into(),
+                redeemed_par.into_val(&env),
+                Option::<Address>::None.into_val(&env),
+            ],
+        );
+        let proceeds = stablecoin_client.balance(&this) - cash_before;
+
+        // The authorization covers only our next call, so it must be subscribe
+        let par_before = Self::bt_balance(&env, &bt_bill_token, to_series_id);
+        authorize_subscription(&env, &vault, &stablecoin, to_series_id, proceeds);
+        env.invoke_contract::<()>(
+            &vault,
+            &Symbol::new(&env, "subscribe"),
+            vec![
+                &env,
+                this.to_val(),
+                to_series_id.into(),
+                proceeds.into_val(&env),
+                Option::<Address>::None.into_val(&env),
+            ],
+        );
+        let minted_par = Self::bt_balance(&env, &bt_bill_token, to_series_id) - par_before;
+
+        // Book whatever the subscription left over; the vault normally takes it all
+        let leftover = stablecoin_client.balance(&this) - cash_before;
+        set_idle_cash(&env, idle_cash(&env) + leftover);
+
+        Self::remove_held_series(&env, from_series_id);
+        Self::add_held_series(&env, to_series_id);
+
+        publish(
+            &env,
+            INDEX,
+            "index_rolled",
+            from_series_id,
+            IndexRolledEvent {
+                from_series_id,
+                to_series_id,
+                redeemed_par,
+                minted_par,
+            },
+        );
+
+        Ok(minted_par)
+    }
+
+    // ============================================
+    // TOKEN INTERFACE
+    // ============================================
+
+    /// Transfer index tokens
+    ///
+    /// # Errors
+    /// - `InvalidAmount`: amount must be positive
+    /// - `InsufficientBalance`: Not enough index tokens
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        from.require_auth();
+
+        move_shares(&env, &from, &to, amount)?;
+
+        publish_global(
+            &env,
+            INDEX,
+            "transfer",
+            IndexTransferEvent { from, to, amount },
+        );
+
+        Ok(())
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        bingo_shares::balance(&env, &id)
+    }
+
+    pub fn total_supply(env: Env) -> i128 {
+        bingo_shares::total_supply(&env)
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    pub fn symbol(env: Env) -> Symbol {
+        Symbol::new(&env, "bBILL")
+    }
+
+    // ============================================
+    // VIEW FUNCTIONS
+    // ============================================
+
+    /// Net asset value: held bT-Bills at accreted price + booked idle stablecoin
+    ///
+    /// Stablecoin sent to the index directly is not booked, so it cannot
+    /// move the share price.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn nav(env: Env) -> Result<i128, Error> {
+        let vault = Self::vault(&env)?;
+        let bt_bill_token = Self::bt_bill_token(&env)?;
+
+        let mut nav = idle_cash(&env);
+        for series_id in Self::get_held_series(env.clone()).iter() {
+            let held = Self::bt_balance(&env, &bt_bill_token, series_id);
+            if held > 0 {
+                nav = nav
+                    .checked_add(Self::collateral_value(&env, &vault, series_id, held))
+                    .ok_or(Error::InvalidAmount)?;
+            }
+        }
+
+        Ok(nav)
+    }
+
+    /// NAV per index token (scaled, 1.0 = SCALE)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn share_price(env: Env) -> Result<i128, Error> {
+        let nav = Self::nav(env.clone())?;
+        calculate_share_price(nav, Self::total_supply(env)).ok_or(Error::InvalidAmount)
+    }
+
+    /// Series the index currently holds
+    pub fn get_held_series(env: Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::HeldSeries)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ============================================
+    // INTERNAL HELPERS
+    // ============================================
+
+    fn vault(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Vault)
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn bt_bill_token(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::BTBillToken)
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn stablecoin(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Stablecoin)
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn collateral_value(env: &Env, vault: &Address, series_id: u32, par_amount: i128) -> i128 {
+        env.invoke_contract(
+            vault,
+            &Symbol::new(env, "collateral_value"),
+            vec![env, series_id.into(), par_amount.into_val(env)],
+        )
+    }
+
+    fn bt_balance(env: &Env, bt_bill_token: &Address, series_id: u32) -> i128 {
+        env.invoke_contract(
+            bt_bill_token,
+            &Symbol::new(env, "balance_of"),
+            vec![
+                env,
+                series_id.into(),
+                env.current_contract_address().to_val(),
+            ],
+        )
+    }
+
+    fn add_held_series(env: &Env, series_id: u32) {
+        let mut held = Self::get_held_series(env.clone());
+        if !held.contains(series_id) {
+            held.push_back(series_id);
+            env.storage().instance().set(&DataKey::HeldSeries, &held);
+        }
+    }
+
+    fn remove_held_series(env: &Env, series_id: u32) {
+        let mut held = Self::get_held_series(env.clone());
+        if let Some(index) = held.first_index_of(series_id) {
+            held.remove(index);
+            env.storage().instance().set(&DataKey::HeldSeries, &held);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use soroban_sdk::contracttype;
+
+// Constants
+/// Discriminant of `SeriesStatus::Active` in bingo_vault
+pub const VAULT_STATUS_ACTIVE: u32 = 1;
+/// Discriminant of `SeriesStatus::FullySubscribed` (closed to new subscriptions, still live)
+pub const VAULT_STATUS_FULLY_SUBSCRIBED: u32 = 6;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Vault,
+    BTBillToken,
+    Stablecoin,
+    HeldSeries, // Vec<u32> of series the index currently holds
+    Initialized,
+    // Balances, supply and idle cash live under bingo_shares::ShareKey
+}
@@ -0,0 +1,91 @@
+#![no_std]
+
+//! Shared event topic convention for every Bingo contract
+//!
+//! Topics: `("bingo", namespace, event, key)`, or `("bingo", namespace, event)`
+//! for contract-wide events. Data: `(SCHEMA_VERSION, payload)`.
+//!
+//! A single indexer filter on topic[0] == "bingo" captures the whole
+//! protocol; topic[1] narrows to one contract. Soroban allows at most four
+//! topics, so any secondary key (e.g. the user on `subscribed`) lives in the
+//! payload. Bump SCHEMA_VERSION whenever a payload layout changes.
+
+use soroban_sdk::{symbol_short, Env, IntoVal, Symbol, Val};
+
+/// Topic[0] shared by all protocol events
+pub const PROTOCOL: Symbol = symbol_short!("bingo");
+
+/// Payload schema version, carried as the first element of the event data
+pub const SCHEMA_VERSION: u32 = 1;
+
+// Topic[1] namespaces, one per contract
+pub const VAULT: Symbol = symbol_short!("vault");
+pub const REPO: Symbol = symbol_short!("repo");
+pub const TOKEN: Symbol = symbol_short!("token");
+pub const INDEX: Symbol = symbol_short!("index");
+pub const LADDER: Symbol = symbol_short!("ladder");
+pub const SERIES: Symbol = symbol_short!("series");
+
+/// Publish a keyed event: `("bingo", namespace, event, key)`
+pub fn publish<K, D>(env: &Env, namespace: Symbol, event: &str, key: K, payload: D)
+where
+    K: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    let key: Val = key.into_val(env);
+    let payload: Val = payload.into_val(env);
+    env.events().publish(
+        (PROTOCOL, namespace, Symbol::new(env, event), key),
+        (SCHEMA_VERSION, payload),
+    );
+}
+
+/// Publish a contract-wide event: `("bingo", namespace, event)`
+pub fn publish_global<D>(env: &Env, namespace: Symbol, event: &str, payload: D)
+where
+    D: IntoVal<Env, Val>,
+{
+    let payload: Val = payload.into_val(env);
+    env.events().publish(
+        (PROTOCOL, namespace, Symbol::new(env, event)),
+        (SCHEMA_VERSION, payload),
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{contract, testutils::Events, vec, Env};
+
+    #[contract]
+    struct Emitter;
+
+    #[test]
+    fn test_topics_and_versioned_payload() {
+        let env = Env::default();
+        let contract_id = env.register(Emitter, ());
+
+        env.as_contract(&contract_id, || {
+            publish(&env, VAULT, "subscribed", 7u32, 100i128);
+            publish_global(&env, REPO, "fee_tiers_updated", 1i128);
+        });
+
+        let events = env.events().all();
+        assert_eq!(
+            events,
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (PROTOCOL, VAULT, Symbol::new(&env, "subscribed"), 7u32).into_val(&env),
+                    (SCHEMA_VERSION, 100i128).into_val(&env),
+                ),
+                (
+                    contract_id,
+                    (PROTOCOL, REPO, Symbol::new(&env, "fee_tiers_updated")).into_val(&env),
+                    (SCHEMA_VERSION, 1i128).into_val(&env),
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::storage::{InvariantKind, Violation};
+
+/// Token supply must match the vault's outstanding PAR exactly
+pub fn check_token_supply(
+    series_id: u32,
+    outstanding_par: i128,
+    supply: i128,
+) -> Option<Violation> {
+    (supply != outstanding_par).then_some(Violation {
+        kind: InvariantKind::TokenSupply,
+        series_id,
+        expected: outstanding_par,
+        actual: supply,
+    })
+}
+
+/// The repo market must hold exactly the collateral of its open positions
+pub fn check_repo_collateral(
+    series_id: u32,
+    locked_par: i128,
+    held_par: i128,
+) -> Option<Violation> {
+    (held_par != locked_par).then_some(Violation {
+        kind: InvariantKind::RepoCollateral,
+        series_id,
+        expected: locked_par,
+        actual: held_par,
+    })
+}
+
+/// The vault must hold at least what its accounting implies
+///
+/// A surplus is an unexpected inflow (see `sweep_unexpected_inflows`),
+/// not a solvency problem, so only shortfalls are reported.
+pub fn check_vault_balance(expected_balance: i128, actual_balance: i128) -> Option<Violation> {
+    (actual_balance < expected_balance).then_some(Violation {
+        kind: InvariantKind::VaultBalance,
+        series_id: 0,
+        expected: expected_balance,
+        actual: actual_balance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCALE: i128 = 10_000_000;
+
+    #[test]
+    fn test_check_token_supply() {
+        assert_eq!(check_token_supply(1, 1_000 * SCALE, 1_000 * SCALE), None);
+
+        let violation = check_token_supply(1, 1_000 * SCALE, 1_001 * SCALE).unwrap();
+        assert_eq!(violation.kind, InvariantKind::TokenSupply);
+        assert_eq!(violation.expected, 1_000 * SCALE);
+        assert_eq!(violation.actual, 1_001 * SCALE);
+    }
+
+    #[test]
+    fn test_check_repo_collateral() {
+        assert_eq!(check_repo_collateral(2, 0, 0), None);
+        assert_eq!(check_repo_collateral(2, 500 * SCALE, 500 * SCALE), None);
+
+        // Missing and surplus collateral are both flagged
+        assert!(check_repo_collateral(2, 500 * SCALE, 499 * SCALE).is_some());
+        assert!(check_repo_collateral(2, 500 * SCALE, 501 * SCALE).is_some());
+    }
+
+    #[test]
+    fn test_check_vault_balance_flags_only_shortfalls() {
+        assert_eq!(check_vault_balance(980 * SCALE, 980 * SCALE), None);
+        assert_eq!(check_vault_balance(980 * SCALE, 985 * SCALE), None);
+
+        let violation = check_vault_balance(980 * SCALE, 975 * SCALE).unwrap();
+        assert_eq!(violation.kind, InvariantKind::VaultBalance);
+        assert_eq!(violation.series_id, 0);
+    }
+}
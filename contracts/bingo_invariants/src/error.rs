@@ -0,0 +1,20 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // ============================================
+    // INITIALIZATION ERRORS (1-5)
+    // ============================================
+    /// Contract already initialized
+    AlreadyInitialized = 1,
+    /// Contract not initialized
+    NotInitialized = 2,
+
+    // ============================================
+    // INVARIANT ERRORS (20-29)
+    // ============================================
+    /// At least one invariant does not hold; call `check` for the report
+    InvariantViolated = 20,
+}
@@ -0,0 +1,212 @@
+#![no_std]
+
+mod checks;
+mod error;
+mod storage;
+
+use checks::{check_repo_collateral, check_token_supply, check_vault_balance};
+pub use error::Error;
+use storage::DataKey;
+pub use storage::{InvariantKind, InvariantReport, Violation};
+
+use soroban_sdk::{contract, contractimpl, token, vec, Address, Env, IntoVal, Map, Symbol, Vec};
+
+/// Read-only monitor cross-checking vault, bT-Bill token and repo market state
+///
+/// Nothing here writes protocol state: `check` and `check_series` return a
+/// structured report, and the `assert_*` entrypoints fail with
+/// `InvariantViolated` so bots can alert on a failed simulation alone.
+#[contract]
+pub struct BingoInvariants;
+
+#[contractimpl]
+impl BingoInvariants {
+    // ============================================
+    // INITIALIZATION
+    // ============================================
+
+    /// Point the monitor at one deployment
+    ///
+    /// # Errors
+    /// - `AlreadyInitialized`: Contract already initialized
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        vault: Address,
+        bt_bill_token: Address,
+        repo_market: Address,
+        stablecoin: Address,
+    ) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Initialized, &true);
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Vault, &vault);
+        env.storage()
+            .instance()
+            .set(&DataKey::BTBillToken, &bt_bill_token);
+        env.storage()
+            .instance()
+            .set(&DataKey::RepoMarket, &repo_market);
+        env.storage()
+            .instance()
+            .set(&DataKey::Stablecoin, &stablecoin);
+
+        Ok(())
+    }
+
+    // ============================================
+    // REPORTS
+    // ============================================
+
+    /// Check every series the vault knows about, plus the vault balance
+    ///
+    /// Reads every series; for large deployments page with `check_series`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn check(env: Env) -> Result<InvariantReport, Error> {
+        let vault = Self::address(&env, DataKey::Vault)?;
+        let stablecoin = Self::address(&env, DataKey::Stablecoin)?;
+        let collateral = Self::repo_collateral(&env, &vault)?;
+
+        let series_ids: Vec<u32> =
+            env.invoke_contract(&vault, &Symbol::new(&env, "get_series_ids"), vec![&env]);
+
+        let mut violations = Vec::new(&env);
+        let mut series_checked: u32 = 0;
+        for series_id in series_ids.iter() {
+            if Self::check_one(&env, series_id, &collateral, &mut violations)? {
+                series_checked += 1;
+            }
+        }
+
+        let expected_balance: i128 =
+            env.invoke_contract(&vault, &Symbol::new(&env, "expected_balance"), vec![&env]);
+        let actual_balance = token::Client::new(&env, &stablecoin).balance(&vault);
+        if let Some(violation) = check_vault_balance(expected_balance, actual_balance) {
+            violations.push_back(violation);
+        }
+
+        Ok(Self::report(&env, series_checked, violations))
+    }
+
+    /// Check a single series' token supply and repo collateral
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn check_series(env: Env, series_id: u32) -> Result<InvariantReport, Error> {
+        let vault = Self::address(&env, DataKey::Vault)?;
+        let collateral = Self::repo_collateral(&env, &vault)?;
+
+        let mut violations = Vec::new(&env);
+        let series_checked = Self::check_one(&env, series_id, &collateral, &mut violations)? as u32;
+
+        Ok(Self::report(&env, series_checked, violations))
+    }
+
+    // ============================================
+    // ASSERTIONS
+    // ============================================
+
+    /// Fail unless every invariant in `check` holds
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvariantViolated`: At least one violation reported
+    pub fn assert_invariants(env: Env) -> Result<(), Error> {
+        Self::assert_healthy(Self::check(env)?)
+    }
+
+    /// Fail unless every invariant in `check_series` holds
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvariantViolated`: At least one violation reported
+    pub fn assert_series(env: Env, series_id: u32) -> Result<(), Error> {
+        Self::assert_healthy(Self::check_series(env, series_id)?)
+    }
+
+    // ============================================
+    // INTERNAL HELPERS
+    // ============================================
+
+    /// Run the per-series checks; returns false if the vault no longer has the series
+    fn check_one(
+        env: &Env,
+        series_id: u32,
+        collateral: &Map<u32, i128>,
+        violations: &mut Vec<Violation>,
+    ) -> Result<bool, Error> {
+        let vault = Self::address(env, DataKey::Vault)?;
+        let bt_bill_token = Self::address(env, DataKey::BTBillToken)?;
+        let repo_market = Self::address(env, DataKey::RepoMarket)?;
+
+        // Pruned series are gone from the vault; nothing left to compare
+        let Ok(Ok(outstanding_par)) = env.try_invoke_contract::<i128, soroban_sdk::Error>(
+            &vault,
+            &Symbol::new(env, "outstanding_par"),
+            vec![env, series_id.into()],
+        ) else {
+            return Ok(false);
+        };
+
+        let supply: i128 = env.invoke_contract(
+            &bt_bill_token,
+            &Symbol::new(env, "total_supply"),
+            vec![env, series_id.into()],
+        );
+        if let Some(violation) = check_token_supply(series_id, outstanding_par, supply) {
+            violations.push_back(violation);
+        }
+
+        let held_par: i128 = env.invoke_contract(
+            &bt_bill_token,
+            &Symbol::new(env, "balance_of"),
+            vec![env, series_id.into(), repo_market.into_val(env)],
+        );
+        let locked_par = collateral.get(series_id).unwrap_or(0);
+        if let Some(violation) = check_repo_collateral(series_id, locked_par, held_par) {
+            violations.push_back(violation);
+        }
+
+        Ok(true)
+    }
+
+    fn repo_collateral(env: &Env, vault: &Address) -> Result<Map<u32, i128>, Error> {
+        let repo_market = Self::address(env, DataKey::RepoMarket)?;
+        Ok(env.invoke_contract(
+            &repo_market,
+            &Symbol::new(env, "collateral_by_series"),
+            vec![env, vault.into_val(env)],
+        ))
+    }
+
+    fn report(env: &Env, series_checked: u32, violations: Vec<Violation>) -> InvariantReport {
+        InvariantReport {
+            checked_at: env.ledger().timestamp(),
+            series_checked,
+            healthy: violations.is_empty(),
+            violations,
+        }
+    }
+
+    fn assert_healthy(report: InvariantReport) -> Result<(), Error> {
+        if report.healthy {
+            Ok(())
+        } else {
+            Err(Error::InvariantViolated)
+        }
+    }
+
+    fn address(env: &Env, key: DataKey) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&key)
+            .ok_or(Error::NotInitialized)
+    }
+}
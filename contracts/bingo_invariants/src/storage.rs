@@ -0,0 +1,47 @@
+use soroban_sdk::{contracttype, Vec};
+
+/// Protocol property checked by the monitor
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvariantKind {
+    /// bT-Bill supply of a series == vault minted_par - redeemed_par
+    TokenSupply = 0,
+    /// Repo market's bT-Bill balance of a series == collateral of its open positions
+    RepoCollateral = 1,
+    /// Vault stablecoin balance >= balance implied by ProtocolAccounting
+    VaultBalance = 2,
+}
+
+/// One failed invariant, with the values that disagreed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+    pub kind: InvariantKind,
+    /// Series checked (0 for protocol-wide invariants)
+    pub series_id: u32,
+    /// Value implied by the source of truth (vault or repo bookkeeping)
+    pub expected: i128,
+    /// Value actually observed
+    pub actual: i128,
+}
+
+/// Result of one pass over the protocol
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantReport {
+    pub checked_at: u64,
+    pub series_checked: u32,
+    pub healthy: bool,
+    pub violations: Vec<Violation>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Vault,
+    BTBillToken,
+    RepoMarket,
+    Stablecoin,
+    Initialized,
+}
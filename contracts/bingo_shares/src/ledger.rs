@@ -0,0 +1,108 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+/// Storage keys of the share ledger, in the fund's instance storage
+///
+/// Variant names match the keys the funds used before the ledger was
+/// shared, so existing balances stay where they are.
+#[contracttype]
+#[derive(Clone)]
+pub enum ShareKey {
+    Balance(Address), // Holder → share balance
+    TotalSupply,
+    IdleCash, // Stablecoin booked for holders (absent = 0)
+}
+
+/// Ledger failures; each fund maps them onto its own error enum
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ShareError {
+    /// Amount must be positive, or a total overflowed
+    InvalidAmount,
+    /// Not enough shares
+    InsufficientBalance,
+}
+
+pub fn balance(env: &Env, id: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(&ShareKey::Balance(id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn total_supply(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&ShareKey::TotalSupply)
+        .unwrap_or(0)
+}
+
+pub fn idle_cash(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&ShareKey::IdleCash)
+        .unwrap_or(0)
+}
+
+pub fn set_idle_cash(env: &Env, amount: i128) {
+    env.storage().instance().set(&ShareKey::IdleCash, &amount);
+}
+
+pub fn mint_shares(env: &Env, to: &Address, shares: i128) -> Result<(), ShareError> {
+    let balance = balance(env, to)
+        .checked_add(shares)
+        .ok_or(ShareError::InvalidAmount)?;
+    let total_supply = total_supply(env)
+        .checked_add(shares)
+        .ok_or(ShareError::InvalidAmount)?;
+
+    env.storage()
+        .instance()
+        .set(&ShareKey::Balance(to.clone()), &balance);
+    env.storage()
+        .instance()
+        .set(&ShareKey::TotalSupply, &total_supply);
+    Ok(())
+}
+
+pub fn burn_shares(env: &Env, from: &Address, shares: i128) -> Result<(), ShareError> {
+    let balance = balance(env, from);
+    if balance < shares {
+        return Err(ShareError::InsufficientBalance);
+    }
+    let total_supply = total_supply(env) - shares;
+
+    env.storage()
+        .instance()
+        .set(&ShareKey::Balance(from.clone()), &(balance - shares));
+    env.storage()
+        .instance()
+        .set(&ShareKey::TotalSupply, &total_supply);
+    Ok(())
+}
+
+/// Move shares between holders; auth and events are the caller's
+pub fn move_shares(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), ShareError> {
+    if amount <= 0 {
+        return Err(ShareError::InvalidAmount);
+    }
+
+    let from_balance = balance(env, from);
+    if from_balance < amount {
+        return Err(ShareError::InsufficientBalance);
+    }
+    let to_balance = balance(env, to)
+        .checked_add(amount)
+        .ok_or(ShareError::InvalidAmount)?;
+
+    env.storage()
+        .instance()
+        .set(&ShareKey::Balance(from.clone()), &(from_balance - amount));
+    env.storage()
+        .instance()
+        .set(&ShareKey::Balance(to.clone()), &to_balance);
+    Ok(())
+}
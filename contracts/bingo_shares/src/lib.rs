@@ -0,0 +1,19 @@
+#![no_std]
+
+//! Share accounting shared by the vault-backed funds (bbill_index, ladder_fund)
+//!
+//! A fund holds bT-Bills plus booked idle stablecoin and issues shares priced
+//! at NAV. Balances, supply and idle cash live in the fund's instance storage
+//! under `ShareKey`; stablecoin sent to a fund directly is never booked, so
+//! it cannot move the share price.
+
+mod ledger;
+mod nav;
+mod vault;
+
+pub use ledger::{
+    balance, burn_shares, idle_cash, mint_shares, move_shares, set_idle_cash, total_supply,
+    ShareError, ShareKey,
+};
+pub use nav::{calculate_pro_rata, calculate_share_price, calculate_shares_for_deposit, SCALE};
+pub use vault::authorize_subscription;
@@ -0,0 +1,77 @@
+// Constants
+pub const SCALE: i128 = 10_000_000; // 7 decimals
+
+/// Calculate shares to mint for a deposit
+///
+/// Formula:
+/// - empty fund: shares = value (1 share starts at 1.0 stablecoin)
+/// - otherwise:  shares = value × total_supply / nav
+///
+/// Returns None when shares are outstanding against a zero NAV.
+///
+/// Example:
+/// - nav: 1,050,000, total_supply: 1,000,000 (share price 1.05)
+/// - deposit value: 10,500
+/// - shares: 10,000
+pub fn calculate_shares_for_deposit(value: i128, total_supply: i128, nav: i128) -> Option<i128> {
+    if total_supply == 0 {
+        return Some(value);
+    }
+    if nav <= 0 {
+        return None;
+    }
+    value.checked_mul(total_supply)?.checked_div(nav)
+}
+
+/// Calculate a holder's pro-rata slice of a fund asset
+///
+/// Formula: out = asset_amount × shares / total_supply
+pub fn calculate_pro_rata(asset_amount: i128, shares: i128, total_supply: i128) -> Option<i128> {
+    if total_supply <= 0 {
+        return None;
+    }
+    asset_amount.checked_mul(shares)?.checked_div(total_supply)
+}
+
+/// Calculate NAV per share
+///
+/// Formula: share_price = nav × SCALE / total_supply
+pub fn calculate_share_price(nav: i128, total_supply: i128) -> Option<i128> {
+    if total_supply == 0 {
+        return Some(SCALE);
+    }
+    nav.checked_mul(SCALE)?.checked_div(total_supply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_deposit_mints_at_value() {
+        let shares = calculate_shares_for_deposit(9_800 * SCALE, 0, 0).unwrap();
+        assert_eq!(shares, 9_800 * SCALE);
+    }
+
+    #[test]
+    fn test_deposit_at_accreted_share_price() {
+        let shares =
+            calculate_shares_for_deposit(10_500 * SCALE, 1_000_000 * SCALE, 1_050_000 * SCALE)
+                .unwrap();
+        assert_eq!(shares, 10_000 * SCALE);
+    }
+
+    #[test]
+    fn test_no_shares_against_zero_nav() {
+        assert_eq!(calculate_shares_for_deposit(SCALE, 1_000 * SCALE, 0), None);
+    }
+
+    #[test]
+    fn test_pro_rata_and_share_price() {
+        let out = calculate_pro_rata(500_000 * SCALE, 250 * SCALE, 1_000 * SCALE).unwrap();
+        assert_eq!(out, 125_000 * SCALE);
+
+        let price = calculate_share_price(1_050_000 * SCALE, 1_000_000 * SCALE).unwrap();
+        assert_eq!(price, 105 * SCALE / 100); // 1.05
+    }
+}
@@ -0,0 +1,82 @@
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contracttype, token, vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+/// Mirrors bingo_vault's `SubscriptionQuote`; field names must match.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SubscriptionQuote {
+    pub pay_amount: i128,
+    pub fee: i128,
+    pub issuer_fee: i128,
+    pub price: i128,
+    pub minted_par: i128,
+}
+
+/// Pre-authorize the stablecoin moves `vault.subscribe` makes from the current contract
+///
+/// Call right before `subscribe`: the entries only cover our next call.
+///
+/// In allowance-pull mode the vault spends an allowance. Otherwise it
+/// transfers from us one level down the call tree: the payment (less the
+/// collector's fee) to itself, then the collector's fee. The funds never
+/// name a referrer, so no referral reward moves the split.
+pub fn authorize_subscription(
+    env: &Env,
+    vault: &Address,
+    stablecoin: &Address,
+    series_id: u32,
+    amount: i128,
+) {
+    let this = env.current_contract_address();
+    let pull: bool =
+        env.invoke_contract(vault, &Symbol::new(env, "is_allowance_pull"), Vec::new(env));
+    if pull {
+        token::Client::new(env, stablecoin).approve(
+            &this,
+            vault,
+            &amount,
+            &env.ledger().sequence(),
+        );
+        return;
+    }
+
+    let quote: SubscriptionQuote = env.invoke_contract(
+        vault,
+        &Symbol::new(env, "preview_subscribe"),
+        vec![env, series_id.into(), amount.into_val(env)],
+    );
+    let collector_fee = quote.fee - quote.issuer_fee;
+    let mut entries = vec![
+        env,
+        transfer_entry(env, stablecoin, vault, amount - collector_fee),
+    ];
+    if collector_fee > 0 {
+        let collector: Address =
+            env.invoke_contract(vault, &Symbol::new(env, "get_fee_collector"), Vec::new(env));
+        entries.push_back(transfer_entry(env, stablecoin, &collector, collector_fee));
+    }
+    env.authorize_as_current_contract(entries);
+}
+
+fn transfer_entry(
+    env: &Env,
+    stablecoin: &Address,
+    to: &Address,
+    amount: i128,
+) -> InvokerContractAuthEntry {
+    InvokerContractAuthEntry::Contract(SubContractInvocation {
+        context: ContractContext {
+            contract: stablecoin.clone(),
+            fn_name: Symbol::new(env, "transfer"),
+            args: vec![
+                env,
+                env.current_contract_address().to_val(),
+                to.to_val(),
+                amount.into_val(env),
+            ],
+        },
+        sub_invocations: Vec::new(env),
+    })
+}
@@ -0,0 +1,312 @@
+use soroban_sdk::Map;
+
+use crate::storage::{ProtocolAccounting, Series, SeriesTally, WithdrawalLimits, BASIS_POINTS};
+
+/// Calculate total value locked
+///
+/// Formula: tvl = stablecoin_held + total_lent
+///
+/// Outstanding repo loans are collateral-backed receivables, so they count
+/// toward TVL even though the stablecoin has left the vault.
+pub fn calculate_tvl(stablecoin_held: i128, total_lent: i128) -> Option<i128> {
+    stablecoin_held.checked_add(total_lent)
+}
+
+/// Calculate utilization in basis points
+///
+/// Formula: utilization = total_lent × 10,000 / (total_lent + idle)
+///
+/// Example:
+/// - total_lent: 250,000
+/// - idle: 750,000
+/// - utilization: 2,500 bps (25%)
+pub fn calculate_utilization_bps(total_lent: i128, idle: i128) -> Option<i128> {
+    let total = total_lent.checked_add(idle)?;
+    if total <= 0 {
+        return Some(0);
+    }
+    total_lent.checked_mul(BASIS_POINTS)?.checked_div(total)
+}
+
+/// Calculate the stablecoin balance the vault should hold per its accounting
+///
+/// Formula: expected = subscriptions + repo_revenue - reported_repo_revenue + settlements
+///                     - redeemed - referral_paid - dust_claimed - treasury_withdrawals
+///                     - emergency_withdrawals - fees_collected + issuer_fees_received
+///                     - issuer_fees_claimed
+///                     + referral_rewards_funded + queued_payouts
+///
+/// Repo loans and the interest reported on them are funded and collected
+/// by the treasury, never the vault, so `total_lent` and the reported part
+/// of repo revenue stay out of the vault's cash.
+pub fn calculate_expected_balance(
+    accounting: &ProtocolAccounting,
+    reported_repo_revenue: i128,
+) -> Option<i128> {
+    accounting
+        .total_subscriptions_collected
+        .checked_add(accounting.total_repo_revenue)?
+        .checked_sub(reported_repo_revenue)?
+        .checked_add(accounting.total_settlement_deposits)?
+        .checked_sub(accounting.total_redeemed)?
+        .checked_sub(accounting.total_referral_rewards_paid)?
+        .checked_sub(accounting.total_dust_claimed)?
+        .checked_sub(accounting.total_treasury_withdrawals)?
+        .checked_sub(accounting.total_emergency_withdrawals)?
+        .checked_sub(accounting.total_fees_collected)?
+        .checked_add(accounting.total_issuer_fees_received)?
+        .checked_sub(accounting.total_issuer_fees_claimed)?
+        .checked_add(accounting.total_referral_rewards_funded)?
+        .checked_add(accounting.queued_payouts)
+}
+
+/// Add a series' subscription tally to the protocol totals
+pub fn apply_series_tally(accounting: &mut ProtocolAccounting, tally: &SeriesTally) -> Option<()> {
+    accounting.total_subscriptions_collected = accounting
+        .total_subscriptions_collected
+        .checked_add(tally.subscriptions_collected)?;
+    accounting.total_par_minted = accounting.total_par_minted.checked_add(tally.par_minted)?;
+    accounting.total_subscription_fees = accounting
+        .total_subscription_fees
+        .checked_add(tally.subscription_fees)?;
+    accounting.total_issuer_fees_received = accounting
+        .total_issuer_fees_received
+        .checked_add(tally.issuer_fees_received)?;
+    accounting.total_referral_rewards_funded = accounting
+        .total_referral_rewards_funded
+        .checked_add(tally.referral_rewards_funded)?;
+    Some(())
+}
+
+/// Calculate the referral reward carved out of a subscription's protocol fee
+///
+/// Formula: reward = fee_amount × reward_bps / 10,000
+///
+/// Example:
+/// - fee_amount: 10,000
+/// - reward_bps: 2,500 (25%)
+/// - reward: 2,500
+pub fn calculate_referral_reward(fee_amount: i128, reward_bps: u32) -> Option<i128> {
+    fee_amount
+        .checked_mul(reward_bps as i128)?
+        .checked_div(BASIS_POINTS)
+}
+
+/// Calculate the keeper fee taken from auto-redeem proceeds
+///
+/// Formula: fee = payout × fee_bps / 10,000
+pub fn calculate_keeper_fee(payout: i128, fee_bps: u32) -> Option<i128> {
+    payout.checked_mul(fee_bps as i128)?.checked_div(BASIS_POINTS)
+}
+
+/// Calculate the protocol fee skimmed off a subscription
+///
+/// Formula: fee = pay_amount × fee_bps / 10,000
+///
+/// Rounds down, so the fee never exceeds the quoted rate.
+pub fn calculate_subscription_fee(pay_amount: i128, fee_bps: u32) -> Option<i128> {
+    pay_amount
+        .checked_mul(fee_bps as i128)?
+        .checked_div(BASIS_POINTS)
+}
+
+/// Calculate the fee withheld from a matured redemption payout
+///
+/// Formula: fee = payout × fee_bps / 10,000
+///
+/// Rounds down, in the holder's favour.
+pub fn calculate_redemption_fee(payout: i128, fee_bps: u32) -> Option<i128> {
+    payout
+        .checked_mul(fee_bps as i128)?
+        .checked_div(BASIS_POINTS)
+}
+
+/// Calculate an issuer's cut of a fee
+///
+/// Formula: share = fee × share_bps / 10,000
+///
+/// Rounds down; the protocol keeps the remainder.
+pub fn calculate_fee_share(fee: i128, share_bps: u32) -> Option<i128> {
+    fee.checked_mul(share_bps as i128)?
+        .checked_div(BASIS_POINTS)
+}
+
+/// Calculate PAR still owed to holders of a series
+///
+/// Formula: outstanding = minted_par - redeemed_par
+pub fn calculate_outstanding_par(series: &Series) -> i128 {
+    series.minted_par.saturating_sub(series.redeemed_par)
+}
+
+/// Calculate how much of the redemption liability the vault balance covers
+///
+/// Formula: health = balance × 10,000 / liability
+///
+/// With nothing owed the vault counts as exactly covered (10,000 bps).
+pub fn calculate_health_ratio_bps(balance: i128, liability: i128) -> Option<i128> {
+    if liability <= 0 {
+        return Some(BASIS_POINTS);
+    }
+    balance
+        .max(0)
+        .checked_mul(BASIS_POINTS)?
+        .checked_div(liability)
+}
+
+/// Validate treasury withdrawal limits
+///
+/// Limits can't be negative, and a set weekly cap can't undercut a set daily cap.
+pub fn validate_withdrawal_limits(limits: &WithdrawalLimits) -> bool {
+    if limits.daily_limit < 0 || limits.weekly_limit < 0 {
+        return false;
+    }
+    limits.daily_limit == 0 || limits.weekly_limit == 0 || limits.weekly_limit >= limits.daily_limit
+}
+
+/// Sum of the `window` buckets ending at `now_bucket`
+pub fn rolling_window_total(outflows: &Map<u64, i128>, now_bucket: u64, window: u64) -> i128 {
+    let start = (now_bucket + 1).saturating_sub(window);
+    let mut total: i128 = 0;
+    for (bucket, amount) in outflows.iter() {
+        if bucket >= start && bucket <= now_bucket {
+            total = total.saturating_add(amount);
+        }
+    }
+    total
+}
+
+/// Whether adding `amount` to `used` breaches `limit` (0 = unlimited)
+pub fn exceeds_withdrawal_limit(used: i128, amount: i128, limit: i128) -> bool {
+    limit > 0 && used.saturating_add(amount) > limit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SCALE;
+    use soroban_sdk::Env;
+
+    #[test]
+    fn test_calculate_tvl() {
+        let tvl = calculate_tvl(750_000 * SCALE, 250_000 * SCALE).unwrap();
+        assert_eq!(tvl, 1_000_000 * SCALE);
+    }
+
+    #[test]
+    fn test_calculate_utilization() {
+        let utilization = calculate_utilization_bps(250_000 * SCALE, 750_000 * SCALE).unwrap();
+        assert_eq!(utilization, 2_500); // 25%
+    }
+
+    #[test]
+    fn test_utilization_empty_vault() {
+        assert_eq!(calculate_utilization_bps(0, 0), Some(0));
+    }
+
+    #[test]
+    fn test_calculate_expected_balance() {
+        let accounting = ProtocolAccounting {
+            total_subscriptions_collected: 1_000_000 * SCALE,
+            total_par_minted: 1_020_000 * SCALE,
+            total_lent: 200_000 * SCALE,
+            total_repo_revenue: 4_000 * SCALE,
+            total_defaults: 0,
+            total_redeemed: 100_000 * SCALE,
+            total_settlement_deposits: 50_000 * SCALE,
+            total_referral_rewards_paid: 1_000 * SCALE,
+            total_dust_claimed: 1_000 * SCALE,
+            queued_payouts: 0,
+            total_early_penalties: 0,
+            total_treasury_withdrawals: 2_000 * SCALE,
+            total_subscription_fees: 3_000 * SCALE,
+            total_redemption_fees: 800 * SCALE,
+            total_fees_collected: 500 * SCALE,
+            total_issuer_fees_received: 300 * SCALE,
+            total_issuer_fees_claimed: 100 * SCALE,
+            total_referral_rewards_funded: 1_200 * SCALE,
+            total_emergency_withdrawals: 400 * SCALE,
+        };
+
+        // 1,000,000 + 4,000 - 1,000 + 50,000 - 100,000 - 1,000 - 1,000 - 2,000 - 400
+        //   - 500 + 300 - 100 + 1,200 = 949,500 (the 200,000 lent never left the vault)
+        let expected = calculate_expected_balance(&accounting, 1_000 * SCALE).unwrap();
+        assert_eq!(expected, 949_500 * SCALE);
+    }
+
+    #[test]
+    fn test_calculate_health_ratio() {
+        let ratio = calculate_health_ratio_bps(980 * SCALE, 1_000 * SCALE);
+        assert_eq!(ratio, Some(9_800));
+        let ratio = calculate_health_ratio_bps(1_500 * SCALE, 1_000 * SCALE);
+        assert_eq!(ratio, Some(15_000));
+        assert_eq!(calculate_health_ratio_bps(5 * SCALE, 0), Some(BASIS_POINTS));
+    }
+
+    #[test]
+    fn test_calculate_keeper_fee() {
+        let fee = calculate_keeper_fee(1_000 * SCALE, 10).unwrap();
+        assert_eq!(fee, SCALE); // 0.1%
+    }
+
+    #[test]
+    fn test_calculate_subscription_fee() {
+        let fee = calculate_subscription_fee(1_000 * SCALE, 50).unwrap();
+        assert_eq!(fee, 5 * SCALE); // 0.5%
+        assert_eq!(calculate_subscription_fee(199, 50), Some(0));
+        assert_eq!(calculate_subscription_fee(1_000 * SCALE, 0), Some(0));
+    }
+
+    #[test]
+    fn test_calculate_redemption_fee() {
+        let fee = calculate_redemption_fee(1_000 * SCALE, 25).unwrap();
+        assert_eq!(fee, 25 * SCALE / 10); // 0.25%
+        assert_eq!(calculate_redemption_fee(399, 25), Some(0));
+    }
+
+    #[test]
+    fn test_calculate_fee_share() {
+        assert_eq!(calculate_fee_share(10 * SCALE, 3_000), Some(3 * SCALE)); // 30%
+        assert_eq!(calculate_fee_share(3, 5_000), Some(1));
+        assert_eq!(calculate_fee_share(10 * SCALE, 0), Some(0));
+    }
+
+    #[test]
+    fn test_calculate_referral_reward() {
+        let reward = calculate_referral_reward(10_000 * SCALE, 2_500).unwrap();
+        assert_eq!(reward, 2_500 * SCALE); // 25% of the fee
+        assert_eq!(
+            calculate_referral_reward(10 * SCALE, 10_000),
+            Some(10 * SCALE)
+        );
+        assert_eq!(calculate_referral_reward(10_000 * SCALE, 0), Some(0));
+    }
+
+    #[test]
+    fn test_withdrawal_limits() {
+        let limits = |daily_limit, weekly_limit| WithdrawalLimits {
+            daily_limit,
+            weekly_limit,
+        };
+        assert!(validate_withdrawal_limits(&limits(100, 500)));
+        assert!(validate_withdrawal_limits(&limits(100, 0)));
+        assert!(!validate_withdrawal_limits(&limits(500, 100)));
+        assert!(!validate_withdrawal_limits(&limits(-1, 0)));
+
+        assert!(exceeds_withdrawal_limit(80, 30, 100));
+        assert!(!exceeds_withdrawal_limit(80, 20, 100));
+        assert!(!exceeds_withdrawal_limit(80, 1_000, 0));
+    }
+
+    #[test]
+    fn test_rolling_window_total() {
+        let env = Env::default();
+        let mut outflows = Map::new(&env);
+        outflows.set(9, 5 * SCALE);
+        outflows.set(30, 7 * SCALE);
+        outflows.set(33, 11 * SCALE);
+
+        assert_eq!(rolling_window_total(&outflows, 33, 24), 18 * SCALE);
+        assert_eq!(rolling_window_total(&outflows, 33, 168), 23 * SCALE);
+        assert_eq!(rolling_window_total(&outflows, 60, 24), 0);
+    }
+}
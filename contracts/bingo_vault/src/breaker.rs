@@ -0,0 +1,108 @@
+use crate::storage::{BreakerState, CircuitBreaker};
+
+/// Validate a circuit breaker config
+///
+/// The window, cap and pause length must all be positive.
+pub fn validate_circuit_breaker(breaker: &CircuitBreaker) -> bool {
+    breaker.window_ledgers > 0 && breaker.max_window_par > 0 && breaker.pause_ledgers > 0
+}
+
+/// Whether subscriptions are halted at `ledger`
+pub fn is_tripped(state: &BreakerState, ledger: u32) -> bool {
+    ledger < state.tripped_until
+}
+
+/// Add `minted_par` to the current window, rolling it if it has elapsed
+///
+/// Returns the new state and, if this subscription tripped the breaker, the
+/// window total that tripped it. The tripping subscription itself goes
+/// through; only later ones halt.
+///
+/// Example:
+/// - window: 100 ledgers, cap: 50k PAR, pause: 500 ledgers
+/// - 30k PAR at ledger 10, then 25k PAR at ledger 60
+/// - window total 55k > 50k → halted until ledger 560
+pub fn record_window_volume(
+    state: Option<BreakerState>,
+    breaker: &CircuitBreaker,
+    ledger: u32,
+    minted_par: i128,
+) -> (BreakerState, Option<i128>) {
+    let mut state = match state {
+        Some(state) if ledger < state.window_start.saturating_add(breaker.window_ledgers) => state,
+        Some(state) => BreakerState {
+            window_start: ledger,
+            window_par: 0,
+            tripped_until: state.tripped_until,
+        },
+        None => BreakerState {
+            window_start: ledger,
+            window_par: 0,
+            tripped_until: 0,
+        },
+    };
+
+    state.window_par = state.window_par.saturating_add(minted_par);
+
+    if state.window_par <= breaker.max_window_par {
+        return (state, None);
+    }
+
+    let window_par = state.window_par;
+    state.tripped_until = ledger.saturating_add(breaker.pause_ledgers);
+    state.window_start = ledger;
+    state.window_par = 0;
+
+    (state, Some(window_par))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SCALE;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker {
+            window_ledgers: 100,
+            max_window_par: 50_000 * SCALE,
+            pause_ledgers: 500,
+        }
+    }
+
+    #[test]
+    fn test_validate_circuit_breaker() {
+        assert!(validate_circuit_breaker(&breaker()));
+        assert!(!validate_circuit_breaker(&CircuitBreaker {
+            window_ledgers: 0,
+            ..breaker()
+        }));
+        assert!(!validate_circuit_breaker(&CircuitBreaker {
+            max_window_par: 0,
+            ..breaker()
+        }));
+    }
+
+    #[test]
+    fn test_trips_within_window() {
+        let (state, tripped) = record_window_volume(None, &breaker(), 10, 30_000 * SCALE);
+        assert_eq!(tripped, None);
+        assert_eq!(state.window_par, 30_000 * SCALE);
+
+        let (state, tripped) = record_window_volume(Some(state), &breaker(), 60, 25_000 * SCALE);
+        assert_eq!(tripped, Some(55_000 * SCALE));
+        assert_eq!(state.tripped_until, 560);
+        assert!(is_tripped(&state, 559));
+        assert!(!is_tripped(&state, 560));
+    }
+
+    #[test]
+    fn test_window_rolls_over() {
+        let (state, _) = record_window_volume(None, &breaker(), 10, 30_000 * SCALE);
+
+        // Ledger 110 starts a fresh window
+        let (state, tripped) = record_window_volume(Some(state), &breaker(), 110, 25_000 * SCALE);
+        assert_eq!(tripped, None);
+        assert_eq!(state.window_start, 110);
+        assert_eq!(state.window_par, 25_000 * SCALE);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::storage::{BASIS_POINTS, PAR_UNIT, SECONDS_PER_YEAR};
+use soroban_sdk::Vec;
+
+/// Annualized simple yield implied by a discount price, in basis points
+///
+/// Formula: yield_bps = (PAR_UNIT - price) / price × SECONDS_PER_YEAR / remaining × 10,000
+///
+/// Example:
+/// - price: 0.98, remaining: half a year
+/// - yield: (0.02 / 0.98) × 2 = 4.08% = 408 bps
+pub fn calculate_implied_yield_bps(price: i128, remaining_secs: u64) -> Option<i128> {
+    if price <= 0 || remaining_secs == 0 {
+        return None;
+    }
+
+    PAR_UNIT
+        .checked_sub(price)?
+        .checked_mul(BASIS_POINTS)?
+        .checked_mul(SECONDS_PER_YEAR as i128)?
+        .checked_div(price.checked_mul(remaining_secs as i128)?)
+}
+
+/// Yield at `tenor` on a curve of (remaining_secs, yield_bps) points
+///
+/// `points` must be ascending by remaining_secs. Interpolates linearly
+/// between neighbours and holds the end yields flat beyond the curve.
+/// Returns None for an empty curve.
+///
+/// Example:
+/// - points: [(30d, 100), (90d, 160)]
+/// - tenor 60d: 130; tenor 7d: 100; tenor 365d: 160
+pub fn interpolate_yield_bps(points: &Vec<(u64, i128)>, tenor: u64) -> Option<i128> {
+    let (first_secs, first_yield) = points.first()?;
+    if tenor <= first_secs {
+        return Some(first_yield);
+    }
+
+    let (mut prev_secs, mut prev_yield) = (first_secs, first_yield);
+    for (secs, yield_bps) in points.iter().skip(1) {
+        if tenor <= secs {
+            let span = (secs - prev_secs) as i128;
+            if span == 0 {
+                return Some(yield_bps);
+            }
+            let offset = (tenor - prev_secs) as i128;
+            return yield_bps
+                .checked_sub(prev_yield)?
+                .checked_mul(offset)?
+                .checked_div(span)?
+                .checked_add(prev_yield);
+        }
+        prev_secs = secs;
+        prev_yield = yield_bps;
+    }
+
+    Some(prev_yield)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SCALE;
+    use soroban_sdk::{vec, Env};
+
+    const DAY: u64 = 86_400;
+
+    #[test]
+    fn test_implied_yield() {
+        let half_year = SECONDS_PER_YEAR / 2;
+        assert_eq!(
+            calculate_implied_yield_bps(98 * SCALE / 100, half_year),
+            Some(408)
+        );
+        assert_eq!(calculate_implied_yield_bps(PAR_UNIT, half_year), Some(0));
+        assert_eq!(calculate_implied_yield_bps(98 * SCALE / 100, 0), None);
+    }
+
+    #[test]
+    fn test_interpolate_yield() {
+        let env = Env::default();
+        let points = vec![&env, (30 * DAY, 100), (90 * DAY, 160)];
+
+        assert_eq!(interpolate_yield_bps(&points, 60 * DAY), Some(130));
+        assert_eq!(interpolate_yield_bps(&points, 7 * DAY), Some(100));
+        assert_eq!(interpolate_yield_bps(&points, 365 * DAY), Some(160));
+        assert_eq!(interpolate_yield_bps(&Vec::new(&env), 30 * DAY), None);
+    }
+}
@@ -9,14 +9,19 @@ pub enum Error {
     // ============================================
     /// Contract already initialized
     AlreadyInitialized = 1,
-    /// Contract not initialized
+    /// Contract not initialized, or storage not yet migrated to this wasm's schema
     NotInitialized = 2,
     
     // ============================================
     // AUTHORIZATION ERRORS (10-15)
     // ============================================
-    /// Caller not authorized (not admin/treasury)
+    /// Caller not authorized (not admin/treasury), issuer not approved for these terms,
+    /// payout asset not offered (or without a converter), or account on the blocklist
     Unauthorized = 10,
+    /// User not KYC-verified on a series that requires it
+    KYCRequired = 11,
+    /// Nonce-guarded admin or treasury call signed with a stale nonce or past its expiry
+    InvalidAuthNonce = 12,
     
     // ============================================
     // SERIES MANAGEMENT ERRORS (20-29)
@@ -31,6 +36,12 @@ pub enum Error {
     SeriesNotMatured = 23,
     /// Invalid series status transition
     InvalidStatus = 24,
+    /// Series still has PAR outstanding
+    SeriesHasOutstandingPar = 25,
+    /// Early redemption not enabled for this series
+    EarlyRedemptionDisabled = 26,
+    /// Series cap_par is fully minted; subscriptions reopen if the cap is raised
+    SeriesFullySubscribed = 27,
     
     // ============================================
     // SUBSCRIPTION ERRORS (30-39)
@@ -39,28 +50,116 @@ pub enum Error {
     ExceedsSeriesCap = 30,
     /// Subscription would exceed user's cap_par
     ExceedsUserCap = 31,
+    /// Relayed subscription signed with an expiry that has passed
+    MetaTxExpired = 32,
+    /// Relayed subscription nonce does not match the user's next nonce
+    InvalidNonce = 33,
+    /// Series circuit breaker tripped; subscriptions halted for now
+    SubscriptionsHalted = 34,
+    /// pay_amount below the series' min_subscription
+    BelowMinimum = 35,
+    /// No subscription receipt with this ID
+    ReceiptNotFound = 36,
     
     // ============================================
     // AMOUNT/BALANCE ERRORS (40-49)
     // ============================================
-    /// Amount must be positive
+    /// Amount must be positive, or the stablecoin delivered a different amount than was sent
     InvalidAmount = 40,
     /// User doesn't have enough bT-Bills
     InsufficientBalance = 41,
+    /// Payout conversion delivered less than min_out
+    SlippageExceeded = 42,
+    /// Converted redemption would jump the redemption lane queue
+    PayoutWouldQueue = 43,
     
     // ============================================
     // TIMESTAMP/VALIDATION ERRORS (50-59)
     // ============================================
     /// Maturity date must be after issue date
     InvalidTimestamp = 50,
-    /// Issue price must be between 0 and PAR_UNIT
-    InvalidIssuePrice = 51,
-    /// Cap amounts must be positive and user_cap <= series_cap
+    /// Cap amounts must be positive, user_cap <= series_cap, and within the issuer's limit
     InvalidCapAmounts = 52,
+    /// Config rejected: circuit breaker window, cap or pause not positive, loyalty tiers
+    /// not ascending or multiplier below 1×, issuance envelope (or issuer limit) bounds
+    /// inconsistent, negative dust threshold, inconsistent withdrawal limits, keeper
+    /// fee above MAX_KEEPER_FEE_BPS, referral reward above 10,000 bps, issue price
+    /// outside (0, PAR_UNIT], or asset decimals above MAX_ASSET_DECIMALS or changed
+    /// after the first series
+    InvalidConfig = 53,
+    /// Amount (or leftover balance under DustMode::Reject) below the dust threshold
+    BelowDustThreshold = 56,
     
     // ============================================
     // OPERATIONAL ERRORS (60-69)
     // ============================================
-    /// Contract is paused
+    /// Contract is paused, or the series is paused by `pause_series`
     ContractPaused = 60,
+    /// create_series/activate_series blocked while issuance is frozen
+    IssuanceFrozen = 61,
+
+    // ============================================
+    // EMERGENCY ERRORS (70-79)
+    // ============================================
+    /// No queued emergency withdrawal matches the request
+    NoPendingWithdrawal = 70,
+    /// Emergency withdrawal timelock has not elapsed
+    TimelockNotElapsed = 71,
+    /// Withdrawal would leave less stablecoin than matured redemption liability
+    BreachesRedemptionLiability = 72,
+    /// No queued admin action with this ID
+    NoPendingAction = 73,
+    /// Queued admin action not yet executable, or a timelocked admin change was
+    /// called directly while an admin delay is set
+    ActionTimelocked = 74,
+
+    // ============================================
+    // SETTLEMENT ERRORS (80-89)
+    // ============================================
+    /// Sender is not a registered settlement source
+    UnregisteredSettlementSender = 80,
+    /// No unexpected inflow to sweep
+    NothingToSweep = 81,
+    /// Series is being settled in treasury-run batches
+    SeriesSettling = 82,
+    /// Settlement batch range is empty or past the holder list, or no batch is pending
+    InvalidBatchRange = 83,
+
+    // ============================================
+    // REFERRAL ERRORS (90-99)
+    // ============================================
+    /// Referrer has no unclaimed rewards
+    NoReferralRewards = 91,
+
+    // ============================================
+    // DELEGATION ERRORS (100-109)
+    // ============================================
+    /// No delegation from this user to this delegate
+    DelegationNotFound = 100,
+    /// Delegation has expired
+    DelegationExpired = 101,
+    /// Action not covered by the delegation
+    ActionNotDelegated = 102,
+    /// Amount exceeds the delegation's remaining allowance
+    ExceedsDelegationLimit = 103,
+
+    // ============================================
+    // AUTOMATION ERRORS (110-119)
+    // ============================================
+    /// User has not opted into this automation (or allowance is used up)
+    AutomationNotEnabled = 110,
+    /// Nothing for the keeper to execute
+    NothingToAutomate = 111,
+    /// repo_market address not configured
+    RepoMarketNotSet = 112,
+
+    // ============================================
+    // OBSERVER ERRORS (120-129)
+    // ============================================
+    /// Observer already registered
+    ObserverAlreadyRegistered = 120,
+    /// Observer not registered
+    ObserverNotRegistered = 121,
+    /// Registry already holds MAX_OBSERVERS
+    TooManyObservers = 122,
 }
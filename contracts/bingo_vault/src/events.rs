@@ -1,4 +1,25 @@
-use soroban_sdk::{contracttype, Address};
+use crate::storage::{
+    AdminAction, AutomationAction, CircuitBreaker, DelegatedAction, DustPolicy, EarlyRedemption,
+    IssuanceEnvelope, IssuerFeeSplit, IssuerLimits, LoyaltyTier, RedemptionLane, RiskFlag, Role,
+    SeriesStatus, ShadowFeature, WithdrawalLimits, YieldCurve,
+};
+use soroban_sdk::{contracttype, Address, BytesN, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContractUpgradedEvent {
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SchemaMigratedEvent {
+    /// 0 for deployments that predate the schema registry
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Keys whose layout hash changed (or is new)
+    pub changed_keys: Vec<Symbol>,
+}
 
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -9,6 +30,7 @@ pub struct SeriesCreatedEvent {
     pub issue_price: i128,
     pub cap_par: i128,
     pub user_cap_par: i128,
+    pub issuer: Address,
 }
 
 #[contracttype]
@@ -17,10 +39,63 @@ pub struct SeriesActivatedEvent {
     pub series_id: u32,
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SeriesCapsUpdatedEvent {
+    pub series_id: u32,
+    pub cap_par: i128,
+    pub user_cap_par: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MinSubscriptionUpdatedEvent {
+    pub series_id: u32,
+    /// In asset units; 0 when cleared
+    pub min_subscription: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CapExemptionUpdatedEvent {
+    pub account: Address,
+    pub exempt: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct KycRegistryUpdatedEvent {
+    pub registry: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct KycStatusUpdatedEvent {
+    pub account: Address,
+    pub verified: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BlocklistUpdatedEvent {
+    pub account: Address,
+    pub blocked: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SeriesKycUpdatedEvent {
+    pub series_id: u32,
+    pub required: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct SubscribedEvent {
+    pub receipt_id: u64,
     pub series_id: u32,
+    /// Account the stablecoin came from; `user` unless subscribed on behalf
+    pub payer: Address,
     pub user: Address,
     pub pay_amount: i128,
     pub minted_par: i128,
@@ -33,11 +108,562 @@ pub struct RedeemedEvent {
     pub series_id: u32,
     pub user: Address,
     pub bt_bill_amount: i128,
+    /// Paid to the holder, net of `fee` and `keeper_fee`
+    pub payout: i128,
+    pub fee: i128,
+    /// Cut taken by the keeper on `auto_redeem`, 0 otherwise
+    pub keeper_fee: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RedemptionRedirectedEvent {
+    pub series_id: u32,
+    pub user: Address,
+    /// Account the payout is paid (or queued) to
+    pub recipient: Address,
+    pub payout: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RefundClaimedEvent {
+    pub series_id: u32,
+    pub user: Address,
+    pub bt_bill_amount: i128,
+    pub refund: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EarlyRedeemedEvent {
+    pub series_id: u32,
+    pub user: Address,
+    pub bt_bill_amount: i128,
+    pub price: i128,
+    pub penalty: i128,
     pub payout: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EarlyRedemptionUpdatedEvent {
+    pub series_id: u32,
+    pub early_redemption: EarlyRedemption,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct SeriesMaturedEvent {
     pub series_id: u32,
 }
+
+/// Emitted when a subscription mints the last PAR of a series' cap
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SeriesFullySubscribedEvent {
+    pub series_id: u32,
+    pub cap_par: i128,
+}
+
+/// Emitted for every series status transition
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StatusChangedEvent {
+    pub series_id: u32,
+    pub from: SeriesStatus,
+    pub to: SeriesStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReconciliationEvent {
+    pub expected_balance: i128,
+    pub actual_balance: i128,
+    pub delta: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EmergencyWithdrawQueuedEvent {
+    pub token: Address,
+    pub amount: i128,
+    pub to: Address,
+    pub executable_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdminActionQueuedEvent {
+    pub action_id: u64,
+    pub action: AdminAction,
+    pub executable_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdminActionEvent {
+    pub action_id: u64,
+    pub action: AdminAction,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdminDelayUpdatedEvent {
+    pub delay_secs: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TreasuryWithdrawalEvent {
+    pub treasury: Address,
+    pub amount: i128,
+    /// Stablecoin left in the vault afterwards
+    pub remaining_balance: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FeeChargedEvent {
+    pub series_id: u32,
+    pub user: Address,
+    pub collector: Address,
+    /// Gross amount paid by the user, fee included
+    pub pay_amount: i128,
+    pub fee: i128,
+    /// Part of `fee` credited to the series issuer (the rest went to the collector)
+    pub issuer_fee: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SubscriptionFeeUpdatedEvent {
+    pub fee_bps: u32,
+    pub collector: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PayoutConverterUpdatedEvent {
+    pub asset: Address,
+    /// None when the converter is removed
+    pub converter: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PayoutAssetsUpdatedEvent {
+    pub series_id: u32,
+    pub assets: Vec<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RedeemedToAssetEvent {
+    pub series_id: u32,
+    pub user: Address,
+    pub bt_bill_amount: i128,
+    /// Stablecoin payout handed to the converter
+    pub payout: i128,
+    pub payout_asset: Address,
+    /// Amount of payout_asset the user received
+    pub amount_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ShadowModeUpdatedEvent {
+    pub feature: ShadowFeature,
+    pub enabled: bool,
+}
+
+/// What a shadowed feature would have done to one user action
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ShadowOutcomeEvent {
+    pub feature: ShadowFeature,
+    pub series_id: u32,
+    pub user: Address,
+    /// Fee that would have been charged, or PAR that would have been halted
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RedemptionFeeUpdatedEvent {
+    /// None for the default fee
+    pub series_id: Option<u32>,
+    /// None when a series override is cleared
+    pub fee_bps: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FeesCollectedEvent {
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RedemptionsFundedEvent {
+    pub series_id: u32,
+    pub amount: i128,
+    pub funded: i128,
+    pub required: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EmergencyWithdrawEvent {
+    pub token: Address,
+    pub amount: i128,
+    pub to: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SettlementSenderUpdatedEvent {
+    pub sender: Address,
+    pub allowed: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SettlementDepositedEvent {
+    pub from: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct UnexpectedInflowSweptEvent {
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReferralRewardUpdatedEvent {
+    pub reward_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReferralAttributedEvent {
+    pub user: Address,
+    pub referrer: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReferralRewardAccruedEvent {
+    pub referrer: Address,
+    pub user: Address,
+    pub series_id: u32,
+    pub reward: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReferralRewardsClaimedEvent {
+    pub referrer: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LoyaltyTiersUpdatedEvent {
+    pub tiers: Vec<LoyaltyTier>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RewardsHookUpdatedEvent {
+    pub hook: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DelegationGrantedEvent {
+    pub user: Address,
+    pub delegate: Address,
+    pub actions: Vec<DelegatedAction>,
+    pub limit_par: i128,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DelegationRevokedEvent {
+    pub user: Address,
+    pub delegate: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AutomationUpdatedEvent {
+    pub user: Address,
+    pub action: AutomationAction,
+    pub allowance: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AutomationExecutedEvent {
+    pub user: Address,
+    pub action: AutomationAction,
+    pub target_id: u64,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AutoRedeemEvent {
+    pub series_id: u32,
+    pub keeper: Address,
+    pub users_redeemed: u32,
+    pub total_redeemed: i128,
+    pub keeper_fees: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IssuanceEnvelopeUpdatedEvent {
+    pub envelope: IssuanceEnvelope,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IssuerApprovedEvent {
+    pub issuer: Address,
+    pub limits: IssuerLimits,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IssuerRevokedEvent {
+    pub issuer: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IssuerFeeSplitUpdatedEvent {
+    pub issuer: Address,
+    pub split: IssuerFeeSplit,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RepoBookedEvent {
+    pub position_id: u64,
+    /// Principal lent, repaid or written off
+    pub principal: i128,
+    /// Interest booked as repo revenue (repayments only)
+    pub revenue: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RepoSpreadBookedEvent {
+    pub series_id: u32,
+    pub from: Address,
+    pub amount: i128,
+    /// Part of `amount` credited to the series issuer (the rest accrues to the protocol)
+    pub issuer_fee: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IssuerFeesClaimedEvent {
+    pub issuer: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SeriesArchivedEvent {
+    pub series_id: u32,
+    pub holders_pruned: u32,
+    /// sha256 of the series' final XDR
+    pub state_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SeriesStorageMigratedEvent {
+    pub series_id: u32,
+    pub positions_migrated: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ObserverUpdatedEvent {
+    pub observer: Address,
+    pub registered: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LargeSubscriptionThresholdUpdatedEvent {
+    pub threshold_par: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerUpdatedEvent {
+    pub series_id: u32,
+    pub breaker: CircuitBreaker,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerClearedEvent {
+    pub series_id: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerTrippedEvent {
+    pub series_id: u32,
+    pub window_par: i128,
+    pub max_window_par: i128,
+    pub halted_until_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AssetDecimalsUpdatedEvent {
+    pub decimals: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct WithdrawalLimitsUpdatedEvent {
+    pub limits: WithdrawalLimits,
+}
+
+/// Admin co-signed a treasury withdrawal beyond a rolling limit
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct WithdrawalLimitCosignedEvent {
+    pub admin: Address,
+    pub amount: i128,
+    /// Withdrawn in each window before this withdrawal
+    pub daily_used: i128,
+    pub weekly_used: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DustPolicyUpdatedEvent {
+    pub policy: DustPolicy,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DustClaimedEvent {
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdminProposedEvent {
+    pub current_admin: Address,
+    pub proposed_admin: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdminTransferredEvent {
+    pub previous_admin: Address,
+    pub new_admin: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct YieldCurveCheckpointEvent {
+    pub curve: YieldCurve,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RoleUpdatedEvent {
+    pub role: Role,
+    pub previous_holder: Address,
+    pub new_holder: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RiskAlertEvent {
+    pub series_id: u32,
+    /// Flags now in force (empty = cleared)
+    pub flags: Vec<RiskFlag>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TreasuryUpdatedEvent {
+    pub previous_treasury: Address,
+    pub new_treasury: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RedemptionLaneUpdatedEvent {
+    pub lane: RedemptionLane,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RedemptionQueuedEvent {
+    pub user: Address,
+    pub series_id: u32,
+    pub amount: i128,
+    /// Entries ahead of this one in the queue
+    pub position: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct QueuedRedemptionPaidEvent {
+    pub user: Address,
+    pub series_id: u32,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SettlementBegunEvent {
+    pub series_id: u32,
+    pub holder_count: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SettlementBatchAnnouncedEvent {
+    pub series_id: u32,
+    pub batch_id: u32,
+    pub start: u32,
+    pub end: u32,
+    /// Funding the batch would need at current balances, in asset units
+    pub estimated_funding: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SettlementBatchProcessedEvent {
+    pub series_id: u32,
+    pub batch_id: u32,
+    pub funded: i128,
+    pub holders_paid: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SettlementCompletedEvent {
+    pub series_id: u32,
+    pub batches: u32,
+}
@@ -1,16 +1,59 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
+mod accounting;
+mod breaker;
+mod curve;
 mod error;
 mod events;
+mod lifecycle;
+mod loyalty;
 mod pricing;
+mod schema;
 mod storage;
+mod test;
 
-use error::Error;
+use accounting::{
+    apply_series_tally, calculate_expected_balance, calculate_fee_share,
+    calculate_health_ratio_bps, calculate_keeper_fee, calculate_outstanding_par,
+    calculate_redemption_fee, calculate_referral_reward, calculate_subscription_fee, calculate_tvl,
+    calculate_utilization_bps, exceeds_withdrawal_limit, rolling_window_total,
+    validate_withdrawal_limits,
+};
+use breaker::{is_tripped, record_window_volume, validate_circuit_breaker};
+use curve::{calculate_implied_yield_bps, interpolate_yield_bps};
+pub use error::Error;
 use events::*;
-use pricing::{calculate_current_price, calculate_minted_par};
-use storage::{DataKey, PAR_UNIT, Series, SeriesStatus, UserPosition};
+use lifecycle::{counts_as_matured, is_live, is_valid_transition};
+use loyalty::{calculate_effective_user_cap, resolve_loyalty_tier, validate_loyalty_tiers};
+use pricing::{
+    calculate_collateral_value, calculate_current_price, calculate_early_redemption,
+    calculate_minted_par, calculate_subscription_dust, from_asset_units, is_within_envelope,
+    to_asset_units,
+};
+use schema::{changed_keys, layout_hashes, STORAGE_SCHEMA_VERSION};
+pub use storage::{ActivityKind, ActivityRecord, RepoActivity, RiskFlag};
+use storage::{
+    AdminAction, AutomationAction, BreakerState, CircuitBreaker, CurvePoint, DataKey, DataKeyExt,
+    DelegatedAction, Delegation, DustMode, DustPolicy, EarlyRedemption, IssuanceEnvelope,
+    IssuerFeeAccount, IssuerFeeSplit, IssuerLimits, IssuerProfile, LiquidityForecast, LoyaltyTier,
+    MaturityFlow, PendingEmergencyWithdraw, PositionSnapshot, ProtocolAccounting,
+    QueuedAdminAction, QueuedRedemption, Reconciliation, RedemptionFunding, RedemptionLane,
+    RedemptionQuote, Role, Series, SeriesStatus, SeriesTally, SettlementBatch, SettlementPlan,
+    ShadowFeature, Solvency, StorageSchema, SubscriptionQuote, SubscriptionReceipt, UserPosition,
+    UserTier, VaultConfig, WithdrawalLimits, WithdrawalUsage, YieldCurve, BASIS_POINTS,
+    DAILY_WITHDRAWAL_BUCKETS, EMERGENCY_WITHDRAW_DELAY, MAX_ADMIN_DELAY, MAX_ASSET_DECIMALS,
+    MAX_EXPORT_PAGE, MAX_KEEPER_FEE_BPS, MAX_OBSERVERS, MAX_REDEMPTION_FEE_BPS,
+    MAX_SUBSCRIPTION_FEE_BPS, PAR_UNIT, RECEIPT_TTL_LEDGERS, SCALE_DECIMALS, STATE_TTL_LEDGERS,
+    STATE_TTL_THRESHOLD, WEEKLY_WITHDRAWAL_BUCKETS, WITHDRAWAL_BUCKET_SECONDS,
+    YIELD_CHECKPOINT_INTERVAL, YIELD_CURVE_TENORS,
+};
 
-use soroban_sdk::{contract, contractimpl, token, vec, Address, Env, IntoVal, Symbol};
+use bingo_events::{publish, publish_global, VAULT};
+use soroban_sdk::{
+    contract, contractimpl, token, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Map,
+    Symbol, TryFromVal, Val, Vec,
+};
 
 #[contract]
 pub struct BingoVault;
@@ -39,13 +82,17 @@ impl BingoVault {
         admin.require_auth();
 
         env.storage().instance().set(&DataKey::Initialized, &true);
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::Treasury, &treasury);
-        env.storage().instance().set(&DataKey::Stablecoin, &stablecoin);
-        env.storage()
-            .instance()
-            .set(&DataKey::BTBillToken, &bt_bill_token);
+        Self::save_config(
+            &env,
+            &VaultConfig {
+                admin,
+                treasury,
+                stablecoin,
+                bt_bill_token,
+            },
+        );
         env.storage().instance().set(&DataKey::Paused, &false);
+        Self::write_schema(&env, layout_hashes(&env));
 
         Ok(())
     }
@@ -54,14 +101,9 @@ impl BingoVault {
     ///
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
-    /// - `Unauthorized`: Caller is not admin
+    /// - `Unauthorized`: Caller is not the pauser
     pub fn pause(env: Env) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        admin.require_auth();
+        Self::require_role(&env, Role::Pauser)?;
 
         env.storage().instance().set(&DataKey::Paused, &true);
         Ok(())
@@ -71,545 +113,7173 @@ impl BingoVault {
     ///
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
-    /// - `Unauthorized`: Caller is not admin
+    /// - `Unauthorized`: Caller is not the pauser
     pub fn unpause(env: Env) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        admin.require_auth();
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::Pauser)?;
 
         env.storage().instance().set(&DataKey::Paused, &false);
         Ok(())
     }
 
-    // ============================================
-    // FLOW 1: TREASURY CREATES SERIES
-    // ============================================
-
-    /// Create a new T-Bill series
+    /// Halt subscriptions and redemptions on a single series
+    ///
+    /// Other series keep running; use `pause` to stop the whole protocol.
     ///
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
-    /// - `Unauthorized`: Caller is not treasury
-    /// - `ContractPaused`: Contract is paused
-    /// - `SeriesAlreadyExists`: Series ID already used
-    /// - `InvalidTimestamp`: Maturity must be after issue date
-    /// - `InvalidIssuePrice`: Price must be in range (0, PAR]
-    /// - `InvalidCapAmounts`: user_cap must be ≤ series_cap, both positive
-    pub fn create_series(
-        env: Env,
-        series_id: u32,
-        issue_date: u64,
-        maturity_date: u64,
-        issue_price: i128,
-        cap_par: i128,
-        user_cap_par: i128,
-    ) -> Result<(), Error> {
-        Self::check_not_paused(&env)?;
+    /// - `SeriesNotFound`: Series doesn't exist
+    pub fn pause_series(env: Env, series_id: u32) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::Pauser)?;
+        Self::load_series(&env, series_id)?;
 
-        let treasury: Address = env
-            .storage()
+        env.storage()
             .instance()
-            .get(&DataKey::Treasury)
-            .ok_or(Error::NotInitialized)?;
+            .set(&DataKeyExt::SeriesPaused(series_id), &true);
+        Ok(())
+    }
 
-        // Treasury must authorize this
-        treasury.require_auth();
+    /// Resume a series halted by `pause_series`
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn unpause_series(env: Env, series_id: u32) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::Pauser)?;
 
-        // Validate: Series doesn't already exist
-        if env
-            .storage()
+        env.storage()
             .instance()
-            .has(&DataKey::Series(series_id))
-        {
-            return Err(Error::SeriesAlreadyExists);
-        }
-
-        // Validate: Maturity after issue
-        if maturity_date <= issue_date {
-            return Err(Error::InvalidTimestamp);
-        }
-
-        // Validate: Issue price in valid range (0, PAR]
-        if issue_price <= 0 || issue_price > PAR_UNIT {
-            return Err(Error::InvalidIssuePrice);
-        }
+            .remove(&DataKeyExt::SeriesPaused(series_id));
+        Ok(())
+    }
 
-        // Validate: Cap amounts are valid
-        if cap_par <= 0 || user_cap_par <= 0 || user_cap_par > cap_par {
-            return Err(Error::InvalidCapAmounts);
-        }
+    pub fn is_series_paused(env: Env, series_id: u32) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::SeriesPaused(series_id))
+            .unwrap_or(false)
+    }
 
-        let series = Series {
-            series_id,
-            issue_date,
-            maturity_date,
-            par_unit: PAR_UNIT,
-            issue_price,
-            cap_par,
-            minted_par: 0,
-            user_cap_par,
-            status: SeriesStatus::Upcoming,
-            total_subscriptions_collected: 0,
-        };
+    /// Propose a new admin (step 1 of 2)
+    ///
+    /// The change only takes effect once `new_admin` calls `accept_admin`.
+    /// Replaces any earlier proposal.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn propose_admin(
+        env: Env,
+        new_admin: Address,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+        Self::consume_auth_nonce(&env, &admin, nonce, expiry)?;
 
         env.storage()
             .instance()
-            .set(&DataKey::Series(series_id), &series);
+            .set(&DataKey::PendingAdmin, &new_admin);
 
-        env.events().publish(
-            (Symbol::new(&env, "series_created"), series_id),
-            SeriesCreatedEvent {
-                series_id,
-                issue_date,
-                maturity_date,
-                issue_price,
-                cap_par,
-                user_cap_par,
+        publish_global(
+            &env,
+            VAULT,
+            "admin_proposed",
+            AdminProposedEvent {
+                current_admin: admin,
+                proposed_admin: new_admin,
             },
         );
 
         Ok(())
     }
 
-    // ============================================
-    // FLOW 2: TREASURY ACTIVATES SERIES
-    // ============================================
-
-    /// Activate a series for subscriptions
+    /// Accept a pending admin proposal (step 2 of 2)
     ///
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
-    /// - `Unauthorized`: Caller is not treasury
-    /// - `ContractPaused`: Contract is paused
-    /// - `SeriesNotFound`: Series doesn't exist
-    /// - `InvalidStatus`: Series not in UPCOMING status
-    pub fn activate_series(env: Env, series_id: u32) -> Result<(), Error> {
-        Self::check_not_paused(&env)?;
-
-        let treasury: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Treasury)
-            .ok_or(Error::NotInitialized)?;
-
-        // Treasury must authorize this
-        treasury.require_auth();
-
-        let mut series: Series = env
+    /// - `Unauthorized`: No admin transfer is pending
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let mut config = Self::load_config(&env)?;
+        let new_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::Series(series_id))
-            .ok_or(Error::SeriesNotFound)?;
+            .get(&DataKey::PendingAdmin)
+            .ok_or(Error::Unauthorized)?;
+        new_admin.require_auth();
 
-        // Validate: Must be UPCOMING status
-        if series.status != SeriesStatus::Upcoming {
-            return Err(Error::InvalidStatus);
-        }
-
-        series.status = SeriesStatus::Active;
-        env.storage()
-            .instance()
-            .set(&DataKey::Series(series_id), &series);
+        let previous_admin = config.admin;
+        config.admin = new_admin.clone();
+        Self::save_config(&env, &config);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
 
-        env.events().publish(
-            (Symbol::new(&env, "series_activated"), series_id),
-            SeriesActivatedEvent { series_id },
+        publish_global(
+            &env,
+            VAULT,
+            "admin_transferred",
+            AdminTransferredEvent {
+                previous_admin,
+                new_admin,
+            },
         );
 
         Ok(())
     }
 
-    // ============================================
-    // FLOW 4: USER SUBSCRIBES (BUYS T-BILLS)
-    // ============================================
-
-    /// Subscribe to a series (buy bT-Bills)
+    /// Move the treasury role to a new custody address
+    ///
+    /// Every treasury-gated call (series creation, activation, settlement)
+    /// authorizes against the new address from the next call on.
     ///
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
-    /// - `ContractPaused`: Contract is paused
-    /// - `InvalidAmount`: pay_amount must be positive
-    /// - `SeriesNotFound`: Series doesn't exist
-    /// - `SeriesNotActive`: Series not yet activated or already matured
-    /// - `ExceedsSeriesCap`: Would exceed series cap_par limit
-    /// - `ExceedsUserCap`: Would exceed user's personal cap_par limit
-    pub fn subscribe(
+    /// - `ActionTimelocked`: An admin delay is set; use `queue_admin_action`
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn set_treasury(
         env: Env,
-        user: Address,
-        series_id: u32,
-        pay_amount: i128,
+        new_treasury: Address,
+        nonce: u64,
+        expiry: u64,
     ) -> Result<(), Error> {
-        Self::check_not_paused(&env)?;
-
-        if pay_amount <= 0 {
-            return Err(Error::InvalidAmount);
-        }
-
-        user.require_auth();
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+        Self::require_no_admin_delay(&env)?;
+        Self::consume_auth_nonce(&env, &admin, nonce, expiry)?;
 
-        let mut series: Series = env
-            .storage()
-            .instance()
-            .get(&DataKey::Series(series_id))
-            .ok_or(Error::SeriesNotFound)?;
+        Self::apply_treasury(&env, new_treasury)
+    }
 
-        // Validate: Series must be ACTIVE
-        if series.status != SeriesStatus::Active {
-            return Err(Error::SeriesNotActive);
-        }
+    /// Current treasury
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn get_treasury(env: Env) -> Result<Address, Error> {
+        Ok(Self::load_config(&env)?.treasury)
+    }
 
-        // Calculate current price (linear accretion)
-        let current_time = env.ledger().timestamp();
-        let current_price = calculate_current_price(&series, current_time);
+    /// Current admin
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        Ok(Self::load_config(&env)?.admin)
+    }
 
-        // Calculate how many PAR units to mint
-        let minted_par =
-            calculate_minted_par(pay_amount, current_price).ok_or(Error::InvalidAmount)?;
+    /// Admin proposed via `propose_admin` and not yet accepted
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
 
-        // Validate: Series cap
-        let new_series_minted = series
-            .minted_par
-            .checked_add(minted_par)
-            .ok_or(Error::InvalidAmount)?;
+    /// Set the settlement asset's decimals (e.g. 6 for USDC-style assets)
+    ///
+    /// PAR and prices stay at SCALE; stablecoin amounts are converted at
+    /// subscribe/redeem. Deployment-time only: locked once a series exists.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidConfig`: Decimals above MAX_ASSET_DECIMALS, or a series has already been created
+    pub fn set_asset_decimals(env: Env, decimals: u32) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
 
-        if new_series_minted > series.cap_par {
-            return Err(Error::ExceedsSeriesCap);
+        if decimals > MAX_ASSET_DECIMALS {
+            return Err(Error::InvalidConfig);
         }
-
-        // Validate: User cap
-        let user_position_key = DataKey::UserPosition(series_id, user.clone());
-        let mut user_position = env
-            .storage()
-            .instance()
-            .get::<DataKey, UserPosition>(&user_position_key)
-            .unwrap_or(UserPosition { subscribed_par: 0 });
-
-        let new_user_subscribed = user_position
-            .subscribed_par
-            .checked_add(minted_par)
-            .ok_or(Error::InvalidAmount)?;
-
-        if new_user_subscribed > series.user_cap_par {
-            return Err(Error::ExceedsUserCap);
+        if !Self::load_series_ids(&env).is_empty()
+            || Self::load_accounting(&env).total_par_minted > 0
+        {
+            return Err(Error::InvalidConfig);
         }
 
-        // Transfer stablecoin from user to vault
-        let stablecoin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Stablecoin)
-            .ok_or(Error::NotInitialized)?;
-
-        let stablecoin_client = token::Client::new(&env, &stablecoin);
-        stablecoin_client.transfer(&user, &env.current_contract_address(), &pay_amount);
-
-        // Mint bT-Bills
-        let bt_bill_token: Address = env
-            .storage()
+        env.storage()
             .instance()
-            .get(&DataKey::BTBillToken)
-            .ok_or(Error::NotInitialized)?;
+            .set(&DataKey::AssetDecimals, &decimals);
 
-        env.invoke_contract::<()>(
-            &bt_bill_token,
-            &Symbol::new(&env, "mint"),
-            vec![
-                &env,
-                series_id.into(),
-                user.to_val(),
-                minted_par.into_val(&env)
-            ],
+        publish_global(
+            &env,
+            VAULT,
+            "asset_decimals_updated",
+            AssetDecimalsUpdatedEvent { decimals },
         );
 
-        // Update state
-        series.minted_par = new_series_minted;
-        series.total_subscriptions_collected = series
-            .total_subscriptions_collected
-            .checked_add(pay_amount)
-            .ok_or(Error::InvalidAmount)?;
-        
-        user_position.subscribed_par = new_user_subscribed;
+        Ok(())
+    }
 
+    pub fn asset_decimals(env: Env) -> u32 {
         env.storage()
             .instance()
-            .set(&DataKey::Series(series_id), &series);
+            .get(&DataKey::AssetDecimals)
+            .unwrap_or(SCALE_DECIMALS)
+    }
+
+    // ============================================
+    // ROLES
+    // ============================================
+
+    /// Hand a role to a dedicated key, taking it away from the admin
+    /// (or the previous holder)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn grant_role(
+        env: Env,
+        role: Role,
+        account: Address,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+        Self::consume_auth_nonce(&env, &admin, nonce, expiry)?;
+
+        let previous_holder = Self::get_role_holder(env.clone(), role)?;
         env.storage()
             .instance()
-            .set(&user_position_key, &user_position);
+            .set(&DataKeyExt::RoleHolder(role), &account);
 
-        // Update protocol accounting
-        use storage::ProtocolAccounting;
-        let mut accounting = env
-            .storage()
-            .instance()
-            .get::<DataKey, ProtocolAccounting>(&DataKey::ProtocolAccounting)
-            .unwrap_or(ProtocolAccounting {
-                total_subscriptions_collected: 0,
-                total_par_minted: 0,
-                total_lent: 0,
-                total_repo_revenue: 0,
-                total_defaults: 0,
-            });
+        publish_global(
+            &env,
+            VAULT,
+            "role_granted",
+            RoleUpdatedEvent {
+                role,
+                previous_holder,
+                new_holder: account,
+            },
+        );
 
-        accounting.total_subscriptions_collected = accounting
-            .total_subscriptions_collected
-            .checked_add(pay_amount)
-            .ok_or(Error::InvalidAmount)?;
-        accounting.total_par_minted = accounting
-            .total_par_minted
-            .checked_add(minted_par)
-            .ok_or(Error::InvalidAmount)?;
+        Ok(())
+    }
+
+    /// Return a role to the admin
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn revoke_role(env: Env, role: Role, nonce: u64, expiry: u64) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+        Self::consume_auth_nonce(&env, &admin, nonce, expiry)?;
 
+        let previous_holder = Self::get_role_holder(env.clone(), role)?;
         env.storage()
             .instance()
-            .set(&DataKey::ProtocolAccounting, &accounting);
+            .remove(&DataKeyExt::RoleHolder(role));
 
-        env.events().publish(
-            (Symbol::new(&env, "subscribed"), series_id, user.clone()),
-            SubscribedEvent {
-                series_id,
-                user,
-                pay_amount,
-                minted_par,
-                price: current_price,
+        publish_global(
+            &env,
+            VAULT,
+            "role_revoked",
+            RoleUpdatedEvent {
+                role,
+                previous_holder,
+                new_holder: admin,
             },
         );
 
         Ok(())
     }
 
+    /// Account currently holding a role (the admin unless granted away)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn get_role_holder(env: Env, role: Role) -> Result<Address, Error> {
+        match env.storage().instance().get(&DataKeyExt::RoleHolder(role)) {
+            Some(holder) => Ok(holder),
+            None => Self::get_admin(env),
+        }
+    }
+
     // ============================================
-    // FLOW 8: USER REDEEMS AT MATURITY
+    // UPGRADES
     // ============================================
 
-    /// Redeem bT-Bills at maturity for PAR value
+    /// Replace the contract wasm
+    ///
+    /// State-changing entrypoints of the new wasm refuse to run until the
+    /// admin calls `migrate_schema` (after any data migration it needs).
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ActionTimelocked`: An admin delay is set; use `queue_admin_action`
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_role(&env, Role::Upgrader)?;
+        Self::require_no_admin_delay(&env)?;
+
+        Self::apply_upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Record that storage now matches this wasm's STORAGE_SCHEMA_VERSION
+    ///
+    /// Rebuilds the running liability totals from every series. No-op when
+    /// already current.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidStatus`: Stored schema is newer than this wasm (downgrade)
+    pub fn migrate_schema(env: Env) -> Result<(), Error> {
+        Self::require_role(&env, Role::Upgrader)?;
+
+        let from_version: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::StorageSchema)
+            .unwrap_or(0);
+        if from_version > STORAGE_SCHEMA_VERSION {
+            return Err(Error::InvalidStatus);
+        }
+        if from_version == STORAGE_SCHEMA_VERSION {
+            return Ok(());
+        }
+
+        let previous =
+            Self::get_storage_schema(env.clone()).map_or(Map::new(&env), |schema| schema.layouts);
+        let layouts = layout_hashes(&env);
+        let changed = changed_keys(&env, &previous, &layouts);
+        Self::rebuild_liability_par(&env)?;
+        Self::write_schema(&env, layouts);
+
+        publish_global(
+            &env,
+            VAULT,
+            "schema_migrated",
+            SchemaMigratedEvent {
+                from_version,
+                to_version: STORAGE_SCHEMA_VERSION,
+                changed_keys: changed,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Fail unless storage was written with `expected_version`, and this wasm reads it
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Schema missing, or not `expected_version` / STORAGE_SCHEMA_VERSION
+    pub fn assert_schema(env: Env, expected_version: u32) -> Result<(), Error> {
+        let version: Option<u32> = env.storage().instance().get(&DataKey::StorageSchema);
+        match version {
+            Some(version) if version == expected_version && version == STORAGE_SCHEMA_VERSION => {
+                Ok(())
+            }
+            _ => Err(Error::NotInitialized),
+        }
+    }
+
+    pub fn get_storage_schema(env: Env) -> Option<StorageSchema> {
+        env.storage().persistent().get(&DataKey::StorageSchema)
+    }
+
+    // ============================================
+    // DUST
+    // ============================================
+
+    /// Set the dust policy (threshold in asset units; 0 only buckets remainders)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidConfig`: Negative threshold
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn set_dust_policy(
+        env: Env,
+        policy: DustPolicy,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+        Self::consume_auth_nonce(&env, &admin, nonce, expiry)?;
+
+        if policy.threshold < 0 {
+            return Err(Error::InvalidConfig);
+        }
+
+        env.storage().instance().set(&DataKey::DustPolicy, &policy);
+
+        publish_global(
+            &env,
+            VAULT,
+            "dust_policy_updated",
+            DustPolicyUpdatedEvent { policy },
+        );
+
+        Ok(())
+    }
+
+    /// Sweep the dust bucket to `to`
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NothingToSweep`: Dust bucket is empty
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn claim_dust(env: Env, to: Address, nonce: u64, expiry: u64) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        let treasury = Self::load_config(&env)?.treasury;
+        treasury.require_auth();
+        Self::consume_auth_nonce(&env, &treasury, nonce, expiry)?;
+
+        let amount = Self::get_dust_bucket(env.clone());
+        if amount <= 0 {
+            return Err(Error::NothingToSweep);
+        }
+
+        env.storage().instance().remove(&DataKey::DustBucket);
+        Self::pay_out(&env, &to, amount)?;
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_dust_claimed = accounting
+            .total_dust_claimed
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(&env, &accounting);
+
+        publish_global(&env, VAULT, "dust_claimed", DustClaimedEvent { to, amount });
+
+        Ok(amount)
+    }
+
+    pub fn get_dust_policy(env: Env) -> Option<DustPolicy> {
+        env.storage().instance().get(&DataKey::DustPolicy)
+    }
+
+    /// Dust threshold in asset units (0 when no policy is set)
+    pub fn get_dust_threshold(env: Env) -> i128 {
+        Self::get_dust_policy(env).map_or(0, |policy| policy.threshold)
+    }
+
+    pub fn get_dust_bucket(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::DustBucket)
+            .unwrap_or(0)
+    }
+
+    /// Freeze issuance: blocks create_series/activate_series only
+    ///
+    /// Subscriptions to already-active series, redemptions and repos keep
+    /// running (unlike `pause`).
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn freeze_issuance(env: Env) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::Pauser)?;
+
+        env.storage().instance().set(&DataKey::IssuanceFrozen, &true);
+        Ok(())
+    }
+
+    /// Lift the issuance freeze
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn unfreeze_issuance(env: Env) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::Pauser)?;
+
+        env.storage().instance().set(&DataKey::IssuanceFrozen, &false);
+        Ok(())
+    }
+
+    pub fn is_issuance_frozen(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::IssuanceFrozen)
+            .unwrap_or(false)
+    }
+
+    /// Set the envelope the treasury may issue within on its own
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidConfig`: Discount outside [0, 10,000] bps,
+    ///   min_tenor > max_tenor, or non-positive max_cap_par
+    pub fn set_issuance_envelope(env: Env, envelope: IssuanceEnvelope) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::SeriesManager)?;
+
+        if envelope.max_discount_bps < 0
+            || envelope.max_discount_bps > BASIS_POINTS
+            || envelope.min_tenor > envelope.max_tenor
+            || envelope.max_cap_par <= 0
+        {
+            return Err(Error::InvalidConfig);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::IssuanceEnvelope, &envelope);
+
+        publish_global(
+            &env,
+            VAULT,
+            "issuance_envelope_updated",
+            IssuanceEnvelopeUpdatedEvent { envelope },
+        );
+
+        Ok(())
+    }
+
+    /// Remove the issuance envelope (treasury issues without co-approval)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn clear_issuance_envelope(env: Env) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::SeriesManager)?;
+
+        env.storage().instance().remove(&DataKey::IssuanceEnvelope);
+        Ok(())
+    }
+
+    pub fn get_issuance_envelope(env: Env) -> Option<IssuanceEnvelope> {
+        env.storage().instance().get(&DataKey::IssuanceEnvelope)
+    }
+
+    // ============================================
+    // KYC
+    // ============================================
+
+    /// Set (or clear) the external registry consulted for KYC-gated series
+    ///
+    /// The registry must expose `is_kyc_verified(account) -> bool`. Accounts
+    /// on the internal allowlist pass without a registry call.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_kyc_registry(env: Env, registry: Option<Address>) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        match &registry {
+            Some(registry) => env
+                .storage()
+                .instance()
+                .set(&DataKeyExt::KycRegistry, registry),
+            None => env.storage().instance().remove(&DataKeyExt::KycRegistry),
+        }
+
+        publish_global(
+            &env,
+            VAULT,
+            "kyc_registry_updated",
+            KycRegistryUpdatedEvent { registry },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_kyc_registry(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKeyExt::KycRegistry)
+    }
+
+    /// Add an account to (or drop it from) the internal KYC allowlist
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_kyc_verified(env: Env, account: Address, verified: bool) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        Self::save_account_flag(&env, &DataKeyExt::KycVerified(account.clone()), verified);
+
+        publish(
+            &env,
+            VAULT,
+            "kyc_status_updated",
+            account.clone(),
+            KycStatusUpdatedEvent { account, verified },
+        );
+
+        Ok(())
+    }
+
+    /// Whether the account is on the internal allowlist or verified by the registry
+    pub fn is_kyc_verified(env: Env, account: Address) -> bool {
+        if Self::load_account_flag(&env, &DataKeyExt::KycVerified(account.clone())) {
+            return true;
+        }
+        match Self::get_kyc_registry(env.clone()) {
+            Some(registry) => env.invoke_contract(
+                &registry,
+                &Symbol::new(&env, "is_kyc_verified"),
+                vec![&env, account.into_val(&env)],
+            ),
+            None => false,
+        }
+    }
+
+    /// Require KYC for a series' subscriptions and redemptions
+    ///
+    /// Refunds of a cancelled series stay open to every holder.
     ///
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
-    /// - `ContractPaused`: Contract is paused
-    /// - `InvalidAmount`: bt_bill_amount must be positive
     /// - `SeriesNotFound`: Series doesn't exist
-    /// - `SeriesNotMatured`: Cannot redeem before maturity_date
-    /// - `InsufficientBalance`: User doesn't have enough bT-Bills
-    pub fn redeem(
+    pub fn set_series_kyc_required(env: Env, series_id: u32, required: bool) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let series = Self::load_series(&env, series_id)?;
+        Self::require_issuer(&env, &series)?;
+
+        let key = DataKeyExt::SeriesKycRequired(series_id);
+        if required {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+
+        publish(
+            &env,
+            VAULT,
+            "series_kyc_updated",
+            series_id,
+            SeriesKycUpdatedEvent {
+                series_id,
+                required,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn is_series_kyc_required(env: Env, series_id: u32) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::SeriesKycRequired(series_id))
+            .unwrap_or(false)
+    }
+
+    // ============================================
+    // RISK FLAGS
+    // ============================================
+
+    /// Replace a series' risk flags (an empty list clears them)
+    ///
+    /// The repo market reads them on every valuation: `UnderReview` raises
+    /// its haircut and `ImpairedIssuer` stops new borrowing against the
+    /// series. Emits a protocol-wide `risk_alert`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    pub fn set_series_risk_flags(
+        env: Env,
+        series_id: u32,
+        flags: Vec<RiskFlag>,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::SeriesManager)?;
+        Self::load_series(&env, series_id)?;
+
+        let mut unique: Vec<RiskFlag> = Vec::new(&env);
+        for flag in flags.iter() {
+            if !unique.contains(flag) {
+                unique.push_back(flag);
+            }
+        }
+
+        let key = DataKeyExt::SeriesRiskFlags(series_id);
+        if unique.is_empty() {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, &unique);
+        }
+
+        publish_global(
+            &env,
+            VAULT,
+            "risk_alert",
+            RiskAlertEvent {
+                series_id,
+                flags: unique,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_series_risk_flags(env: Env, series_id: u32) -> Vec<RiskFlag> {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::SeriesRiskFlags(series_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ============================================
+    // BLOCKLIST
+    // ============================================
+
+    /// Block an account from subscribing, redeeming and refunds
+    ///
+    /// The repo market also refuses collateral from blocked accounts.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn add_to_blocklist(env: Env, account: Address) -> Result<(), Error> {
+        Self::set_blocked(&env, account, true)
+    }
+
+    /// Lift a block set by `add_to_blocklist`
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn remove_from_blocklist(env: Env, account: Address) -> Result<(), Error> {
+        Self::set_blocked(&env, account, false)
+    }
+
+    pub fn is_blocked(env: Env, account: Address) -> bool {
+        Self::load_account_flag(&env, &DataKeyExt::Blocked(account))
+    }
+
+    // ============================================
+    // ISSUER REGISTRY
+    // ============================================
+
+    /// Approve an issuer (or update its limits)
+    ///
+    /// Only approved issuers can be named in `create_series`, and only for
+    /// tenors and payment assets their limits allow, up to
+    /// `max_outstanding_par` across their open series. Series run by the
+    /// treasury itself are not limited.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidConfig`: Non-positive max_outstanding_par,
+    ///   min_tenor > max_tenor, or no allowed assets
+    pub fn approve_issuer(env: Env, issuer: Address, limits: IssuerLimits) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if limits.max_outstanding_par <= 0
+            || limits.min_tenor > limits.max_tenor
+            || limits.allowed_assets.is_empty()
+        {
+            return Err(Error::InvalidConfig);
+        }
+
+        let outstanding_par = Self::get_issuer(env.clone(), issuer.clone())
+            .map_or(0, |profile| profile.outstanding_par);
+        env.storage().instance().set(
+            &DataKeyExt::Issuer(issuer.clone()),
+            &IssuerProfile {
+                limits: limits.clone(),
+                outstanding_par,
+                approved: true,
+            },
+        );
+
+        publish(
+            &env,
+            VAULT,
+            "issuer_approved",
+            issuer.clone(),
+            IssuerApprovedEvent { issuer, limits },
+        );
+
+        Ok(())
+    }
+
+    /// Revoke an issuer: its existing series run on, but it cannot create
+    /// new ones or raise caps
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Issuer was never approved
+    pub fn revoke_issuer(env: Env, issuer: Address) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        let mut profile =
+            Self::get_issuer(env.clone(), issuer.clone()).ok_or(Error::Unauthorized)?;
+        profile.approved = false;
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::Issuer(issuer.clone()), &profile);
+
+        publish(
+            &env,
+            VAULT,
+            "issuer_revoked",
+            issuer.clone(),
+            IssuerRevokedEvent { issuer },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_issuer(env: Env, issuer: Address) -> Option<IssuerProfile> {
+        env.storage().instance().get(&DataKeyExt::Issuer(issuer))
+    }
+
+    // ============================================
+    // ISSUER FEES
+    // ============================================
+
+    /// Share subscription fees and repo spreads on an issuer's series with the issuer
+    ///
+    /// The issuer's cut accrues in its own bucket (`claim_issuer_fees`); the
+    /// protocol keeps the rest as before.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Issuer not in the registry
+    /// - `InvalidAmount`: A share above 10,000 bps
+    pub fn set_issuer_fee_split(
+        env: Env,
+        issuer: Address,
+        split: IssuerFeeSplit,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::FeeManager)?;
+
+        if Self::get_issuer(env.clone(), issuer.clone()).is_none() {
+            return Err(Error::Unauthorized);
+        }
+        if split.subscription_share_bps as i128 > BASIS_POINTS
+            || split.spread_share_bps as i128 > BASIS_POINTS
+        {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::IssuerFeeSplit(issuer.clone()), &split);
+
+        publish(
+            &env,
+            VAULT,
+            "issuer_fee_split_updated",
+            issuer.clone(),
+            IssuerFeeSplitUpdatedEvent { issuer, split },
+        );
+
+        Ok(())
+    }
+
+    /// Pay in repo spread revenue earned against a series' collateral
+    ///
+    /// Books it as repo revenue, credits the series issuer's spread share
+    /// and accrues the rest for `collect_fees`.
+    ///
+    /// # Errors
+    /// - `InvalidAmount`: amount must be positive
+    /// - `SeriesNotFound`: Series doesn't exist
+    pub fn book_repo_spread(
+        env: Env,
+        from: Address,
+        series_id: u32,
+        amount: i128,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        from.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let series = Self::load_series(&env, series_id)?;
+
+        let stablecoin = Self::load_config(&env)?.stablecoin;
+        token::Client::new(&env, &stablecoin).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let issuer_fee = Self::issuer_fee_share(&env, &series, amount, true)?;
+        let protocol_fee = amount - issuer_fee;
+        if let Some(issuer) = &series.issuer {
+            Self::book_issuer_fees(&env, issuer, issuer_fee, protocol_fee, true)?;
+        }
+        let accrued = Self::get_accrued_fees(env.clone())
+            .checked_add(protocol_fee)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::AccruedFees, &accrued);
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_repo_revenue = accounting
+            .total_repo_revenue
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(&env, &accounting);
+
+        publish(
+            &env,
+            VAULT,
+            "repo_spread_booked",
+            series_id,
+            RepoSpreadBookedEvent {
+                series_id,
+                from,
+                amount,
+                issuer_fee,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Repo market hook: book cash lent against a new position
+    ///
+    /// Only the repo market set with `set_repo_market` may report. The loan
+    /// is funded by the treasury, so it counts towards `total_lent` but not
+    /// towards the vault's expected balance.
+    ///
+    /// # Errors
+    /// - `RepoMarketNotSet`: No repo_market configured
+    /// - `InvalidAmount`: amount must be positive
+    pub fn record_loan(env: Env, position_id: u64, amount: i128) -> Result<(), Error> {
+        Self::require_repo_market(&env)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_lent = accounting
+            .total_lent
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(&env, &accounting);
+
+        publish(
+            &env,
+            VAULT,
+            "repo_loan_recorded",
+            position_id,
+            RepoBookedEvent {
+                position_id,
+                principal: amount,
+                revenue: 0,
+            },
+        );
+        Ok(())
+    }
+
+    /// Repo market hook: book a closed position's principal and interest
+    ///
+    /// Releases `principal` from `total_lent` and books `interest` as repo
+    /// revenue collected by the treasury.
+    ///
+    /// # Errors
+    /// - `RepoMarketNotSet`: No repo_market configured
+    /// - `InvalidAmount`: Negative principal or interest
+    pub fn record_repayment(
+        env: Env,
+        position_id: u64,
+        principal: i128,
+        interest: i128,
+    ) -> Result<(), Error> {
+        Self::require_repo_market(&env)?;
+        if principal < 0 || interest < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_lent = accounting.total_lent.saturating_sub(principal).max(0);
+        accounting.total_repo_revenue = accounting
+            .total_repo_revenue
+            .checked_add(interest)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(&env, &accounting);
+
+        let reported = Self::get_reported_repo_revenue(env.clone())
+            .checked_add(interest)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::ReportedRepoRevenue, &reported);
+
+        publish(
+            &env,
+            VAULT,
+            "repo_repayment_recorded",
+            position_id,
+            RepoBookedEvent {
+                position_id,
+                principal,
+                revenue: interest,
+            },
+        );
+        Ok(())
+    }
+
+    /// Repo market hook: write off a defaulted position's principal
+    ///
+    /// # Errors
+    /// - `RepoMarketNotSet`: No repo_market configured
+    /// - `InvalidAmount`: Negative principal
+    pub fn record_default(env: Env, position_id: u64, principal: i128) -> Result<(), Error> {
+        Self::require_repo_market(&env)?;
+        if principal < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_lent = accounting.total_lent.saturating_sub(principal).max(0);
+        accounting.total_defaults = accounting.total_defaults.saturating_add(1);
+        Self::save_accounting(&env, &accounting);
+
+        publish(
+            &env,
+            VAULT,
+            "repo_default_recorded",
+            position_id,
+            RepoBookedEvent {
+                position_id,
+                principal,
+                revenue: 0,
+            },
+        );
+        Ok(())
+    }
+
+    /// Repo revenue the repo market reported via `record_repayment`
+    pub fn get_reported_repo_revenue(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::ReportedRepoRevenue)
+            .unwrap_or(0)
+    }
+
+    /// Pay an issuer every fee credited to it
+    ///
+    /// Returns the amount claimed, in asset units.
+    ///
+    /// # Errors
+    /// - `NothingToSweep`: Nothing claimable
+    pub fn claim_issuer_fees(env: Env, issuer: Address) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        issuer.require_auth();
+
+        let mut account = Self::get_issuer_fees(env.clone(), issuer.clone());
+        let amount = account.claimable;
+        if amount <= 0 {
+            return Err(Error::NothingToSweep);
+        }
+
+        account.claimable = 0;
+        account.claimed = account
+            .claimed
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::IssuerFees(issuer.clone()), &account);
+        Self::pay_out(&env, &issuer, amount)?;
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_issuer_fees_claimed = accounting
+            .total_issuer_fees_claimed
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(&env, &accounting);
+
+        publish(
+            &env,
+            VAULT,
+            "issuer_fees_claimed",
+            issuer.clone(),
+            IssuerFeesClaimedEvent { issuer, amount },
+        );
+
+        Ok(amount)
+    }
+
+    pub fn get_issuer_fee_split(env: Env, issuer: Address) -> Option<IssuerFeeSplit> {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::IssuerFeeSplit(issuer))
+    }
+
+    /// Fees earned on an issuer's series, split by recipient and source
+    pub fn get_issuer_fees(env: Env, issuer: Address) -> IssuerFeeAccount {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::IssuerFees(issuer))
+            .unwrap_or_default()
+    }
+
+    // ============================================
+    // FLOW 1: TREASURY CREATES SERIES
+    // ============================================
+
+    /// Create a new T-Bill series
+    ///
+    /// When an issuance envelope is set, series outside it (discount, tenor
+    /// or cap) also require the admin's authorization (co-approval).
+    ///
+    /// `issuer` takes over the treasury's series-scoped actions (activation,
+    /// cap changes, cancellation, closing and settlement funding) and must
+    /// co-sign; None leaves the series with the global treasury. An issuer
+    /// other than the treasury must be approved (`approve_issuer`) and the
+    /// series must fit its limits.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is not treasury, or issuer not approved for this tenor/asset
+    /// - `ContractPaused`: Contract is paused
+    /// - `IssuanceFrozen`: New series issuance is frozen
+    /// - `SeriesAlreadyExists`: Series ID already used (including pruned series)
+    /// - `InvalidTimestamp`: Maturity must be after issue date
+    /// - `InvalidConfig`: Price must be in range (0, PAR]
+    /// - `InvalidCapAmounts`: user_cap must be ≤ series_cap, both positive
+    pub fn create_series(
+        env: Env,
+        series_id: u32,
+        issue_date: u64,
+        maturity_date: u64,
+        issue_price: i128,
+        cap_par: i128,
+        user_cap_par: i128,
+        issuer: Option<Address>,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+        Self::check_issuance_open(&env)?;
+
+        let treasury = Self::load_config(&env)?.treasury;
+
+        // Treasury must authorize this
+        treasury.require_auth();
+
+        // A separate issuer accepts the series it will run
+        if let Some(issuer) = &issuer {
+            if *issuer != treasury {
+                issuer.require_auth();
+            }
+        }
+
+        // Validate: Series doesn't already exist
+        if Self::has_series(&env, series_id)
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::ArchivedSeries(series_id))
+        {
+            return Err(Error::SeriesAlreadyExists);
+        }
+
+        // Validate: Maturity after issue
+        if maturity_date <= issue_date {
+            return Err(Error::InvalidTimestamp);
+        }
+
+        // Validate: Issue price in valid range (0, PAR]
+        if issue_price <= 0 || issue_price > PAR_UNIT {
+            return Err(Error::InvalidConfig);
+        }
+
+        // Validate: Cap amounts are valid
+        if cap_par <= 0 || user_cap_par <= 0 || user_cap_par > cap_par {
+            return Err(Error::InvalidCapAmounts);
+        }
+
+        // Validate: Onboarded issuer, within its limits
+        if let Some(issuer) = issuer.as_ref().filter(|issuer| **issuer != treasury) {
+            Self::check_issuer_terms(&env, issuer, issue_date, maturity_date)?;
+            Self::adjust_issuer_par(&env, issuer, cap_par)?;
+        }
+
+        // Out-of-envelope series need admin co-approval
+        if let Some(envelope) = Self::get_issuance_envelope(env.clone()) {
+            if !is_within_envelope(&envelope, issue_date, maturity_date, issue_price, cap_par) {
+                let admin = Self::load_config(&env)?.admin;
+                admin.require_auth();
+            }
+        }
+
+        let series = Series {
+            series_id,
+            issue_date,
+            maturity_date,
+            par_unit: PAR_UNIT,
+            issue_price,
+            cap_par,
+            minted_par: 0,
+            user_cap_par,
+            status: SeriesStatus::Upcoming,
+            total_subscriptions_collected: 0,
+            redeemed_par: 0,
+            issuer: issuer.clone(),
+        };
+
+        Self::save_series(&env, &series);
+
+        let mut series_ids = Self::load_series_ids(&env);
+        series_ids.push_back(series_id);
+        Self::save_series_ids(&env, &series_ids);
+
+        publish(
+            &env,
+            VAULT,
+            "series_created",
+            series_id,
+            SeriesCreatedEvent {
+                series_id,
+                issue_date,
+                maturity_date,
+                issue_price,
+                cap_par,
+                user_cap_par,
+                issuer: issuer.unwrap_or(treasury),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Create a series whose ID is derived from its issuance parameters
+    ///
+    /// ID = first 4 bytes (big-endian) of
+    /// sha256(issue_date ‖ maturity_date ‖ issue_price ‖ nonce), so independent
+    /// operators cannot collide by picking the same u32 and the ID can be
+    /// referenced before the series exists (see `derive_series_id`). Bump
+    /// `nonce` to issue the same terms twice. Returns the derived ID.
+    ///
+    /// # Errors
+    /// - Same as `create_series`
+    pub fn create_series_derived(
+        env: Env,
+        issue_date: u64,
+        maturity_date: u64,
+        issue_price: i128,
+        nonce: u64,
+        cap_par: i128,
+        user_cap_par: i128,
+        issuer: Option<Address>,
+    ) -> Result<u32, Error> {
+        let series_id =
+            Self::derive_series_id(env.clone(), issue_date, maturity_date, issue_price, nonce);
+
+        Self::create_series(
+            env,
+            series_id,
+            issue_date,
+            maturity_date,
+            issue_price,
+            cap_par,
+            user_cap_par,
+            issuer,
+        )?;
+
+        Ok(series_id)
+    }
+
+    /// Compute the series ID `create_series_derived` would assign
+    pub fn derive_series_id(
+        env: Env,
+        issue_date: u64,
+        maturity_date: u64,
+        issue_price: i128,
+        nonce: u64,
+    ) -> u32 {
+        let mut preimage = Bytes::new(&env);
+        preimage.extend_from_array(&issue_date.to_be_bytes());
+        preimage.extend_from_array(&maturity_date.to_be_bytes());
+        preimage.extend_from_array(&issue_price.to_be_bytes());
+        preimage.extend_from_array(&nonce.to_be_bytes());
+
+        let digest = env.crypto().sha256(&preimage).to_array();
+        u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+    }
+
+    // ============================================
+    // FLOW 2: TREASURY ACTIVATES SERIES
+    // ============================================
+
+    /// Activate a series for subscriptions
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is not the series issuer
+    /// - `ContractPaused`: Contract is paused
+    /// - `IssuanceFrozen`: New series issuance is frozen
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidStatus`: Series not in UPCOMING status
+    pub fn activate_series(env: Env, series_id: u32) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+        Self::check_issuance_open(&env)?;
+
+        let mut series = Self::load_series(&env, series_id)?;
+
+        // Issuer (or treasury) must authorize this
+        Self::require_issuer(&env, &series)?;
+
+        Self::transition_series(&env, &mut series, SeriesStatus::Active)?;
+
+        publish(
+            &env,
+            VAULT,
+            "series_activated",
+            series_id,
+            SeriesActivatedEvent { series_id },
+        );
+
+        Ok(())
+    }
+
+    /// Change a series' total and per-user caps
+    ///
+    /// The total cap cannot drop below the PAR already minted. Raising it
+    /// past the issuance envelope needs the admin's co-approval, as at
+    /// creation.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidStatus`: Series not UPCOMING, ACTIVE or FULLY_SUBSCRIBED
+    /// - `Unauthorized`: Raising the cap of a revoked issuer's series
+    /// - `InvalidCapAmounts`: user_cap must be ≤ series_cap, both positive, series_cap ≥ minted,
+    ///   and within the issuer's max_outstanding_par
+    pub fn set_series_caps(
+        env: Env,
+        series_id: u32,
+        cap_par: i128,
+        user_cap_par: i128,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let mut series = Self::load_series(&env, series_id)?;
+        Self::require_issuer(&env, &series)?;
+
+        if series.status != SeriesStatus::Upcoming && !is_live(series.status) {
+            return Err(Error::InvalidStatus);
+        }
+
+        Self::write_series_caps(&env, &mut series, cap_par, user_cap_par)
+    }
+
+    /// Raise a live series' total and per-user caps
+    ///
+    /// Lets an oversubscribed issue grow without a new series. Neither cap
+    /// may go down, so holders can rely on the terms they subscribed under;
+    /// otherwise checked like `set_series_caps`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidStatus`: Series not ACTIVE or FULLY_SUBSCRIBED
+    /// - `Unauthorized`: Raising the cap of a revoked issuer's series
+    /// - `InvalidCapAmounts`: A cap would decrease, user_cap > series_cap, or the
+    ///   issuer's max_outstanding_par would be exceeded
+    pub fn update_series_caps(
+        env: Env,
+        series_id: u32,
+        new_cap_par: i128,
+        new_user_cap_par: i128,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let mut series = Self::load_series(&env, series_id)?;
+        Self::require_issuer(&env, &series)?;
+
+        if !is_live(series.status) {
+            return Err(Error::InvalidStatus);
+        }
+        if new_cap_par < series.cap_par || new_user_cap_par < series.user_cap_par {
+            return Err(Error::InvalidCapAmounts);
+        }
+
+        Self::write_series_caps(&env, &mut series, new_cap_par, new_user_cap_par)
+    }
+
+    /// Set the smallest pay_amount a subscription to the series accepts
+    ///
+    /// Keeps out dust positions that cost more in storage rent than they
+    /// are worth. In asset units; 0 removes the minimum.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidAmount`: min_subscription is negative
+    pub fn set_min_subscription(
+        env: Env,
+        series_id: u32,
+        min_subscription: i128,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let series = Self::load_series(&env, series_id)?;
+        Self::require_issuer(&env, &series)?;
+
+        if min_subscription < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let key = DataKeyExt::MinSubscription(series_id);
+        if min_subscription == 0 {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, &min_subscription);
+        }
+
+        publish(
+            &env,
+            VAULT,
+            "min_subscription_updated",
+            series_id,
+            MinSubscriptionUpdatedEvent {
+                series_id,
+                min_subscription,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Minimum pay_amount for a series, in asset units (0 = none)
+    pub fn get_min_subscription(env: Env, series_id: u32) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::MinSubscription(series_id))
+            .unwrap_or(0)
+    }
+
+    /// Address authorizing a series' scoped actions (its issuer, else the treasury)
+    pub fn get_series_issuer(env: Env, series_id: u32) -> Result<Address, Error> {
+        let series = Self::load_series(&env, series_id)?;
+        match series.issuer {
+            Some(issuer) => Ok(issuer),
+            None => Self::get_treasury(env),
+        }
+    }
+
+    /// Cancel a series before maturity
+    ///
+    /// Blocks further subscriptions; subscribers get their payments back
+    /// through `claim_refund`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidStatus`: Series not UPCOMING or ACTIVE
+    pub fn cancel_series(env: Env, series_id: u32) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let mut series = Self::load_series(&env, series_id)?;
+        Self::require_issuer(&env, &series)?;
+
+        Self::transition_series(&env, &mut series, SeriesStatus::Cancelled)
+    }
+
+    /// Burn a subscriber's bT-Bills in a cancelled series and refund what they paid
+    ///
+    /// Burns up to the PAR the user subscribed (and still holds); the refund
+    /// is the matching pro-rata share of their paid amount, so a full burn
+    /// returns exactly what was paid. Returns the refund in asset units.
+    /// Cancelled series never redeem at PAR, so the refund is the only payout.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidStatus`: Series not CANCELLED
+    /// - `InvalidAmount`: Nothing left to refund
+    /// - `Unauthorized`: User is on the blocklist
+    pub fn claim_refund(env: Env, user: Address, series_id: u32) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        user.require_auth();
+        Self::check_not_blocked(&env, &user)?;
+
+        let mut series = Self::load_series(&env, series_id)?;
+        if series.status != SeriesStatus::Cancelled {
+            return Err(Error::InvalidStatus);
+        }
+
+        let mut position = Self::load_user_position(&env, series_id, &user);
+        let bt_bill_token = Self::load_config(&env)?.bt_bill_token;
+        let held: i128 = env.invoke_contract(
+            &bt_bill_token,
+            &Symbol::new(&env, "balance_of"),
+            vec![&env, series_id.into(), user.to_val()],
+        );
+        let burn_amount = held.min(position.subscribed_par);
+        if burn_amount <= 0 || position.paid_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let refund = position
+            .paid_amount
+            .checked_mul(burn_amount)
+            .and_then(|v| v.checked_div(position.subscribed_par))
+            .ok_or(Error::InvalidAmount)?;
+
+        env.invoke_contract::<()>(
+            &bt_bill_token,
+            &Symbol::new(&env, "burn"),
+            vec![
+                &env,
+                series_id.into(),
+                user.to_val(),
+                burn_amount.into_val(&env)
+            ],
+        );
+
+        series.redeemed_par = series
+            .redeemed_par
+            .checked_add(burn_amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_series(&env, &series);
+        Self::book_liability_par(&env, series.status, -burn_amount)?;
+        Self::unindex_user_series(&env, &user, series_id)?;
+
+        // A fully refunded position is zeroed so bT-Bills later transferred
+        // in cannot draw a second refund against it
+        position.subscribed_par -= burn_amount;
+        position.paid_amount -= refund;
+        if position.subscribed_par == 0 {
+            Self::remove_user_position(&env, series_id, &user);
+        } else {
+            Self::save_user_position(&env, series_id, &user, &position);
+        }
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_redeemed = accounting
+            .total_redeemed
+            .checked_add(refund)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(&env, &accounting);
+        Self::record_activity(
+            &env,
+            &user,
+            ActivityKind::Refund,
+            series_id,
+            burn_amount,
+            refund,
+            None,
+            0,
+        );
+
+        publish(
+            &env,
+            VAULT,
+            "refund_claimed",
+            series_id,
+            RefundClaimedEvent {
+                series_id,
+                user: user.clone(),
+                bt_bill_amount: burn_amount,
+                refund,
+            },
+        );
+
+        Self::pay_out(&env, &user, refund)?;
+        Ok(refund)
+    }
+
+    /// Mark an active or matured series as failed (underlying did not settle)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidStatus`: Series not ACTIVE or MATURED
+    pub fn fail_series(env: Env, series_id: u32) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::SeriesManager)?;
+
+        let mut series = Self::load_series(&env, series_id)?;
+
+        Self::transition_series(&env, &mut series, SeriesStatus::Failed)
+    }
+
+    /// Close a matured series once every PAR unit has been redeemed
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `SeriesHasOutstandingPar`: Holders still have PAR to redeem
+    /// - `InvalidStatus`: Series not in MATURED status
+    pub fn close_series(env: Env, series_id: u32) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let mut series = Self::load_series(&env, series_id)?;
+        Self::require_issuer(&env, &series)?;
+
+        if calculate_outstanding_par(&series) > 0 {
+            return Err(Error::SeriesHasOutstandingPar);
+        }
+
+        Self::mature_if_due(&env, &mut series)?;
+        Self::transition_series(&env, &mut series, SeriesStatus::Closed)
+    }
+
+    // ============================================
+    // FLOW 4: USER SUBSCRIBES (BUYS T-BILLS)
+    // ============================================
+
+    /// Subscribe to a series (buy bT-Bills)
+    ///
+    /// `referrer` is recorded on the user's first referred subscription and
+    /// never overwritten (first-touch). While a referrer is attributed, every
+    /// subscription accrues `ReferralRewardBps` of the protocol fee to them,
+    /// withheld from the fee collector's share.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ContractPaused`: Contract or series is paused
+    /// - `InvalidAmount`: pay_amount must be positive
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `Unauthorized`: User is blocked
+    /// - `KYCRequired`: Series requires KYC and the user is not verified
+    /// - `SeriesNotActive`: Series not yet activated or already matured
+    /// - `BelowDustThreshold`: pay_amount below the dust threshold
+    /// - `BelowMinimum`: pay_amount below the series' min_subscription
+    /// - `SeriesFullySubscribed`: Series cap_par is fully minted
+    /// - `ExceedsSeriesCap`: Would exceed series cap_par limit
+    /// - `ExceedsUserCap`: Would exceed user's personal cap_par limit (after loyalty multiplier;
+    ///   skipped for cap-exempt accounts)
+    pub fn subscribe(
+        env: Env,
+        user: Address,
+        series_id: u32,
+        pay_amount: i128,
+        referrer: Option<Address>,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        if pay_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        user.require_auth();
+
+        let payer = Some(user.clone());
+        Self::execute_subscribe(env, user, payer, series_id, pay_amount, referrer)
+    }
+
+    /// Subscribe on behalf of a user through a fee-paying relayer
+    ///
+    /// The relayer submits (and pays for) the transaction; the user only signs
+    /// a Soroban authorization entry for this call. Because the signed args
+    /// include `nonce` and `expiry`, the signature is bound to one specific
+    /// subscription and cannot be replayed or used after it expires.
+    ///
+    /// # Errors
+    /// - `MetaTxExpired`: Ledger time is past `expiry`
+    /// - `InvalidNonce`: `nonce` does not match the user's next nonce
+    /// - Any error from `subscribe`
+    pub fn subscribe_for(
+        env: Env,
+        user: Address,
+        series_id: u32,
+        pay_amount: i128,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        if pay_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        user.require_auth();
+
+        if env.ledger().timestamp() > expiry {
+            return Err(Error::MetaTxExpired);
+        }
+
+        let nonce_key = DataKey::MetaNonce(user.clone());
+        let expected_nonce: u64 = Self::load_account_entry(&env, &nonce_key).unwrap_or(0);
+        if nonce != expected_nonce {
+            return Err(Error::InvalidNonce);
+        }
+        Self::save_account_entry(&env, &nonce_key, &(expected_nonce + 1));
+
+        let payer = Some(user.clone());
+        Self::execute_subscribe(env, user, payer, series_id, pay_amount, None)
+    }
+
+    /// Subscribe with one account's stablecoin for another account
+    ///
+    /// For custodians and router contracts: `payer` authorizes and pays,
+    /// while the bT-Bills, position, user cap, KYC check and receipt all
+    /// belong to `beneficiary`. Named apart from `subscribe_for`, which is
+    /// the relayed (meta-transaction) path where the user still pays.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: payer or beneficiary blocked
+    /// - `KYCRequired`: beneficiary not KYC-verified on a gated series
+    /// - Any error from `subscribe`
+    pub fn subscribe_on_behalf(
+        env: Env,
+        payer: Address,
+        beneficiary: Address,
+        series_id: u32,
+        pay_amount: i128,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        if pay_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        payer.require_auth();
+
+        Self::execute_subscribe(env, beneficiary, Some(payer), series_id, pay_amount, None)
+    }
+
+    /// Next nonce expected by `subscribe_for` for this user
+    pub fn get_meta_nonce(env: Env, user: Address) -> u64 {
+        Self::load_account_entry(&env, &DataKey::MetaNonce(user)).unwrap_or(0)
+    }
+
+    /// Next nonce expected from `signer` by nonce-guarded admin and treasury calls
+    ///
+    /// Withdrawals, `set_treasury` and fee settings take `(nonce, expiry)`
+    /// so a signed authorization executes once, before `expiry`.
+    pub fn get_auth_nonce(env: Env, signer: Address) -> u64 {
+        let key = DataKeyExt::AuthNonce(signer);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .or_else(|| env.storage().instance().get(&key))
+            .unwrap_or(0)
+    }
+
+    // ============================================
+    // FLOW 8: USER REDEEMS AT MATURITY
+    // ============================================
+
+    /// Redeem bT-Bills at maturity for PAR value
+    ///
+    /// The payout goes to `recipient` (e.g. an institution's settlement
+    /// account) when given, else to the holder.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ContractPaused`: Contract or series is paused
+    /// - `InvalidAmount`: bt_bill_amount must be positive
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `Unauthorized`: User or recipient is blocked
+    /// - `KYCRequired`: Series requires KYC and the user is not verified
+    /// - `SeriesNotMatured`: Cannot redeem before maturity_date
+    /// - `InvalidStatus`: Series was cancelled or failed
+    /// - `InsufficientBalance`: User doesn't have enough bT-Bills
+    pub fn redeem(
+        env: Env,
+        user: Address,
+        series_id: u32,
+        bt_bill_amount: i128,
+        recipient: Option<Address>,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        if bt_bill_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        user.require_auth();
+
+        let recipient = recipient.unwrap_or(user.clone());
+        Self::check_not_blocked(&env, &recipient)?;
+
+        let (payout, _) = Self::burn_matured(&env, &user, series_id, bt_bill_amount, 0)?;
+        if recipient != user {
+            publish(
+                &env,
+                VAULT,
+                "redemption_redirected",
+                series_id,
+                RedemptionRedirectedEvent {
+                    series_id,
+                    user,
+                    recipient: recipient.clone(),
+                    payout,
+                },
+            );
+        }
+        Self::pay_redemption(&env, &recipient, series_id, payout)
+    }
+
+    /// Redeem the user's whole bT-Bill balance in a matured series
+    ///
+    /// Reads the balance from the token contract in the same call, so no
+    /// separate balance query (and no stale amount) is needed. Returns the
+    /// PAR redeemed.
+    ///
+    /// # Errors
+    /// - `InvalidAmount`: User holds no bT-Bills in the series
+    /// - Any error from `redeem`
+    pub fn redeem_all(env: Env, user: Address, series_id: u32) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        user.require_auth();
+
+        let held = Self::bt_bill_balance(&env, series_id, &user)?;
+        if held <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let (payout, _) = Self::burn_matured(&env, &user, series_id, held, 0)?;
+        Self::pay_redemption(&env, &user, series_id, payout)?;
+        Ok(held)
+    }
+
+    /// Redeem bT-Bills at maturity, paid out in another of the series' assets
+    ///
+    /// The stablecoin payout is handed to the asset's registered converter,
+    /// which delivers `payout_asset` to the user. The vault checks the
+    /// user's balance grew by at least `min_out`. Converted redemptions are
+    /// paid at once, so they fail rather than jump a redemption lane queue.
+    /// Returns the amount of `payout_asset` received.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ContractPaused`: Contract or series is paused
+    /// - `InvalidAmount`: Non-positive amount or negative min_out
+    /// - `Unauthorized`: payout_asset not offered for the series, or has no converter
+    /// - `KYCRequired`: Series requires KYC and the user is not verified
+    /// - `SeriesNotMatured`: Cannot redeem before maturity_date
+    /// - `PayoutWouldQueue`: The redemption lane would queue this payout
+    /// - `SlippageExceeded`: The conversion delivered less than min_out
+    pub fn redeem_to_asset(
+        env: Env,
+        user: Address,
+        series_id: u32,
+        bt_bill_amount: i128,
+        payout_asset: Address,
+        min_out: i128,
+    ) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        if bt_bill_amount <= 0 || min_out < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        user.require_auth();
+
+        if !Self::get_series_payout_assets(env.clone(), series_id).contains(&payout_asset) {
+            return Err(Error::Unauthorized);
+        }
+        let converter = Self::get_payout_converter(env.clone(), payout_asset.clone())
+            .ok_or(Error::Unauthorized)?;
+
+        let (payout, _) = Self::burn_matured(&env, &user, series_id, bt_bill_amount, 0)?;
+        if Self::would_queue_redemption(&env, payout)? {
+            return Err(Error::PayoutWouldQueue);
+        }
+
+        let stablecoin = Self::load_config(&env)?.stablecoin;
+        let asset_client = token::Client::new(&env, &payout_asset);
+        let balance_before = asset_client.balance(&user);
+
+        Self::pay_out(&env, &converter, payout)?;
+        env.invoke_contract::<i128>(
+            &converter,
+            &Symbol::new(&env, "convert"),
+            vec![
+                &env,
+                stablecoin.to_val(),
+                payout_asset.to_val(),
+                payout.into_val(&env),
+                min_out.into_val(&env),
+                user.to_val()
+            ],
+        );
+
+        // Trust the balance, not the converter's return value
+        let amount_out = asset_client.balance(&user) - balance_before;
+        if amount_out < min_out {
+            return Err(Error::SlippageExceeded);
+        }
+
+        publish(
+            &env,
+            VAULT,
+            "redeemed_to_asset",
+            series_id,
+            RedeemedToAssetEvent {
+                series_id,
+                user,
+                bt_bill_amount,
+                payout,
+                payout_asset,
+                amount_out,
+            },
+        );
+
+        Ok(amount_out)
+    }
+
+    /// Register (or with None, remove) the converter for a payout asset
+    ///
+    /// A converter implements
+    /// `convert(from, to, amount_in, min_out, recipient) -> i128`: it holds
+    /// the `amount_in` of `from` the vault sent it and delivers `to` to
+    /// `recipient`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_payout_converter(
+        env: Env,
+        asset: Address,
+        converter: Option<Address>,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        let key = DataKeyExt::PayoutConverter(asset.clone());
+        match &converter {
+            Some(converter) => env.storage().instance().set(&key, converter),
+            None => env.storage().instance().remove(&key),
+        }
+
+        publish_global(
+            &env,
+            VAULT,
+            "payout_converter_updated",
+            PayoutConverterUpdatedEvent { asset, converter },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_payout_converter(env: Env, asset: Address) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::PayoutConverter(asset))
+    }
+
+    /// Set the assets besides the stablecoin a series can be redeemed into
+    ///
+    /// An empty list leaves the stablecoin as the only payout asset.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `Unauthorized`: An asset has no registered converter
+    pub fn set_series_payout_assets(
+        env: Env,
+        series_id: u32,
+        assets: Vec<Address>,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let series = Self::load_series(&env, series_id)?;
+        Self::require_issuer(&env, &series)?;
+
+        for asset in assets.iter() {
+            if Self::get_payout_converter(env.clone(), asset).is_none() {
+                return Err(Error::Unauthorized);
+            }
+        }
+
+        let key = DataKeyExt::SeriesPayoutAssets(series_id);
+        if assets.is_empty() {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, &assets);
+        }
+
+        publish(
+            &env,
+            VAULT,
+            "payout_assets_updated",
+            series_id,
+            PayoutAssetsUpdatedEvent { series_id, assets },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_series_payout_assets(env: Env, series_id: u32) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::SeriesPayoutAssets(series_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Quote `subscribe(series_id, pay_amount)` at the current ledger time
+    ///
+    /// Uses the same fee and rounding as `subscribe`; status, caps and dust
+    /// policy are only checked when the subscription executes.
+    ///
+    /// # Errors
+    /// - `InvalidAmount`: pay_amount must be positive
+    /// - `SeriesNotFound`: Series doesn't exist
+    pub fn preview_subscribe(
+        env: Env,
+        series_id: u32,
+        pay_amount: i128,
+    ) -> Result<SubscriptionQuote, Error> {
+        if pay_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let series = Self::load_series(&env, series_id)?;
+        Self::quote_subscription(&env, &series, pay_amount, true)
+    }
+
+    /// Quote `redeem(series_id, bt_bill_amount)` once the series has matured
+    ///
+    /// Uses the same redemption fee and rounding as `redeem`. Large payouts
+    /// may still be queued by the redemption lane.
+    ///
+    /// # Errors
+    /// - `InvalidAmount`: bt_bill_amount must be positive
+    /// - `SeriesNotFound`: Series doesn't exist
+    pub fn preview_redeem(
+        env: Env,
+        series_id: u32,
+        bt_bill_amount: i128,
+    ) -> Result<RedemptionQuote, Error> {
+        if bt_bill_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if !Self::has_series(&env, series_id) {
+            return Err(Error::SeriesNotFound);
+        }
+        Self::quote_redemption(&env, series_id, bt_bill_amount)
+    }
+
+    // ============================================
+    // EARLY REDEMPTION
+    // ============================================
+
+    /// Set a series' early exit terms
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidAmount`: penalty_bps exceeds 10,000
+    pub fn set_early_redemption(
+        env: Env,
+        series_id: u32,
+        early_redemption: EarlyRedemption,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::SeriesManager)?;
+
+        if !Self::has_series(&env, series_id) {
+            return Err(Error::SeriesNotFound);
+        }
+        if early_redemption.penalty_bps as i128 > BASIS_POINTS {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::EarlyRedemption(series_id), &early_redemption);
+
+        publish(
+            &env,
+            VAULT,
+            "early_redemption_updated",
+            series_id,
+            EarlyRedemptionUpdatedEvent {
+                series_id,
+                early_redemption,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_early_redemption(env: Env, series_id: u32) -> Option<EarlyRedemption> {
+        env.storage()
+            .instance()
+            .get(&DataKey::EarlyRedemption(series_id))
+    }
+
+    /// Exit before maturity at the accreted price, less the series' penalty
+    ///
+    /// The penalty stays in the vault. Payouts go through the redemption
+    /// lane like maturity redemptions. Returns the payout in asset units.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ContractPaused`: Contract or series is paused
+    /// - `InvalidAmount`: bt_bill_amount must be positive
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `Unauthorized`: User is blocked
+    /// - `KYCRequired`: Series requires KYC and the user is not verified
+    /// - `EarlyRedemptionDisabled`: Series has no enabled early exit terms
+    /// - `SeriesNotActive`: Series not in ACTIVE or FULLY_SUBSCRIBED status
+    /// - `InvalidStatus`: Series has reached maturity; use `redeem`
+    pub fn redeem_early(
+        env: Env,
+        user: Address,
+        series_id: u32,
+        bt_bill_amount: i128,
+    ) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        if bt_bill_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        user.require_auth();
+        Self::check_series_not_paused(&env, series_id)?;
+        Self::check_not_blocked(&env, &user)?;
+        Self::check_kyc(&env, series_id, &user)?;
+
+        let mut series = Self::load_series(&env, series_id)?;
+        let terms = Self::get_early_redemption(env.clone(), series_id)
+            .filter(|terms| terms.enabled)
+            .ok_or(Error::EarlyRedemptionDisabled)?;
+        if !is_live(series.status) {
+            return Err(Error::SeriesNotActive);
+        }
+        let current_time = env.ledger().timestamp();
+        if current_time >= series.maturity_date {
+            return Err(Error::InvalidStatus);
+        }
+
+        let price = calculate_current_price(&series, current_time);
+        let asset_decimals = Self::asset_decimals(env.clone());
+        let value = calculate_collateral_value(bt_bill_amount, price)
+            .and_then(|value| to_asset_units(value, asset_decimals))
+            .ok_or(Error::InvalidAmount)?;
+        let payout = calculate_early_redemption(bt_bill_amount, price, terms.penalty_bps)
+            .and_then(|proceeds| to_asset_units(proceeds, asset_decimals))
+            .ok_or(Error::InvalidAmount)?;
+        let penalty = value.saturating_sub(payout);
+
+        let bt_bill_token = Self::load_config(&env)?.bt_bill_token;
+        env.invoke_contract::<()>(
+            &bt_bill_token,
+            &Symbol::new(&env, "burn"),
+            vec![
+                &env,
+                series_id.into(),
+                user.to_val(),
+                bt_bill_amount.into_val(&env)
+            ],
+        );
+
+        series.redeemed_par = series
+            .redeemed_par
+            .checked_add(bt_bill_amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_series(&env, &series);
+        Self::book_liability_par(&env, series.status, -bt_bill_amount)?;
+        Self::unindex_user_series(&env, &user, series_id)?;
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_redeemed = accounting
+            .total_redeemed
+            .checked_add(payout)
+            .ok_or(Error::InvalidAmount)?;
+        accounting.total_early_penalties = accounting
+            .total_early_penalties
+            .checked_add(penalty)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(&env, &accounting);
+        Self::record_activity(
+            &env,
+            &user,
+            ActivityKind::EarlyRedemption,
+            series_id,
+            bt_bill_amount,
+            payout,
+            None,
+            0,
+        );
+
+        publish(
+            &env,
+            VAULT,
+            "early_redeemed",
+            series_id,
+            EarlyRedeemedEvent {
+                series_id,
+                user: user.clone(),
+                bt_bill_amount,
+                price,
+                penalty,
+                payout,
+            },
+        );
+
+        Self::pay_redemption(&env, &user, series_id, payout)?;
+        Ok(payout)
+    }
+
+    // ============================================
+    // DELEGATED OPERATIONS (SESSION KEYS)
+    // ============================================
+
+    /// Authorize a hot key to perform specific low-risk actions on the user's behalf
+    ///
+    /// The delegate can only move value back to the user (redeem) or keep it
+    /// inside the vault (roll), capped at `limit_par` in total until
+    /// `expires_at`. Replaces any existing delegation to the same key.
+    ///
+    /// # Errors
+    /// - `InvalidAmount`: limit_par must be positive
+    /// - `InvalidTimestamp`: expires_at must be in the future
+    pub fn grant_delegation(
+        env: Env,
+        user: Address,
+        delegate: Address,
+        actions: Vec<DelegatedAction>,
+        limit_par: i128,
+        expires_at: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        if limit_par <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if expires_at <= env.ledger().timestamp() {
+            return Err(Error::InvalidTimestamp);
+        }
+
+        user.require_auth();
+
+        let delegation = Delegation {
+            actions,
+            remaining_par: limit_par,
+            expires_at,
+        };
+        Self::save_account_entry(
+            &env,
+            &DataKey::Delegation(user.clone(), delegate.clone()),
+            &delegation,
+        );
+
+        publish(
+            &env,
+            VAULT,
+            "delegation_granted",
+            user.clone(),
+            DelegationGrantedEvent {
+                user,
+                delegate,
+                actions: delegation.actions,
+                limit_par,
+                expires_at,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Revoke a delegate's permissions immediately
+    ///
+    /// # Errors
+    /// - `DelegationNotFound`: No delegation to revoke
+    pub fn revoke_delegation(env: Env, user: Address, delegate: Address) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        user.require_auth();
+
+        let key = DataKey::Delegation(user.clone(), delegate.clone());
+        if !env.storage().persistent().has(&key) && !env.storage().instance().has(&key) {
+            return Err(Error::DelegationNotFound);
+        }
+        Self::remove_account_entry(&env, &key);
+
+        publish(
+            &env,
+            VAULT,
+            "delegation_revoked",
+            user.clone(),
+            DelegationRevokedEvent { user, delegate },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_delegation(env: Env, user: Address, delegate: Address) -> Option<Delegation> {
+        Self::load_account_entry(&env, &DataKey::Delegation(user, delegate))
+    }
+
+    /// Redeem a user's matured bT-Bills as their delegate (payout goes to the user)
+    ///
+    /// # Errors
+    /// - `DelegationNotFound` / `DelegationExpired` / `ActionNotDelegated`
+    /// - `ExceedsDelegationLimit`: Amount above the delegate's remaining allowance
+    /// - Any error from `redeem`
+    pub fn redeem_as_delegate(
+        env: Env,
+        delegate: Address,
+        user: Address,
+        series_id: u32,
+        bt_bill_amount: i128,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        if bt_bill_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        delegate.require_auth();
+        Self::consume_delegation(
+            &env,
+            &user,
+            &delegate,
+            DelegatedAction::Redeem,
+            bt_bill_amount,
+        )?;
+
+        let (payout, _) = Self::burn_matured(&env, &user, series_id, bt_bill_amount, 0)?;
+        Self::pay_redemption(&env, &user, series_id, payout)
+    }
+
+    /// Roll a user's matured bT-Bills into another active series as their delegate
+    ///
+    /// Redemption proceeds stay in the vault and are subscribed for the user
+    /// at the target series' current price.
+    ///
+    /// # Errors
+    /// - `DelegationNotFound` / `DelegationExpired` / `ActionNotDelegated`
+    /// - `ExceedsDelegationLimit`: Amount above the delegate's remaining allowance
+    /// - Any error from `redeem` (source series) or `subscribe` (target series)
+    pub fn roll_as_delegate(
+        env: Env,
+        delegate: Address,
+        user: Address,
+        from_series_id: u32,
+        to_series_id: u32,
+        bt_bill_amount: i128,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        if bt_bill_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        delegate.require_auth();
+        Self::consume_delegation(
+            &env,
+            &user,
+            &delegate,
+            DelegatedAction::Roll,
+            bt_bill_amount,
+        )?;
+
+        let (proceeds, _) = Self::burn_matured(&env, &user, from_series_id, bt_bill_amount, 0)?;
+        Self::execute_subscribe(env, user, None, to_series_id, proceeds, None)
+    }
+
+    // ============================================
+    // KEEPER AUTOMATION
+    // ============================================
+
+    /// Set the repo_market contract used for AutoCloseRepo
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_repo_market(env: Env, repo_market: Address) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RepoMarket, &repo_market);
+        Ok(())
+    }
+
+    /// Opt into (or out of) a keeper-executed action
+    ///
+    /// `allowance` caps the total amount keepers may act on: PAR redeemed for
+    /// AutoRedeem, stablecoin repaid for AutoCloseRepo. Zero opts out.
+    /// AutoCloseRepo additionally requires approving repo_market as a
+    /// stablecoin spender.
+    ///
+    /// # Errors
+    /// - `InvalidAmount`: allowance must not be negative
+    pub fn set_automation(
+        env: Env,
+        user: Address,
+        action: AutomationAction,
+        allowance: i128,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        if allowance < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        user.require_auth();
+
+        Self::save_automation_allowance(&env, &user, action, allowance);
+
+        publish(
+            &env,
+            VAULT,
+            "automation_updated",
+            user.clone(),
+            AutomationUpdatedEvent {
+                user,
+                action,
+                allowance,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Remaining keeper allowance for a user's opted-in action (0 = not opted in)
+    pub fn get_automation_allowance(env: Env, user: Address, action: AutomationAction) -> i128 {
+        Self::load_account_entry(&env, &DataKey::Automation(user, action)).unwrap_or(0)
+    }
+
+    /// Execute an opted-in action for a user (permissionless, keeper-called)
+    ///
+    /// `target_id` is the series_id for AutoRedeem and the position_id for
+    /// AutoCloseRepo. Returns the amount acted on.
+    ///
+    /// # Errors
+    /// - `AutomationNotEnabled`: User has no remaining allowance for this action
+    /// - `NothingToAutomate`: User holds nothing in the series
+    /// - `RepoMarketNotSet`: AutoCloseRepo without a configured repo_market
+    /// - Any error from `redeem` or repo_market `close_repo_automated`
+    pub fn execute_user_automation(
+        env: Env,
+        user: Address,
+        action: AutomationAction,
+        target_id: u64,
+    ) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        let allowance = Self::get_automation_allowance(env.clone(), user.clone(), action);
+        if allowance <= 0 {
+            return Err(Error::AutomationNotEnabled);
+        }
+
+        let amount = match action {
+            AutomationAction::AutoRedeem => {
+                let series_id = u32::try_from(target_id).map_err(|_| Error::SeriesNotFound)?;
+                let amount = Self::bt_bill_balance(&env, series_id, &user)?.min(allowance);
+                if amount <= 0 {
+                    return Err(Error::NothingToAutomate);
+                }
+
+                let (payout, _) = Self::burn_matured(&env, &user, series_id, amount, 0)?;
+                Self::pay_redemption(&env, &user, series_id, payout)?;
+                amount
+            }
+            AutomationAction::AutoCloseRepo => {
+                let repo_market: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::RepoMarket)
+                    .ok_or(Error::RepoMarketNotSet)?;
+
+                env.invoke_contract::<i128>(
+                    &repo_market,
+                    &Symbol::new(&env, "close_repo_automated"),
+                    vec![
+                        &env,
+                        target_id.into_val(&env),
+                        user.to_val(),
+                        allowance.into_val(&env),
+                    ],
+                )
+            }
+        };
+
+        Self::save_automation_allowance(&env, &user, action, allowance - amount);
+
+        publish(
+            &env,
+            VAULT,
+            "automation_executed",
+            user.clone(),
+            AutomationExecutedEvent {
+                user,
+                action,
+                target_id,
+                amount,
+            },
+        );
+
+        Ok(amount)
+    }
+
+    /// Set the keeper fee taken from auto_redeem proceeds
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidConfig`: fee_bps above MAX_KEEPER_FEE_BPS
+    /// - `ActionTimelocked`: An admin delay is set; use `queue_admin_action`
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn set_keeper_fee_bps(
+        env: Env,
+        fee_bps: u32,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let signer = Self::require_role(&env, Role::FeeManager)?;
+        Self::require_no_admin_delay(&env)?;
+        Self::consume_auth_nonce(&env, &signer, nonce, expiry)?;
+
+        Self::apply_keeper_fee_bps(&env, fee_bps)
+    }
+
+    pub fn get_keeper_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::KeeperFeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Batch-redeem a matured series for users opted into AutoRedeem
+    ///
+    /// Each user's full balance (capped by their remaining allowance) is
+    /// redeemed; the keeper fee is deducted from the payout and sent to the
+    /// keeper. Users without an opt-in or balance are skipped, not rejected.
+    /// Returns the total PAR redeemed.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ContractPaused`: Contract or series is paused
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `SeriesNotMatured`: Cannot redeem before maturity_date
+    pub fn auto_redeem(
+        env: Env,
+        keeper: Address,
+        series_id: u32,
+        users: Vec<Address>,
+    ) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        keeper.require_auth();
+
+        let series = Self::load_series(&env, series_id)?;
+        if env.ledger().timestamp() < series.maturity_date {
+            return Err(Error::SeriesNotMatured);
+        }
+
+        let fee_bps = Self::get_keeper_fee_bps(env.clone());
+        let mut users_redeemed = 0u32;
+        let mut total_redeemed = 0i128;
+        let mut keeper_fees = 0i128;
+
+        for user in users.iter() {
+            let allowance = Self::get_automation_allowance(
+                env.clone(),
+                user.clone(),
+                AutomationAction::AutoRedeem,
+            );
+            let amount = Self::bt_bill_balance(&env, series_id, &user)?.min(allowance);
+            if amount <= 0 {
+                continue;
+            }
+
+            let (payout, fee) = Self::burn_matured(&env, &user, series_id, amount, fee_bps)?;
+            Self::pay_out(&env, &user, payout)?;
+
+            Self::save_automation_allowance(
+                &env,
+                &user,
+                AutomationAction::AutoRedeem,
+                allowance - amount,
+            );
+
+            users_redeemed += 1;
+            total_redeemed = total_redeemed
+                .checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+            keeper_fees = keeper_fees.checked_add(fee).ok_or(Error::InvalidAmount)?;
+        }
+
+        if keeper_fees > 0 {
+            Self::pay_out(&env, &keeper, keeper_fees)?;
+        }
+
+        publish(
+            &env,
+            VAULT,
+            "auto_redeem",
+            series_id,
+            AutoRedeemEvent {
+                series_id,
+                keeper,
+                users_redeemed,
+                total_redeemed,
+                keeper_fees,
+            },
+        );
+
+        Ok(total_redeemed)
+    }
+
+    // ============================================
+    // EMERGENCY WITHDRAWAL
+    // ============================================
+
+    /// Queue an emergency withdrawal (executable after EMERGENCY_WITHDRAW_DELAY)
+    ///
+    /// Replaces any previously queued withdrawal.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: amount must be positive
+    pub fn queue_emergency_withdraw(
+        env: Env,
+        token: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<u64, Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let executable_at = env.ledger().timestamp() + EMERGENCY_WITHDRAW_DELAY;
+        let pending = PendingEmergencyWithdraw {
+            token: token.clone(),
+            amount,
+            to: to.clone(),
+            executable_at,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingEmergencyWithdraw, &pending);
+
+        publish_global(
+            &env,
+            VAULT,
+            "emergency_withdraw_queued",
+            EmergencyWithdrawQueuedEvent {
+                token,
+                amount,
+                to,
+                executable_at,
+            },
+        );
+
+        Ok(executable_at)
+    }
+
+    /// Cancel the queued emergency withdrawal
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NoPendingWithdrawal`: Nothing queued
+    pub fn cancel_emergency_withdraw(env: Env) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if !env
+            .storage()
+            .instance()
+            .has(&DataKey::PendingEmergencyWithdraw)
+        {
+            return Err(Error::NoPendingWithdrawal);
+        }
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::PendingEmergencyWithdraw);
+        Ok(())
+    }
+
+    /// Execute a queued emergency withdrawal
+    ///
+    /// Protocol stablecoin can never be withdrawn below the outstanding
+    /// liability of matured series, so holder claims stay covered. Stablecoin
+    /// withdrawals are booked in `total_emergency_withdrawals` for `reconcile`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NoPendingWithdrawal`: Parameters don't match the queued withdrawal
+    /// - `TimelockNotElapsed`: Called before executable_at
+    /// - `BreachesRedemptionLiability`: Would leave matured redemptions underfunded
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn emergency_withdraw(
+        env: Env,
+        token: Address,
+        amount: i128,
+        to: Address,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+        Self::consume_auth_nonce(&env, &admin, nonce, expiry)?;
+
+        let pending: PendingEmergencyWithdraw = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingEmergencyWithdraw)
+            .ok_or(Error::NoPendingWithdrawal)?;
+
+        if pending.token != token || pending.amount != amount || pending.to != to {
+            return Err(Error::NoPendingWithdrawal);
+        }
+
+        if env.ledger().timestamp() < pending.executable_at {
+            return Err(Error::TimelockNotElapsed);
+        }
+
+        let stablecoin = Self::load_config(&env)?.stablecoin;
+
+        if token == stablecoin {
+            let remaining = Self::stablecoin_balance(&env)?
+                .checked_sub(amount)
+                .ok_or(Error::InvalidAmount)?;
+            if remaining < Self::matured_liability(&env) {
+                return Err(Error::BreachesRedemptionLiability);
+            }
+
+            let mut accounting = Self::load_booked_accounting(&env);
+            accounting.total_emergency_withdrawals = accounting
+                .total_emergency_withdrawals
+                .checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+            Self::save_accounting(&env, &accounting);
+        }
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::PendingEmergencyWithdraw);
+
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+
+        publish_global(
+            &env,
+            VAULT,
+            "emergency_withdraw",
+            EmergencyWithdrawEvent { token, amount, to },
+        );
+
+        Ok(())
+    }
+
+    // ============================================
+    // ADMIN TIMELOCK
+    // ============================================
+
+    /// Set the admin action timelock (seconds) while none is in force
+    ///
+    /// With a delay set, treasury changes, fee and fee collector changes and
+    /// upgrades must go through `queue_admin_action` / `execute_admin_action`, and
+    /// the delay itself can only change via a queued `SetAdminDelay`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ActionTimelocked`: A delay is already set
+    /// - `InvalidTimestamp`: delay_secs above MAX_ADMIN_DELAY
+    pub fn set_admin_delay(env: Env, delay_secs: u64) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+        Self::require_no_admin_delay(&env)?;
+
+        Self::apply_admin_delay(&env, delay_secs)
+    }
+
+    /// Current admin action timelock in seconds (0 = changes apply directly)
+    pub fn get_admin_delay(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::AdminDelay)
+            .unwrap_or(0)
+    }
+
+    /// Queue a sensitive admin change, executable once the admin delay has passed
+    ///
+    /// Authorized by whoever may make the change directly: the admin for
+    /// treasury and delay changes, the fee manager for fees, the upgrader
+    /// for upgrades. Parameters are validated now so holders see exactly
+    /// what will apply.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series-scoped fee for a series that doesn't exist
+    /// - `InvalidAmount`: Fee above its cap
+    /// - `InvalidConfig`: Keeper fee above MAX_KEEPER_FEE_BPS
+    /// - `InvalidTimestamp`: Delay above MAX_ADMIN_DELAY
+    pub fn queue_admin_action(env: Env, action: AdminAction) -> Result<u64, Error> {
+        Self::require_schema(&env)?;
+        Self::require_admin_action_auth(&env, &action)?;
+
+        match &action {
+            AdminAction::SetSubscriptionFee(fee_bps) if *fee_bps > MAX_SUBSCRIPTION_FEE_BPS => {
+                return Err(Error::InvalidAmount);
+            }
+            AdminAction::SetRedemptionFee(fee_bps) if *fee_bps > MAX_REDEMPTION_FEE_BPS => {
+                return Err(Error::InvalidAmount);
+            }
+            AdminAction::SetAdminDelay(delay_secs) if *delay_secs > MAX_ADMIN_DELAY => {
+                return Err(Error::InvalidTimestamp);
+            }
+            AdminAction::SetSeriesRedemptionFee(series_id, fee_bps) => {
+                Self::load_series(&env, *series_id)?;
+                if fee_bps.is_some_and(|bps| bps > MAX_REDEMPTION_FEE_BPS) {
+                    return Err(Error::InvalidAmount);
+                }
+            }
+            AdminAction::SetKeeperFee(fee_bps) if *fee_bps > MAX_KEEPER_FEE_BPS => {
+                return Err(Error::InvalidConfig);
+            }
+            _ => {}
+        }
+
+        let action_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKeyExt::NextAdminActionId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::NextAdminActionId, &(action_id + 1));
+
+        let queued_at = env.ledger().timestamp();
+        let executable_at = queued_at + Self::get_admin_delay(env.clone());
+        env.storage().instance().set(
+            &DataKeyExt::AdminActionQueue(action_id),
+            &QueuedAdminAction {
+                action: action.clone(),
+                queued_at,
+                executable_at,
+            },
+        );
+
+        publish_global(
+            &env,
+            VAULT,
+            "admin_action_queued",
+            AdminActionQueuedEvent {
+                action_id,
+                action,
+                executable_at,
+            },
+        );
+
+        Ok(action_id)
+    }
+
+    /// Apply a queued admin change once its timelock has elapsed
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NoPendingAction`: No queued action with this ID
+    /// - `ActionTimelocked`: Called before executable_at
+    pub fn execute_admin_action(env: Env, action_id: u64) -> Result<(), Error> {
+        let queued = Self::load_admin_action(&env, action_id)?;
+        // An upgrade must stay executable while storage awaits migrate_schema
+        if !matches!(queued.action, AdminAction::Upgrade(_)) {
+            Self::require_schema(&env)?;
+        }
+        Self::require_admin_action_auth(&env, &queued.action)?;
+
+        if env.ledger().timestamp() < queued.executable_at {
+            return Err(Error::ActionTimelocked);
+        }
+
+        env.storage()
+            .instance()
+            .remove(&DataKeyExt::AdminActionQueue(action_id));
+
+        match queued.action.clone() {
+            AdminAction::SetTreasury(new_treasury) => Self::apply_treasury(&env, new_treasury)?,
+            AdminAction::SetSubscriptionFee(fee_bps) => {
+                Self::apply_subscription_fee_bps(&env, fee_bps)?
+            }
+            AdminAction::SetRedemptionFee(fee_bps) => {
+                Self::apply_redemption_fee_bps(&env, fee_bps)?
+            }
+            AdminAction::Upgrade(wasm_hash) => Self::apply_upgrade(&env, wasm_hash),
+            AdminAction::SetAdminDelay(delay_secs) => Self::apply_admin_delay(&env, delay_secs)?,
+            AdminAction::SetSeriesRedemptionFee(series_id, fee_bps) => {
+                Self::apply_series_redemption_fee_bps(&env, series_id, fee_bps)?
+            }
+            AdminAction::SetFeeCollector(collector) => Self::apply_fee_collector(&env, collector),
+            AdminAction::SetKeeperFee(fee_bps) => Self::apply_keeper_fee_bps(&env, fee_bps)?,
+        }
+
+        publish_global(
+            &env,
+            VAULT,
+            "admin_action_executed",
+            AdminActionEvent {
+                action_id,
+                action: queued.action,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Drop a queued admin change before it executes
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NoPendingAction`: No queued action with this ID
+    pub fn cancel_admin_action(env: Env, action_id: u64) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let queued = Self::load_admin_action(&env, action_id)?;
+        Self::require_admin_action_auth(&env, &queued.action)?;
+
+        env.storage()
+            .instance()
+            .remove(&DataKeyExt::AdminActionQueue(action_id));
+
+        publish_global(
+            &env,
+            VAULT,
+            "admin_action_cancelled",
+            AdminActionEvent {
+                action_id,
+                action: queued.action,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Queued admin action by ID (None once executed or cancelled)
+    pub fn get_admin_action(env: Env, action_id: u64) -> Option<QueuedAdminAction> {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::AdminActionQueue(action_id))
+    }
+
+    // ============================================
+    // TREASURY SWEEP
+    // ============================================
+
+    /// Sweep excess stablecoin to the treasury for deployment
+    ///
+    /// The vault must still hold the face value of every outstanding PAR
+    /// unit plus queued payouts afterwards, so only balance above that
+    /// (fees, early penalties, over-funded settlement) can leave. Withdrawals
+    /// beyond the rolling `set_withdrawal_limits` caps need the admin's
+    /// co-authorization.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ContractPaused`: Contract is paused
+    /// - `InvalidAmount`: amount must be positive and at most the vault balance
+    /// - `BreachesRedemptionLiability`: Would leave less than outstanding liability
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn withdraw_to_treasury(
+        env: Env,
+        amount: i128,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        let treasury = Self::load_config(&env)?.treasury;
+        treasury.require_auth();
+        Self::consume_auth_nonce(&env, &treasury, nonce, expiry)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let remaining_balance = Self::stablecoin_balance(&env)?
+            .checked_sub(amount)
+            .filter(|remaining| *remaining >= 0)
+            .ok_or(Error::InvalidAmount)?;
+        if remaining_balance < Self::outstanding_liability(&env)? {
+            return Err(Error::BreachesRedemptionLiability);
+        }
+        Self::record_treasury_withdrawal(&env, amount)?;
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_treasury_withdrawals = accounting
+            .total_treasury_withdrawals
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(&env, &accounting);
+
+        Self::pay_out(&env, &treasury, amount)?;
+
+        publish_global(
+            &env,
+            VAULT,
+            "treasury_withdrawal",
+            TreasuryWithdrawalEvent {
+                treasury,
+                amount,
+                remaining_balance,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Cap withdraw_to_treasury over rolling daily and weekly windows
+    ///
+    /// Withdrawals beyond either cap still succeed, but only with the admin's
+    /// co-authorization, limiting what a compromised treasury key can drain.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidConfig`: Negative limit or weekly below daily
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn set_withdrawal_limits(
+        env: Env,
+        limits: WithdrawalLimits,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+        Self::consume_auth_nonce(&env, &admin, nonce, expiry)?;
+
+        if !validate_withdrawal_limits(&limits) {
+            return Err(Error::InvalidConfig);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::WithdrawalLimits, &limits);
+
+        publish_global(
+            &env,
+            VAULT,
+            "withdrawal_limits_updated",
+            WithdrawalLimitsUpdatedEvent { limits },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_withdrawal_limits(env: Env) -> WithdrawalLimits {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::WithdrawalLimits)
+            .unwrap_or(WithdrawalLimits {
+                daily_limit: 0,
+                weekly_limit: 0,
+            })
+    }
+
+    /// Treasury withdrawals made in the trailing day and week
+    pub fn get_withdrawal_usage(env: Env) -> WithdrawalUsage {
+        let outflows = Self::load_withdrawal_outflows(&env);
+        let now_bucket = env.ledger().timestamp() / WITHDRAWAL_BUCKET_SECONDS;
+
+        WithdrawalUsage {
+            daily_used: rolling_window_total(&outflows, now_bucket, DAILY_WITHDRAWAL_BUCKETS),
+            weekly_used: rolling_window_total(&outflows, now_bucket, WEEKLY_WITHDRAWAL_BUCKETS),
+            limits: Self::get_withdrawal_limits(env.clone()),
+        }
+    }
+
+    /// Return stablecoin to the vault ahead of a series' maturity
+    ///
+    /// Booked as a settlement deposit and tracked per series against the
+    /// face value of its minted PAR (see `get_redemption_funding`).
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: amount must be positive
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidStatus`: Series is not Active or Matured
+    pub fn fund_redemptions(env: Env, series_id: u32, amount: i128) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let series = Self::load_series(&env, series_id)?;
+        let issuer = Self::require_issuer(&env, &series)?;
+        if !is_live(series.status) && series.status != SeriesStatus::Matured {
+            return Err(Error::InvalidStatus);
+        }
+
+        let mut funding = Self::redemption_funding(&env, &series)?;
+        funding.funded = funding
+            .funded
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+
+        Self::deposit_settlement_from(&env, &issuer, amount)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::RedemptionFunded(series_id), &funding.funded);
+
+        publish(
+            &env,
+            VAULT,
+            "redemptions_funded",
+            series_id,
+            RedemptionsFundedEvent {
+                series_id,
+                amount,
+                funded: funding.funded,
+                required: funding.required,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_redemption_funding(env: Env, series_id: u32) -> Result<RedemptionFunding, Error> {
+        let series = Self::load_series(&env, series_id)?;
+        Self::redemption_funding(&env, &series)
+    }
+
+    /// Whether the treasury has returned the series' full face value
+    ///
+    /// # Errors
+    /// - `SeriesNotFound`: Series doesn't exist
+    pub fn is_fully_funded(env: Env, series_id: u32) -> Result<bool, Error> {
+        let funding = Self::get_redemption_funding(env, series_id)?;
+        Ok(funding.funded >= funding.required)
+    }
+
+    // ============================================
+    // SETTLEMENT INFLOWS
+    // ============================================
+
+    /// Toggle allowlist mode for settlement deposits
+    ///
+    /// When enabled, only registered senders may call `deposit_settlement`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_settlement_allowlist(env: Env, enabled: bool) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::SettlementAllowlistEnabled, &enabled);
+        Ok(())
+    }
+
+    /// Register or unregister a settlement sender (treasury, repo market, insurance fund)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_settlement_sender(env: Env, sender: Address, allowed: bool) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        let key = DataKey::SettlementSender(sender.clone());
+        if allowed {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+
+        publish_global(
+            &env,
+            VAULT,
+            "settlement_sender_updated",
+            SettlementSenderUpdatedEvent { sender, allowed },
+        );
+
+        Ok(())
+    }
+
+    /// Deposit stablecoin as a legitimate settlement inflow
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: amount must be positive
+    /// - `UnregisteredSettlementSender`: Allowlist mode on and sender not registered
+    pub fn deposit_settlement(env: Env, from: Address, amount: i128) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        from.require_auth();
+
+        if !Self::is_settlement_sender_allowed(&env, &from) {
+            return Err(Error::UnregisteredSettlementSender);
+        }
+
+        Self::deposit_settlement_from(&env, &from, amount)
+    }
+
+    /// Sweep stablecoin that arrived outside any accounted flow
+    ///
+    /// Transfers the positive reconciliation delta to `to`, restoring
+    /// actual balance == expected balance.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NothingToSweep`: Actual balance does not exceed expected balance
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn sweep_unexpected_inflows(
+        env: Env,
+        to: Address,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+        Self::consume_auth_nonce(&env, &admin, nonce, expiry)?;
+
+        let actual_balance = Self::stablecoin_balance(&env)?;
+        let expected_balance = Self::expected_balance(env.clone())?;
+        let excess = actual_balance.saturating_sub(expected_balance);
+        if excess <= 0 {
+            return Err(Error::NothingToSweep);
+        }
+
+        let stablecoin = Self::load_config(&env)?.stablecoin;
+        token::Client::new(&env, &stablecoin).transfer(
+            &env.current_contract_address(),
+            &to,
+            &excess,
+        );
+
+        let swept = Self::get_unexpected_inflows_swept(env.clone())
+            .checked_add(excess)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::UnexpectedInflowsSwept, &swept);
+
+        publish_global(
+            &env,
+            VAULT,
+            "unexpected_inflow_swept",
+            UnexpectedInflowSweptEvent { to, amount: excess },
+        );
+
+        Ok(excess)
+    }
+
+    /// Check whether an address is a registered settlement sender
+    pub fn is_settlement_sender(env: Env, sender: Address) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::SettlementSender(sender))
+            .unwrap_or(false)
+    }
+
+    /// Cumulative unexpected inflows swept out of the vault
+    pub fn get_unexpected_inflows_swept(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::UnexpectedInflowsSwept)
+            .unwrap_or(0)
+    }
+
+    // ============================================
+    // BATCHED SETTLEMENT
+    // ============================================
+
+    /// Switch a matured series to issuer-run batched settlement
+    ///
+    /// While settling, holders cannot redeem the series themselves; the
+    /// issuer pays them out batch by batch so the full liability never
+    /// has to be liquid at once. Holders not on the series holder list
+    /// (e.g. transferees) redeem normally once settlement completes.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidStatus`: Series not in MATURED status, or already settling
+    pub fn begin_batched_settlement(env: Env, series_id: u32) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let mut series = Self::load_series(&env, series_id)?;
+        Self::require_issuer(&env, &series)?;
+        Self::mature_if_due(&env, &mut series)?;
+        if series.status != SeriesStatus::Matured {
+            return Err(Error::InvalidStatus);
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::SettlementPlan(series_id))
+        {
+            return Err(Error::InvalidStatus);
+        }
+
+        let plan = SettlementPlan {
+            next_holder: 0,
+            announced: 0,
+            processed: 0,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::SettlementPlan(series_id), &plan);
+
+        publish(
+            &env,
+            VAULT,
+            "settlement_begun",
+            series_id,
+            SettlementBegunEvent {
+                series_id,
+                holder_count: Self::series_holder_count(&env, series_id),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Announce the next batch, covering holder indices `[next_holder, end)`
+    ///
+    /// Returns the batch ID. The announced funding is an estimate at current
+    /// balances; the batch is funded at actual balances when processed.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series is not settling
+    /// - `InvalidBatchRange`: end must be past the next holder and within the holder list
+    pub fn announce_settlement_batch(env: Env, series_id: u32, end: u32) -> Result<u32, Error> {
+        Self::require_schema(&env)?;
+        let series = Self::load_series(&env, series_id)?;
+        Self::require_issuer(&env, &series)?;
+
+        let mut plan: SettlementPlan = env
+            .storage()
+            .instance()
+            .get(&DataKey::SettlementPlan(series_id))
+            .ok_or(Error::SeriesNotFound)?;
+
+        let start = plan.next_holder;
+        if end <= start || end > Self::series_holder_count(&env, series_id) {
+            return Err(Error::InvalidBatchRange);
+        }
+
+        let (estimated_funding, _) = Self::batch_payouts(&env, series_id, start, end)?;
+
+        let batch_id = plan.announced;
+        env.storage().instance().set(
+            &DataKey::SettlementBatch(series_id, batch_id),
+            &SettlementBatch {
+                start,
+                end,
+                funded: 0,
+                processed: false,
+            },
+        );
+        plan.next_holder = end;
+        plan.announced += 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::SettlementPlan(series_id), &plan);
+
+        publish(
+            &env,
+            VAULT,
+            "settlement_batch_announced",
+            series_id,
+            SettlementBatchAnnouncedEvent {
+                series_id,
+                batch_id,
+                start,
+                end,
+                estimated_funding,
+            },
+        );
+
+        Ok(batch_id)
+    }
+
+    /// Fund and pay out the oldest unprocessed batch
+    ///
+    /// Pulls the batch's payout from the series issuer, then redeems every holder
+    /// in range in full. Once the last batch covering the holder list is
+    /// processed, the series leaves settlement and redemptions reopen.
+    /// Returns the amount funded, in asset units.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series is not settling
+    /// - `InvalidBatchRange`: Every announced batch has been processed
+    pub fn process_settlement_batch(env: Env, series_id: u32) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        let series = Self::load_series(&env, series_id)?;
+        let issuer = Self::require_issuer(&env, &series)?;
+
+        let mut plan: SettlementPlan = env
+            .storage()
+            .instance()
+            .get(&DataKey::SettlementPlan(series_id))
+            .ok_or(Error::SeriesNotFound)?;
+        if plan.processed >= plan.announced {
+            return Err(Error::InvalidBatchRange);
+        }
+
+        let batch_id = plan.processed;
+        let batch_key = DataKey::SettlementBatch(series_id, batch_id);
+        let mut batch: SettlementBatch = env
+            .storage()
+            .instance()
+            .get(&batch_key)
+            .ok_or(Error::InvalidBatchRange)?;
+
+        let (funding, balances) = Self::batch_payouts(&env, series_id, batch.start, batch.end)?;
+
+        if funding > 0 {
+            Self::deposit_settlement_from(&env, &issuer, funding)?;
+        }
+
+        let mut holders_paid = 0u32;
+        for (holder, balance) in balances.iter() {
+            let (payout, _) = Self::burn_and_book(&env, &holder, series_id, balance, false, 0)?;
+            Self::pay_out(&env, &holder, payout)?;
+            holders_paid += 1;
+        }
+
+        batch.funded = funding;
+        batch.processed = true;
+        env.storage().instance().set(&batch_key, &batch);
+
+        plan.processed += 1;
+        let complete = plan.processed == plan.announced
+            && plan.next_holder >= Self::series_holder_count(&env, series_id);
+        if complete {
+            env.storage()
+                .instance()
+                .remove(&DataKey::SettlementPlan(series_id));
+        } else {
+            env.storage()
+                .instance()
+                .set(&DataKey::SettlementPlan(series_id), &plan);
+        }
+
+        publish(
+            &env,
+            VAULT,
+            "settlement_batch_processed",
+            series_id,
+            SettlementBatchProcessedEvent {
+                series_id,
+                batch_id,
+                funded: funding,
+                holders_paid,
+            },
+        );
+        if complete {
+            publish(
+                &env,
+                VAULT,
+                "settlement_completed",
+                series_id,
+                SettlementCompletedEvent {
+                    series_id,
+                    batches: plan.processed,
+                },
+            );
+        }
+
+        Ok(funding)
+    }
+
+    /// Batched settlement plan for a series, if it is settling
+    pub fn get_settlement_plan(env: Env, series_id: u32) -> Option<SettlementPlan> {
+        env.storage()
+            .instance()
+            .get(&DataKey::SettlementPlan(series_id))
+    }
+
+    /// Announced settlement batch, if any
+    pub fn get_settlement_batch(
+        env: Env,
+        series_id: u32,
+        batch_id: u32,
+    ) -> Option<SettlementBatch> {
+        env.storage()
+            .instance()
+            .get(&DataKey::SettlementBatch(series_id, batch_id))
+    }
+
+    // ============================================
+    // REDEMPTION FAST LANE
+    // ============================================
+
+    /// Configure the retail fast lane for redemptions
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: threshold and retail_reserve must not be negative
+    pub fn set_redemption_lane(env: Env, lane: RedemptionLane) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if lane.threshold < 0 || lane.retail_reserve < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RedemptionLane, &lane);
+
+        publish_global(
+            &env,
+            VAULT,
+            "redemption_lane_updated",
+            RedemptionLaneUpdatedEvent { lane },
+        );
+
+        Ok(())
+    }
+
+    /// Pay queued large redemptions, oldest first, while liquidity allows
+    ///
+    /// Permissionless. Stops at the first entry that would dip into the
+    /// retail reserve, or after `max_entries`. Returns the number paid.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ContractPaused`: Contract is paused
+    pub fn process_redemption_queue(env: Env, max_entries: u32) -> Result<u32, Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        let mut queue = Self::get_redemption_queue(env.clone());
+        let retail_reserve = Self::get_redemption_lane(env.clone())
+            .map(|lane| lane.retail_reserve)
+            .unwrap_or(0);
+        let mut balance = Self::stablecoin_balance(&env)?;
+        let mut accounting = Self::load_booked_accounting(&env);
+        let mut paid = 0u32;
+
+        while paid < max_entries {
+            let Some(entry) = queue.first() else {
+                break;
+            };
+            if balance.saturating_sub(entry.amount) < retail_reserve {
+                break;
+            }
+
+            Self::pay_out(&env, &entry.user, entry.amount)?;
+            balance -= entry.amount;
+            accounting.queued_payouts = accounting
+                .queued_payouts
+                .checked_sub(entry.amount)
+                .ok_or(Error::InvalidAmount)?;
+            queue.pop_front();
+            paid += 1;
+
+            publish(
+                &env,
+                VAULT,
+                "queued_redemption_paid",
+                entry.series_id,
+                QueuedRedemptionPaidEvent {
+                    user: entry.user,
+                    series_id: entry.series_id,
+                    amount: entry.amount,
+                },
+            );
+        }
+
+        if paid > 0 {
+            Self::save_accounting(&env, &accounting);
+            env.storage()
+                .instance()
+                .set(&DataKey::RedemptionQueue, &queue);
+        }
+
+        Ok(paid)
+    }
+
+    /// Current redemption fast lane, if configured
+    pub fn get_redemption_lane(env: Env) -> Option<RedemptionLane> {
+        env.storage().instance().get(&DataKey::RedemptionLane)
+    }
+
+    /// Large redemptions waiting for liquidity, oldest first
+    pub fn get_redemption_queue(env: Env) -> Vec<QueuedRedemption> {
+        env.storage()
+            .instance()
+            .get(&DataKey::RedemptionQueue)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ============================================
+    // LOYALTY TIERS
+    // ============================================
+
+    /// Configure volume-based multipliers on the per-user cap
+    ///
+    /// Passing an empty list disables boosts (every user at 1×).
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidConfig`: Tiers not ascending or multiplier below 1×
+    pub fn set_loyalty_tiers(env: Env, tiers: Vec<LoyaltyTier>) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if !validate_loyalty_tiers(&tiers) {
+            return Err(Error::InvalidConfig);
+        }
+
+        env.storage().instance().set(&DataKey::LoyaltyTiers, &tiers);
+
+        publish_global(
+            &env,
+            VAULT,
+            "loyalty_tiers_updated",
+            LoyaltyTiersUpdatedEvent { tiers },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_loyalty_tiers(env: Env) -> Vec<LoyaltyTier> {
+        env.storage()
+            .instance()
+            .get(&DataKey::LoyaltyTiers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get user's cumulative volume and the cap multiplier applied at subscribe
+    pub fn get_user_tier(env: Env, user: Address) -> UserTier {
+        let volume: i128 = Self::load_account_entry(&env, &DataKey::UserVolume(user)).unwrap_or(0);
+
+        let tiers = Self::get_loyalty_tiers(env.clone());
+        let (tier, cap_multiplier_bps) = resolve_loyalty_tier(&tiers, volume);
+
+        UserTier {
+            volume,
+            tier,
+            cap_multiplier_bps,
+        }
+    }
+
+    /// Let a whitelisted account (market maker, institution) subscribe beyond user_cap_par
+    ///
+    /// The series cap still applies to exempt accounts.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_cap_exempt(env: Env, account: Address, exempt: bool) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        Self::save_account_flag(&env, &DataKeyExt::CapExempt(account.clone()), exempt);
+
+        publish(
+            &env,
+            VAULT,
+            "cap_exemption_updated",
+            account.clone(),
+            CapExemptionUpdatedEvent { account, exempt },
+        );
+
+        Ok(())
+    }
+
+    pub fn is_cap_exempt(env: Env, account: Address) -> bool {
+        Self::load_account_flag(&env, &DataKeyExt::CapExempt(account))
+    }
+
+    /// PAR the user can still subscribe in a series (boosted cap - subscribed)
+    ///
+    /// Cap-exempt accounts are only limited by the series' remaining cap.
+    ///
+    /// # Errors
+    /// - `SeriesNotFound`: Series doesn't exist
+    pub fn get_remaining_capacity(env: Env, series_id: u32, user: Address) -> Result<i128, Error> {
+        let series = Self::load_series(&env, series_id)?;
+        let series_remaining = series.cap_par.saturating_sub(series.minted_par);
+        if Self::is_cap_exempt(env.clone(), user.clone()) {
+            return Ok(series_remaining.max(0));
+        }
+
+        let user_tier = Self::get_user_tier(env.clone(), user.clone());
+        let effective_user_cap =
+            calculate_effective_user_cap(series.user_cap_par, user_tier.cap_multiplier_bps)
+                .ok_or(Error::InvalidAmount)?;
+        let subscribed = Self::get_user_position(env, series_id, user).subscribed_par;
+
+        Ok(effective_user_cap
+            .saturating_sub(subscribed)
+            .min(series_remaining)
+            .max(0))
+    }
+
+    // ============================================
+    // REWARDS HOOK
+    // ============================================
+
+    /// Set (or clear) the external incentives contract notified on user actions
+    ///
+    /// The hook must expose `on_action(user: Address, action: Symbol, notional: i128)`.
+    /// Hook failures are swallowed so a broken campaign cannot block core flows.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_rewards_hook(env: Env, hook: Option<Address>) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        match &hook {
+            Some(hook) => env.storage().instance().set(&DataKey::RewardsHook, hook),
+            None => env.storage().instance().remove(&DataKey::RewardsHook),
+        }
+
+        publish_global(
+            &env,
+            VAULT,
+            "rewards_hook_updated",
+            RewardsHookUpdatedEvent { hook },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_rewards_hook(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::RewardsHook)
+    }
+
+    // ============================================
+    // OBSERVERS
+    // ============================================
+
+    /// Register a contract notified of series lifecycle events
+    ///
+    /// Observers must expose `on_vault_event(event: Symbol, series_id: u32, amount: i128)`
+    /// and are called best-effort for `series_activated`, `series_matured`
+    /// (amount = outstanding PAR) and `large_subscription` (amount = minted PAR).
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ObserverAlreadyRegistered`: Observer is already registered
+    /// - `TooManyObservers`: Registry already holds MAX_OBSERVERS
+    pub fn add_observer(env: Env, observer: Address) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        let mut observers = Self::get_observers(env.clone());
+        if observers.contains(&observer) {
+            return Err(Error::ObserverAlreadyRegistered);
+        }
+        if observers.len() >= MAX_OBSERVERS {
+            return Err(Error::TooManyObservers);
+        }
+        observers.push_back(observer.clone());
+        env.storage().instance().set(&DataKey::Observers, &observers);
+
+        publish_global(
+            &env,
+            VAULT,
+            "observer_updated",
+            ObserverUpdatedEvent {
+                observer,
+                registered: true,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Stop notifying an observer
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ObserverNotRegistered`: Observer is not registered
+    pub fn remove_observer(env: Env, observer: Address) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        let mut observers = Self::get_observers(env.clone());
+        let index = observers
+            .first_index_of(&observer)
+            .ok_or(Error::ObserverNotRegistered)?;
+        observers.remove(index);
+        env.storage().instance().set(&DataKey::Observers, &observers);
+
+        publish_global(
+            &env,
+            VAULT,
+            "observer_updated",
+            ObserverUpdatedEvent {
+                observer,
+                registered: false,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Set the minted PAR at or above which a subscription notifies observers (0 disables)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: Threshold is negative
+    pub fn set_large_subscription_threshold(env: Env, threshold_par: i128) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if threshold_par < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::LargeSubscriptionPar, &threshold_par);
+
+        publish_global(
+            &env,
+            VAULT,
+            "large_sub_threshold_updated",
+            LargeSubscriptionThresholdUpdatedEvent { threshold_par },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_observers(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Observers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_large_subscription_threshold(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::LargeSubscriptionPar)
+            .unwrap_or(0)
+    }
+
+    // ============================================
+    // CIRCUIT BREAKER
+    // ============================================
+
+    /// Cap PAR minted per ledger window for a series
+    ///
+    /// A subscription that pushes the window over `max_window_par` still
+    /// completes, then halts further subscriptions for `pause_ledgers`.
+    /// Replacing the config keeps any trip in force.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidConfig`: Window, cap or pause is zero
+    pub fn set_circuit_breaker(
+        env: Env,
+        series_id: u32,
+        breaker: CircuitBreaker,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::SeriesManager)?;
+
+        if !Self::has_series(&env, series_id) {
+            return Err(Error::SeriesNotFound);
+        }
+        if !validate_circuit_breaker(&breaker) {
+            return Err(Error::InvalidConfig);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::CircuitBreaker(series_id), &breaker);
+
+        publish(
+            &env,
+            VAULT,
+            "circuit_breaker_updated",
+            series_id,
+            CircuitBreakerUpdatedEvent { series_id, breaker },
+        );
+
+        Ok(())
+    }
+
+    /// Remove a series' circuit breaker and lift any trip in force
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn clear_circuit_breaker(env: Env, series_id: u32) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::SeriesManager)?;
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::CircuitBreaker(series_id));
+        env.storage()
+            .instance()
+            .remove(&DataKey::BreakerState(series_id));
+
+        publish(
+            &env,
+            VAULT,
+            "circuit_breaker_cleared",
+            series_id,
+            CircuitBreakerClearedEvent { series_id },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_circuit_breaker(env: Env, series_id: u32) -> Option<CircuitBreaker> {
+        env.storage()
+            .instance()
+            .get(&DataKey::CircuitBreaker(series_id))
+    }
+
+    pub fn get_breaker_state(env: Env, series_id: u32) -> Option<BreakerState> {
+        env.storage()
+            .instance()
+            .get(&DataKey::BreakerState(series_id))
+    }
+
+    // ============================================
+    // PAYMENT COLLECTION
+    // ============================================
+
+    /// Collect subscription payments with `transfer_from` against an allowance
+    ///
+    /// When enabled, subscribers `approve` the vault for the payment (plus
+    /// any fee) and `subscribe` pulls it, instead of the subscriber's auth
+    /// covering a direct `transfer`. Either way the vault checks that its
+    /// balance grew by exactly the amount it collected.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_allowance_pull(env: Env, enabled: bool) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if enabled {
+            env.storage()
+                .instance()
+                .set(&DataKeyExt::AllowancePull, &true);
+        } else {
+            env.storage().instance().remove(&DataKeyExt::AllowancePull);
+        }
+        Ok(())
+    }
+
+    pub fn is_allowance_pull(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::AllowancePull)
+            .unwrap_or(false)
+    }
+
+    // ============================================
+    // SUBSCRIPTION FEE
+    // ============================================
+
+    /// Set the fee charged on `subscribe` (bps of pay_amount, 0 disables it)
+    ///
+    /// The fee is paid by the subscriber straight to the fee collector, and
+    /// bT-Bills are minted for the remainder only. Rolls are not charged.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: fee_bps above MAX_SUBSCRIPTION_FEE_BPS
+    /// - `ActionTimelocked`: An admin delay is set; use `queue_admin_action`
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn set_subscription_fee_bps(
+        env: Env,
+        fee_bps: u32,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let signer = Self::require_role(&env, Role::FeeManager)?;
+        Self::require_no_admin_delay(&env)?;
+        Self::consume_auth_nonce(&env, &signer, nonce, expiry)?;
+
+        Self::apply_subscription_fee_bps(&env, fee_bps)
+    }
+
+    /// Set the address receiving subscription fees
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ActionTimelocked`: An admin delay is set; use `queue_admin_action`
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn set_fee_collector(
+        env: Env,
+        collector: Address,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let signer = Self::require_role(&env, Role::FeeManager)?;
+        Self::require_no_admin_delay(&env)?;
+        Self::consume_auth_nonce(&env, &signer, nonce, expiry)?;
+
+        Self::apply_fee_collector(&env, collector);
+        Ok(())
+    }
+
+    pub fn get_subscription_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SubscriptionFeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Fee collector, defaulting to the treasury
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn get_fee_collector(env: Env) -> Result<Address, Error> {
+        if let Some(collector) = env.storage().instance().get(&DataKey::FeeCollector) {
+            return Ok(collector);
+        }
+        Self::get_treasury(env)
+    }
+
+    // ============================================
+    // REDEMPTION FEE
+    // ============================================
+
+    /// Set the default fee withheld from matured redemptions (bps of payout, 0 disables it)
+    ///
+    /// Applies to every maturity payout (`redeem`, delegated and automated
+    /// redemptions, rolls, batched settlement), not to `redeem_early` or
+    /// refunds. The fee stays in the vault until `collect_fees`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: fee_bps above MAX_REDEMPTION_FEE_BPS
+    /// - `ActionTimelocked`: An admin delay is set; use `queue_admin_action`
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn set_redemption_fee_bps(
+        env: Env,
+        fee_bps: u32,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let signer = Self::require_role(&env, Role::FeeManager)?;
+        Self::require_no_admin_delay(&env)?;
+        Self::consume_auth_nonce(&env, &signer, nonce, expiry)?;
+
+        Self::apply_redemption_fee_bps(&env, fee_bps)
+    }
+
+    /// Override the redemption fee for one series (None falls back to the default)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidAmount`: fee_bps above MAX_REDEMPTION_FEE_BPS
+    /// - `ActionTimelocked`: An admin delay is set; use `queue_admin_action`
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn set_series_redemption_fee_bps(
+        env: Env,
+        series_id: u32,
+        fee_bps: Option<u32>,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let signer = Self::require_role(&env, Role::FeeManager)?;
+        Self::require_no_admin_delay(&env)?;
+        Self::consume_auth_nonce(&env, &signer, nonce, expiry)?;
+
+        Self::apply_series_redemption_fee_bps(&env, series_id, fee_bps)
+    }
+
+    /// Pay every accrued protocol fee (redemption fees, spread shares) to the fee collector
+    ///
+    /// Returns the amount collected, in asset units.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NothingToSweep`: No fees accrued since the last collection
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn collect_fees(env: Env, nonce: u64, expiry: u64) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        let treasury = Self::load_config(&env)?.treasury;
+        treasury.require_auth();
+        Self::consume_auth_nonce(&env, &treasury, nonce, expiry)?;
+
+        let amount = Self::get_accrued_fees(env.clone());
+        if amount <= 0 {
+            return Err(Error::NothingToSweep);
+        }
+
+        let to = Self::get_fee_collector(env.clone())?;
+        env.storage().instance().remove(&DataKeyExt::AccruedFees);
+        Self::pay_out(&env, &to, amount)?;
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_fees_collected = accounting
+            .total_fees_collected
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(&env, &accounting);
+
+        publish_global(
+            &env,
+            VAULT,
+            "fees_collected",
+            FeesCollectedEvent { to, amount },
+        );
+
+        Ok(amount)
+    }
+
+    /// Redemption fee applied to a series: its override, else the default
+    pub fn get_redemption_fee_bps(env: Env, series_id: u32) -> u32 {
+        let storage = env.storage().instance();
+        storage
+            .get(&DataKeyExt::SeriesRedemptionFeeBps(series_id))
+            .or_else(|| storage.get(&DataKeyExt::RedemptionFeeBps))
+            .unwrap_or(0)
+    }
+
+    pub fn get_accrued_fees(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::AccruedFees)
+            .unwrap_or(0)
+    }
+
+    // ============================================
+    // SHADOW MODE
+    // ============================================
+
+    /// Run a risk feature in shadow mode, or put it back into enforcement
+    ///
+    /// While shadowed, the feature is evaluated on every subscription or
+    /// redemption and its would-be outcome emitted as `shadow_outcome`, but
+    /// the behavior from before the feature applies. This lets new
+    /// parameters be validated against live flow before they bite.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAuthNonce`: `nonce` is not the signer's next auth nonce, or ledger time is past `expiry`
+    pub fn set_shadow_mode(
+        env: Env,
+        feature: ShadowFeature,
+        enabled: bool,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+        Self::consume_auth_nonce(&env, &admin, nonce, expiry)?;
+
+        if enabled {
+            env.storage()
+                .instance()
+                .set(&DataKeyExt::ShadowMode(feature), &true);
+        } else {
+            env.storage()
+                .instance()
+                .remove(&DataKeyExt::ShadowMode(feature));
+        }
+
+        publish_global(
+            &env,
+            VAULT,
+            "shadow_mode_updated",
+            ShadowModeUpdatedEvent { feature, enabled },
+        );
+
+        Ok(())
+    }
+
+    pub fn is_shadow_mode(env: Env, feature: ShadowFeature) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::ShadowMode(feature))
+            .unwrap_or(false)
+    }
+
+    // ============================================
+    // REFERRALS
+    // ============================================
+
+    /// Set the referral reward rate (bps of the protocol subscription fee, 0 disables accrual)
+    ///
+    /// Capped at 10,000 so a reward can never exceed the fee it is carved from.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidConfig`: reward_bps exceeds 10,000
+    pub fn set_referral_reward_bps(env: Env, reward_bps: u32) -> Result<(), Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::FeeManager)?;
+
+        if reward_bps as i128 > BASIS_POINTS {
+            return Err(Error::InvalidConfig);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReferralRewardBps, &reward_bps);
+
+        publish_global(
+            &env,
+            VAULT,
+            "referral_reward_updated",
+            ReferralRewardUpdatedEvent { reward_bps },
+        );
+
+        Ok(())
+    }
+
+    /// Pay out a referrer's accrued rewards
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ContractPaused`: Contract is paused
+    /// - `NoReferralRewards`: Nothing accrued
+    pub fn claim_referral_rewards(env: Env, referrer: Address) -> Result<i128, Error> {
+        Self::require_schema(&env)?;
+        Self::check_not_paused(&env)?;
+
+        referrer.require_auth();
+
+        let amount = Self::get_referral_rewards(env.clone(), referrer.clone());
+        if amount <= 0 {
+            return Err(Error::NoReferralRewards);
+        }
+
+        Self::remove_account_entry(&env, &DataKey::ReferralRewards(referrer.clone()));
+
+        let stablecoin = Self::load_config(&env)?.stablecoin;
+        token::Client::new(&env, &stablecoin).transfer(
+            &env.current_contract_address(),
+            &referrer,
+            &amount,
+        );
+
+        let mut accounting = Self::load_booked_accounting(&env);
+        accounting.total_referral_rewards_paid = accounting
+            .total_referral_rewards_paid
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(&env, &accounting);
+
+        publish(
+            &env,
+            VAULT,
+            "referral_claimed",
+            referrer.clone(),
+            ReferralRewardsClaimedEvent { referrer, amount },
+        );
+
+        Ok(amount)
+    }
+
+    pub fn get_referral_reward_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ReferralRewardBps)
+            .unwrap_or(0)
+    }
+
+    /// Referrer attributed to a user, if any
+    pub fn get_referrer(env: Env, user: Address) -> Option<Address> {
+        Self::load_account_entry(&env, &DataKey::Referrer(user))
+    }
+
+    /// Unclaimed rewards accrued to a referrer
+    pub fn get_referral_rewards(env: Env, referrer: Address) -> i128 {
+        Self::load_account_entry(&env, &DataKey::ReferralRewards(referrer)).unwrap_or(0)
+    }
+
+    // ============================================
+    // RECONCILIATION
+    // ============================================
+
+    /// Compare internal accounting to the actual stablecoin balance
+    ///
+    /// Callable by anyone. Emits a `ReconciliationEvent` with the delta so
+    /// unexpected inflows/outflows become visible on-chain.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn reconcile(env: Env) -> Result<Reconciliation, Error> {
+        let actual_balance = Self::stablecoin_balance(&env)?;
+        let expected_balance = Self::expected_balance(env.clone())?;
+        let delta = actual_balance
+            .checked_sub(expected_balance)
+            .ok_or(Error::InvalidAmount)?;
+
+        publish_global(
+            &env,
+            VAULT,
+            "reconciliation",
+            ReconciliationEvent {
+                expected_balance,
+                actual_balance,
+                delta,
+            },
+        );
+
+        Ok(Reconciliation {
+            expected_balance,
+            actual_balance,
+            delta,
+        })
+    }
+
+    // ============================================
+    // STORAGE PRUNING
+    // ============================================
+
+    /// Archive and remove Closed series to keep storage rent bounded
+    ///
+    /// For each Closed series, emits `series_archived` with a hash of its
+    /// final state, then drops the series, its holder index and every holder
+    /// position. A persistent tombstone keeps the ID from being reused.
+    /// Series that are missing or not Closed are skipped. Returns the number
+    /// of series pruned.
+    pub fn prune_series(env: Env, keeper: Address, series_ids: Vec<u32>) -> u32 {
+        if Self::require_schema(&env).is_err() {
+            return 0;
+        }
+        keeper.require_auth();
+
+        let mut all_series_ids = Self::load_series_ids(&env);
+        let mut pruned = 0u32;
+
+        for series_id in series_ids.iter() {
+            let Some(series) = Self::try_load_series(&env, series_id) else {
+                continue;
+            };
+            if series.status != SeriesStatus::Closed {
+                continue;
+            }
+            if Self::fold_series_tally(&env, series_id).is_err() {
+                continue;
+            }
+
+            let state_hash: BytesN<32> = env.crypto().sha256(&series.to_xdr(&env)).into();
+
+            let holder_count = Self::series_holder_count(&env, series_id);
+            for index in 0..holder_count {
+                if let Some(holder) = Self::series_holder(&env, series_id, index) {
+                    Self::remove_user_position(&env, series_id, &holder);
+                }
+                env.storage()
+                    .persistent()
+                    .remove(&DataKeyExt::SeriesHolder(series_id, index));
+            }
+            env.storage()
+                .persistent()
+                .remove(&DataKeyExt::SeriesHolderCount(series_id));
+            env.storage()
+                .instance()
+                .remove(&DataKey::SeriesHolders(series_id));
+            Self::remove_series(&env, series_id);
+
+            if let Some(index) = all_series_ids.first_index_of(series_id) {
+                all_series_ids.remove(index);
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::ArchivedSeries(series_id), &state_hash);
+
+            publish(
+                &env,
+                VAULT,
+                "series_archived",
+                series_id,
+                SeriesArchivedEvent {
+                    series_id,
+                    holders_pruned: holder_count,
+                    state_hash,
+                },
+            );
+            pruned += 1;
+        }
+
+        Self::save_series_ids(&env, &all_series_ids);
+        pruned
+    }
+
+    /// Get the final state hash recorded when a series was pruned
+    pub fn get_archived_series_hash(env: Env, series_id: u32) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ArchivedSeries(series_id))
+    }
+
+    /// Move a series, its holder index and holder positions from instance to persistent storage
+    ///
+    /// One-off migration for deployments that predate persistent series
+    /// storage, where the series, the series ID list, each series' holder
+    /// index and every holder position all lived in instance storage.
+    /// Until migrated, reads fall back to the instance copies and any write
+    /// (a subscription, redemption or refund) moves the entry it touches, so
+    /// legacy positions stay usable throughout.
+    ///
+    /// Call once per series, paging through its holders with `start` /
+    /// `limit` (capped at MAX_EXPORT_PAGE) until it returns 0. Each page
+    /// copies the holders' index entries and positions across; the legacy
+    /// holder list is dropped once the last page is copied. Safe to re-run.
+    /// Returns the number of holders processed.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: Series doesn't exist
+    pub fn migrate_series_storage(
+        env: Env,
+        series_id: u32,
+        start: u32,
+        limit: u32,
+    ) -> Result<u32, Error> {
+        Self::require_schema(&env)?;
+        Self::require_role(&env, Role::Upgrader)?;
+
+        // Both copy themselves to persistent storage on save
+        Self::save_series_ids(&env, &Self::load_series_ids(&env));
+        let series = Self::load_series(&env, series_id)?;
+        Self::save_series(&env, &series);
+
+        let legacy_key = DataKey::SeriesHolders(series_id);
+        let legacy: Option<Vec<Address>> = env.storage().instance().get(&legacy_key);
+        let holder_count = Self::series_holder_count(&env, series_id);
+        let end = start
+            .saturating_add(limit.min(MAX_EXPORT_PAGE))
+            .min(holder_count);
+
+        let storage = env.storage().persistent();
+        let mut positions_migrated = 0u32;
+        for index in start..end {
+            let Some(holder) = Self::series_holder(&env, series_id, index) else {
+                continue;
+            };
+            let holder_key = DataKeyExt::SeriesHolder(series_id, index);
+            storage.set(&holder_key, &holder);
+            storage.extend_ttl(&holder_key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+
+            let key = DataKey::UserPosition(series_id, holder.clone());
+            if let Some(position) = env.storage().instance().get::<DataKey, UserPosition>(&key) {
+                Self::save_user_position(&env, series_id, &holder, &position);
+                positions_migrated += 1;
+            }
+        }
+
+        if legacy.is_some() {
+            let count_key = DataKeyExt::SeriesHolderCount(series_id);
+            storage.set(&count_key, &holder_count);
+            storage.extend_ttl(&count_key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+            if end >= holder_count {
+                env.storage().instance().remove(&legacy_key);
+            }
+        }
+
+        if end > start {
+            publish(
+                &env,
+                VAULT,
+                "series_storage_migrated",
+                series_id,
+                SeriesStorageMigratedEvent {
+                    series_id,
+                    positions_migrated,
+                },
+            );
+        }
+
+        Ok(end.saturating_sub(start))
+    }
+
+    /// Extend the TTL of a series and a page of its holder index and positions
+    ///
+    /// Permissionless, so keepers can keep long-dated series alive without
+    /// waiting for user activity. Pages through holders with `start` /
+    /// `limit` (capped at MAX_EXPORT_PAGE); returns the number of holders
+    /// covered, 0 once past the end.
+    ///
+    /// # Errors
+    /// - `SeriesNotFound`: Series doesn't exist
+    pub fn extend_series_ttl(
+        env: Env,
+        series_id: u32,
+        start: u32,
+        limit: u32,
+    ) -> Result<u32, Error> {
+        Self::require_schema(&env)?;
+        // load_series bumps the series entry itself
+        Self::load_series(&env, series_id)?;
+
+        let storage = env.storage().persistent();
+        if storage.has(&DataKey::SeriesIds) {
+            storage.extend_ttl(&DataKey::SeriesIds, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+        }
+        let count_key = DataKeyExt::SeriesHolderCount(series_id);
+        if storage.has(&count_key) {
+            storage.extend_ttl(&count_key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+        }
+
+        let end = start
+            .saturating_add(limit.min(MAX_EXPORT_PAGE))
+            .min(Self::series_holder_count(&env, series_id));
+        for index in start..end {
+            let holder_key = DataKeyExt::SeriesHolder(series_id, index);
+            if storage.has(&holder_key) {
+                storage.extend_ttl(&holder_key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+            }
+            let Some(holder) = Self::series_holder(&env, series_id, index) else {
+                continue;
+            };
+            let key = DataKey::UserPosition(series_id, holder);
+            if storage.has(&key) {
+                storage.extend_ttl(&key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+            }
+        }
+
+        Ok(end.saturating_sub(start))
+    }
+
+    // ============================================
+    // VIEW FUNCTIONS
+    // ============================================
+
+    /// Get current price for a series
+    pub fn current_price(env: Env, series_id: u32) -> Result<i128, Error> {
+        let series = Self::load_series(&env, series_id)?;
+
+        let current_time = env.ledger().timestamp();
+        Ok(calculate_current_price(&series, current_time))
+    }
+
+    /// Canonical collateral valuation hook: stablecoin value of `amount` bT-Bills
+    ///
+    /// Returns amount × current accreted price, in settlement asset units.
+    /// External money markets should
+    /// call this instead of re-implementing the accretion curve.
+    ///
+    /// # Errors
+    /// - `SeriesNotFound`: Series doesn't exist
+    /// - `InvalidAmount`: amount must not be negative
+    pub fn collateral_value(env: Env, series_id: u32, amount: i128) -> Result<i128, Error> {
+        if amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let price = Self::current_price(env.clone(), series_id)?;
+        calculate_collateral_value(amount, price)
+            .and_then(|value| to_asset_units(value, Self::asset_decimals(env)))
+            .ok_or(Error::InvalidAmount)
+    }
+
+    /// Get series details
+    pub fn get_series(env: Env, series_id: u32) -> Result<Series, Error> {
+        Self::load_series(&env, series_id)
+    }
+
+    /// Get series status only (cheap cross-contract check for integrators)
+    pub fn get_series_status(env: Env, series_id: u32) -> Result<SeriesStatus, Error> {
+        Ok(Self::get_series(env, series_id)?.status)
+    }
+
+    /// Get series maturity only (used by repo markets to resolve a series' vault)
+    pub fn get_series_maturity(env: Env, series_id: u32) -> Result<u64, Error> {
+        Ok(Self::get_series(env, series_id)?.maturity_date)
+    }
+
+    /// Get PAR minted and not yet redeemed (cheap cross-contract check for monitors)
+    pub fn outstanding_par(env: Env, series_id: u32) -> Result<i128, Error> {
+        let series = Self::get_series(env, series_id)?;
+        Ok(calculate_outstanding_par(&series))
+    }
+
+    /// Get every created series ID, in creation order
+    ///
+    /// Pruned series drop out of the index (see `get_archived_series_hash`).
+    pub fn get_series_ids(env: Env) -> Vec<u32> {
+        Self::load_series_ids(&env)
+    }
+
+    /// Page through the series index: series at positions `[start, start + limit)`
+    ///
+    /// `limit` is capped at MAX_EXPORT_PAGE; an empty result means the end.
+    pub fn get_series_page(env: Env, start: u32, limit: u32) -> Vec<Series> {
+        let series_ids = Self::load_series_ids(&env);
+        let end = start
+            .saturating_add(limit.min(MAX_EXPORT_PAGE))
+            .min(series_ids.len());
+
+        let mut page = Vec::new(&env);
+        for i in start..end {
+            let series_id = series_ids.get_unchecked(i);
+            if let Some(series) = Self::try_load_series(&env, series_id) {
+                page.push_back(series);
+            }
+        }
+        page
+    }
+
+    /// Project the treasury's liquidity needs over the next `horizon_secs`
+    ///
+    /// Lists every series with PAR outstanding that matures (or already
+    /// matured) by the horizon, alongside repo repayments due in the same
+    /// window from the configured repo market (0 if none is set).
+    ///
+    /// # Errors
+    /// - `InvalidAmount`: Liability overflows
+    pub fn liquidity_forecast(env: Env, horizon_secs: u64) -> Result<LiquidityForecast, Error> {
+        let horizon_end = env.ledger().timestamp().saturating_add(horizon_secs);
+        let asset_decimals = Self::asset_decimals(env.clone());
+
+        let mut maturities: Vec<MaturityFlow> = Vec::new(&env);
+        let mut total_maturing: i128 = 0;
+        for series_id in Self::load_series_ids(&env).iter() {
+            let Some(series) = Self::try_load_series(&env, series_id) else {
+                continue;
+            };
+            let outstanding = calculate_outstanding_par(&series);
+            if outstanding <= 0 || series.maturity_date > horizon_end {
+                continue;
+            }
+
+            let liability =
+                to_asset_units(outstanding, asset_decimals).ok_or(Error::InvalidAmount)?;
+            total_maturing = total_maturing
+                .checked_add(liability)
+                .ok_or(Error::InvalidAmount)?;
+
+            let index = maturities
+                .iter()
+                .position(|flow| flow.maturity_date > series.maturity_date)
+                .unwrap_or(maturities.len() as usize) as u32;
+            maturities.insert(
+                index,
+                MaturityFlow {
+                    series_id,
+                    maturity_date: series.maturity_date,
+                    liability,
+                },
+            );
+        }
+
+        let repo_repayments_due = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::RepoMarket)
+            .and_then(|repo_market| {
+                env.try_invoke_contract::<i128, soroban_sdk::Error>(
+                    &repo_market,
+                    &Symbol::new(&env, "repayments_due"),
+                    vec![
+                        &env,
+                        env.current_contract_address().to_val(),
+                        horizon_end.into_val(&env),
+                    ],
+                )
+                .ok()
+                .and_then(|result| result.ok())
+            })
+            .unwrap_or(0);
+
+        Ok(LiquidityForecast {
+            horizon_end,
+            maturities,
+            total_maturing,
+            repo_repayments_due,
+        })
+    }
+
+    /// Current protocol yield curve implied by active series
+    ///
+    /// Each active series contributes its remaining tenor and the yield
+    /// implied by its accreted price; benchmark tenors (1M/3M/6M/12M) are
+    /// interpolated linearly between series and held flat beyond them.
+    pub fn yield_curve(env: Env) -> YieldCurve {
+        let now = env.ledger().timestamp();
+
+        // (remaining_secs, yield_bps), ascending by remaining_secs
+        let mut observed: Vec<(u64, i128)> = Vec::new(&env);
+        for series_id in Self::load_series_ids(&env).iter() {
+            let Some(series) = Self::try_load_series(&env, series_id) else {
+                continue;
+            };
+            if !is_live(series.status) || now >= series.maturity_date {
+                continue;
+            }
+
+            let remaining = series.maturity_date - now;
+            let price = calculate_current_price(&series, now);
+            let Some(yield_bps) = calculate_implied_yield_bps(price, remaining) else {
+                continue;
+            };
+
+            let index = observed
+                .iter()
+                .position(|(secs, _)| secs > remaining)
+                .unwrap_or(observed.len() as usize) as u32;
+            observed.insert(index, (remaining, yield_bps));
+        }
+
+        let mut points = Vec::new(&env);
+        for tenor_secs in YIELD_CURVE_TENORS {
+            if let Some(yield_bps) = interpolate_yield_bps(&observed, tenor_secs) {
+                points.push_back(CurvePoint {
+                    tenor_secs,
+                    yield_bps,
+                });
+            }
+        }
+
+        YieldCurve {
+            timestamp: now,
+            points,
+            series_count: observed.len(),
+        }
+    }
+
+    /// Publish the yield curve as an on-chain checkpoint
+    ///
+    /// Permissionless. Refreshes the stored checkpoint only once it is at
+    /// least YIELD_CHECKPOINT_INTERVAL old; returns the checkpoint in force.
+    /// Before `migrate_schema` after an upgrade, returns the live curve unstored.
+    pub fn checkpoint_yield_curve(env: Env) -> YieldCurve {
+        if Self::require_schema(&env).is_err() {
+            return Self::yield_curve(env);
+        }
+        if let Some(checkpoint) = Self::get_yield_curve_checkpoint(env.clone()) {
+            if env.ledger().timestamp()
+                < checkpoint
+                    .timestamp
+                    .saturating_add(YIELD_CHECKPOINT_INTERVAL)
+            {
+                return checkpoint;
+            }
+        }
+
+        let curve = Self::yield_curve(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::YieldCurveCheckpoint, &curve);
+
+        publish_global(
+            &env,
+            VAULT,
+            "yield_curve_checkpoint",
+            YieldCurveCheckpointEvent {
+                curve: curve.clone(),
+            },
+        );
+
+        curve
+    }
+
+    /// Last published yield curve checkpoint
+    pub fn get_yield_curve_checkpoint(env: Env) -> Option<YieldCurve> {
+        env.storage().instance().get(&DataKey::YieldCurveCheckpoint)
+    }
+
+    // ============================================
+    // STATEMENTS
+    // ============================================
+
+    /// A user's statement lines stamped within [from_ts, to_ts], oldest first
+    ///
+    /// Covers subscriptions, redemptions (at maturity, early and refunds),
+    /// bT-Bill transfers on series whose transfer hook is this vault, and
+    /// repo activity reported by the registered repo market. Series are
+    /// zero-coupon, so there are no coupon lines. `start` skips lines inside
+    /// the window; `limit` is capped at MAX_EXPORT_PAGE and an empty result
+    /// means the end.
+    pub fn get_statement(
+        env: Env,
+        user: Address,
+        from_ts: u64,
+        to_ts: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<ActivityRecord> {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKeyExt::UserActivityCount(user.clone()))
+            .unwrap_or(0);
+        let first = Self::first_activity_at(&env, &user, count, from_ts).saturating_add(start);
+        let end = first.saturating_add(limit.min(MAX_EXPORT_PAGE)).min(count);
+
+        let mut page = Vec::new(&env);
+        for i in first..end {
+            let Some(record) = env
+                .storage()
+                .persistent()
+                .get::<DataKeyExt, ActivityRecord>(&DataKeyExt::UserActivity(user.clone(), i))
+            else {
+                continue;
+            };
+            if record.timestamp > to_ts {
+                break;
+            }
+            page.push_back(record);
+        }
+        page
+    }
+
+    /// bt_bill_token transfer hook: puts a transfer on both parties' statements
+    ///
+    /// Register the vault with the token's `set_transfer_hook` for each
+    /// series whose transfers should appear on statements. Transfers from or
+    /// to a blocked account fail, so a blocking registration also enforces
+    /// the blocklist on that series' transfers.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: `from` or `to` is blocked
+    pub fn on_transfer(
+        env: Env,
+        series_id: u32,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        Self::load_config(&env)?.bt_bill_token.require_auth();
+        Self::check_not_blocked(&env, &from)?;
+        Self::check_not_blocked(&env, &to)?;
+
+        Self::record_activity(
+            &env,
+            &from,
+            ActivityKind::TransferOut,
+            series_id,
+            amount,
+            0,
+            Some(to.clone()),
+            0,
+        );
+        Self::record_activity(
+            &env,
+            &to,
+            ActivityKind::TransferIn,
+            series_id,
+            amount,
+            0,
+            Some(from),
+            0,
+        );
+        Ok(())
+    }
+
+    /// Repo market hook: puts a borrower's repo activity on their statement
+    ///
+    /// Only the repo market set with `set_repo_market` may report.
+    ///
+    /// # Errors
+    /// - `RepoMarketNotSet`: No repo_market configured
+    pub fn record_repo_activity(
+        env: Env,
+        borrower: Address,
+        activity: RepoActivity,
+        series_id: u32,
+        position_id: u64,
+        collateral_par: i128,
+        cash_amount: i128,
+    ) -> Result<(), Error> {
+        let repo_market = Self::require_repo_market(&env)?;
+
+        let kind = match activity {
+            RepoActivity::Opened => ActivityKind::RepoOpened,
+            RepoActivity::Closed => ActivityKind::RepoClosed,
+            RepoActivity::Defaulted => ActivityKind::RepoDefaulted,
+        };
+        Self::record_activity(
+            &env,
+            &borrower,
+            kind,
+            series_id,
+            collateral_par,
+            cash_amount,
+            Some(repo_market),
+            position_id,
+        );
+        Ok(())
+    }
+
+    // ============================================
+    // STATE EXPORT (MIGRATIONS / AUDIT)
+    // ============================================
+
+    /// Page through every series in creation order (same pages as `get_series_page`)
+    pub fn export_series(env: Env, start: u32, limit: u32) -> Vec<Series> {
+        Self::get_series_page(env, start, limit)
+    }
+
+    /// Page through every holder position in a series in first-subscription order
+    ///
+    /// `limit` is capped at MAX_EXPORT_PAGE; an empty result means the end.
+    pub fn export_positions(
+        env: Env,
+        series_id: u32,
+        start: u32,
+        limit: u32,
+    ) -> Vec<PositionSnapshot> {
+        let end = start
+            .saturating_add(limit.min(MAX_EXPORT_PAGE))
+            .min(Self::series_holder_count(&env, series_id));
+
+        let mut page = Vec::new(&env);
+        for i in start..end {
+            let Some(user) = Self::series_holder(&env, series_id, i) else {
+                continue;
+            };
+            let position = Self::get_user_position(env.clone(), series_id, user.clone());
+            page.push_back(PositionSnapshot {
+                series_id,
+                user,
+                subscribed_par: position.subscribed_par,
+            });
+        }
+        page
+    }
+
+    /// Subscription receipt by ID
+    ///
+    /// # Errors
+    /// - `ReceiptNotFound`: No receipt with this ID (or it has expired)
+    pub fn get_receipt(env: Env, receipt_id: u64) -> Result<SubscriptionReceipt, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SubscriptionReceipt(receipt_id))
+            .ok_or(Error::ReceiptNotFound)
+    }
+
+    /// Page through a user's subscription receipts, oldest first
+    ///
+    /// `limit` is capped at MAX_EXPORT_PAGE; an empty result means the end.
+    pub fn get_user_receipts(
+        env: Env,
+        user: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<SubscriptionReceipt> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserReceipts(user))
+            .unwrap_or(Vec::new(&env));
+        let end = start
+            .saturating_add(limit.min(MAX_EXPORT_PAGE))
+            .min(ids.len());
+
+        let mut page = Vec::new(&env);
+        for i in start..end {
+            if let Ok(receipt) = Self::get_receipt(env.clone(), ids.get_unchecked(i)) {
+                page.push_back(receipt);
+            }
+        }
+        page
+    }
+
+    /// Series the user still holds bT-Bills in, in first-subscription order
+    ///
+    /// Maintained by the vault on subscribe and on burns (redeem, early
+    /// exit, refund, settlement): a series drops out once the user's balance
+    /// reaches zero. bT-Bills moved by token transfer are not tracked.
+    pub fn get_user_series(env: Env, user: Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyExt::UserSeries(user))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get user position in a series
+    pub fn get_user_position(env: Env, series_id: u32, user: Address) -> UserPosition {
+        Self::load_user_position(&env, series_id, &user)
+    }
+
+    /// Get protocol accounting (revenue tracking)
+    pub fn get_protocol_accounting(env: Env) -> ProtocolAccounting {
+        Self::load_accounting(&env)
+    }
+
+    /// Stablecoin balance implied by ProtocolAccounting (`reconcile` without the event)
+    ///
+    /// # Errors
+    /// - `InvalidAmount`: Accounting totals overflow
+    pub fn expected_balance(env: Env) -> Result<i128, Error> {
+        calculate_expected_balance(
+            &Self::load_accounting(&env),
+            Self::get_reported_repo_revenue(env.clone()),
+        )
+        .ok_or(Error::InvalidAmount)
+    }
+
+    /// Calculate protocol profit (subscriptions + repo - redemption liability)
+    /// Note: This is unrealized profit until maturity
+    pub fn calculate_protocol_profit(env: Env) -> i128 {
+        let accounting = Self::load_accounting(&env);
+
+        // Revenue = subscriptions + repo profits
+        let revenue = accounting
+            .total_subscriptions_collected
+            .checked_add(accounting.total_repo_revenue)
+            .unwrap_or(0);
+
+        // Liability = PAR minted (will need to pay at maturity)
+        let liability = accounting.total_par_minted;
+
+        // Profit (can be negative early on)
+        revenue.saturating_sub(liability)
+    }
+
+    /// Calculate available USDC for repo lending
+    /// 
+    /// With 100% liquidity model: ALL vault USDC is available
+    /// Safety ensured by haircut on each repo position
+    pub fn calculate_available_for_lending(env: Env) -> i128 {
+        let accounting = Self::load_accounting(&env);
+
+        // Total USDC in vault = subscriptions + repo returns
+        let total_usdc = accounting
+            .total_subscriptions_collected
+            .checked_add(accounting.total_repo_revenue)
+            .unwrap_or(0);
+
+        // Currently lent out
+        let lent = accounting.total_lent;
+
+        // Available = total - lent (100% of remaining balance)
+        total_usdc.saturating_sub(lent)
+    }
+
+    /// Total value locked: stablecoin held by the vault + outstanding repo receivables
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn tvl(env: Env) -> Result<i128, Error> {
+        let held = Self::stablecoin_balance(&env)?;
+        let accounting = Self::load_booked_accounting(&env);
+
+        calculate_tvl(held, accounting.total_lent).ok_or(Error::InvalidAmount)
+    }
+
+    /// Whether the vault can honor redemptions, in one call
+    ///
+    /// Walks every series, like `liquidity_forecast`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: Liability overflows
+    pub fn get_solvency(env: Env) -> Result<Solvency, Error> {
+        let actual_balance = Self::stablecoin_balance(&env)?;
+
+        let mut funded: i128 = 0;
+        for series_id in Self::load_series_ids(&env).iter() {
+            if let Some(series) = Self::try_load_series(&env, series_id) {
+                funded = funded
+                    .checked_add(Self::redemption_funding(&env, &series)?.funded)
+                    .ok_or(Error::InvalidAmount)?;
+            }
+        }
+
+        let outstanding_liability = Self::outstanding_liability(&env)?;
+        let health_ratio_bps = calculate_health_ratio_bps(actual_balance, outstanding_liability)
+            .ok_or(Error::InvalidAmount)?;
+
+        Ok(Solvency {
+            actual_balance,
+            outstanding_liability,
+            matured_liability: Self::matured_liability(&env),
+            funded,
+            health_ratio_bps,
+        })
+    }
+
+    /// Share of vault liquidity currently lent out via repo, in basis points
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn utilization(env: Env) -> Result<i128, Error> {
+        let held = Self::stablecoin_balance(&env)?;
+        let accounting = Self::load_booked_accounting(&env);
+
+        calculate_utilization_bps(accounting.total_lent, held).ok_or(Error::InvalidAmount)
+    }
+
+    // ============================================
+    // INTERNAL HELPERS
+    // ============================================
+
+    /// Spend `signer`'s next auth nonce, failing if `expiry` has passed
+    fn consume_auth_nonce(
+        env: &Env,
+        signer: &Address,
+        nonce: u64,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        let expected_nonce = Self::get_auth_nonce(env.clone(), signer.clone());
+        if env.ledger().timestamp() > expiry || nonce != expected_nonce {
+            return Err(Error::InvalidAuthNonce);
+        }
+        let nonce_key = DataKeyExt::AuthNonce(signer.clone());
+        env.storage()
+            .persistent()
+            .set(&nonce_key, &(expected_nonce + 1));
+        env.storage()
+            .persistent()
+            .extend_ttl(&nonce_key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+        env.storage().instance().remove(&nonce_key);
+        Ok(())
+    }
+
+    /// Issuer's cut of a subscription fee or repo spread earned on `series` (0 without a split)
+    fn issuer_fee_share(
+        env: &Env,
+        series: &Series,
+        fee: i128,
+        spread: bool,
+    ) -> Result<i128, Error> {
+        let Some(split) = series
+            .issuer
+            .clone()
+            .and_then(|issuer| Self::get_issuer_fee_split(env.clone(), issuer))
+        else {
+            return Ok(0);
+        };
+        let share_bps = if spread {
+            split.spread_share_bps
+        } else {
+            split.subscription_share_bps
+        };
+        calculate_fee_share(fee, share_bps).ok_or(Error::InvalidAmount)
+    }
+
+    /// Record a fee earned on one of `issuer`'s series in its fee account
+    fn book_issuer_fees(
+        env: &Env,
+        issuer: &Address,
+        issuer_fee: i128,
+        protocol_fee: i128,
+        spread: bool,
+    ) -> Result<(), Error> {
+        let mut account = Self::get_issuer_fees(env.clone(), issuer.clone());
+        let (issuer_total, protocol_total) = if spread {
+            (
+                &mut account.issuer_spread_fees,
+                &mut account.protocol_spread_fees,
+            )
+        } else {
+            (
+                &mut account.issuer_subscription_fees,
+                &mut account.protocol_subscription_fees,
+            )
+        };
+        *issuer_total = issuer_total
+            .checked_add(issuer_fee)
+            .ok_or(Error::InvalidAmount)?;
+        *protocol_total = protocol_total
+            .checked_add(protocol_fee)
+            .ok_or(Error::InvalidAmount)?;
+        account.claimable = account
+            .claimable
+            .checked_add(issuer_fee)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::IssuerFees(issuer.clone()), &account);
+        Ok(())
+    }
+
+    /// Fail unless `issuer` is approved for this tenor and the vault's stablecoin
+    fn check_issuer_terms(
+        env: &Env,
+        issuer: &Address,
+        issue_date: u64,
+        maturity_date: u64,
+    ) -> Result<(), Error> {
+        let profile = Self::get_issuer(env.clone(), issuer.clone())
+            .filter(|profile| profile.approved)
+            .ok_or(Error::Unauthorized)?;
+        let stablecoin = Self::load_config(env)?.stablecoin;
+
+        let tenor = maturity_date.saturating_sub(issue_date);
+        if tenor < profile.limits.min_tenor
+            || tenor > profile.limits.max_tenor
+            || !profile.limits.allowed_assets.contains(&stablecoin)
+        {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Count `delta` cap PAR against an issuer's outstanding limit
+    ///
+    /// Increases need an approved issuer with room under
+    /// max_outstanding_par; decreases always apply. No-op for addresses
+    /// not in the registry (the treasury, or series created before it).
+    fn adjust_issuer_par(env: &Env, issuer: &Address, delta: i128) -> Result<(), Error> {
+        let Some(mut profile) = Self::get_issuer(env.clone(), issuer.clone()) else {
+            return Ok(());
+        };
+
+        let outstanding_par = profile
+            .outstanding_par
+            .checked_add(delta)
+            .ok_or(Error::InvalidAmount)?
+            .max(0);
+        if delta > 0 {
+            if !profile.approved {
+                return Err(Error::Unauthorized);
+            }
+            if outstanding_par > profile.limits.max_outstanding_par {
+                return Err(Error::InvalidCapAmounts);
+            }
+        }
+
+        profile.outstanding_par = outstanding_par;
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::Issuer(issuer.clone()), &profile);
+        Ok(())
+    }
+
+    /// Require auth from the holder of `role`; returns the signer
+    fn require_role(env: &Env, role: Role) -> Result<Address, Error> {
+        let holder = Self::get_role_holder(env.clone(), role)?;
+        holder.require_auth();
+        Ok(holder)
+    }
+
+    /// Require the auth of the repo market set with `set_repo_market`
+    fn require_repo_market(env: &Env) -> Result<Address, Error> {
+        let repo_market: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::RepoMarket)
+            .ok_or(Error::RepoMarketNotSet)?;
+        repo_market.require_auth();
+        Ok(repo_market)
+    }
+
+    /// Reject direct timelocked admin changes while an admin delay is set
+    fn require_no_admin_delay(env: &Env) -> Result<(), Error> {
+        if Self::get_admin_delay(env.clone()) > 0 {
+            return Err(Error::ActionTimelocked);
+        }
+        Ok(())
+    }
+
+    /// Require the auth of whoever may make this admin change directly
+    fn require_admin_action_auth(env: &Env, action: &AdminAction) -> Result<(), Error> {
+        match action {
+            AdminAction::SetTreasury(_) | AdminAction::SetAdminDelay(_) => {
+                Self::load_config(env)?.admin.require_auth();
+            }
+            AdminAction::SetSubscriptionFee(_)
+            | AdminAction::SetRedemptionFee(_)
+            | AdminAction::SetSeriesRedemptionFee(..)
+            | AdminAction::SetFeeCollector(_)
+            | AdminAction::SetKeeperFee(_) => {
+                Self::require_role(env, Role::FeeManager)?;
+            }
+            AdminAction::Upgrade(_) => {
+                Self::require_role(env, Role::Upgrader)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn load_admin_action(env: &Env, action_id: u64) -> Result<QueuedAdminAction, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::AdminActionQueue(action_id))
+            .ok_or(Error::NoPendingAction)
+    }
+
+    fn apply_treasury(env: &Env, new_treasury: Address) -> Result<(), Error> {
+        let mut config = Self::load_config(env)?;
+        let previous_treasury = config.treasury;
+        config.treasury = new_treasury.clone();
+        Self::save_config(env, &config);
+
+        publish_global(
+            env,
+            VAULT,
+            "treasury_updated",
+            TreasuryUpdatedEvent {
+                previous_treasury,
+                new_treasury,
+            },
+        );
+        Ok(())
+    }
+
+    fn apply_subscription_fee_bps(env: &Env, fee_bps: u32) -> Result<(), Error> {
+        if fee_bps > MAX_SUBSCRIPTION_FEE_BPS {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::SubscriptionFeeBps, &fee_bps);
+
+        publish_global(
+            env,
+            VAULT,
+            "subscription_fee_updated",
+            SubscriptionFeeUpdatedEvent {
+                fee_bps,
+                collector: Self::get_fee_collector(env.clone())?,
+            },
+        );
+        Ok(())
+    }
+
+    fn apply_redemption_fee_bps(env: &Env, fee_bps: u32) -> Result<(), Error> {
+        if fee_bps > MAX_REDEMPTION_FEE_BPS {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::RedemptionFeeBps, &fee_bps);
+
+        publish_global(
+            env,
+            VAULT,
+            "redemption_fee_updated",
+            RedemptionFeeUpdatedEvent {
+                series_id: None,
+                fee_bps: Some(fee_bps),
+            },
+        );
+        Ok(())
+    }
+
+    fn apply_series_redemption_fee_bps(
+        env: &Env,
+        series_id: u32,
+        fee_bps: Option<u32>,
+    ) -> Result<(), Error> {
+        Self::load_series(env, series_id)?;
+
+        let key = DataKeyExt::SeriesRedemptionFeeBps(series_id);
+        match fee_bps {
+            Some(bps) if bps > MAX_REDEMPTION_FEE_BPS => return Err(Error::InvalidAmount),
+            Some(bps) => env.storage().instance().set(&key, &bps),
+            None => env.storage().instance().remove(&key),
+        }
+
+        publish(
+            env,
+            VAULT,
+            "redemption_fee_updated",
+            series_id,
+            RedemptionFeeUpdatedEvent {
+                series_id: Some(series_id),
+                fee_bps,
+            },
+        );
+        Ok(())
+    }
+
+    fn apply_fee_collector(env: &Env, collector: Address) {
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeCollector, &collector);
+
+        publish_global(
+            env,
+            VAULT,
+            "subscription_fee_updated",
+            SubscriptionFeeUpdatedEvent {
+                fee_bps: Self::get_subscription_fee_bps(env.clone()),
+                collector,
+            },
+        );
+    }
+
+    fn apply_keeper_fee_bps(env: &Env, fee_bps: u32) -> Result<(), Error> {
+        if fee_bps > MAX_KEEPER_FEE_BPS {
+            return Err(Error::InvalidConfig);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::KeeperFeeBps, &fee_bps);
+        Ok(())
+    }
+
+    fn apply_upgrade(env: &Env, new_wasm_hash: BytesN<32>) {
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        publish_global(
+            env,
+            VAULT,
+            "contract_upgraded",
+            ContractUpgradedEvent {
+                wasm_hash: new_wasm_hash,
+            },
+        );
+    }
+
+    fn apply_admin_delay(env: &Env, delay_secs: u64) -> Result<(), Error> {
+        if delay_secs > MAX_ADMIN_DELAY {
+            return Err(Error::InvalidTimestamp);
+        }
+        if delay_secs == 0 {
+            env.storage().instance().remove(&DataKeyExt::AdminDelay);
+        } else {
+            env.storage()
+                .instance()
+                .set(&DataKeyExt::AdminDelay, &delay_secs);
+        }
+
+        publish_global(
+            env,
+            VAULT,
+            "admin_delay_updated",
+            AdminDelayUpdatedEvent { delay_secs },
+        );
+        Ok(())
+    }
+
+    /// Require the series issuer's auth (the treasury when none is set); returns the signer
+    fn require_issuer(env: &Env, series: &Series) -> Result<Address, Error> {
+        let issuer = match &series.issuer {
+            Some(issuer) => issuer.clone(),
+            None => Self::load_config(env)?.treasury,
+        };
+        issuer.require_auth();
+        Ok(issuer)
+    }
+
+    /// Core addresses, from the packed config or, until it is first written, the legacy keys
+    fn load_config(env: &Env) -> Result<VaultConfig, Error> {
+        if let Some(config) = env.storage().instance().get(&DataKeyExt::Config) {
+            return Ok(config);
+        }
+        let legacy = |key: DataKey| -> Result<Address, Error> {
+            env.storage()
+                .instance()
+                .get(&key)
+                .ok_or(Error::NotInitialized)
+        };
+        Ok(VaultConfig {
+            admin: legacy(DataKey::Admin)?,
+            treasury: legacy(DataKey::Treasury)?,
+            stablecoin: legacy(DataKey::Stablecoin)?,
+            bt_bill_token: legacy(DataKey::BTBillToken)?,
+        })
+    }
+
+    fn save_config(env: &Env, config: &VaultConfig) {
+        env.storage().instance().set(&DataKeyExt::Config, config);
+        for key in [
+            DataKey::Admin,
+            DataKey::Treasury,
+            DataKey::Stablecoin,
+            DataKey::BTBillToken,
+        ] {
+            env.storage().instance().remove(&key);
+        }
+    }
+
+    /// Guard for state-changing entrypoints after an upgrade
+    ///
+    /// Every state-changing entrypoint calls this except `pause`, which must
+    /// keep working as the emergency stop for a bad upgrade.
+    fn require_schema(env: &Env) -> Result<(), Error> {
+        Self::assert_schema(env.clone(), STORAGE_SCHEMA_VERSION)
+    }
+
+    /// Full record (with layout hashes) is persistent; only the version is
+    /// kept in instance storage, which every call loads
+    fn write_schema(env: &Env, layouts: Map<Symbol, BytesN<32>>) {
+        env.storage()
+            .instance()
+            .set(&DataKey::StorageSchema, &STORAGE_SCHEMA_VERSION);
+        env.storage().persistent().set(
+            &DataKey::StorageSchema,
+            &StorageSchema {
+                version: STORAGE_SCHEMA_VERSION,
+                layouts,
+            },
+        );
+        env.storage().persistent().extend_ttl(
+            &DataKey::StorageSchema,
+            STATE_TTL_THRESHOLD,
+            STATE_TTL_LEDGERS,
+        );
+    }
+
+    fn check_not_paused(env: &Env) -> Result<(), Error> {
+        let paused = env
+            .storage()
+            .instance()
+            .get::<DataKey, bool>(&DataKey::Paused)
+            .unwrap_or(false);
+
+        if paused {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    fn check_series_not_paused(env: &Env, series_id: u32) -> Result<(), Error> {
+        if Self::is_series_paused(env.clone(), series_id) {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    fn check_kyc(env: &Env, series_id: u32, user: &Address) -> Result<(), Error> {
+        if Self::is_series_kyc_required(env.clone(), series_id)
+            && !Self::is_kyc_verified(env.clone(), user.clone())
+        {
+            return Err(Error::KYCRequired);
+        }
+        Ok(())
+    }
+
+    fn check_not_blocked(env: &Env, account: &Address) -> Result<(), Error> {
+        if Self::is_blocked(env.clone(), account.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn set_blocked(env: &Env, account: Address, blocked: bool) -> Result<(), Error> {
+        Self::require_schema(env)?;
+        let admin = Self::load_config(env)?.admin;
+        admin.require_auth();
+
+        Self::save_account_flag(env, &DataKeyExt::Blocked(account.clone()), blocked);
+
+        publish(
+            env,
+            VAULT,
+            "blocklist_updated",
+            account.clone(),
+            BlocklistUpdatedEvent { account, blocked },
+        );
+
+        Ok(())
+    }
+
+    fn check_issuance_open(env: &Env) -> Result<(), Error> {
+        if Self::is_issuance_frozen(env.clone()) {
+            return Err(Error::IssuanceFrozen);
+        }
+        Ok(())
+    }
+
+    fn is_settlement_sender_allowed(env: &Env, sender: &Address) -> bool {
+        let allowlist_enabled = env
+            .storage()
+            .instance()
+            .get::<DataKey, bool>(&DataKey::SettlementAllowlistEnabled)
+            .unwrap_or(false);
+
+        !allowlist_enabled || Self::is_settlement_sender(env.clone(), sender.clone())
+    }
+
+    /// Read per-account state from persistent storage, bumping its TTL
+    ///
+    /// Falls back to instance storage for entries written before the migration.
+    fn load_account_entry<K, V>(env: &Env, key: &K) -> Option<V>
+    where
+        K: IntoVal<Env, Val>,
+        V: TryFromVal<Env, Val>,
+    {
+        if let Some(value) = env.storage().persistent().get::<K, V>(key) {
+            env.storage()
+                .persistent()
+                .extend_ttl(key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+            return Some(value);
+        }
+        env.storage().instance().get(key)
+    }
+
+    /// Write per-account state to persistent storage, dropping any pre-migration copy
+    fn save_account_entry<K, V>(env: &Env, key: &K, value: &V)
+    where
+        K: IntoVal<Env, Val>,
+        V: IntoVal<Env, Val>,
+    {
+        env.storage().persistent().set(key, value);
+        env.storage()
+            .persistent()
+            .extend_ttl(key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+        env.storage().instance().remove(key);
+    }
+
+    fn remove_account_entry<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        env.storage().persistent().remove(key);
+        env.storage().instance().remove(key);
+    }
+
+    fn load_account_flag(env: &Env, key: &DataKeyExt) -> bool {
+        Self::load_account_entry(env, key).unwrap_or(false)
+    }
+
+    /// Set or clear a per-account flag (absent = false)
+    fn save_account_flag(env: &Env, key: &DataKeyExt, flag: bool) {
+        if flag {
+            Self::save_account_entry(env, key, &true);
+        } else {
+            Self::remove_account_entry(env, key);
+        }
+    }
+
+    /// Every created series ID, falling back to the pre-migration instance copy
+    fn load_series_ids(env: &Env) -> Vec<u32> {
+        if let Some(series_ids) = env.storage().persistent().get(&DataKey::SeriesIds) {
+            return series_ids;
+        }
+        env.storage()
+            .instance()
+            .get(&DataKey::SeriesIds)
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Write the series ID list to persistent storage, dropping any pre-migration copy
+    fn save_series_ids(env: &Env, series_ids: &Vec<u32>) {
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::SeriesIds, series_ids);
+        storage.extend_ttl(&DataKey::SeriesIds, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+        env.storage().instance().remove(&DataKey::SeriesIds);
+    }
+
+    fn load_series(env: &Env, series_id: u32) -> Result<Series, Error> {
+        Self::try_load_series(env, series_id).ok_or(Error::SeriesNotFound)
+    }
+
+    /// Read a series from persistent storage, bumping its TTL
+    ///
+    /// Falls back to instance storage for series not yet migrated.
+    fn try_load_series(env: &Env, series_id: u32) -> Option<Series> {
+        let key = DataKey::Series(series_id);
+        if let Some(series) = env.storage().persistent().get::<DataKey, Series>(&key) {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+            return Some(series);
+        }
+        env.storage().instance().get(&key)
+    }
+
+    fn has_series(env: &Env, series_id: u32) -> bool {
+        let key = DataKey::Series(series_id);
+        env.storage().persistent().has(&key) || env.storage().instance().has(&key)
+    }
+
+    /// Write a series to persistent storage, dropping any pre-migration copy
+    fn save_series(env: &Env, series: &Series) {
+        let key = DataKey::Series(series.series_id);
+        env.storage().persistent().set(&key, series);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+        env.storage().instance().remove(&key);
+    }
+
+    fn remove_series(env: &Env, series_id: u32) {
+        let key = DataKey::Series(series_id);
+        env.storage().persistent().remove(&key);
+        env.storage().instance().remove(&key);
+    }
+
+    /// Read a holder position (zero if absent), bumping its TTL
+    fn load_user_position(env: &Env, series_id: u32, user: &Address) -> UserPosition {
+        let key = DataKey::UserPosition(series_id, user.clone());
+        if let Some(position) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, UserPosition>(&key)
+        {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+            return position;
+        }
+        env.storage().instance().get(&key).unwrap_or(UserPosition {
+            subscribed_par: 0,
+            paid_amount: 0,
+        })
+    }
+
+    /// Write a holder position to persistent storage, dropping any pre-migration copy
+    fn save_user_position(env: &Env, series_id: u32, user: &Address, position: &UserPosition) {
+        let key = DataKey::UserPosition(series_id, user.clone());
+        env.storage().persistent().set(&key, position);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+        env.storage().instance().remove(&key);
+    }
+
+    fn remove_user_position(env: &Env, series_id: u32, user: &Address) {
+        let key = DataKey::UserPosition(series_id, user.clone());
+        env.storage().persistent().remove(&key);
+        env.storage().instance().remove(&key);
+    }
+
+    /// Outstanding PAR across every series plus queued payouts, in asset units
+    fn outstanding_liability(env: &Env) -> Result<i128, Error> {
+        let outstanding_par = Self::load_liability_par(env, &DataKeyExt::OutstandingPar);
+
+        to_asset_units(outstanding_par, Self::asset_decimals(env.clone()))
+            .and_then(|owed| owed.checked_add(Self::load_booked_accounting(env).queued_payouts))
+            .ok_or(Error::InvalidAmount)
+    }
+
+    fn load_withdrawal_outflows(env: &Env) -> Map<u64, i128> {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::WithdrawalOutflows)
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Book a treasury withdrawal in the rolling windows
+    ///
+    /// Requires the admin's co-authorization when it breaches either limit.
+    fn record_treasury_withdrawal(env: &Env, amount: i128) -> Result<(), Error> {
+        let usage = Self::get_withdrawal_usage(env.clone());
+        if exceeds_withdrawal_limit(usage.daily_used, amount, usage.limits.daily_limit)
+            || exceeds_withdrawal_limit(usage.weekly_used, amount, usage.limits.weekly_limit)
+        {
+            let admin = Self::load_config(env)?.admin;
+            admin.require_auth();
+
+            publish_global(
+                env,
+                VAULT,
+                "withdrawal_limit_cosigned",
+                WithdrawalLimitCosignedEvent {
+                    admin,
+                    amount,
+                    daily_used: usage.daily_used,
+                    weekly_used: usage.weekly_used,
+                },
+            );
+        }
+
+        let now_bucket = env.ledger().timestamp() / WITHDRAWAL_BUCKET_SECONDS;
+        let oldest_bucket = (now_bucket + 1).saturating_sub(WEEKLY_WITHDRAWAL_BUCKETS);
+
+        let mut outflows = Self::load_withdrawal_outflows(env);
+        for bucket in outflows.keys().iter() {
+            if bucket < oldest_bucket {
+                outflows.remove(bucket);
+            }
+        }
+        let booked = outflows
+            .get(now_bucket)
+            .unwrap_or(0)
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        outflows.set(now_bucket, booked);
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::WithdrawalOutflows, &outflows);
+
+        Ok(())
+    }
+
+    /// Outstanding PAR across every Matured or Closed series, in asset units
+    ///
+    /// A series past its maturity date counts once it is matured, by
+    /// `mature_series` or lazily on its first post-maturity flow.
+    fn matured_liability(env: &Env) -> i128 {
+        let liability = Self::load_liability_par(env, &DataKeyExt::MaturedPar);
+
+        to_asset_units(liability, Self::asset_decimals(env.clone()))
+            .and_then(|owed| owed.checked_add(Self::load_booked_accounting(env).queued_payouts))
+            .unwrap_or(i128::MAX)
+    }
+
+    /// Move the running liability totals by `delta` PAR of `status`'s series
+    fn book_liability_par(env: &Env, status: SeriesStatus, delta: i128) -> Result<(), Error> {
+        Self::add_liability_par(env, &DataKeyExt::OutstandingPar, delta)?;
+        if counts_as_matured(status) {
+            Self::add_liability_par(env, &DataKeyExt::MaturedPar, delta)?;
+        }
+        Ok(())
+    }
+
+    /// Running PAR total under OutstandingPar or MaturedPar (absent = 0)
+    fn load_liability_par(env: &Env, key: &DataKeyExt) -> i128 {
+        env.storage().instance().get(key).unwrap_or(0)
+    }
+
+    fn add_liability_par(env: &Env, key: &DataKeyExt, delta: i128) -> Result<(), Error> {
+        let total = Self::load_liability_par(env, key)
+            .checked_add(delta)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage().instance().set(key, &total);
+        Ok(())
+    }
+
+    /// Recompute the running liability totals from every stored series
+    fn rebuild_liability_par(env: &Env) -> Result<(), Error> {
+        let mut outstanding_par: i128 = 0;
+        let mut matured_par: i128 = 0;
+        for series_id in Self::load_series_ids(env).iter() {
+            if let Some(series) = Self::try_load_series(env, series_id) {
+                let owed = calculate_outstanding_par(&series);
+                outstanding_par = outstanding_par
+                    .checked_add(owed)
+                    .ok_or(Error::InvalidAmount)?;
+                if counts_as_matured(series.status) {
+                    matured_par = matured_par.checked_add(owed).ok_or(Error::InvalidAmount)?;
+                }
+            }
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::OutstandingPar, &outstanding_par);
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::MaturedPar, &matured_par);
+
+        Ok(())
+    }
+
+    fn redemption_funding(env: &Env, series: &Series) -> Result<RedemptionFunding, Error> {
+        let required = to_asset_units(series.minted_par, Self::asset_decimals(env.clone()))
+            .ok_or(Error::InvalidAmount)?;
+        let funded = env
+            .storage()
+            .instance()
+            .get(&DataKey::RedemptionFunded(series.series_id))
+            .unwrap_or(0);
+
+        Ok(RedemptionFunding { funded, required })
+    }
+
+    /// Protocol totals, with every live series' subscription tally folded in
+    fn load_accounting(env: &Env) -> ProtocolAccounting {
+        let mut accounting = Self::load_booked_accounting(env);
+        for series_id in Self::load_series_ids(env).iter() {
+            let tally = Self::load_series_tally(env, series_id);
+            apply_series_tally(&mut accounting, &tally);
+        }
+        accounting
+    }
+
+    fn load_series_tally(env: &Env, series_id: u32) -> SeriesTally {
+        env.storage()
+            .persistent()
+            .get(&DataKeyExt::SeriesTally(series_id))
+            .unwrap_or_default()
+    }
+
+    /// Merge an archived series' tally into the booked totals
+    fn fold_series_tally(env: &Env, series_id: u32) -> Result<(), Error> {
+        let key = DataKeyExt::SeriesTally(series_id);
+        let Some(tally) = env.storage().persistent().get::<_, SeriesTally>(&key) else {
+            return Ok(());
+        };
+        let mut accounting = Self::load_booked_accounting(env);
+        apply_series_tally(&mut accounting, &tally).ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(env, &accounting);
+        env.storage().persistent().remove(&key);
+        Ok(())
+    }
+
+    fn save_accounting(env: &Env, accounting: &ProtocolAccounting) {
+        let key = DataKey::ProtocolAccounting;
+        env.storage().persistent().set(&key, accounting);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+        env.storage().instance().remove(&key);
+    }
+
+    /// Stored totals without the series tallies (pre-migration copies are read from instance)
+    fn load_booked_accounting(env: &Env) -> ProtocolAccounting {
+        let key = DataKey::ProtocolAccounting;
+        env.storage()
+            .persistent()
+            .get::<DataKey, ProtocolAccounting>(&key)
+            .or_else(|| env.storage().instance().get(&key))
+            .unwrap_or(ProtocolAccounting {
+                total_subscriptions_collected: 0,
+                total_par_minted: 0,
+                total_lent: 0,
+                total_repo_revenue: 0,
+                total_defaults: 0,
+                total_redeemed: 0,
+                total_settlement_deposits: 0,
+                total_referral_rewards_paid: 0,
+                total_dust_claimed: 0,
+                queued_payouts: 0,
+                total_early_penalties: 0,
+                total_treasury_withdrawals: 0,
+                total_subscription_fees: 0,
+                total_redemption_fees: 0,
+                total_fees_collected: 0,
+                total_issuer_fees_received: 0,
+                total_issuer_fees_claimed: 0,
+                total_referral_rewards_funded: 0,
+                total_emergency_withdrawals: 0,
+            })
+    }
+
+    /// Validate maturity, burn bT-Bills and book the redemption
+    ///
+    /// Returns the stablecoin payout owed to the user (PAR in asset units,
+    /// less the redemption fee and `keeper_fee_bps` of it) and the keeper
+    /// fee; the caller pays both.
+    /// Fails with `SeriesSettling` while the series is in batched settlement,
+    /// `ContractPaused` while it is paused, `Unauthorized` for a blocked
+    /// user, `KYCRequired` for one without KYC on a KYC-gated series, and `InvalidStatus`
+    /// unless the series is (or just became) MATURED.
+    fn burn_matured(
+        env: &Env,
+        user: &Address,
+        series_id: u32,
+        bt_bill_amount: i128,
+        keeper_fee_bps: u32,
+    ) -> Result<(i128, i128), Error> {
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::SettlementPlan(series_id))
+        {
+            return Err(Error::SeriesSettling);
+        }
+        Self::check_series_not_paused(env, series_id)?;
+        Self::check_not_blocked(env, user)?;
+        Self::check_kyc(env, series_id, user)?;
+
+        Self::burn_and_book(env, user, series_id, bt_bill_amount, true, keeper_fee_bps)
+    }
+
+    /// `burn_matured` without the settlement check
+    ///
+    /// Batched settlement redeems whole balances and skips the dust policy.
+    fn burn_and_book(
+        env: &Env,
+        user: &Address,
+        series_id: u32,
+        bt_bill_amount: i128,
+        apply_dust_policy: bool,
+        keeper_fee_bps: u32,
+    ) -> Result<(i128, i128), Error> {
+        let mut series = Self::load_series(env, series_id)?;
+
+        // Validate: Must be at or past maturity
+        let current_time = env.ledger().timestamp();
+        if current_time < series.maturity_date {
+            return Err(Error::SeriesNotMatured);
+        }
+        Self::mature_if_due(env, &mut series)?;
+        // Cancelled series refund via claim_refund; failed ones are halted
+        if series.status != SeriesStatus::Matured {
+            return Err(Error::InvalidStatus);
+        }
+
+        let asset_decimals = Self::asset_decimals(env.clone());
+        let RedemptionQuote { gross, fee, payout } =
+            Self::quote_redemption(env, series_id, bt_bill_amount)?;
+        if Self::is_shadow_mode(env.clone(), ShadowFeature::RedemptionFee) {
+            let fee_bps = Self::get_redemption_fee_bps(env.clone(), series_id);
+            let shadow_fee =
+                calculate_redemption_fee(gross, fee_bps).ok_or(Error::InvalidAmount)?;
+            Self::publish_shadow_outcome(
+                env,
+                ShadowFeature::RedemptionFee,
+                series_id,
+                user,
+                shadow_fee,
+            );
+        }
+        let dust_par = if apply_dust_policy {
+            Self::redemption_dust(env, user, series_id, bt_bill_amount, gross)?
+        } else {
+            0
+        };
+
+        // Burn bT-Bills (plus any leftover dust swept into the bucket)
+        let bt_bill_token = Self::load_config(env)?.bt_bill_token;
+
+        let burn_amount = bt_bill_amount
+            .checked_add(dust_par)
+            .ok_or(Error::InvalidAmount)?;
+        env.invoke_contract::<()>(
+            &bt_bill_token,
+            &Symbol::new(env, "burn"),
+            vec![
+                env,
+                series_id.into(),
+                user.to_val(),
+                burn_amount.into_val(env)
+            ],
+        );
+
+        series.redeemed_par = series
+            .redeemed_par
+            .checked_add(burn_amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_series(env, &series);
+        Self::book_liability_par(env, series.status, -burn_amount)?;
+        Self::unindex_user_series(env, user, series_id)?;
+
+        if dust_par > 0 {
+            let dust = to_asset_units(dust_par, asset_decimals).ok_or(Error::InvalidAmount)?;
+            Self::add_to_dust_bucket(env, dust)?;
+        }
+
+        if fee > 0 {
+            let accrued = Self::get_accrued_fees(env.clone())
+                .checked_add(fee)
+                .ok_or(Error::InvalidAmount)?;
+            env.storage()
+                .instance()
+                .set(&DataKeyExt::AccruedFees, &accrued);
+        }
+
+        let mut accounting = Self::load_booked_accounting(env);
+        accounting.total_redeemed = accounting
+            .total_redeemed
+            .checked_add(payout)
+            .ok_or(Error::InvalidAmount)?;
+        accounting.total_redemption_fees = accounting
+            .total_redemption_fees
+            .checked_add(fee)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(env, &accounting);
+
+        // The keeper's cut comes out of the holder's payout
+        let keeper_fee =
+            calculate_keeper_fee(payout, keeper_fee_bps).ok_or(Error::InvalidAmount)?;
+        let payout = payout - keeper_fee;
+
+        Self::notify_rewards_hook(env, user, "redeem", bt_bill_amount);
+        Self::record_activity(
+            env,
+            user,
+            ActivityKind::Redemption,
+            series_id,
+            bt_bill_amount,
+            payout,
+            None,
+            0,
+        );
+
+        publish(
+            env,
+            VAULT,
+            "redeemed",
+            series_id,
+            RedeemedEvent {
+                series_id,
+                user: user.clone(),
+                bt_bill_amount,
+                payout,
+                fee,
+                keeper_fee,
+            },
+        );
+
+        Ok((payout, keeper_fee))
+    }
+
+    /// Apply the dust policy to a redemption
+    ///
+    /// Returns the sub-threshold leftover PAR to burn into the dust bucket
+    /// (DustMode::Bucket), or 0. DustMode::Reject fails instead.
+    fn redemption_dust(
+        env: &Env,
+        user: &Address,
+        series_id: u32,
+        bt_bill_amount: i128,
+        payout: i128,
+    ) -> Result<i128, Error> {
+        let Some(policy) = Self::get_dust_policy(env.clone()) else {
+            return Ok(0);
+        };
+        if payout < policy.threshold {
+            return Err(Error::BelowDustThreshold);
+        }
+
+        let leftover_par =
+            Self::bt_bill_balance(env, series_id, user)?.saturating_sub(bt_bill_amount);
+        let leftover = to_asset_units(leftover_par, Self::asset_decimals(env.clone()))
+            .ok_or(Error::InvalidAmount)?;
+        if leftover_par <= 0 || leftover >= policy.threshold {
+            return Ok(0);
+        }
+
+        match policy.mode {
+            DustMode::Reject => Err(Error::BelowDustThreshold),
+            DustMode::Bucket => Ok(leftover_par),
+        }
+    }
+
+    fn add_to_dust_bucket(env: &Env, amount: i128) -> Result<(), Error> {
+        if amount <= 0 {
+            return Ok(());
+        }
+        let bucket = Self::get_dust_bucket(env.clone())
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage().instance().set(&DataKey::DustBucket, &bucket);
+        Ok(())
+    }
+
+    /// Pay a redemption, or queue it behind the retail reserve if it is large
+    fn pay_redemption(
+        env: &Env,
+        user: &Address,
+        series_id: u32,
+        payout: i128,
+    ) -> Result<(), Error> {
+        if !Self::would_queue_redemption(env, payout)? {
+            return Self::pay_out(env, user, payout);
+        }
+
+        let mut queue = Self::get_redemption_queue(env.clone());
+        let position = queue.len();
+        queue.push_back(QueuedRedemption {
+            user: user.clone(),
+            series_id,
+            amount: payout,
+            queued_at: env.ledger().timestamp(),
+        });
+        env.storage()
+            .instance()
+            .set(&DataKey::RedemptionQueue, &queue);
+
+        let mut accounting = Self::load_booked_accounting(env);
+        accounting.queued_payouts = accounting
+            .queued_payouts
+            .checked_add(payout)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(env, &accounting);
+
+        publish(
+            env,
+            VAULT,
+            "redemption_queued",
+            series_id,
+            RedemptionQueuedEvent {
+                user: user.clone(),
+                series_id,
+                amount: payout,
+                position,
+            },
+        );
+        Ok(())
+    }
+
+    /// Whether the redemption lane would queue a payout of this size
+    ///
+    /// Large payouts queue while others are waiting or when paying them
+    /// would dip into the retail reserve.
+    fn would_queue_redemption(env: &Env, payout: i128) -> Result<bool, Error> {
+        let Some(lane) = Self::get_redemption_lane(env.clone()) else {
+            return Ok(false);
+        };
+        Ok(payout > lane.threshold
+            && (!Self::get_redemption_queue(env.clone()).is_empty()
+                || Self::stablecoin_balance(env)?.saturating_sub(payout) < lane.retail_reserve))
+    }
+
+    /// Pull `amount` of stablecoin from `from` and book it as a settlement deposit
+    fn deposit_settlement_from(env: &Env, from: &Address, amount: i128) -> Result<(), Error> {
+        let stablecoin = Self::load_config(env)?.stablecoin;
+        token::Client::new(env, &stablecoin).transfer(
+            from,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let mut accounting = Self::load_booked_accounting(env);
+        accounting.total_settlement_deposits = accounting
+            .total_settlement_deposits
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_accounting(env, &accounting);
+
+        publish(
+            env,
+            VAULT,
+            "settlement_deposited",
+            from.clone(),
+            SettlementDepositedEvent {
+                from: from.clone(),
+                amount,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Number of subscribers indexed for a series
+    ///
+    /// Falls back to the legacy instance list for series not yet migrated.
+    fn series_holder_count(env: &Env, series_id: u32) -> u32 {
+        if let Some(count) = env
+            .storage()
+            .persistent()
+            .get(&DataKeyExt::SeriesHolderCount(series_id))
+        {
+            return count;
+        }
+        env.storage()
+            .instance()
+            .get::<DataKey, Vec<Address>>(&DataKey::SeriesHolders(series_id))
+            .map_or(0, |holders| holders.len())
+    }
+
+    /// Subscriber at `index` of a series' holder index, in first-subscription order
+    ///
+    /// Falls back to the legacy instance list for entries not yet migrated.
+    fn series_holder(env: &Env, series_id: u32, index: u32) -> Option<Address> {
+        if let Some(holder) = env
+            .storage()
+            .persistent()
+            .get(&DataKeyExt::SeriesHolder(series_id, index))
+        {
+            return Some(holder);
+        }
+        env.storage()
+            .instance()
+            .get::<DataKey, Vec<Address>>(&DataKey::SeriesHolders(series_id))
+            .and_then(|holders| holders.get(index))
+    }
+
+    /// Append a first-time subscriber to a series' holder index
+    fn append_series_holder(env: &Env, series_id: u32, holder: &Address) {
+        let index = Self::series_holder_count(env, series_id);
+        let holder_key = DataKeyExt::SeriesHolder(series_id, index);
+        let count_key = DataKeyExt::SeriesHolderCount(series_id);
+        let storage = env.storage().persistent();
+        storage.set(&holder_key, holder);
+        storage.extend_ttl(&holder_key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+        storage.set(&count_key, &(index + 1));
+        storage.extend_ttl(&count_key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+    }
+
+    /// Total payout (asset units) and non-zero bT-Bill balances of holders `[start, end)`
+    fn batch_payouts(
+        env: &Env,
+        series_id: u32,
+        start: u32,
+        end: u32,
+    ) -> Result<(i128, Vec<(Address, i128)>), Error> {
+        let asset_decimals = Self::asset_decimals(env.clone());
+        let mut total: i128 = 0;
+        let mut balances = Vec::new(env);
+        for index in start..end {
+            let holder =
+                Self::series_holder(env, series_id, index).ok_or(Error::InvalidBatchRange)?;
+            let balance = Self::bt_bill_balance(env, series_id, &holder)?;
+            if balance > 0 {
+                let payout = to_asset_units(balance, asset_decimals).ok_or(Error::InvalidAmount)?;
+                total = total.checked_add(payout).ok_or(Error::InvalidAmount)?;
+                balances.push_back((holder, balance));
+            }
+        }
+        Ok((total, balances))
+    }
+
+    /// Add a series to the user's holdings index (no-op if already listed)
+    fn index_user_series(env: &Env, user: &Address, series_id: u32) {
+        let key = DataKeyExt::UserSeries(user.clone());
+        let mut series_ids = Self::get_user_series(env.clone(), user.clone());
+        if !series_ids.contains(series_id) {
+            series_ids.push_back(series_id);
+            env.storage().persistent().set(&key, &series_ids);
+        }
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+    }
+
+    /// Drop a series from the user's holdings index once a burn empties their balance
+    fn unindex_user_series(env: &Env, user: &Address, series_id: u32) -> Result<(), Error> {
+        if Self::bt_bill_balance(env, series_id, user)? > 0 {
+            return Ok(());
+        }
+
+        let key = DataKeyExt::UserSeries(user.clone());
+        let mut series_ids = Self::get_user_series(env.clone(), user.clone());
+        if let Some(index) = series_ids.first_index_of(series_id) {
+            series_ids.remove(index);
+            if series_ids.is_empty() {
+                env.storage().persistent().remove(&key);
+            } else {
+                env.storage().persistent().set(&key, &series_ids);
+            }
+        }
+        Ok(())
+    }
+
+    /// User's bT-Bill balance in a series
+    fn bt_bill_balance(env: &Env, series_id: u32, user: &Address) -> Result<i128, Error> {
+        let bt_bill_token = Self::load_config(env)?.bt_bill_token;
+
+        Ok(env.invoke_contract(
+            &bt_bill_token,
+            &Symbol::new(env, "balance_of"),
+            vec![env, series_id.into(), user.to_val()],
+        ))
+    }
+
+    /// Transfer stablecoin (asset units) from the vault to a user
+    fn pay_out(env: &Env, to: &Address, amount: i128) -> Result<(), Error> {
+        let stablecoin = Self::load_config(env)?.stablecoin;
+
+        let stablecoin_client = token::Client::new(env, &stablecoin);
+        stablecoin_client.transfer(&env.current_contract_address(), to, &amount);
+        Ok(())
+    }
+
+    /// Store a user's remaining keeper allowance, dropping the opt-in once it reaches 0
+    fn save_automation_allowance(
+        env: &Env,
+        user: &Address,
+        action: AutomationAction,
+        allowance: i128,
+    ) {
+        let key = DataKey::Automation(user.clone(), action);
+        if allowance > 0 {
+            Self::save_account_entry(env, &key, &allowance);
+        } else {
+            Self::remove_account_entry(env, &key);
+        }
+    }
+
+    /// Check a delegate may perform `action` for `amount` and draw down its allowance
+    fn consume_delegation(
+        env: &Env,
+        user: &Address,
+        delegate: &Address,
+        action: DelegatedAction,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let key = DataKey::Delegation(user.clone(), delegate.clone());
+        let mut delegation: Delegation =
+            Self::load_account_entry(env, &key).ok_or(Error::DelegationNotFound)?;
+
+        if env.ledger().timestamp() > delegation.expires_at {
+            return Err(Error::DelegationExpired);
+        }
+        if !delegation.actions.contains(action) {
+            return Err(Error::ActionNotDelegated);
+        }
+        if amount > delegation.remaining_par {
+            return Err(Error::ExceedsDelegationLimit);
+        }
+
+        delegation.remaining_par -= amount;
+        Self::save_account_entry(env, &key, &delegation);
+        Ok(())
+    }
+
+    /// Fee split and PAR minted for a subscription at the current price
+    fn quote_subscription(
+        env: &Env,
+        series: &Series,
+        pay_amount: i128,
+        collect_payment: bool,
+    ) -> Result<SubscriptionQuote, Error> {
+        let fee = if collect_payment
+            && !Self::is_shadow_mode(env.clone(), ShadowFeature::SubscriptionFee)
+        {
+            calculate_subscription_fee(pay_amount, Self::get_subscription_fee_bps(env.clone()))
+                .ok_or(Error::InvalidAmount)?
+        } else {
+            0
+        };
+        let net_amount = pay_amount.checked_sub(fee).ok_or(Error::InvalidAmount)?;
+        let issuer_fee = Self::issuer_fee_share(env, series, fee, false)?;
+
+        // Calculate current price (linear accretion)
+        let price = calculate_current_price(series, env.ledger().timestamp());
+
+        // Calculate how many PAR units to mint (net_amount is in asset units)
+        let minted_par = from_asset_units(net_amount, Self::asset_decimals(env.clone()))
+            .and_then(|pay_value| calculate_minted_par(pay_value, price))
+            .ok_or(Error::InvalidAmount)?;
+
+        Ok(SubscriptionQuote {
+            pay_amount,
+            fee,
+            issuer_fee,
+            price,
+            minted_par,
+        })
+    }
+
+    /// Face value, redemption fee and payout for matured PAR
+    fn quote_redemption(
+        env: &Env,
+        series_id: u32,
+        bt_bill_amount: i128,
+    ) -> Result<RedemptionQuote, Error> {
+        let gross = to_asset_units(bt_bill_amount, Self::asset_decimals(env.clone()))
+            .ok_or(Error::InvalidAmount)?;
+        let fee = if Self::is_shadow_mode(env.clone(), ShadowFeature::RedemptionFee) {
+            0
+        } else {
+            let fee_bps = Self::get_redemption_fee_bps(env.clone(), series_id);
+            calculate_redemption_fee(gross, fee_bps).ok_or(Error::InvalidAmount)?
+        };
+        Ok(RedemptionQuote {
+            gross,
+            fee,
+            payout: gross - fee,
+        })
+    }
+
+    /// Subscription body shared by `subscribe`, `subscribe_for` and rolls (auth already checked)
+    fn execute_subscribe(
         env: Env,
         user: Address,
+        payer: Option<Address>,
         series_id: u32,
-        bt_bill_amount: i128,
+        pay_amount: i128,
+        referrer: Option<Address>,
     ) -> Result<(), Error> {
-        Self::check_not_paused(&env)?;
+        // Rolls pass no payer: their proceeds never left the vault
+        let collect_payment = payer.is_some();
+        Self::check_series_not_paused(&env, series_id)?;
+        Self::check_not_blocked(&env, &user)?;
+        if let Some(payer) = &payer {
+            Self::check_not_blocked(&env, payer)?;
+        }
+        Self::check_kyc(&env, series_id, &user)?;
+        let mut series = Self::load_series(&env, series_id)?;
+        let config = Self::load_config(&env)?;
+
+        // Validate: Series must be ACTIVE
+        if series.status == SeriesStatus::FullySubscribed {
+            return Err(Error::SeriesFullySubscribed);
+        }
+        if series.status != SeriesStatus::Active {
+            return Err(Error::SeriesNotActive);
+        }
+
+        let dust_policy = Self::get_dust_policy(env.clone());
+        if let Some(policy) = &dust_policy {
+            if pay_amount < policy.threshold {
+                return Err(Error::BelowDustThreshold);
+            }
+        }
+        if pay_amount < Self::get_min_subscription(env.clone(), series_id) {
+            return Err(Error::BelowMinimum);
+        }
+
+        // Skim the protocol fee; only the remainder buys bT-Bills
+        let SubscriptionQuote {
+            pay_amount: gross_amount,
+            fee,
+            issuer_fee,
+            price: current_price,
+            minted_par,
+        } = Self::quote_subscription(&env, &series, pay_amount, collect_payment)?;
+        let pay_amount = gross_amount - fee;
+        let protocol_fee = fee - issuer_fee;
+        if collect_payment && Self::is_shadow_mode(env.clone(), ShadowFeature::SubscriptionFee) {
+            let shadow_fee = calculate_subscription_fee(
+                gross_amount,
+                Self::get_subscription_fee_bps(env.clone()),
+            )
+            .ok_or(Error::InvalidAmount)?;
+            Self::publish_shadow_outcome(
+                &env,
+                ShadowFeature::SubscriptionFee,
+                series_id,
+                &user,
+                shadow_fee,
+            );
+        }
+
+        // Validate: Series cap
+        let new_series_minted = series
+            .minted_par
+            .checked_add(minted_par)
+            .ok_or(Error::InvalidAmount)?;
+
+        if new_series_minted > series.cap_par {
+            return Err(Error::ExceedsSeriesCap);
+        }
+
+        // Validate: User cap
+        let mut user_position = Self::load_user_position(&env, series_id, &user);
+
+        let new_user_subscribed = user_position
+            .subscribed_par
+            .checked_add(minted_par)
+            .ok_or(Error::InvalidAmount)?;
 
-        if bt_bill_amount <= 0 {
-            return Err(Error::InvalidAmount);
+        let user_tier = Self::get_user_tier(env.clone(), user.clone());
+        if !Self::is_cap_exempt(env.clone(), user.clone()) {
+            let effective_user_cap =
+                calculate_effective_user_cap(series.user_cap_par, user_tier.cap_multiplier_bps)
+                    .ok_or(Error::InvalidAmount)?;
+            if new_user_subscribed > effective_user_cap {
+                return Err(Error::ExceedsUserCap);
+            }
         }
 
-        user.require_auth();
+        Self::record_breaker_volume(&env, &user, series_id, minted_par)?;
 
-        let series: Series = env
-            .storage()
-            .instance()
-            .get(&DataKey::Series(series_id))
-            .ok_or(Error::SeriesNotFound)?;
+        // The referral reward is carved out of the protocol fee, never principal
+        let referral_reward =
+            Self::accrue_referral_reward(&env, &user, series_id, protocol_fee, referrer)?;
+        let collector_fee = protocol_fee - referral_reward;
 
-        // Validate: Must be at or past maturity
-        let current_time = env.ledger().timestamp();
-        if current_time < series.maturity_date {
-            return Err(Error::SeriesNotMatured);
-        }
+        // Transfer stablecoin from the payer to the vault (skipped for rolls)
+        if let Some(payer) = &payer {
+            // The issuer's fee share and the referral reward are held by the
+            // vault until claimed
+            let stablecoin_client = token::Client::new(&env, &config.stablecoin);
+            let this = env.current_contract_address();
+            let pull = Self::is_allowance_pull(env.clone());
+            let to_vault = pay_amount
+                .checked_add(issuer_fee)
+                .and_then(|amount| amount.checked_add(referral_reward))
+                .ok_or(Error::InvalidAmount)?;
+            let balance_before = stablecoin_client.balance(&this);
+            if pull {
+                stablecoin_client.transfer_from(&this, payer, &this, &to_vault);
+            } else {
+                stablecoin_client.transfer(payer, &this, &to_vault);
+            }
 
-        // Burn bT-Bills
-        let bt_bill_token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::BTBillToken)
-            .ok_or(Error::NotInitialized)?;
+            // Fee-on-transfer or deflationary assets must not under-deliver
+            // against the PAR about to be minted
+            if stablecoin_client.balance(&this) - balance_before != to_vault {
+                return Err(Error::InvalidAmount);
+            }
+
+            if fee > 0 {
+                let collector = Self::get_fee_collector(env.clone())?;
+                if collector_fee > 0 {
+                    if pull {
+                        stablecoin_client.transfer_from(&this, payer, &collector, &collector_fee);
+                    } else {
+                        stablecoin_client.transfer(payer, &collector, &collector_fee);
+                    }
+                }
+                if let Some(issuer) = &series.issuer {
+                    Self::book_issuer_fees(&env, issuer, issuer_fee, protocol_fee, false)?;
+                }
+                publish(
+                    &env,
+                    VAULT,
+                    "fee_charged",
+                    series_id,
+                    FeeChargedEvent {
+                        series_id,
+                        user: user.clone(),
+                        collector,
+                        pay_amount: gross_amount,
+                        fee,
+                        issuer_fee,
+                    },
+                );
+            }
+        }
 
+        // Mint bT-Bills
         env.invoke_contract::<()>(
-            &bt_bill_token,
-            &Symbol::new(&env, "burn"),
+            &config.bt_bill_token,
+            &Symbol::new(&env, "mint"),
             vec![
                 &env,
                 series_id.into(),
                 user.to_val(),
-                bt_bill_amount.into_val(&env)
+                minted_par.into_val(&env)
             ],
         );
 
-        // Transfer stablecoin from vault to user (1:1 PAR value)
-        let stablecoin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Stablecoin)
-            .ok_or(Error::NotInitialized)?;
+        // Update state
+        series.minted_par = new_series_minted;
+        series.total_subscriptions_collected = series
+            .total_subscriptions_collected
+            .checked_add(pay_amount)
+            .ok_or(Error::InvalidAmount)?;
+        
+        if user_position.subscribed_par == 0 {
+            Self::append_series_holder(&env, series_id, &user);
+        }
+        Self::index_user_series(&env, &user, series_id);
 
-        let stablecoin_client = token::Client::new(&env, &stablecoin);
-        stablecoin_client.transfer(&env.current_contract_address(), &user, &bt_bill_amount);
+        user_position.subscribed_par = new_user_subscribed;
+        user_position.paid_amount = user_position
+            .paid_amount
+            .checked_add(pay_amount)
+            .ok_or(Error::InvalidAmount)?;
 
-        env.events().publish(
-            (Symbol::new(&env, "redeemed"), series_id, user.clone()),
-            RedeemedEvent {
+        Self::save_series(&env, &series);
+        Self::book_liability_par(&env, series.status, minted_par)?;
+        Self::save_user_position(&env, series_id, &user, &user_position);
+
+        let user_volume = user_tier
+            .volume
+            .checked_add(minted_par)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_account_entry(&env, &DataKey::UserVolume(user.clone()), &user_volume);
+
+        let receipt_id = Self::write_subscription_receipt(
+            &env,
+            &user,
+            series_id,
+            pay_amount,
+            minted_par,
+            current_price,
+        );
+        Self::record_activity(
+            &env,
+            &user,
+            ActivityKind::Subscription,
+            series_id,
+            minted_par,
+            pay_amount,
+            payer.clone().filter(|payer| *payer != user),
+            receipt_id,
+        );
+
+        if dust_policy.is_some_and(|policy| policy.mode == DustMode::Bucket) {
+            let dust = calculate_subscription_dust(
+                pay_amount,
+                minted_par,
+                current_price,
+                Self::asset_decimals(env.clone()),
+            )
+            .ok_or(Error::InvalidAmount)?;
+            Self::add_to_dust_bucket(&env, dust)?;
+        }
+
+        // Book protocol accounting on the series' own tally
+        let mut tally = Self::load_series_tally(&env, series_id);
+        tally.subscriptions_collected = tally
+            .subscriptions_collected
+            .checked_add(pay_amount)
+            .ok_or(Error::InvalidAmount)?;
+        tally.par_minted = tally
+            .par_minted
+            .checked_add(minted_par)
+            .ok_or(Error::InvalidAmount)?;
+        tally.subscription_fees = tally
+            .subscription_fees
+            .checked_add(collector_fee)
+            .ok_or(Error::InvalidAmount)?;
+        tally.referral_rewards_funded = tally
+            .referral_rewards_funded
+            .checked_add(referral_reward)
+            .ok_or(Error::InvalidAmount)?;
+        tally.issuer_fees_received = tally
+            .issuer_fees_received
+            .checked_add(issuer_fee)
+            .ok_or(Error::InvalidAmount)?;
+        let tally_key = DataKeyExt::SeriesTally(series_id);
+        env.storage().persistent().set(&tally_key, &tally);
+        env.storage()
+            .persistent()
+            .extend_ttl(&tally_key, STATE_TTL_THRESHOLD, STATE_TTL_LEDGERS);
+
+        Self::notify_rewards_hook(&env, &user, "subscribe", pay_amount);
+
+        let large_subscription_par = Self::get_large_subscription_threshold(env.clone());
+        if large_subscription_par > 0 && minted_par >= large_subscription_par {
+            Self::notify_observers(&env, "large_subscription", series_id, minted_par);
+        }
+
+        publish(
+            &env,
+            VAULT,
+            "subscribed",
+            series_id,
+            SubscribedEvent {
+                receipt_id,
                 series_id,
+                payer: payer.unwrap_or(user.clone()),
                 user,
-                bt_bill_amount,
-                payout: bt_bill_amount,
+                pay_amount,
+                minted_par,
+                price: current_price,
             },
         );
 
+        if series.minted_par == series.cap_par {
+            Self::mark_fully_subscribed(&env, &mut series)?;
+        }
+
         Ok(())
     }
 
-    // ============================================
-    // VIEW FUNCTIONS
-    // ============================================
+    /// Validate and store new caps, keeping the fill state in step
+    fn write_series_caps(
+        env: &Env,
+        series: &mut Series,
+        cap_par: i128,
+        user_cap_par: i128,
+    ) -> Result<(), Error> {
+        if cap_par <= 0
+            || user_cap_par <= 0
+            || user_cap_par > cap_par
+            || cap_par < series.minted_par
+        {
+            return Err(Error::InvalidCapAmounts);
+        }
 
-    /// Get current price for a series
-    pub fn current_price(env: Env, series_id: u32) -> Result<i128, Error> {
-        let series: Series = env
-            .storage()
-            .instance()
-            .get(&DataKey::Series(series_id))
-            .ok_or(Error::SeriesNotFound)?;
+        if let Some(envelope) = Self::get_issuance_envelope(env.clone()) {
+            if !is_within_envelope(
+                &envelope,
+                series.issue_date,
+                series.maturity_date,
+                series.issue_price,
+                cap_par,
+            ) {
+                let admin = Self::load_config(env)?.admin;
+                admin.require_auth();
+            }
+        }
 
-        let current_time = env.ledger().timestamp();
-        Ok(calculate_current_price(&series, current_time))
+        if let Some(issuer) = &series.issuer {
+            Self::adjust_issuer_par(env, issuer, cap_par - series.cap_par)?;
+        }
+
+        series.cap_par = cap_par;
+        series.user_cap_par = user_cap_par;
+        Self::save_series(env, series);
+
+        // Keep the fill state in step with the new cap
+        if series.status == SeriesStatus::FullySubscribed && cap_par > series.minted_par {
+            Self::transition_series(env, series, SeriesStatus::Active)?;
+        } else if series.status == SeriesStatus::Active && cap_par == series.minted_par {
+            Self::mark_fully_subscribed(env, series)?;
+        }
+
+        publish(
+            env,
+            VAULT,
+            "series_caps_updated",
+            series.series_id,
+            SeriesCapsUpdatedEvent {
+                series_id: series.series_id,
+                cap_par,
+                user_cap_par,
+            },
+        );
+
+        Ok(())
     }
 
-    /// Get series details
-    pub fn get_series(env: Env, series_id: u32) -> Result<Series, Error> {
-        env.storage()
-            .instance()
-            .get(&DataKey::Series(series_id))
-            .ok_or(Error::SeriesNotFound)
+    /// Close a series to new subscriptions once its cap is fully minted
+    fn mark_fully_subscribed(env: &Env, series: &mut Series) -> Result<(), Error> {
+        Self::transition_series(env, series, SeriesStatus::FullySubscribed)?;
+
+        publish(
+            env,
+            VAULT,
+            "series_fully_subscribed",
+            series.series_id,
+            SeriesFullySubscribedEvent {
+                series_id: series.series_id,
+                cap_par: series.cap_par,
+            },
+        );
+
+        Ok(())
     }
 
-    /// Get user position in a series
-    pub fn get_user_position(env: Env, series_id: u32, user: Address) -> UserPosition {
+    /// Store a receipt for a subscription and index it under the user
+    fn write_subscription_receipt(
+        env: &Env,
+        user: &Address,
+        series_id: u32,
+        pay_amount: i128,
+        minted_par: i128,
+        price: i128,
+    ) -> u64 {
+        let id = env
+            .storage()
+            .instance()
+            .get::<DataKey, u64>(&DataKey::SubscriptionCounter)
+            .unwrap_or(0)
+            + 1;
         env.storage()
             .instance()
-            .get::<DataKey, UserPosition>(&DataKey::UserPosition(series_id, user))
-            .unwrap_or(UserPosition { subscribed_par: 0 })
+            .set(&DataKey::SubscriptionCounter, &id);
+
+        let receipt_key = DataKey::SubscriptionReceipt(id);
+        env.storage().persistent().set(
+            &receipt_key,
+            &SubscriptionReceipt {
+                id,
+                user: user.clone(),
+                series_id,
+                pay_amount,
+                minted_par,
+                price,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        env.storage().persistent().extend_ttl(
+            &receipt_key,
+            RECEIPT_TTL_LEDGERS,
+            RECEIPT_TTL_LEDGERS,
+        );
+
+        let user_key = DataKey::UserReceipts(user.clone());
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&user_key)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(id);
+        env.storage().persistent().set(&user_key, &ids);
+        env.storage()
+            .persistent()
+            .extend_ttl(&user_key, RECEIPT_TTL_LEDGERS, RECEIPT_TTL_LEDGERS);
+
+        id
     }
 
-    /// Get protocol accounting (revenue tracking)
-    pub fn get_protocol_accounting(env: Env) -> storage::ProtocolAccounting {
-        use storage::ProtocolAccounting;
+    /// Append a line to a user's statement
+    fn record_activity(
+        env: &Env,
+        user: &Address,
+        kind: ActivityKind,
+        series_id: u32,
+        par_amount: i128,
+        cash_amount: i128,
+        counterparty: Option<Address>,
+        reference: u64,
+    ) {
+        let count_key = DataKeyExt::UserActivityCount(user.clone());
+        let index: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let record_key = DataKeyExt::UserActivity(user.clone(), index);
+        env.storage().persistent().set(
+            &record_key,
+            &ActivityRecord {
+                kind,
+                series_id,
+                par_amount,
+                cash_amount,
+                counterparty,
+                reference,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        env.storage().persistent().extend_ttl(
+            &record_key,
+            RECEIPT_TTL_LEDGERS,
+            RECEIPT_TTL_LEDGERS,
+        );
+
+        env.storage().persistent().set(&count_key, &(index + 1));
         env.storage()
-            .instance()
-            .get::<DataKey, ProtocolAccounting>(&DataKey::ProtocolAccounting)
-            .unwrap_or(ProtocolAccounting {
-                total_subscriptions_collected: 0,
-                total_par_minted: 0,
-                total_lent: 0,
-                total_repo_revenue: 0,
-                total_defaults: 0,
-            })
+            .persistent()
+            .extend_ttl(&count_key, RECEIPT_TTL_LEDGERS, RECEIPT_TTL_LEDGERS);
     }
 
-    /// Calculate protocol profit (subscriptions + repo - redemption liability)
-    /// Note: This is unrealized profit until maturity
-    pub fn calculate_protocol_profit(env: Env) -> i128 {
-        use storage::ProtocolAccounting;
-        let accounting = env
+    /// Index of a user's first statement line stamped at or after `from_ts`
+    ///
+    /// Lines are appended in time order; expired lines count as older.
+    fn first_activity_at(env: &Env, user: &Address, count: u32, from_ts: u64) -> u32 {
+        let (mut low, mut high) = (0u32, count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let stamped = env
+                .storage()
+                .persistent()
+                .get::<DataKeyExt, ActivityRecord>(&DataKeyExt::UserActivity(user.clone(), mid))
+                .map_or(0, |record| record.timestamp);
+            if stamped < from_ts {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Notify the rewards hook, if configured, of a user action
+    fn notify_rewards_hook(env: &Env, user: &Address, action: &str, notional: i128) {
+        if let Some(hook) = Self::get_rewards_hook(env.clone()) {
+            let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &hook,
+                &Symbol::new(env, "on_action"),
+                vec![
+                    env,
+                    user.to_val(),
+                    Symbol::new(env, action).to_val(),
+                    notional.into_val(env),
+                ],
+            );
+        }
+    }
+
+    /// Count minted PAR against the series circuit breaker, if configured
+    ///
+    /// Fails with `SubscriptionsHalted` while a trip is in force; trips the
+    /// breaker (without failing) when this subscription breaches the window cap.
+    ///
+    /// In shadow mode the window is still tracked, but a halted subscription
+    /// only emits `shadow_outcome` and a trip publishes nothing.
+    fn record_breaker_volume(
+        env: &Env,
+        user: &Address,
+        series_id: u32,
+        minted_par: i128,
+    ) -> Result<(), Error> {
+        let Some(breaker) = env
             .storage()
             .instance()
-            .get::<DataKey, ProtocolAccounting>(&DataKey::ProtocolAccounting)
-            .unwrap_or(ProtocolAccounting {
-                total_subscriptions_collected: 0,
-                total_par_minted: 0,
-                total_lent: 0,
-                total_repo_revenue: 0,
-                total_defaults: 0,
-            });
+            .get::<DataKey, CircuitBreaker>(&DataKey::CircuitBreaker(series_id))
+        else {
+            return Ok(());
+        };
 
-        // Revenue = subscriptions + repo profits
-        let revenue = accounting
-            .total_subscriptions_collected
-            .checked_add(accounting.total_repo_revenue)
-            .unwrap_or(0);
+        let shadow = Self::is_shadow_mode(env.clone(), ShadowFeature::CircuitBreaker);
+        let ledger = env.ledger().sequence();
+        let state = Self::get_breaker_state(env.clone(), series_id);
+        if let Some(state) = &state {
+            if is_tripped(state, ledger) {
+                if !shadow {
+                    return Err(Error::SubscriptionsHalted);
+                }
+                Self::publish_shadow_outcome(
+                    env,
+                    ShadowFeature::CircuitBreaker,
+                    series_id,
+                    user,
+                    minted_par,
+                );
+            }
+        }
 
-        // Liability = PAR minted (will need to pay at maturity)
-        let liability = accounting.total_par_minted;
+        let (state, tripped) = record_window_volume(state, &breaker, ledger, minted_par);
+        env.storage()
+            .instance()
+            .set(&DataKey::BreakerState(series_id), &state);
 
-        // Profit (can be negative early on)
-        revenue.saturating_sub(liability)
+        if let Some(window_par) = tripped.filter(|_| !shadow) {
+            publish(
+                env,
+                VAULT,
+                "circuit_breaker_tripped",
+                series_id,
+                CircuitBreakerTrippedEvent {
+                    series_id,
+                    window_par,
+                    max_window_par: breaker.max_window_par,
+                    halted_until_ledger: state.tripped_until,
+                },
+            );
+        }
+
+        Ok(())
     }
 
-    /// Calculate available USDC for repo lending
-    /// 
-    /// With 100% liquidity model: ALL vault USDC is available
-    /// Safety ensured by haircut on each repo position
-    pub fn calculate_available_for_lending(env: Env) -> i128 {
-        use storage::ProtocolAccounting;
-        let accounting = env
-            .storage()
-            .instance()
-            .get::<DataKey, ProtocolAccounting>(&DataKey::ProtocolAccounting)
-            .unwrap_or(ProtocolAccounting {
-                total_subscriptions_collected: 0,
-                total_par_minted: 0,
-                total_lent: 0,
-                total_repo_revenue: 0,
-                total_defaults: 0,
-            });
+    fn publish_shadow_outcome(
+        env: &Env,
+        feature: ShadowFeature,
+        series_id: u32,
+        user: &Address,
+        amount: i128,
+    ) {
+        publish(
+            env,
+            VAULT,
+            "shadow_outcome",
+            series_id,
+            ShadowOutcomeEvent {
+                feature,
+                series_id,
+                user: user.clone(),
+                amount,
+            },
+        );
+    }
 
-        // Total USDC in vault = subscriptions + repo returns
-        let total_usdc = accounting
-            .total_subscriptions_collected
-            .checked_add(accounting.total_repo_revenue)
-            .unwrap_or(0);
+    /// Best-effort fan-out to registered observers; failures are swallowed
+    fn notify_observers(env: &Env, event: &str, series_id: u32, amount: i128) {
+        for observer in Self::get_observers(env.clone()).iter() {
+            let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &observer,
+                &Symbol::new(env, "on_vault_event"),
+                vec![
+                    env,
+                    Symbol::new(env, event).to_val(),
+                    series_id.into_val(env),
+                    amount.into_val(env),
+                ],
+            );
+        }
+    }
 
-        // Currently lent out
-        let lent = accounting.total_lent;
+    /// Record first-touch attribution and accrue the referrer's reward
+    ///
+    /// Returns the reward accrued; the caller withholds it from the protocol
+    /// fee so the vault holds it until claim_referral_rewards.
+    fn accrue_referral_reward(
+        env: &Env,
+        user: &Address,
+        series_id: u32,
+        fee_amount: i128,
+        referrer: Option<Address>,
+    ) -> Result<i128, Error> {
+        let referrer_key = DataKey::Referrer(user.clone());
+        let attributed = match Self::load_account_entry::<_, Address>(env, &referrer_key) {
+            Some(existing) => existing,
+            None => match referrer {
+                // Self-referral is ignored rather than rejected
+                Some(referrer) if referrer != *user => {
+                    Self::save_account_entry(env, &referrer_key, &referrer);
+                    publish(
+                        env,
+                        VAULT,
+                        "referral_attributed",
+                        user.clone(),
+                        ReferralAttributedEvent {
+                            user: user.clone(),
+                            referrer: referrer.clone(),
+                        },
+                    );
+                    referrer
+                }
+                _ => return Ok(0),
+            },
+        };
 
-        // Available = total - lent (100% of remaining balance)
-        total_usdc.saturating_sub(lent)
+        let reward_bps = Self::get_referral_reward_bps(env.clone());
+        let reward =
+            calculate_referral_reward(fee_amount, reward_bps).ok_or(Error::InvalidAmount)?;
+        if reward <= 0 {
+            return Ok(0);
+        }
+
+        let rewards = Self::get_referral_rewards(env.clone(), attributed.clone())
+            .checked_add(reward)
+            .ok_or(Error::InvalidAmount)?;
+        Self::save_account_entry(env, &DataKey::ReferralRewards(attributed.clone()), &rewards);
+
+        publish(
+            env,
+            VAULT,
+            "referral_accrued",
+            attributed.clone(),
+            ReferralRewardAccruedEvent {
+                referrer: attributed,
+                user: user.clone(),
+                series_id,
+                reward,
+            },
+        );
+
+        Ok(reward)
     }
 
-    // ============================================
-    // INTERNAL HELPERS
-    // ============================================
+    /// Apply a lifecycle transition, persist the series and emit StatusChangedEvent
+    fn transition_series(env: &Env, series: &mut Series, to: SeriesStatus) -> Result<(), Error> {
+        let from = series.status;
+        if !is_valid_transition(from, to) {
+            return Err(Error::InvalidStatus);
+        }
 
-    fn check_not_paused(env: &Env) -> Result<(), Error> {
-        let paused = env
-            .storage()
-            .instance()
-            .get::<DataKey, bool>(&DataKey::Paused)
-            .unwrap_or(false);
+        series.status = to;
+        Self::save_series(env, series);
+        if counts_as_matured(from) != counts_as_matured(to) {
+            let owed = calculate_outstanding_par(series);
+            let moved = if counts_as_matured(to) { owed } else { -owed };
+            Self::add_liability_par(env, &DataKeyExt::MaturedPar, moved)?;
+        }
 
-        if paused {
-            return Err(Error::ContractPaused);
+        publish(
+            env,
+            VAULT,
+            "status_changed",
+            series.series_id,
+            StatusChangedEvent {
+                series_id: series.series_id,
+                from,
+                to,
+            },
+        );
+
+        match to {
+            SeriesStatus::Active => {
+                Self::notify_observers(env, "series_activated", series.series_id, 0);
+            }
+            SeriesStatus::Matured => {
+                let outstanding_par = calculate_outstanding_par(series);
+                Self::notify_observers(env, "series_matured", series.series_id, outstanding_par);
+            }
+            SeriesStatus::Closed | SeriesStatus::Cancelled => {
+                if let Some(issuer) = &series.issuer {
+                    Self::adjust_issuer_par(env, issuer, -series.cap_par)?;
+                }
+            }
+            _ => {}
         }
+
         Ok(())
     }
 
+    /// Actual stablecoin balance held by the vault contract
+    fn stablecoin_balance(env: &Env) -> Result<i128, Error> {
+        let stablecoin = Self::load_config(env)?.stablecoin;
+
+        Ok(token::Client::new(env, &stablecoin).balance(&env.current_contract_address()))
+    }
+
     /// Mark series as matured (can be called by anyone at maturity)
     pub fn mature_series(env: Env, series_id: u32) -> Result<(), Error> {
-        let mut series: Series = env
-            .storage()
-            .instance()
-            .get(&DataKey::Series(series_id))
-            .ok_or(Error::SeriesNotFound)?;
+        Self::require_schema(&env)?;
+        let mut series = Self::load_series(&env, series_id)?;
 
         let current_time = env.ledger().timestamp();
         if current_time < series.maturity_date {
             return Err(Error::SeriesNotMatured);
         }
 
-        if series.status != SeriesStatus::Active {
-            return Err(Error::InvalidStatus);
+        Self::mark_matured(&env, &mut series)
+    }
+
+    /// Lazily move a live series past its maturity date to Matured
+    ///
+    /// Post-maturity flows call this so the stored status never lags the
+    /// clock, whether or not anyone called `mature_series`.
+    fn mature_if_due(env: &Env, series: &mut Series) -> Result<(), Error> {
+        if is_live(series.status) && env.ledger().timestamp() >= series.maturity_date {
+            Self::mark_matured(env, series)?;
         }
+        Ok(())
+    }
 
-        series.status = SeriesStatus::Matured;
-        env.storage()
-            .instance()
-            .set(&DataKey::Series(series_id), &series);
+    fn mark_matured(env: &Env, series: &mut Series) -> Result<(), Error> {
+        Self::transition_series(env, series, SeriesStatus::Matured)?;
 
-        env.events().publish(
-            (Symbol::new(&env, "series_matured"), series_id),
-            SeriesMaturedEvent { series_id },
+        publish(
+            env,
+            VAULT,
+            "series_matured",
+            series.series_id,
+            SeriesMaturedEvent {
+                series_id: series.series_id,
+            },
         );
 
         Ok(())
@@ -0,0 +1,125 @@
+use crate::storage::SeriesStatus;
+
+/// Check a series status transition against the lifecycle table
+///
+/// ```text
+/// Upcoming ──► Active ──► Matured ──► Closed
+///    │         │ ▲  │  ▲     │
+///    │         ▼ │  │  │     ▼
+///    │   FullySubscribed ┘  Failed
+///    ▼         │    ▼
+/// Cancelled ◄──┘  Failed
+/// ```
+///
+/// FullySubscribed behaves like Active except for new subscriptions, and
+/// returns to Active if its cap is raised. Cancelled, Failed and Closed are
+/// terminal.
+pub fn is_valid_transition(from: SeriesStatus, to: SeriesStatus) -> bool {
+    matches!(
+        (from, to),
+        (SeriesStatus::Upcoming, SeriesStatus::Active)
+            | (SeriesStatus::Upcoming, SeriesStatus::Cancelled)
+            | (SeriesStatus::Active, SeriesStatus::FullySubscribed)
+            | (SeriesStatus::Active, SeriesStatus::Cancelled)
+            | (SeriesStatus::Active, SeriesStatus::Matured)
+            | (SeriesStatus::Active, SeriesStatus::Failed)
+            | (SeriesStatus::FullySubscribed, SeriesStatus::Active)
+            | (SeriesStatus::FullySubscribed, SeriesStatus::Cancelled)
+            | (SeriesStatus::FullySubscribed, SeriesStatus::Matured)
+            | (SeriesStatus::FullySubscribed, SeriesStatus::Failed)
+            | (SeriesStatus::Matured, SeriesStatus::Closed)
+            | (SeriesStatus::Matured, SeriesStatus::Failed)
+    )
+}
+
+/// Issued and running to maturity (Active or FullySubscribed)
+pub fn is_live(status: SeriesStatus) -> bool {
+    matches!(status, SeriesStatus::Active | SeriesStatus::FullySubscribed)
+}
+
+/// Past maturity and redeemable (Matured, or Closed once fully redeemed)
+pub fn counts_as_matured(status: SeriesStatus) -> bool {
+    matches!(status, SeriesStatus::Matured | SeriesStatus::Closed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_transitions() {
+        assert!(is_valid_transition(
+            SeriesStatus::Upcoming,
+            SeriesStatus::Active
+        ));
+        assert!(is_valid_transition(
+            SeriesStatus::Active,
+            SeriesStatus::Matured
+        ));
+        assert!(is_valid_transition(
+            SeriesStatus::Matured,
+            SeriesStatus::Closed
+        ));
+    }
+
+    #[test]
+    fn test_rejects_skips_and_reversals() {
+        assert!(!is_valid_transition(
+            SeriesStatus::Upcoming,
+            SeriesStatus::Matured
+        ));
+        assert!(!is_valid_transition(
+            SeriesStatus::Matured,
+            SeriesStatus::Active
+        ));
+        assert!(!is_valid_transition(
+            SeriesStatus::Matured,
+            SeriesStatus::Cancelled
+        ));
+        assert!(!is_valid_transition(
+            SeriesStatus::Active,
+            SeriesStatus::Active
+        ));
+        assert!(!is_valid_transition(
+            SeriesStatus::Upcoming,
+            SeriesStatus::FullySubscribed
+        ));
+    }
+
+    #[test]
+    fn test_fully_subscribed_transitions() {
+        for to in [
+            SeriesStatus::Active,
+            SeriesStatus::Matured,
+            SeriesStatus::Cancelled,
+            SeriesStatus::Failed,
+        ] {
+            assert!(is_valid_transition(SeriesStatus::FullySubscribed, to));
+        }
+        assert!(!is_valid_transition(
+            SeriesStatus::FullySubscribed,
+            SeriesStatus::Closed
+        ));
+    }
+
+    #[test]
+    fn test_terminal_states() {
+        for from in [
+            SeriesStatus::Cancelled,
+            SeriesStatus::Failed,
+            SeriesStatus::Closed,
+        ] {
+            for to in [
+                SeriesStatus::Upcoming,
+                SeriesStatus::Active,
+                SeriesStatus::Matured,
+                SeriesStatus::Closed,
+                SeriesStatus::Cancelled,
+                SeriesStatus::Failed,
+                SeriesStatus::FullySubscribed,
+            ] {
+                assert!(!is_valid_transition(from, to));
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::storage::{LoyaltyTier, BASIS_POINTS};
+use soroban_sdk::Vec;
+
+/// Validate a loyalty tier schedule
+///
+/// Tiers must be strictly ascending by min_volume and every multiplier must
+/// be at least 1× (10,000 bps) so a tier can never shrink a user's cap.
+pub fn validate_loyalty_tiers(tiers: &Vec<LoyaltyTier>) -> bool {
+    let mut prev_volume: Option<i128> = None;
+    for tier in tiers.iter() {
+        if tier.min_volume < 0 || tier.cap_multiplier_bps < BASIS_POINTS {
+            return false;
+        }
+        if let Some(prev) = prev_volume {
+            if tier.min_volume <= prev {
+                return false;
+            }
+        }
+        prev_volume = Some(tier.min_volume);
+    }
+    true
+}
+
+/// Resolve the highest tier reached for a cumulative subscribed volume
+///
+/// Returns (1-based tier index, cap_multiplier_bps), or (0, 10,000) if no
+/// tier is reached.
+///
+/// Example:
+/// - tiers: [100k → 2×, 1M → 5×]
+/// - volume: 250k
+/// - result: (1, 20,000)
+pub fn resolve_loyalty_tier(tiers: &Vec<LoyaltyTier>, volume: i128) -> (u32, i128) {
+    let mut resolved = (0u32, BASIS_POINTS);
+    for (i, tier) in tiers.iter().enumerate() {
+        if volume < tier.min_volume {
+            break;
+        }
+        resolved = (i as u32 + 1, tier.cap_multiplier_bps);
+    }
+    resolved
+}
+
+/// Apply a loyalty multiplier to a series' per-user cap
+///
+/// Formula: effective_cap = user_cap_par × cap_multiplier_bps / 10,000
+pub fn calculate_effective_user_cap(user_cap_par: i128, cap_multiplier_bps: i128) -> Option<i128> {
+    user_cap_par
+        .checked_mul(cap_multiplier_bps)?
+        .checked_div(BASIS_POINTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SCALE;
+    use soroban_sdk::{vec, Env};
+
+    fn tiers(env: &Env) -> Vec<LoyaltyTier> {
+        vec![
+            env,
+            LoyaltyTier {
+                min_volume: 100_000 * SCALE,
+                cap_multiplier_bps: 20_000,
+            },
+            LoyaltyTier {
+                min_volume: 1_000_000 * SCALE,
+                cap_multiplier_bps: 50_000,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_validate_loyalty_tiers() {
+        let env = Env::default();
+        assert!(validate_loyalty_tiers(&tiers(&env)));
+
+        let shrinking = vec![
+            &env,
+            LoyaltyTier {
+                min_volume: 0,
+                cap_multiplier_bps: 5_000,
+            },
+        ];
+        assert!(!validate_loyalty_tiers(&shrinking));
+
+        let mut unordered = tiers(&env);
+        unordered.push_back(LoyaltyTier {
+            min_volume: 500_000 * SCALE,
+            cap_multiplier_bps: 30_000,
+        });
+        assert!(!validate_loyalty_tiers(&unordered));
+    }
+
+    #[test]
+    fn test_resolve_loyalty_tier() {
+        let env = Env::default();
+        let tiers = tiers(&env);
+
+        assert_eq!(resolve_loyalty_tier(&tiers, 50_000 * SCALE), (0, 10_000));
+        assert_eq!(resolve_loyalty_tier(&tiers, 250_000 * SCALE), (1, 20_000));
+        assert_eq!(resolve_loyalty_tier(&tiers, 1_000_000 * SCALE), (2, 50_000));
+    }
+
+    #[test]
+    fn test_calculate_effective_user_cap() {
+        let cap = calculate_effective_user_cap(10_000 * SCALE, 20_000).unwrap();
+        assert_eq!(cap, 20_000 * SCALE); // 2×
+    }
+}
@@ -1,4 +1,4 @@
-use crate::storage::{PAR_UNIT, Series};
+use crate::storage::{IssuanceEnvelope, Series, BASIS_POINTS, PAR_UNIT, SCALE_DECIMALS};
 
 /// Calculate current price with linear accretion
 /// 
@@ -23,7 +23,7 @@ pub fn calculate_current_price(series: &Series, current_time: u64) -> i128 {
     let total_duration = series.maturity_date - series.issue_date;
     
     let price_delta = PAR_UNIT - series.issue_price;
-    let accreted_value = (price_delta as i128)
+    let accreted_value = price_delta
         .checked_mul(elapsed as i128)
         .and_then(|v| v.checked_div(total_duration as i128))
         .unwrap_or(0);
@@ -45,6 +45,116 @@ pub fn calculate_minted_par(pay_amount: i128, current_price: i128) -> Option<i12
         .checked_div(current_price)
 }
 
+/// Calculate the stablecoin value of a bT-Bill amount at a given price
+///
+/// Formula: value = par_amount × current_price / PAR_UNIT
+///
+/// Example:
+/// - par_amount: 10,000 PAR
+/// - current_price: 0.99
+/// - value: 9,900 USDC
+pub fn calculate_collateral_value(par_amount: i128, current_price: i128) -> Option<i128> {
+    par_amount.checked_mul(current_price)?.checked_div(PAR_UNIT)
+}
+
+/// Calculate the discount to PAR at issue, in basis points
+///
+/// Formula: discount_bps = (PAR_UNIT - issue_price) × 10,000 / PAR_UNIT
+///
+/// Example:
+/// - issue_price: 0.98
+/// - discount: 200 bps (2%)
+pub fn calculate_discount_bps(issue_price: i128) -> Option<i128> {
+    PAR_UNIT
+        .checked_sub(issue_price)?
+        .checked_mul(BASIS_POINTS)?
+        .checked_div(PAR_UNIT)
+}
+
+/// Check whether series parameters fall inside an issuance envelope
+pub fn is_within_envelope(
+    envelope: &IssuanceEnvelope,
+    issue_date: u64,
+    maturity_date: u64,
+    issue_price: i128,
+    cap_par: i128,
+) -> bool {
+    let tenor = maturity_date.saturating_sub(issue_date);
+    let discount_bps = match calculate_discount_bps(issue_price) {
+        Some(discount_bps) => discount_bps,
+        None => return false,
+    };
+
+    discount_bps <= envelope.max_discount_bps
+        && tenor >= envelope.min_tenor
+        && tenor <= envelope.max_tenor
+        && cap_par <= envelope.max_cap_par
+}
+
+/// Rescale a SCALE-denominated value into settlement asset units
+///
+/// Truncates when the asset has fewer decimals, so rounding favours the vault.
+///
+/// Example:
+/// - amount: 1.5 at SCALE (15,000,000)
+/// - asset_decimals: 6
+/// - result: 1,500,000
+pub fn to_asset_units(amount: i128, asset_decimals: u32) -> Option<i128> {
+    rescale(amount, SCALE_DECIMALS, asset_decimals)
+}
+
+/// Rescale settlement asset units into a SCALE-denominated value
+pub fn from_asset_units(amount: i128, asset_decimals: u32) -> Option<i128> {
+    rescale(amount, asset_decimals, SCALE_DECIMALS)
+}
+
+/// Payment left over after minting, in asset units
+///
+/// Formula: dust = pay_amount - to_asset(minted_par × price / PAR_UNIT)
+///
+/// minted_par is rounded down, so its cost never exceeds the payment; the
+/// remainder is residue no holder has a claim on.
+pub fn calculate_subscription_dust(
+    pay_amount: i128,
+    minted_par: i128,
+    current_price: i128,
+    asset_decimals: u32,
+) -> Option<i128> {
+    let cost = calculate_collateral_value(minted_par, current_price)?;
+    let cost = to_asset_units(cost, asset_decimals)?;
+    Some(pay_amount.checked_sub(cost)?.max(0))
+}
+
+/// Calculate the proceeds of an early exit before maturity
+///
+/// Formula: proceeds = value - value × penalty_bps / 10,000,
+///          where value = par_amount × current_price / PAR_UNIT
+///
+/// Example:
+/// - par_amount: 10,000 PAR
+/// - current_price: 0.99
+/// - penalty_bps: 50 (0.5%)
+/// - proceeds: 9,900 - 49.5 = 9,850.5 USDC
+pub fn calculate_early_redemption(
+    par_amount: i128,
+    current_price: i128,
+    penalty_bps: u32,
+) -> Option<i128> {
+    let value = calculate_collateral_value(par_amount, current_price)?;
+    let penalty = value
+        .checked_mul(penalty_bps as i128)?
+        .checked_div(BASIS_POINTS)?;
+    value.checked_sub(penalty)
+}
+
+fn rescale(amount: i128, from_decimals: u32, to_decimals: u32) -> Option<i128> {
+    if to_decimals >= from_decimals {
+        amount.checked_mul(10i128.checked_pow(to_decimals - from_decimals)?)
+    } else {
+        amount.checked_div(10i128.checked_pow(from_decimals - to_decimals)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,6 +172,9 @@ mod tests {
             minted_par: 0,
             user_cap_par: 100_000 * SCALE,
             status: SeriesStatus::Active,
+            total_subscriptions_collected: 0,
+            redeemed_par: 0,
+            issuer: None,
         };
 
         let price = calculate_current_price(&series, 1000);
@@ -80,6 +193,9 @@ mod tests {
             minted_par: 0,
             user_cap_par: 100_000 * SCALE,
             status: SeriesStatus::Active,
+            total_subscriptions_collected: 0,
+            redeemed_par: 0,
+            issuer: None,
         };
 
         let price = calculate_current_price(&series, 2000);
@@ -98,6 +214,9 @@ mod tests {
             minted_par: 0,
             user_cap_par: 100_000 * SCALE,
             status: SeriesStatus::Active,
+            total_subscriptions_collected: 0,
+            redeemed_par: 0,
+            issuer: None,
         };
 
         let price = calculate_current_price(&series, 1500); // Halfway
@@ -112,4 +231,94 @@ mod tests {
         let minted = calculate_minted_par(pay_amount, current_price).unwrap();
         assert_eq!(minted, 100 * SCALE); // 100 PAR
     }
+
+    #[test]
+    fn test_calculate_collateral_value() {
+        let par_amount = 10_000 * SCALE;
+        let current_price = 99 * SCALE / 100; // 0.99
+
+        let value = calculate_collateral_value(par_amount, current_price).unwrap();
+        assert_eq!(value, 9_900 * SCALE);
+    }
+
+    #[test]
+    fn test_calculate_discount_bps() {
+        assert_eq!(calculate_discount_bps(98 * SCALE / 100), Some(200)); // 2%
+        assert_eq!(calculate_discount_bps(PAR_UNIT), Some(0));
+    }
+
+    #[test]
+    fn test_is_within_envelope() {
+        let envelope = IssuanceEnvelope {
+            max_discount_bps: 500,   // 5%
+            min_tenor: 28 * 86_400,  // 4 weeks
+            max_tenor: 365 * 86_400, // 1 year
+            max_cap_par: 10_000_000 * SCALE,
+        };
+        let price = 98 * SCALE / 100;
+        let cap = 1_000_000 * SCALE;
+
+        assert!(is_within_envelope(&envelope, 0, 182 * 86_400, price, cap));
+        // Too deep a discount
+        assert!(!is_within_envelope(&envelope, 0, 182 * 86_400, 90 * SCALE / 100, cap));
+        // Tenor too short / too long
+        assert!(!is_within_envelope(&envelope, 0, 7 * 86_400, price, cap));
+        assert!(!is_within_envelope(&envelope, 0, 730 * 86_400, price, cap));
+        // Cap too large
+        assert!(!is_within_envelope(&envelope, 0, 182 * 86_400, price, 20_000_000 * SCALE));
+    }
+
+    #[test]
+    fn test_asset_unit_conversion() {
+        // 6-decimal stablecoin
+        assert_eq!(to_asset_units(15 * SCALE / 10, 6), Some(1_500_000));
+        assert_eq!(from_asset_units(1_500_000, 6), Some(15 * SCALE / 10));
+        // Sub-unit remainder is truncated
+        assert_eq!(to_asset_units(9, 6), Some(0));
+
+        // 7 decimals is the identity
+        assert_eq!(to_asset_units(123 * SCALE, 7), Some(123 * SCALE));
+
+        // 18-decimal asset
+        assert_eq!(to_asset_units(SCALE, 18), Some(1_000_000_000_000_000_000));
+    }
+
+    #[test]
+    fn test_calculate_subscription_dust() {
+        // 100 at 0.97 → 103.0927835 PAR, costing 99.9999999
+        let pay_amount = 100 * SCALE;
+        let price = 97 * SCALE / 100;
+        let minted = calculate_minted_par(pay_amount, price).unwrap();
+        assert_eq!(minted, 1_030_927_835);
+        assert_eq!(
+            calculate_subscription_dust(pay_amount, minted, price, 7),
+            Some(1)
+        );
+
+        // Cost truncates to 99.999999 in a 6-decimal asset
+        let pay_amount = 100_000_000;
+        assert_eq!(
+            calculate_subscription_dust(pay_amount, minted, price, 6),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_calculate_early_redemption() {
+        let price = 99 * SCALE / 100;
+        assert_eq!(
+            calculate_early_redemption(10_000 * SCALE, price, 50),
+            Some(98_505 * SCALE / 10)
+        );
+
+        // No penalty pays the accreted value; a full penalty pays nothing
+        assert_eq!(
+            calculate_early_redemption(10_000 * SCALE, price, 0),
+            Some(9_900 * SCALE)
+        );
+        assert_eq!(
+            calculate_early_redemption(10_000 * SCALE, price, 10_000),
+            Some(0)
+        );
+    }
 }
@@ -0,0 +1,161 @@
+use soroban_sdk::{Bytes, BytesN, Env, Map, Symbol, Vec};
+
+/// Storage layout version written at initialize / migrate_schema
+///
+/// Bump it, and update STORAGE_LAYOUTS, whenever a stored type gains,
+/// loses or reorders a field, or a key changes what it holds.
+pub const STORAGE_SCHEMA_VERSION: u32 = 9;
+
+/// (DataKey variant, layout of the value stored under it)
+///
+/// Hashed per key into the schema record so off-chain tooling can see
+/// exactly which entries changed between two wasm builds.
+pub const STORAGE_LAYOUTS: [(&str, &str); 23] = [
+    (
+        "Series",
+        "series_id:u32,issue_date:u64,maturity_date:u64,par_unit:i128,issue_price:i128,\
+         cap_par:i128,minted_par:i128,user_cap_par:i128,status:SeriesStatus,\
+         total_subscriptions_collected:i128,redeemed_par:i128,issuer:Option<Address>",
+    ),
+    ("UserPosition", "subscribed_par:i128,paid_amount:i128"),
+    (
+        "SubscriptionReceipt",
+        "id:u64,user:Address,series_id:u32,pay_amount:i128,minted_par:i128,price:i128,\
+         timestamp:u64",
+    ),
+    (
+        "LoyaltyTiers",
+        "Vec<min_volume:i128,cap_multiplier_bps:i128>",
+    ),
+    (
+        "Delegation",
+        "actions:Vec<DelegatedAction>,remaining_par:i128,expires_at:u64",
+    ),
+    (
+        "IssuanceEnvelope",
+        "max_discount_bps:i128,min_tenor:u64,max_tenor:u64,max_cap_par:i128",
+    ),
+    (
+        "ProtocolAccounting",
+        "total_subscriptions_collected:i128,total_par_minted:i128,total_lent:i128,\
+         total_repo_revenue:i128,total_defaults:u32,total_redeemed:i128,\
+         total_settlement_deposits:i128,total_referral_rewards_paid:i128,\
+         total_dust_claimed:i128,queued_payouts:i128,total_early_penalties:i128,\
+         total_treasury_withdrawals:i128,total_subscription_fees:i128,\
+         total_redemption_fees:i128,total_fees_collected:i128,\
+         total_issuer_fees_received:i128,total_issuer_fees_claimed:i128,\
+         total_referral_rewards_funded:i128,total_emergency_withdrawals:i128",
+    ),
+    ("DustPolicy", "threshold:i128,mode:DustMode"),
+    (
+        "CircuitBreaker",
+        "window_ledgers:u32,max_window_par:i128,pause_ledgers:u32",
+    ),
+    (
+        "BreakerState",
+        "window_start:u32,window_par:i128,tripped_until:u32",
+    ),
+    ("EarlyRedemption", "enabled:bool,penalty_bps:u32"),
+    ("RedemptionLane", "threshold:i128,retail_reserve:i128"),
+    (
+        "RedemptionQueue",
+        "Vec<user:Address,series_id:u32,amount:i128,queued_at:u64>",
+    ),
+    (
+        "YieldCurveCheckpoint",
+        "timestamp:u64,points:Vec<tenor_secs:u64,yield_bps:i128>,series_count:u32",
+    ),
+    (
+        "SettlementPlan",
+        "next_holder:u32,announced:u32,processed:u32",
+    ),
+    (
+        "SettlementBatch",
+        "start:u32,end:u32,funded:i128,processed:bool",
+    ),
+    (
+        "Issuer",
+        "limits:IssuerLimits<max_outstanding_par:i128,min_tenor:u64,max_tenor:u64,\
+         allowed_assets:Vec<Address>>,outstanding_par:i128,approved:bool",
+    ),
+    (
+        "IssuerFeeSplit",
+        "subscription_share_bps:u32,spread_share_bps:u32",
+    ),
+    (
+        "IssuerFees",
+        "issuer_subscription_fees:i128,issuer_spread_fees:i128,\
+         protocol_subscription_fees:i128,protocol_spread_fees:i128,claimable:i128,claimed:i128",
+    ),
+    (
+        "PendingEmergencyWithdraw",
+        "token:Address,amount:i128,to:Address,executable_at:u64",
+    ),
+    (
+        "Config",
+        "admin:Address,treasury:Address,stablecoin:Address,bt_bill_token:Address",
+    ),
+    (
+        "SeriesTally",
+        "subscriptions_collected:i128,par_minted:i128,subscription_fees:i128,\
+         issuer_fees_received:i128,referral_rewards_funded:i128",
+    ),
+    ("WithdrawalLimits", "daily_limit:i128,weekly_limit:i128"),
+];
+
+/// Hash every entry of STORAGE_LAYOUTS, keyed by its DataKey variant
+pub fn layout_hashes(env: &Env) -> Map<Symbol, BytesN<32>> {
+    let mut hashes = Map::new(env);
+    for (key, layout) in STORAGE_LAYOUTS.iter() {
+        let digest = env
+            .crypto()
+            .sha256(&Bytes::from_slice(env, layout.as_bytes()));
+        hashes.set(Symbol::new(env, key), digest.into());
+    }
+    hashes
+}
+
+/// Keys whose layout hash differs between two schema records (or is new)
+pub fn changed_keys(
+    env: &Env,
+    previous: &Map<Symbol, BytesN<32>>,
+    current: &Map<Symbol, BytesN<32>>,
+) -> Vec<Symbol> {
+    let mut changed = Vec::new(env);
+    for (key, hash) in current.iter() {
+        if previous.get(key.clone()) != Some(hash) {
+            changed.push_back(key);
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_keys_unique() {
+        let env = Env::default();
+        assert_eq!(layout_hashes(&env).len(), STORAGE_LAYOUTS.len() as u32);
+    }
+
+    #[test]
+    fn test_changed_keys() {
+        let env = Env::default();
+        let current = layout_hashes(&env);
+        assert!(changed_keys(&env, &current, &current).is_empty());
+
+        let mut previous = current.clone();
+        previous.set(
+            Symbol::new(&env, "ProtocolAccounting"),
+            BytesN::from_array(&env, &[0; 32]),
+        );
+        previous.remove(Symbol::new(&env, "Series"));
+
+        let changed = changed_keys(&env, &previous, &current);
+        assert_eq!(changed.len(), 2);
+        assert!(changed.contains(Symbol::new(&env, "ProtocolAccounting")));
+        assert!(changed.contains(Symbol::new(&env, "Series")));
+    }
+}
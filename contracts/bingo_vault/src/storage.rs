@@ -1,11 +1,30 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, Map, Symbol, Vec};
 
 // Constants
 pub const SCALE: i128 = 10_000_000; // 7 decimals
-pub const PAR_UNIT: i128 = 1 * SCALE; // 1.0000000
+pub const SCALE_DECIMALS: u32 = 7; // Decimals of SCALE; PAR and prices always use this
+pub const MAX_ASSET_DECIMALS: u32 = 18; // Upper bound on the settlement asset's decimals
+pub const PAR_UNIT: i128 = SCALE; // 1.0000000
+pub const BASIS_POINTS: i128 = 10_000; // 100% = 10,000 basis points
+pub const EMERGENCY_WITHDRAW_DELAY: u64 = 48 * 3600; // 48 hours
+pub const MAX_ADMIN_DELAY: u64 = 30 * 86_400; // Upper bound on the admin action timelock
+pub const MAX_KEEPER_FEE_BPS: u32 = 100; // Keeper fee on auto-redeem capped at 1%
+pub const MAX_SUBSCRIPTION_FEE_BPS: u32 = 500; // Subscription fee capped at 5%
+pub const MAX_REDEMPTION_FEE_BPS: u32 = 100; // Redemption fee capped at 1% of PAR payout
+pub const MAX_EXPORT_PAGE: u32 = 100; // Max entries returned per export call
+pub const MAX_OBSERVERS: u32 = 10; // Bounds the cost of notifying observers
+pub const RECEIPT_TTL_LEDGERS: u32 = 365 * 17_280; // Subscription receipts are extended ~1 year at write
+pub const STATE_TTL_THRESHOLD: u32 = 30 * 17_280; // Series, positions and account state are bumped once under ~30 days of TTL
+pub const STATE_TTL_LEDGERS: u32 = 120 * 17_280; // ...back up to ~120 days
+pub const SECONDS_PER_YEAR: u64 = 365 * 86_400; // Annualization basis for implied yields
+pub const YIELD_CURVE_TENORS: [u64; 4] = [30 * 86_400, 91 * 86_400, 182 * 86_400, 365 * 86_400]; // 1M/3M/6M/12M
+pub const YIELD_CHECKPOINT_INTERVAL: u64 = 86_400; // Minimum age before a checkpoint is refreshed
+pub const WITHDRAWAL_BUCKET_SECONDS: u64 = 3600; // Treasury withdrawals are tracked in hourly buckets
+pub const DAILY_WITHDRAWAL_BUCKETS: u64 = 24; // Rolling daily window
+pub const WEEKLY_WITHDRAWAL_BUCKETS: u64 = 7 * 24; // Rolling weekly window
 
 #[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum SeriesStatus {
     /// Series created but not yet active for subscriptions
     Upcoming = 0,
@@ -15,6 +34,12 @@ pub enum SeriesStatus {
     Matured = 2,
     /// Series ended (optional final state)
     Closed = 3,
+    /// Series withdrawn before maturity; subscribers claim refunds
+    Cancelled = 4,
+    /// Underlying failed to settle; series halted for off-chain resolution
+    Failed = 5,
+    /// Active series whose cap_par is fully minted; no further subscriptions
+    FullySubscribed = 6,
 }
 
 #[contracttype]
@@ -40,6 +65,11 @@ pub struct Series {
     pub status: SeriesStatus,
     /// Total USDC collected from subscriptions (for accounting)
     pub total_subscriptions_collected: i128,
+    /// PAR units redeemed (burned) at maturity or via redeem_early
+    pub redeemed_par: i128,
+    /// Authorizes activation, cap changes, cancellation and settlement
+    /// funding for this series (None = global treasury)
+    pub issuer: Option<Address>,
 }
 
 #[contracttype]
@@ -47,6 +77,193 @@ pub struct Series {
 pub struct UserPosition {
     /// Total PAR units subscribed by this user in this series
     pub subscribed_par: i128,
+    /// Total paid for those PAR units, in asset units (refunded on cancellation)
+    pub paid_amount: i128,
+}
+
+/// UserPosition keyed by its owner, as returned by `export_positions`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionSnapshot {
+    pub series_id: u32,
+    pub user: Address,
+    pub subscribed_par: i128,
+}
+
+/// Immutable record of a single subscription, keyed by a sequential ID
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionReceipt {
+    pub id: u64,
+    pub user: Address,
+    pub series_id: u32,
+    /// Stablecoin paid, in asset units (roll proceeds for rolls)
+    pub pay_amount: i128,
+    pub minted_par: i128,
+    /// Accreted price the subscription executed at
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// What a statement line records
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ActivityKind {
+    Subscription = 0,
+    Redemption = 1,
+    EarlyRedemption = 2,
+    Refund = 3,
+    TransferIn = 4,
+    TransferOut = 5,
+    RepoOpened = 6,
+    RepoClosed = 7,
+    RepoDefaulted = 8,
+}
+
+/// Repo market event reported into a borrower's statement
+///
+/// The repo market mirrors these discriminants; keep them stable.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RepoActivity {
+    Opened = 0,
+    Closed = 1,
+    Defaulted = 2,
+}
+
+/// One line of a user's statement, written when the action happens
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActivityRecord {
+    pub kind: ActivityKind,
+    pub series_id: u32,
+    /// bT-Bills minted, burned, moved or posted as repo collateral
+    pub par_amount: i128,
+    /// Stablecoin paid or received, in asset units (0 for transfers)
+    pub cash_amount: i128,
+    /// Payer on a subscription paid by someone else, transfer peer, or repo market
+    pub counterparty: Option<Address>,
+    /// Subscription receipt ID or repo position ID (0 = none)
+    pub reference: u64,
+    pub timestamp: u64,
+}
+
+/// Volume threshold that multiplies a user's per-series cap
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyTier {
+    /// Cumulative PAR subscribed (across all series) required to reach this tier
+    pub min_volume: i128,
+    /// Multiplier applied to user_cap_par in basis points (e.g., 20,000 = 2×)
+    pub cap_multiplier_bps: i128,
+}
+
+/// User's current standing in the loyalty tier schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserTier {
+    /// Cumulative PAR subscribed across all series
+    pub volume: i128,
+    /// 1-based tier index (0 = no tier reached)
+    pub tier: u32,
+    /// Multiplier currently applied to user_cap_par
+    pub cap_multiplier_bps: i128,
+}
+
+/// Low-risk action a user can delegate to a session key
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DelegatedAction {
+    /// Redeem matured bT-Bills (payout always goes to the user)
+    Redeem = 0,
+    /// Roll matured bT-Bills into another series (proceeds stay in the vault)
+    Roll = 1,
+}
+
+/// Scoped, time-bounded permission granted by a user to a delegate key
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delegation {
+    /// Actions the delegate may perform
+    pub actions: Vec<DelegatedAction>,
+    /// PAR the delegate may still act on (drawn down per action)
+    pub remaining_par: i128,
+    /// Unix timestamp after which the delegation is void
+    pub expires_at: u64,
+}
+
+/// Pre-approved bounds for series the treasury (issuer) may create alone
+///
+/// Series outside the envelope need the admin's co-signature.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuanceEnvelope {
+    /// Maximum discount to PAR at issue, in basis points (e.g., 500 = 5%)
+    pub max_discount_bps: i128,
+    /// Minimum maturity_date - issue_date, in seconds
+    pub min_tenor: u64,
+    /// Maximum maturity_date - issue_date, in seconds
+    pub max_tenor: u64,
+    /// Maximum cap_par per series
+    pub max_cap_par: i128,
+}
+
+/// Terms an approved issuer may create series under
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerLimits {
+    /// Maximum cap_par summed over the issuer's open series
+    pub max_outstanding_par: i128,
+    /// Minimum maturity_date - issue_date, in seconds
+    pub min_tenor: u64,
+    /// Maximum maturity_date - issue_date, in seconds
+    pub max_tenor: u64,
+    /// Settlement assets the issuer may take payment in
+    pub allowed_assets: Vec<Address>,
+}
+
+/// Issuer registry entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerProfile {
+    pub limits: IssuerLimits,
+    /// cap_par of the issuer's series not yet Closed or Cancelled
+    pub outstanding_par: i128,
+    /// False once revoked: existing series run on, but no new series or cap raises
+    pub approved: bool,
+}
+
+/// How fees earned on an issuer's series are split with the protocol
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerFeeSplit {
+    /// Issuer's share of subscription fees, in bps of the fee
+    pub subscription_share_bps: u32,
+    /// Issuer's share of repo spreads booked against its series, in bps of the spread
+    pub spread_share_bps: u32,
+}
+
+/// Fees earned on one issuer's series, by recipient and source
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IssuerFeeAccount {
+    pub issuer_subscription_fees: i128,
+    pub issuer_spread_fees: i128,
+    pub protocol_subscription_fees: i128,
+    pub protocol_spread_fees: i128,
+    /// Issuer fees not yet paid out by claim_issuer_fees
+    pub claimable: i128,
+    pub claimed: i128,
+}
+
+/// Keeper-executable action a user can opt into
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AutomationAction {
+    /// Redeem matured bT-Bills back to the user
+    AutoRedeem = 0,
+    /// Repay a repo position shortly before its deadline
+    AutoCloseRepo = 1,
 }
 
 /// Protocol-wide accounting for revenue tracking
@@ -63,24 +280,493 @@ pub struct ProtocolAccounting {
     pub total_subscriptions_collected: i128,
     /// Total PAR units minted (redemption liability at maturity)
     pub total_par_minted: i128,
-    /// USDC currently lent out via repo market
+    /// USDC currently lent out via repo market (record_loan less repaid or defaulted principal)
     pub total_lent: i128,
-    /// USDC received from repo repayments (principal + spread)
+    /// Repo spread revenue paid in via book_repo_spread, plus interest reported via record_repayment
     pub total_repo_revenue: i128,
-    /// Number of defaults (for analytics)
+    /// Repo defaults reported via record_default (for analytics)
     pub total_defaults: u32,
+    /// Total USDC paid out to holders on redemption (and cancellation refunds)
+    pub total_redeemed: i128,
+    /// USDC deposited via deposit_settlement (treasury, repo market, insurance fund)
+    pub total_settlement_deposits: i128,
+    /// Total USDC paid out to referrers via claim_referral_rewards
+    pub total_referral_rewards_paid: i128,
+    /// Total USDC swept out of the dust bucket via claim_dust
+    pub total_dust_claimed: i128,
+    /// Redemption payouts booked in total_redeemed but still waiting in the queue
+    pub queued_payouts: i128,
+    /// Early-exit penalties retained by the vault via redeem_early
+    pub total_early_penalties: i128,
+    /// Subscription proceeds swept to the treasury via withdraw_to_treasury
+    pub total_treasury_withdrawals: i128,
+    /// Subscription fees paid straight to the fee collector, net of referral rewards (never held by the vault)
+    pub total_subscription_fees: i128,
+    /// Redemption fees withheld from matured payouts (held until collect_fees)
+    pub total_redemption_fees: i128,
+    /// Protocol fees (redemption fees, protocol spread shares) paid out via collect_fees
+    pub total_fees_collected: i128,
+    /// Issuer shares of subscription fees, paid into the vault for claim_issuer_fees
+    pub total_issuer_fees_received: i128,
+    /// Issuer fees paid out via claim_issuer_fees
+    pub total_issuer_fees_claimed: i128,
+    /// Referral rewards withheld from subscription fees, held for claim_referral_rewards
+    pub total_referral_rewards_funded: i128,
+    /// Stablecoin paid out via emergency_withdraw
+    pub total_emergency_withdrawals: i128,
+}
+
+/// Subscription totals booked against one series
+///
+/// Subscriptions update this small per-series record instead of the global
+/// ProtocolAccounting; reads fold every tally back into the totals, and a
+/// series' tally is merged into them when it is archived.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SeriesTally {
+    pub subscriptions_collected: i128,
+    pub par_minted: i128,
+    pub subscription_fees: i128,
+    pub issuer_fees_received: i128,
+    pub referral_rewards_funded: i128,
+}
+
+/// Addresses nearly every call needs, packed into one entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaultConfig {
+    pub admin: Address,
+    pub treasury: Address,
+    pub stablecoin: Address,
+    pub bt_bill_token: Address,
+}
+
+/// Result of comparing internal accounting to the actual stablecoin balance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reconciliation {
+    /// Balance implied by ProtocolAccounting
+    pub expected_balance: i128,
+    /// Stablecoin balance actually held by the vault
+    pub actual_balance: i128,
+    /// actual - expected (positive = unexpected inflow, negative = unexpected outflow)
+    pub delta: i128,
+}
+
+/// Single-call view of the vault's ability to honor redemptions, in asset units
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Solvency {
+    /// Stablecoin balance actually held by the vault
+    pub actual_balance: i128,
+    /// Face value of every outstanding PAR unit plus queued payouts
+    pub outstanding_liability: i128,
+    /// Part of the liability already redeemable (matured series + queue)
+    pub matured_liability: i128,
+    /// Stablecoin returned by the treasury via fund_redemptions, all series
+    pub funded: i128,
+    /// actual_balance / outstanding_liability in bps (10,000 = fully covered)
+    pub health_ratio_bps: i128,
+}
+
+/// Rolling caps on withdraw_to_treasury, in asset units (0 = unlimited)
+///
+/// Withdrawals still succeed beyond a cap, but only with the admin's
+/// co-authorization.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalLimits {
+    /// Max withdrawn over the trailing 24 hours
+    pub daily_limit: i128,
+    /// Max withdrawn over the trailing 7 days
+    pub weekly_limit: i128,
+}
+
+/// Treasury withdrawals already made in each rolling window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalUsage {
+    pub daily_used: i128,
+    pub weekly_used: i128,
+    pub limits: WithdrawalLimits,
+}
+
+/// Subscription priced at the current ledger time, in asset units unless noted
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionQuote {
+    /// Gross amount paid by the user, fee included
+    pub pay_amount: i128,
+    /// Subscription fee skimmed off `pay_amount`
+    pub fee: i128,
+    /// Part of `fee` credited to the series issuer
+    pub issuer_fee: i128,
+    /// Accreted price (SCALE = 1.0 PAR)
+    pub price: i128,
+    /// PAR units minted for `pay_amount - fee`
+    pub minted_par: i128,
+}
+
+/// Matured redemption priced with the series' redemption fee, in asset units
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RedemptionQuote {
+    /// Face value of the redeemed PAR
+    pub gross: i128,
+    pub fee: i128,
+    /// gross - fee, paid to the holder
+    pub payout: i128,
+}
+
+/// Storage schema the stored data was written with
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageSchema {
+    pub version: u32,
+    /// DataKey variant → sha256 of the layout stored under it
+    pub layouts: Map<Symbol, BytesN<32>>,
+}
+
+/// How sub-threshold remainders are handled
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DustMode {
+    /// Fail any redemption that would leave a sub-threshold balance behind
+    Reject = 0,
+    /// Burn sub-threshold leftovers and book their value, plus subscription
+    /// rounding remainders, to a dust bucket the treasury can claim
+    Bucket = 1,
+}
+
+/// Dust policy, with `threshold` in settlement asset units
+///
+/// Subscriptions, redemptions and repo cash below the threshold are always
+/// rejected; `mode` decides what happens to sub-threshold remainders.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DustPolicy {
+    pub threshold: i128,
+    pub mode: DustMode,
+}
+
+/// Per-series cap on PAR minted within a rolling ledger window
+///
+/// Exceeding the cap halts further subscriptions to the series for
+/// `pause_ledgers`, guarding against mint storms and runaway scripts.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircuitBreaker {
+    /// Length of the volume window, in ledgers
+    pub window_ledgers: u32,
+    /// Max PAR minted within one window
+    pub max_window_par: i128,
+    /// How long subscriptions stay halted once tripped, in ledgers
+    pub pause_ledgers: u32,
+}
+
+/// Running circuit breaker window for a series
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BreakerState {
+    /// Ledger the current window opened at
+    pub window_start: u32,
+    /// PAR minted in the current window
+    pub window_par: i128,
+    /// Subscriptions are halted before this ledger (0 = never tripped)
+    pub tripped_until: u32,
+}
+
+/// Risk feature that can run in shadow mode
+///
+/// A shadowed feature is still evaluated, but its outcome is only emitted
+/// as a `shadow_outcome` event; the pre-feature behavior applies.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShadowFeature {
+    /// Subscription fee: nothing is skimmed; the event carries the would-be fee
+    SubscriptionFee = 0,
+    /// Redemption fee: PAR is paid in full; the event carries the would-be fee
+    RedemptionFee = 1,
+    /// Circuit breaker: halted subscriptions go through; the event carries their PAR
+    CircuitBreaker = 2,
+}
+
+/// Operational role split out of the admin key
+///
+/// Each role has at most one holder; while none is granted, the admin
+/// holds it.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// Protocol-wide and per-series pause, issuance freeze
+    Pauser = 0,
+    /// Series failure, early-exit terms, issuance envelope, circuit breaker, risk flags
+    SeriesManager = 1,
+    /// Subscription, redemption, keeper and referral fees, fee collector and issuer splits
+    FeeManager = 2,
+    /// Wasm upgrades and storage migrations
+    Upgrader = 3,
+}
+
+/// Per-series early exit terms for redeem_early
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EarlyRedemption {
+    /// Whether holders may exit before maturity
+    pub enabled: bool,
+    /// Haircut on the accreted value, in bps (max 10,000)
+    pub penalty_bps: u32,
+}
+
+/// Per-series maturity funding, in settlement asset units
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RedemptionFunding {
+    /// Cumulative stablecoin returned by the treasury via fund_redemptions
+    pub funded: i128,
+    /// Face value of every PAR unit minted for the series
+    pub required: i128,
+}
+
+/// Retail fast lane for redemptions, in settlement asset units
+///
+/// Payouts up to `threshold` are always paid immediately. Larger payouts are
+/// queued whenever paying them would leave less than `retail_reserve` in the
+/// vault, or while earlier large payouts are still queued.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RedemptionLane {
+    pub threshold: i128,
+    pub retail_reserve: i128,
+}
+
+/// Large redemption waiting for liquidity (bT-Bills already burned)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedRedemption {
+    /// Account the payout goes to (the holder unless redeemed to a recipient)
+    pub user: Address,
+    pub series_id: u32,
+    /// Payout owed, in asset units
+    pub amount: i128,
+    pub queued_at: u64,
+}
+
+/// Implied yield at one benchmark tenor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CurvePoint {
+    pub tenor_secs: u64,
+    /// Annualized simple yield, in basis points
+    pub yield_bps: i128,
+}
+
+/// Protocol yield curve ("Bingo Bill Rate") implied by active series prices
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldCurve {
+    pub timestamp: u64,
+    /// One point per YIELD_CURVE_TENORS entry (empty with no active series)
+    pub points: Vec<CurvePoint>,
+    /// Active series the curve was built from
+    pub series_count: u32,
+}
+
+/// Redemption liability of one series falling due within a forecast horizon
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaturityFlow {
+    pub series_id: u32,
+    pub maturity_date: u64,
+    /// Outstanding PAR, in settlement asset units
+    pub liability: i128,
+}
+
+/// Projected treasury flows up to `horizon_end`, in settlement asset units
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidityForecast {
+    pub horizon_end: u64,
+    /// Outflows: series maturing by the horizon, ordered by maturity date
+    pub maturities: Vec<MaturityFlow>,
+    pub total_maturing: i128,
+    /// Inflows: repo repurchases due to the treasury by the horizon
+    pub repo_repayments_due: i128,
+}
+
+/// Treasury-run batched settlement of a matured series
+///
+/// Batches cover contiguous ranges of the series holder list and are
+/// processed in announcement order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementPlan {
+    /// Holder index the next announced batch starts at
+    pub next_holder: u32,
+    /// Batches announced so far
+    pub announced: u32,
+    /// Batches processed so far
+    pub processed: u32,
+}
+
+/// Holder index range `[start, end)` settled together
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementBatch {
+    pub start: u32,
+    pub end: u32,
+    /// Treasury funding pulled in when processed, in asset units
+    pub funded: i128,
+    pub processed: bool,
+}
+
+/// Emergency withdrawal queued by admin, executable after the timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingEmergencyWithdraw {
+    pub token: Address,
+    pub amount: i128,
+    pub to: Address,
+    /// Earliest timestamp at which the withdrawal can execute
+    pub executable_at: u64,
+}
+
+/// Risk assessment attached to a series, read by the repo market
+///
+/// The repo market mirrors these discriminants; keep them stable.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RiskFlag {
+    /// Series or its underlying is under review; repo haircuts rise
+    UnderReview = 0,
+    /// Issuer credit impaired; no new repo borrowing against the series
+    ImpairedIssuer = 1,
+}
+
+/// Sensitive admin change that runs through the admin timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    /// Move the treasury role (admin)
+    SetTreasury(Address),
+    /// Default subscription fee in bps (fee manager)
+    SetSubscriptionFee(u32),
+    /// Default redemption fee in bps (fee manager)
+    SetRedemptionFee(u32),
+    /// Replace the contract wasm (upgrader)
+    Upgrade(BytesN<32>),
+    /// Change the timelock itself, in seconds (admin)
+    SetAdminDelay(u64),
+    /// Per-series redemption fee override, None clears it (fee manager)
+    SetSeriesRedemptionFee(u32, Option<u32>),
+    /// Address receiving subscription and protocol fees (fee manager)
+    SetFeeCollector(Address),
+    /// Keeper fee on auto_redeem proceeds in bps (fee manager)
+    SetKeeperFee(u32),
+}
+
+/// Admin action waiting out the timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedAdminAction {
+    pub action: AdminAction,
+    pub queued_at: u64,
+    /// Earliest timestamp at which the action can execute
+    pub executable_at: u64,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
-    Admin,
-    Treasury,
-    Stablecoin,
-    BTBillToken,
-    Series(u32),
-    UserPosition(u32, Address), // (series_id, user)
-    ProtocolAccounting,         // NEW: Global accounting
+    Admin,                      // Legacy: folded into DataKeyExt::Config on first config write
+    PendingAdmin,               // Proposed admin awaiting accept_admin
+    Treasury,                   // Legacy, like Admin
+    Stablecoin,                 // Legacy, like Admin
+    BTBillToken,                // Legacy, like Admin
+    Series(u32),                // Persistent; instance on pre-migration deployments
+    SeriesIds,                  // Vec<u32> of every created series; persistent (instance before migration)
+    UserPosition(u32, Address), // (series_id, user), persistent like Series
+    SeriesHolders(u32),         // Legacy instance holder index, superseded by DataKeyExt::SeriesHolder
+    ProtocolAccounting,         // Global accounting; persistent (instance before schema v6)
+    PendingEmergencyWithdraw,
+    SettlementAllowlistEnabled,
+    SettlementSender(Address),  // Registered settlement sender → bool
+    UnexpectedInflowsSwept,     // Cumulative unexpected inflows swept out
+    ReferralRewardBps,          // Reward paid to referrers, bps of the protocol subscription fee
+    Referrer(Address),          // user → referrer (first-touch, immutable, persistent)
+    ReferralRewards(Address),   // referrer → unclaimed rewards (persistent)
+    LoyaltyTiers,               // Vec<LoyaltyTier>, ascending by min_volume
+    UserVolume(Address),        // user → cumulative PAR subscribed (persistent)
+    RewardsHook,                // External incentives contract notified on user actions
+    MetaNonce(Address),         // user → next subscribe_for nonce (persistent)
+    Delegation(Address, Address), // (user, delegate) → Delegation (persistent)
+    RepoMarket,                 // repo_market contract used for AutoCloseRepo
+    Automation(Address, AutomationAction), // (user, action) → remaining allowance (persistent)
+    KeeperFeeBps,               // Fee paid to keepers from auto_redeem proceeds
     Initialized,
     Paused,
+    IssuanceFrozen,             // Blocks create/activate series only
+    IssuanceEnvelope,           // Bounds for issuer-only series creation
+    ArchivedSeries(u32),        // Pruned series → final state hash (persistent tombstone)
+    Observers,                  // Vec<Address> notified on lifecycle events
+    AssetDecimals,              // Settlement asset decimals (defaults to SCALE_DECIMALS)
+    DustPolicy,                 // DustPolicy applied to subscribe/redeem
+    DustBucket,                 // Unclaimed dust, in asset units
+    SubscriptionCounter,        // Last issued subscription receipt ID
+    SubscriptionReceipt(u64),   // Receipt ID → SubscriptionReceipt (persistent)
+    UserReceipts(Address),      // user → Vec<u64> receipt IDs, oldest first (persistent)
+    CircuitBreaker(u32),        // series_id → CircuitBreaker
+    BreakerState(u32),          // series_id → BreakerState
+    LargeSubscriptionPar,       // Observer alert threshold in minted PAR (0 = off)
+    SettlementPlan(u32),        // series_id → SettlementPlan (present while settling)
+    SettlementBatch(u32, u32),  // (series_id, batch_id) → SettlementBatch
+    RedemptionLane,             // RedemptionLane (absent = every redemption paid immediately)
+    RedemptionQueue,            // Vec<QueuedRedemption>, oldest first
+    YieldCurveCheckpoint,       // Last YieldCurve published by checkpoint_yield_curve
+    EarlyRedemption(u32),       // series_id → EarlyRedemption (absent = disabled)
+    RedemptionFunded(u32),      // series_id → stablecoin funded via fund_redemptions
+    SubscriptionFeeBps,         // Fee on subscribe, bps of pay_amount (absent = 0)
+    FeeCollector,               // Receives subscription fees (absent = treasury)
+    StorageSchema,              // StorageSchema (persistent) + its version (instance)
+}
+
+/// Keys added once DataKey reached the 50-variant contracttype limit
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKeyExt {
+    RedemptionFeeBps, // Default fee on matured redemptions, bps of payout (absent = 0)
+    SeriesRedemptionFeeBps(u32), // series_id → redemption fee override (absent = default)
+    AccruedFees,      // Protocol fees (redemption fees, spread shares) awaiting collect_fees
+    Issuer(Address),  // issuer → IssuerProfile (absent = not onboarded)
+    UserSeries(Address), // user → Vec<u32> series still held, first-subscription order (persistent)
+    IssuerFeeSplit(Address), // issuer → IssuerFeeSplit (absent = protocol keeps every fee)
+    IssuerFees(Address), // issuer → IssuerFeeAccount
+    AuthNonce(Address), // admin/treasury signer → next nonce for nonce-guarded calls (persistent)
+    ShadowMode(ShadowFeature), // feature → true while evaluated but not enforced (absent = enforced)
+    SeriesPaused(u32), // series_id → true while its subscriptions/redemptions are halted (absent = running)
+    AllowancePull,    // true: subscribe pulls payment with transfer_from (absent = transfer)
+    MinSubscription(u32), // series_id → minimum pay_amount in asset units (absent = none)
+    PayoutConverter(Address), // payout asset → converter contract swapping stablecoin into it
+    SeriesPayoutAssets(u32), // series_id → Vec<Address> alternative payout assets (absent = stablecoin only)
+    CapExempt(Address), // account → true when subscriptions skip user_cap_par (absent = capped, persistent)
+    KycRegistry, // External registry answering is_kyc_verified(account) (absent = allowlist only)
+    KycVerified(Address), // account → true when on the internal KYC allowlist (persistent)
+    SeriesKycRequired(u32), // series_id → true when subscribe/redeem need a KYC'd user (absent = open)
+    Blocked(Address), // account → true while on the sanctions blocklist (absent = allowed, persistent)
+    RoleHolder(Role), // role → account holding it (absent = admin)
+    Config,           // VaultConfig (absent = legacy Admin/Treasury/Stablecoin/BTBillToken keys)
+    SeriesTally(u32), // series_id → SeriesTally of its subscriptions (persistent)
+    AdminDelay,       // Timelock in seconds on AdminAction changes (absent = 0, applied directly)
+    NextAdminActionId, // Next queued admin action ID
+    AdminActionQueue(u64), // action_id → QueuedAdminAction awaiting execute or cancel
+    SeriesRiskFlags(u32), // series_id → Vec<RiskFlag> (absent = none)
+    UserActivityCount(Address), // user → statement lines written (persistent)
+    UserActivity(Address, u32), // (user, index) → ActivityRecord, oldest first (persistent)
+    ReportedRepoRevenue, // Part of total_repo_revenue reported by the repo market (never held by the vault)
+    SeriesHolderCount(u32), // series_id → subscribers indexed so far (persistent)
+    SeriesHolder(u32, u32), // (series_id, index) → subscriber, first-subscription order (persistent)
+    WithdrawalLimits, // WithdrawalLimits on withdraw_to_treasury (absent = unlimited)
+    WithdrawalOutflows, // Map<u64, i128>: hourly bucket → withdrawn to treasury
+    OutstandingPar, // PAR minted and not yet burned, every series (rebuilt by migrate_schema)
+    MaturedPar, // Part of OutstandingPar in Matured or Closed series
 }
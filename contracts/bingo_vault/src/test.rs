@@ -0,0 +1,3092 @@
+#![cfg(test)]
+
+use super::*;
+use crate::schema::STORAGE_LAYOUTS;
+use crate::storage::{EMERGENCY_WITHDRAW_DELAY, SCALE};
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Events, Ledger},
+    token::StellarAssetClient,
+    Address, Env, TryFromVal,
+};
+
+/// Minimal stand-in for bt_bill_token (keeps vault unit tests self-contained)
+#[contract]
+pub struct MockBTBillToken;
+
+#[contractimpl]
+impl MockBTBillToken {
+    pub fn mint(env: Env, series_id: u32, to: Address, amount: i128) {
+        let key = (series_id, to);
+        let balance: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(balance + amount));
+    }
+
+    pub fn burn(env: Env, series_id: u32, from: Address, amount: i128) {
+        let key = (series_id, from);
+        let balance: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        if balance < amount {
+            panic!("insufficient balance");
+        }
+        env.storage().instance().set(&key, &(balance - amount));
+    }
+
+    pub fn balance_of(env: Env, series_id: u32, user: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&(series_id, user))
+            .unwrap_or(0)
+    }
+}
+
+/// Incentives contract stand-in that records every action it is notified of
+#[contract]
+pub struct MockRewardsHook;
+
+#[contractimpl]
+impl MockRewardsHook {
+    pub fn on_action(env: Env, user: Address, action: Symbol, notional: i128) {
+        let mut actions: Vec<(Address, Symbol, i128)> = env
+            .storage()
+            .instance()
+            .get(&0u32)
+            .unwrap_or(Vec::new(&env));
+        actions.push_back((user, action, notional));
+        env.storage().instance().set(&0u32, &actions);
+    }
+
+    pub fn actions(env: Env) -> Vec<(Address, Symbol, i128)> {
+        env.storage()
+            .instance()
+            .get(&0u32)
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+/// Auto-roll fund stand-in that records every vault event it observes
+#[contract]
+pub struct MockObserver;
+
+#[contractimpl]
+impl MockObserver {
+    pub fn on_vault_event(env: Env, event: Symbol, series_id: u32, amount: i128) {
+        let mut events: Vec<(Symbol, u32, i128)> = env
+            .storage()
+            .instance()
+            .get(&0u32)
+            .unwrap_or(Vec::new(&env));
+        events.push_back((event, series_id, amount));
+        env.storage().instance().set(&0u32, &events);
+    }
+
+    pub fn events(env: Env) -> Vec<(Symbol, u32, i128)> {
+        env.storage()
+            .instance()
+            .get(&0u32)
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+/// Fee-on-transfer stablecoin stand-in that burns 1% of every transfer
+#[contract]
+pub struct MockDeflationaryToken;
+
+#[contractimpl]
+impl MockDeflationaryToken {
+    pub fn credit(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().instance().set(&to, &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().instance().get(&id).unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        let from_balance = Self::balance(env.clone(), from.clone());
+        env.storage()
+            .instance()
+            .set(&from, &(from_balance - amount));
+        Self::credit(env, to, amount - amount / 100);
+    }
+}
+
+/// Payout converter stand-in paying 0.99 of `to` per unit of `from` out of its own balance
+#[contract]
+pub struct MockConverter;
+
+#[contractimpl]
+impl MockConverter {
+    pub fn convert(
+        env: Env,
+        _from: Address,
+        to: Address,
+        amount_in: i128,
+        _min_out: i128,
+        recipient: Address,
+    ) -> i128 {
+        let amount_out = amount_in * 99 / 100;
+        token::Client::new(&env, &to).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount_out,
+        );
+        amount_out
+    }
+}
+
+/// External KYC registry stand-in with a settable verified set
+#[contract]
+pub struct MockKycRegistry;
+
+#[contractimpl]
+impl MockKycRegistry {
+    pub fn set_verified(env: Env, account: Address, verified: bool) {
+        env.storage().instance().set(&account, &verified);
+    }
+
+    pub fn is_kyc_verified(env: Env, account: Address) -> bool {
+        env.storage().instance().get(&account).unwrap_or(false)
+    }
+}
+
+/// Repo market stand-in with a fixed repayment schedule
+#[contract]
+pub struct MockRepoMarket;
+
+#[contractimpl]
+impl MockRepoMarket {
+    pub fn repayments_due(_env: Env, _vault: Address, until: u64) -> i128 {
+        if until >= MATURITY_DATE {
+            250 * SCALE
+        } else {
+            0
+        }
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    treasury: Address,
+    user: Address,
+    stablecoin: StellarAssetClient<'a>,
+    vault: BingoVaultClient<'a>,
+}
+
+const ISSUE_DATE: u64 = 1_000;
+const MATURITY_DATE: u64 = 2_000;
+const ISSUE_PRICE: i128 = 98 * SCALE / 100; // 0.98
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(ISSUE_DATE);
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let stablecoin_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let stablecoin = StellarAssetClient::new(&env, &stablecoin_id);
+    stablecoin.mint(&user, &(1_000_000 * SCALE));
+    stablecoin.mint(&treasury, &(1_000_000 * SCALE));
+
+    let bt_bill_token = env.register(MockBTBillToken, ());
+
+    let vault_id = env.register(BingoVault, ());
+    let vault = BingoVaultClient::new(&env, &vault_id);
+    vault.initialize(&admin, &treasury, &stablecoin_id, &bt_bill_token);
+
+    Setup {
+        env,
+        admin,
+        treasury,
+        user,
+        stablecoin,
+        vault,
+    }
+}
+
+/// Expiry passed to nonce-guarded admin and treasury calls
+const AUTH_EXPIRY: u64 = 1_000_000;
+
+/// Nonce the next nonce-guarded call signed by `signer` must carry
+fn nonce(s: &Setup, signer: &Address) -> u64 {
+    s.vault.get_auth_nonce(signer)
+}
+
+fn create_active_series(s: &Setup, series_id: u32) {
+    s.vault.create_series(
+        &series_id,
+        &ISSUE_DATE,
+        &MATURITY_DATE,
+        &ISSUE_PRICE,
+        &(1_000_000 * SCALE),
+        &(100_000 * SCALE),
+        &None,
+    );
+    s.vault.activate_series(&series_id);
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let s = setup();
+    let new_admin = Address::generate(&s.env);
+
+    assert_eq!(s.vault.try_accept_admin(), Err(Ok(Error::Unauthorized)));
+
+    s.vault
+        .propose_admin(&new_admin, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    assert_eq!(s.vault.get_admin(), s.admin);
+    assert_eq!(s.vault.get_pending_admin(), Some(new_admin.clone()));
+
+    s.vault.accept_admin();
+    assert_eq!(
+        s.env.auths()[0].0,
+        new_admin,
+        "accept_admin must be authorized by the proposed admin"
+    );
+    assert_eq!(s.vault.get_admin(), new_admin);
+    assert_eq!(s.vault.get_pending_admin(), None);
+}
+
+#[test]
+fn test_set_treasury_moves_series_auth() {
+    let s = setup();
+    let custody = Address::generate(&s.env);
+
+    s.vault
+        .set_treasury(&custody, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    assert_eq!(s.vault.get_treasury(), custody);
+
+    create_active_series(&s, 1);
+    let auths = s.env.auths();
+    assert_eq!(auths[0].0, custody);
+    assert!(!auths.iter().any(|(addr, _)| *addr == s.treasury));
+}
+
+#[test]
+fn test_admin_calls_bound_to_nonce_and_expiry() {
+    let s = setup();
+    s.vault.set_subscription_fee_bps(&50, &0, &AUTH_EXPIRY);
+    assert_eq!(nonce(&s, &s.admin), 1);
+
+    // A captured invocation can't be replayed...
+    assert_eq!(
+        s.vault.try_set_subscription_fee_bps(&50, &0, &AUTH_EXPIRY),
+        Err(Ok(Error::InvalidAuthNonce))
+    );
+
+    // ...or executed once its expiry has passed
+    s.env.ledger().set_timestamp(AUTH_EXPIRY + 1);
+    assert_eq!(
+        s.vault.try_set_subscription_fee_bps(&0, &1, &AUTH_EXPIRY),
+        Err(Ok(Error::InvalidAuthNonce))
+    );
+    s.vault.set_subscription_fee_bps(&0, &1, &(AUTH_EXPIRY + 1));
+    assert_eq!(s.vault.get_subscription_fee_bps(), 0);
+
+    // Each signer has its own sequence
+    assert_eq!(nonce(&s, &s.admin), 2);
+    assert_eq!(nonce(&s, &s.treasury), 0);
+
+    // Role grants, admin proposals and treasury sweeps are bound the same way
+    let pauser = Address::generate(&s.env);
+    let live = AUTH_EXPIRY + 1;
+    s.vault.grant_role(&Role::Pauser, &pauser, &2, &live);
+    assert_eq!(
+        s.vault.try_revoke_role(&Role::Pauser, &2, &live),
+        Err(Ok(Error::InvalidAuthNonce))
+    );
+    assert_eq!(
+        s.vault.try_propose_admin(&pauser, &3, &AUTH_EXPIRY),
+        Err(Ok(Error::InvalidAuthNonce))
+    );
+    assert_eq!(
+        s.vault.try_collect_fees(&1, &live),
+        Err(Ok(Error::InvalidAuthNonce))
+    );
+    assert_eq!(
+        s.vault.try_claim_dust(&s.treasury, &0, &live),
+        Err(Ok(Error::NothingToSweep))
+    );
+    assert_eq!(nonce(&s, &s.admin), 3);
+    assert_eq!(nonce(&s, &s.treasury), 0);
+    s.env.as_contract(&s.vault.address, || {
+        let key = DataKeyExt::AuthNonce(s.admin.clone());
+        assert!(s.env.storage().persistent().has(&key));
+        assert!(!s.env.storage().instance().has(&key));
+    });
+}
+
+#[test]
+fn test_series_issuer_scopes_admin_actions() {
+    let s = setup();
+    let issuer = Address::generate(&s.env);
+    s.stablecoin.mint(&issuer, &(1_000 * SCALE));
+    s.vault
+        .approve_issuer(&issuer, &issuer_limits(&s, 1_000_000 * SCALE));
+
+    s.vault.create_series(
+        &1,
+        &ISSUE_DATE,
+        &MATURITY_DATE,
+        &ISSUE_PRICE,
+        &(1_000_000 * SCALE),
+        &(100_000 * SCALE),
+        &Some(issuer.clone()),
+    );
+    let auths = s.env.auths();
+    assert!(auths.iter().any(|(addr, _)| *addr == s.treasury));
+    assert!(auths.iter().any(|(addr, _)| *addr == issuer));
+    assert_eq!(s.vault.get_series_issuer(&1), issuer);
+
+    s.vault.activate_series(&1);
+    let auths = s.env.auths();
+    assert_eq!(auths[0].0, issuer);
+    assert!(!auths.iter().any(|(addr, _)| *addr == s.treasury));
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault
+        .set_series_caps(&1, &(2_000 * SCALE), &(1_500 * SCALE));
+    assert_eq!(s.env.auths()[0].0, issuer);
+    let series = s.vault.get_series(&1);
+    assert_eq!(
+        (series.cap_par, series.user_cap_par),
+        (2_000 * SCALE, 1_500 * SCALE)
+    );
+    assert_eq!(
+        s.vault
+            .try_set_series_caps(&1, &(999 * SCALE), &(999 * SCALE)),
+        Err(Ok(Error::InvalidCapAmounts))
+    );
+
+    // Settlement funding comes out of the issuer, not the treasury
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    s.vault.fund_redemptions(&1, &(1_000 * SCALE));
+    assert_eq!(s.env.auths()[0].0, issuer);
+    assert_eq!(stablecoin.balance(&issuer), 0);
+
+    // Series without an issuer stay with the treasury
+    create_active_series(&s, 2);
+    assert_eq!(s.vault.get_series_issuer(&2), s.treasury);
+    s.vault.cancel_series(&2);
+    assert_eq!(s.env.auths()[0].0, s.treasury);
+}
+
+#[test]
+fn test_pause_series_halts_only_that_series() {
+    let s = setup();
+    create_active_series(&s, 1);
+    create_active_series(&s, 2);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+
+    s.vault.pause_series(&1);
+    assert!(s.vault.is_series_paused(&1));
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(980 * SCALE), &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    s.vault.subscribe(&s.user, &2, &(980 * SCALE), &None);
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.deposit_settlement(&s.treasury, &(40 * SCALE));
+    assert_eq!(
+        s.vault.try_redeem(&s.user, &1, &(1_000 * SCALE), &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    s.vault.redeem(&s.user, &2, &(1_000 * SCALE), &None);
+
+    s.vault.unpause_series(&1);
+    assert!(!s.vault.is_series_paused(&1));
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+    assert_eq!(s.vault.try_pause_series(&3), Err(Ok(Error::SeriesNotFound)));
+}
+
+#[test]
+fn test_min_subscription_rejects_dust_positions() {
+    let s = setup();
+    create_active_series(&s, 1);
+    assert_eq!(s.vault.get_min_subscription(&1), 0);
+
+    s.vault.set_min_subscription(&1, &(100 * SCALE));
+    assert_eq!(s.env.auths()[0].0, s.treasury);
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(99 * SCALE), &None),
+        Err(Ok(Error::BelowMinimum))
+    );
+    s.vault.subscribe(&s.user, &1, &(100 * SCALE), &None);
+
+    s.vault.set_min_subscription(&1, &0);
+    s.vault.subscribe(&s.user, &1, &SCALE, &None);
+    assert_eq!(
+        s.vault.try_set_min_subscription(&1, &-1),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_update_series_caps_only_raises_live_caps() {
+    let s = setup();
+    s.vault.create_series(
+        &1,
+        &ISSUE_DATE,
+        &MATURITY_DATE,
+        &ISSUE_PRICE,
+        &(1_000 * SCALE),
+        &(1_000 * SCALE),
+        &None,
+    );
+    assert_eq!(
+        s.vault
+            .try_update_series_caps(&1, &(2_000 * SCALE), &(1_000 * SCALE)),
+        Err(Ok(Error::InvalidStatus))
+    );
+
+    s.vault.activate_series(&1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::FullySubscribed);
+
+    // An oversubscribed issue grows in place and reopens
+    s.vault
+        .update_series_caps(&1, &(3_000 * SCALE), &(1_500 * SCALE));
+    assert_eq!(s.env.auths()[0].0, s.treasury);
+    let series = s.vault.get_series(&1);
+    assert_eq!(
+        (series.cap_par, series.user_cap_par, series.status),
+        (3_000 * SCALE, 1_500 * SCALE, SeriesStatus::Active)
+    );
+
+    assert_eq!(
+        s.vault
+            .try_update_series_caps(&1, &(2_500 * SCALE), &(1_500 * SCALE)),
+        Err(Ok(Error::InvalidCapAmounts))
+    );
+    assert_eq!(
+        s.vault
+            .try_update_series_caps(&1, &(3_000 * SCALE), &(1_200 * SCALE)),
+        Err(Ok(Error::InvalidCapAmounts))
+    );
+}
+
+#[test]
+fn test_series_closes_when_cap_fills() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault
+        .set_series_caps(&1, &(1_500 * SCALE), &(1_500 * SCALE));
+
+    s.vault.subscribe(&s.user, &1, &(490 * SCALE), &None);
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::Active);
+
+    // The subscription minting the last PAR closes the series
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    let filled = Symbol::new(&s.env, "series_fully_subscribed");
+    assert!(s.env.events().all().iter().any(|(_, topics, _)| {
+        topics
+            .get(2)
+            .is_some_and(|topic| Symbol::try_from_val(&s.env, &topic) == Ok(filled.clone()))
+    }));
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::FullySubscribed);
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(SCALE), &None),
+        Err(Ok(Error::SeriesFullySubscribed))
+    );
+
+    // Raising the cap reopens it; the series still matures as usual
+    s.vault
+        .set_series_caps(&1, &(2_000 * SCALE), &(2_000 * SCALE));
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::Active);
+    s.vault.subscribe(&s.user, &1, &(490 * SCALE), &None);
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::FullySubscribed);
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.mature_series(&1);
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::Matured);
+}
+
+fn issuer_limits(s: &Setup, max_outstanding_par: i128) -> IssuerLimits {
+    IssuerLimits {
+        max_outstanding_par,
+        min_tenor: 500,
+        max_tenor: 5_000,
+        allowed_assets: vec![&s.env, s.stablecoin.address.clone()],
+    }
+}
+
+#[test]
+fn test_issuer_registry_bounds_series() {
+    let s = setup();
+    let issuer = Address::generate(&s.env);
+    let create = |series_id: u32, maturity_date: u64, cap_par: i128| {
+        s.vault.try_create_series(
+            &series_id,
+            &ISSUE_DATE,
+            &maturity_date,
+            &ISSUE_PRICE,
+            &cap_par,
+            &cap_par,
+            &Some(issuer.clone()),
+        )
+    };
+
+    // Not onboarded yet
+    assert_eq!(
+        create(1, MATURITY_DATE, 600 * SCALE),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        s.vault.try_approve_issuer(&issuer, &issuer_limits(&s, 0)),
+        Err(Ok(Error::InvalidConfig))
+    );
+    s.vault
+        .approve_issuer(&issuer, &issuer_limits(&s, 1_000 * SCALE));
+
+    // Tenor outside 500..=5,000 seconds
+    assert_eq!(
+        create(1, ISSUE_DATE + 10_000, 600 * SCALE),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    create(1, MATURITY_DATE, 600 * SCALE).unwrap().unwrap();
+    assert_eq!(
+        create(2, MATURITY_DATE, 500 * SCALE),
+        Err(Ok(Error::InvalidCapAmounts))
+    );
+    assert_eq!(
+        s.vault
+            .try_set_series_caps(&1, &(1_100 * SCALE), &(100 * SCALE)),
+        Err(Ok(Error::InvalidCapAmounts))
+    );
+    assert_eq!(
+        s.vault.get_issuer(&issuer).unwrap().outstanding_par,
+        600 * SCALE
+    );
+
+    // Cancelling releases the series' cap
+    s.vault.cancel_series(&1);
+    assert_eq!(s.vault.get_issuer(&issuer).unwrap().outstanding_par, 0);
+    create(2, MATURITY_DATE, 1_000 * SCALE).unwrap().unwrap();
+
+    // Payment asset not allowed
+    let mut limits = issuer_limits(&s, 2_000 * SCALE);
+    limits.allowed_assets = vec![&s.env, Address::generate(&s.env)];
+    s.vault.approve_issuer(&issuer, &limits);
+    assert_eq!(
+        create(3, MATURITY_DATE, 100 * SCALE),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // Revoked issuers keep running existing series only
+    s.vault.revoke_issuer(&issuer);
+    s.vault.activate_series(&2);
+    assert_eq!(
+        s.vault
+            .try_set_series_caps(&2, &(1_100 * SCALE), &(100 * SCALE)),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // The treasury itself is never limited
+    create_active_series(&s, 4);
+}
+
+#[test]
+fn test_liquidity_forecast_orders_maturities() {
+    let s = setup();
+    s.vault.create_series(
+        &1,
+        &ISSUE_DATE,
+        &(MATURITY_DATE + 1_000),
+        &ISSUE_PRICE,
+        &(1_000_000 * SCALE),
+        &(100_000 * SCALE),
+        &None,
+    );
+    s.vault.activate_series(&1);
+    create_active_series(&s, 2);
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.subscribe(&s.user, &2, &(490 * SCALE), &None);
+
+    let near = s.vault.liquidity_forecast(&(MATURITY_DATE - ISSUE_DATE));
+    assert_eq!(near.maturities.len(), 1);
+    assert_eq!(near.total_maturing, 500 * SCALE);
+    assert_eq!(near.repo_repayments_due, 0);
+
+    let repo_market = s.env.register(MockRepoMarket, ());
+    s.vault.set_repo_market(&repo_market);
+
+    let horizon = MATURITY_DATE + 1_000 - ISSUE_DATE;
+    let far = s.vault.liquidity_forecast(&horizon);
+    assert_eq!(far.horizon_end, MATURITY_DATE + 1_000);
+    assert_eq!(far.maturities.get(0).unwrap().series_id, 2);
+    assert_eq!(far.maturities.get(1).unwrap().series_id, 1);
+    assert_eq!(far.total_maturing, 1_500 * SCALE);
+    assert_eq!(far.repo_repayments_due, 250 * SCALE);
+}
+
+#[test]
+fn test_yield_curve_interpolates_active_series() {
+    const DAY: u64 = 86_400;
+    let s = setup();
+    for (series_id, tenor, issue_price) in [
+        (1u32, 30 * DAY, 99 * SCALE / 100),
+        (2u32, 182 * DAY, ISSUE_PRICE),
+    ] {
+        s.vault.create_series(
+            &series_id,
+            &ISSUE_DATE,
+            &(ISSUE_DATE + tenor),
+            &issue_price,
+            &(1_000_000 * SCALE),
+            &(100_000 * SCALE),
+            &None,
+        );
+        s.vault.activate_series(&series_id);
+    }
+
+    let curve = s.vault.yield_curve();
+    assert_eq!(curve.series_count, 2);
+    assert_eq!(curve.points.len(), 4);
+    let one_month = curve.points.get(0).unwrap().yield_bps;
+    let three_month = curve.points.get(1).unwrap().yield_bps;
+    let six_month = curve.points.get(2).unwrap().yield_bps;
+    assert_eq!(one_month, 1_228); // 1% discount over 30 days
+    assert_eq!(six_month, 409); // 2% discount over 182 days
+    assert!(three_month < one_month && three_month > six_month);
+    assert_eq!(curve.points.get(3).unwrap().yield_bps, six_month);
+
+    let checkpoint = s.vault.checkpoint_yield_curve();
+    assert_eq!(
+        s.vault.get_yield_curve_checkpoint(),
+        Some(checkpoint.clone())
+    );
+
+    s.env.ledger().set_timestamp(ISSUE_DATE + DAY / 2);
+    assert_eq!(s.vault.checkpoint_yield_curve(), checkpoint);
+    s.env.ledger().set_timestamp(ISSUE_DATE + DAY);
+    assert_eq!(s.vault.checkpoint_yield_curve().timestamp, ISSUE_DATE + DAY);
+}
+
+#[test]
+fn test_migrate_series_storage_moves_instance_entries() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let other = Address::generate(&s.env);
+    s.stablecoin.mint(&other, &(10_000 * SCALE));
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.subscribe(&other, &1, &(490 * SCALE), &None);
+
+    let series_key = DataKey::Series(1);
+    let user_key = DataKey::UserPosition(1, s.user.clone());
+    let other_key = DataKey::UserPosition(1, other.clone());
+
+    // Simulate a pre-migration deployment: series, series ID list, holder
+    // list and positions all in instance storage
+    s.env.as_contract(&s.vault.address, || {
+        let storage = s.env.storage();
+        let series: Series = storage.persistent().get(&series_key).unwrap();
+        let series_ids: Vec<u32> = storage.persistent().get(&DataKey::SeriesIds).unwrap();
+        storage.persistent().remove(&series_key);
+        storage.persistent().remove(&DataKey::SeriesIds);
+        storage.instance().set(&series_key, &series);
+        storage.instance().set(&DataKey::SeriesIds, &series_ids);
+        for key in [user_key.clone(), other_key.clone()] {
+            let position: UserPosition = storage.persistent().get(&key).unwrap();
+            storage.persistent().remove(&key);
+            storage.instance().set(&key, &position);
+        }
+        storage.persistent().remove(&DataKeyExt::SeriesHolder(1, 0));
+        storage.persistent().remove(&DataKeyExt::SeriesHolder(1, 1));
+        storage.persistent().remove(&DataKeyExt::SeriesHolderCount(1));
+        storage.instance().set(
+            &DataKey::SeriesHolders(1),
+            &vec![&s.env, s.user.clone(), other.clone()],
+        );
+    });
+
+    // Legacy entries stay readable before migration
+    assert_eq!(
+        s.vault.get_user_position(&1, &s.user).subscribed_par,
+        1_000 * SCALE
+    );
+    assert_eq!(s.vault.export_positions(&1, &0, &10).len(), 2);
+    assert_eq!(s.vault.get_series_page(&0, &10).len(), 1);
+
+    // One holder per page: the legacy list goes once the last page is copied
+    assert_eq!(s.vault.migrate_series_storage(&1, &0, &1), 1);
+    s.env.as_contract(&s.vault.address, || {
+        let storage = s.env.storage();
+        assert!(storage.persistent().has(&series_key));
+        assert!(!storage.instance().has(&series_key));
+        assert!(storage.persistent().has(&DataKey::SeriesIds));
+        assert!(!storage.instance().has(&DataKey::SeriesIds));
+        assert!(storage.persistent().has(&user_key));
+        assert!(storage.instance().has(&other_key));
+        assert!(storage.instance().has(&DataKey::SeriesHolders(1)));
+    });
+    assert_eq!(s.vault.migrate_series_storage(&1, &1, &1), 1);
+    s.env.as_contract(&s.vault.address, || {
+        let storage = s.env.storage();
+        assert!(storage.persistent().has(&other_key));
+        assert!(!storage.instance().has(&other_key));
+        assert!(!storage.instance().has(&DataKey::SeriesHolders(1)));
+        assert!(storage.persistent().has(&DataKeyExt::SeriesHolder(1, 1)));
+    });
+
+    assert_eq!(s.vault.migrate_series_storage(&1, &2, &10), 0);
+    assert_eq!(s.vault.extend_series_ttl(&1, &0, &10), 2);
+    assert_eq!(s.vault.extend_series_ttl(&1, &2, &10), 0);
+    assert_eq!(s.vault.get_series(&1).minted_par, 1_500 * SCALE);
+    let page = s.vault.export_positions(&1, &0, &10);
+    assert_eq!(page.get(1).unwrap().user, other);
+}
+
+#[test]
+fn test_redeem_early_pays_accreted_price_less_penalty() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+
+    assert_eq!(
+        s.vault.try_redeem_early(&s.user, &1, &(500 * SCALE)),
+        Err(Ok(Error::EarlyRedemptionDisabled))
+    );
+    let terms = EarlyRedemption {
+        enabled: true,
+        penalty_bps: 10_001,
+    };
+    assert_eq!(
+        s.vault.try_set_early_redemption(&1, &terms),
+        Err(Ok(Error::InvalidAmount))
+    );
+    s.vault.set_early_redemption(
+        &1,
+        &EarlyRedemption {
+            enabled: true,
+            penalty_bps: 100,
+        },
+    );
+
+    // Halfway: 500 PAR at 0.99 = 495, less 1% = 490.05
+    s.env.ledger().set_timestamp(1_500);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    let balance_before = stablecoin.balance(&s.user);
+    let payout = s.vault.redeem_early(&s.user, &1, &(500 * SCALE));
+    assert_eq!(payout, 49_005 * SCALE / 100);
+    assert_eq!(stablecoin.balance(&s.user) - balance_before, payout);
+
+    assert_eq!(s.vault.get_series(&1).redeemed_par, 500 * SCALE);
+    let accounting = s.vault.get_protocol_accounting();
+    assert_eq!(accounting.total_early_penalties, 495 * SCALE / 100);
+    assert_eq!(s.vault.reconcile().delta, 0);
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    assert_eq!(
+        s.vault.try_redeem_early(&s.user, &1, &(500 * SCALE)),
+        Err(Ok(Error::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_cancelled_series_refunds_subscribers() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    let balance_before = stablecoin.balance(&s.user);
+
+    s.vault.subscribe(&s.user, &1, &(490 * SCALE), &None);
+    s.env.ledger().set_timestamp(1_500);
+    s.vault.subscribe(&s.user, &1, &(495 * SCALE), &None);
+    assert_eq!(
+        s.vault.get_user_position(&1, &s.user).paid_amount,
+        985 * SCALE
+    );
+    assert_eq!(
+        s.vault.try_claim_refund(&s.user, &1),
+        Err(Ok(Error::InvalidStatus))
+    );
+
+    s.vault.cancel_series(&1);
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(100 * SCALE), &None),
+        Err(Ok(Error::SeriesNotActive))
+    );
+
+    assert_eq!(s.vault.claim_refund(&s.user, &1), 985 * SCALE);
+    assert_eq!(stablecoin.balance(&s.user), balance_before);
+    assert_eq!(s.vault.get_series(&1).redeemed_par, 1_000 * SCALE);
+    assert_eq!(s.vault.reconcile().delta, 0);
+    assert_eq!(
+        s.vault.try_claim_refund(&s.user, &1),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_cancelled_series_cannot_be_redeemed() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.cancel_series(&1);
+
+    // Past maturity a cancelled series still only pays out through claim_refund
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.deposit_settlement(&s.treasury, &(1_000 * SCALE));
+    assert_eq!(
+        s.vault.try_redeem(&s.user, &1, &(1_000 * SCALE), &None),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(s.vault.get_series(&1).status, SeriesStatus::Cancelled);
+    assert_eq!(s.vault.claim_refund(&s.user, &1), 980 * SCALE);
+}
+
+#[test]
+fn test_failed_series_cannot_be_redeemed() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.fail_series(&1);
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.deposit_settlement(&s.treasury, &(1_000 * SCALE));
+    assert_eq!(
+        s.vault.try_redeem(&s.user, &1, &(1_000 * SCALE), &None),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(
+        s.vault.try_redeem_all(&s.user, &1),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(s.vault.get_series(&1).status, SeriesStatus::Failed);
+}
+
+#[test]
+fn test_withdraw_to_treasury_keeps_outstanding_liability() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+
+    // The 1,000 PAR owed at maturity stays in the vault even before it
+    assert_eq!(
+        s.vault
+            .try_withdraw_to_treasury(&SCALE, &nonce(&s, &s.treasury), &AUTH_EXPIRY),
+        Err(Ok(Error::BreachesRedemptionLiability))
+    );
+
+    // Only the excess over face value can be deployed
+    s.vault.deposit_settlement(&s.treasury, &(100 * SCALE));
+    s.vault
+        .withdraw_to_treasury(&(80 * SCALE), &nonce(&s, &s.treasury), &AUTH_EXPIRY);
+    assert_eq!(
+        s.vault.get_protocol_accounting().total_treasury_withdrawals,
+        80 * SCALE
+    );
+    assert_eq!(s.vault.reconcile().delta, 0);
+    assert_eq!(
+        s.vault
+            .try_withdraw_to_treasury(&(21 * SCALE), &nonce(&s, &s.treasury), &AUTH_EXPIRY),
+        Err(Ok(Error::BreachesRedemptionLiability))
+    );
+    assert_eq!(
+        s.vault
+            .try_withdraw_to_treasury(&(2_000 * SCALE), &nonce(&s, &s.treasury), &AUTH_EXPIRY),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_withdraw_to_treasury_rolling_limits() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.deposit_settlement(&s.treasury, &(200 * SCALE));
+
+    assert_eq!(
+        s.vault.try_set_withdrawal_limits(
+            &WithdrawalLimits {
+                daily_limit: 100 * SCALE,
+                weekly_limit: 50 * SCALE,
+            },
+            &nonce(&s, &s.admin),
+            &AUTH_EXPIRY
+        ),
+        Err(Ok(Error::InvalidConfig))
+    );
+    s.vault.set_withdrawal_limits(
+        &WithdrawalLimits {
+            daily_limit: 50 * SCALE,
+            weekly_limit: 80 * SCALE,
+        },
+        &nonce(&s, &s.admin),
+        &AUTH_EXPIRY,
+    );
+
+    // Within the daily cap the treasury signs alone
+    s.vault
+        .withdraw_to_treasury(&(50 * SCALE), &nonce(&s, &s.treasury), &AUTH_EXPIRY);
+    assert!(!s.env.auths().iter().any(|(addr, _)| *addr == s.admin));
+
+    // Beyond it the admin must co-sign
+    s.vault
+        .withdraw_to_treasury(&(10 * SCALE), &nonce(&s, &s.treasury), &AUTH_EXPIRY);
+    assert!(s.env.auths().iter().any(|(addr, _)| *addr == s.admin));
+    let usage = s.vault.get_withdrawal_usage();
+    assert_eq!(usage.daily_used, 60 * SCALE);
+    assert_eq!(usage.weekly_used, 60 * SCALE);
+
+    // A day later only the weekly window still counts the earlier withdrawals
+    s.env.ledger().set_timestamp(ISSUE_DATE + 86_400);
+    let usage = s.vault.get_withdrawal_usage();
+    assert_eq!(usage.daily_used, 0);
+    assert_eq!(usage.weekly_used, 60 * SCALE);
+    s.vault
+        .withdraw_to_treasury(&(20 * SCALE), &nonce(&s, &s.treasury), &AUTH_EXPIRY);
+    assert!(!s.env.auths().iter().any(|(addr, _)| *addr == s.admin));
+    s.vault
+        .withdraw_to_treasury(&SCALE, &nonce(&s, &s.treasury), &AUTH_EXPIRY);
+    assert!(s.env.auths().iter().any(|(addr, _)| *addr == s.admin));
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_fund_redemptions_tracks_series_funding() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+
+    let funding = s.vault.get_redemption_funding(&1);
+    assert_eq!(funding.funded, 0);
+    assert_eq!(funding.required, 1_000 * SCALE);
+    assert!(!s.vault.is_fully_funded(&1));
+
+    s.vault.fund_redemptions(&1, &(600 * SCALE));
+    assert!(!s.vault.is_fully_funded(&1));
+    s.vault.fund_redemptions(&1, &(400 * SCALE));
+    assert!(s.vault.is_fully_funded(&1));
+    assert_eq!(s.vault.get_redemption_funding(&1).funded, 1_000 * SCALE);
+    assert_eq!(
+        s.vault.get_protocol_accounting().total_settlement_deposits,
+        1_000 * SCALE
+    );
+
+    assert_eq!(
+        s.vault.try_fund_redemptions(&1, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(
+        s.vault.try_fund_redemptions(&9, &SCALE),
+        Err(Ok(Error::SeriesNotFound))
+    );
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_get_solvency_reports_coverage() {
+    let s = setup();
+    create_active_series(&s, 1);
+
+    let empty = s.vault.get_solvency();
+    assert_eq!(empty.outstanding_liability, 0);
+    assert_eq!(empty.health_ratio_bps, 10_000);
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    let solvency = s.vault.get_solvency();
+    assert_eq!(solvency.actual_balance, 980 * SCALE);
+    assert_eq!(solvency.outstanding_liability, 1_000 * SCALE);
+    assert_eq!(solvency.matured_liability, 0);
+    assert_eq!(solvency.health_ratio_bps, 9_800);
+
+    s.vault.fund_redemptions(&1, &(20 * SCALE));
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    assert_eq!(s.vault.get_solvency().matured_liability, 0);
+    s.vault.mature_series(&1);
+    let solvency = s.vault.get_solvency();
+    assert_eq!(solvency.funded, 20 * SCALE);
+    assert_eq!(solvency.matured_liability, 1_000 * SCALE);
+    assert_eq!(solvency.health_ratio_bps, 10_000);
+}
+
+#[test]
+fn test_liability_totals_rebuilt_by_migrate_schema() {
+    let s = setup();
+    create_active_series(&s, 1);
+    create_active_series(&s, 2);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.subscribe(&s.user, &2, &(490 * SCALE), &None);
+    s.vault.deposit_settlement(&s.treasury, &(30 * SCALE));
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.redeem(&s.user, &1, &(400 * SCALE), &None);
+
+    let solvency = s.vault.get_solvency();
+    assert_eq!(solvency.outstanding_liability, 1_100 * SCALE);
+    assert_eq!(solvency.matured_liability, 600 * SCALE);
+
+    // Storage written before the running totals existed, as seen after an upgrade
+    s.env.as_contract(&s.vault.address, || {
+        s.env
+            .storage()
+            .instance()
+            .remove(&DataKeyExt::OutstandingPar);
+        s.env.storage().instance().remove(&DataKeyExt::MaturedPar);
+        s.env.storage().instance().remove(&DataKey::StorageSchema);
+        s.env.storage().persistent().remove(&DataKey::StorageSchema);
+    });
+    assert_eq!(s.vault.get_solvency().outstanding_liability, 0);
+
+    s.vault.migrate_schema();
+    assert_eq!(s.vault.get_solvency(), solvency);
+}
+
+#[test]
+fn test_allowance_pull_collects_exact_amount() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+
+    s.vault.set_allowance_pull(&true);
+    assert!(s.vault.is_allowance_pull());
+    stablecoin.approve(&s.user, &s.vault.address, &(980 * SCALE), &1_000);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    assert_eq!(stablecoin.allowance(&s.user, &s.vault.address), 0);
+    assert_eq!(stablecoin.balance(&s.vault.address), 980 * SCALE);
+
+    // Without a fresh allowance there is nothing to pull
+    assert!(s
+        .vault
+        .try_subscribe(&s.user, &1, &(980 * SCALE), &None)
+        .is_err());
+}
+
+#[test]
+fn test_subscribe_rejects_under_delivering_stablecoin() {
+    let s = setup();
+    let token_id = s.env.register(MockDeflationaryToken, ());
+    MockDeflationaryTokenClient::new(&s.env, &token_id).credit(&s.user, &(1_000 * SCALE));
+
+    let vault = BingoVaultClient::new(&s.env, &s.env.register(BingoVault, ()));
+    vault.initialize(
+        &s.admin,
+        &s.treasury,
+        &token_id,
+        &s.env.register(MockBTBillToken, ()),
+    );
+    vault.create_series(
+        &1,
+        &ISSUE_DATE,
+        &MATURITY_DATE,
+        &ISSUE_PRICE,
+        &(1_000_000 * SCALE),
+        &(100_000 * SCALE),
+        &None,
+    );
+    vault.activate_series(&1);
+
+    // 1% is burned in transit, so the vault would mint PAR it never received
+    assert_eq!(
+        vault.try_subscribe(&s.user, &1, &(980 * SCALE), &None),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_redeem_to_asset_through_converter() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.deposit_settlement(&s.treasury, &(20 * SCALE));
+
+    let fund_share = s
+        .env
+        .register_stellar_asset_contract_v2(s.admin.clone())
+        .address();
+    let converter = s.env.register(MockConverter, ());
+    StellarAssetClient::new(&s.env, &fund_share).mint(&converter, &(10_000 * SCALE));
+
+    assert_eq!(
+        s.vault
+            .try_set_series_payout_assets(&1, &vec![&s.env, fund_share.clone()]),
+        Err(Ok(Error::Unauthorized))
+    );
+    s.vault
+        .set_payout_converter(&fund_share, &Some(converter.clone()));
+    s.vault
+        .set_series_payout_assets(&1, &vec![&s.env, fund_share.clone()]);
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    assert_eq!(
+        s.vault
+            .try_redeem_to_asset(&s.user, &1, &(500 * SCALE), &stablecoin.address, &0),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        s.vault
+            .try_redeem_to_asset(&s.user, &1, &(500 * SCALE), &fund_share, &(496 * SCALE)),
+        Err(Ok(Error::SlippageExceeded))
+    );
+
+    // A payout the redemption lane would queue is refused rather than converted
+    s.vault.set_redemption_lane(&RedemptionLane {
+        threshold: 100 * SCALE,
+        retail_reserve: 10_000 * SCALE,
+    });
+    assert_eq!(
+        s.vault
+            .try_redeem_to_asset(&s.user, &1, &(500 * SCALE), &fund_share, &(495 * SCALE)),
+        Err(Ok(Error::PayoutWouldQueue))
+    );
+    s.vault.set_redemption_lane(&RedemptionLane {
+        threshold: 1_000 * SCALE,
+        retail_reserve: 0,
+    });
+
+    // 500 PAR → 500 stablecoin → 495 fund shares
+    let received =
+        s.vault
+            .redeem_to_asset(&s.user, &1, &(500 * SCALE), &fund_share, &(495 * SCALE));
+    assert_eq!(received, 495 * SCALE);
+    assert_eq!(
+        token::Client::new(&s.env, &fund_share).balance(&s.user),
+        495 * SCALE
+    );
+    assert_eq!(stablecoin.balance(&converter), 500 * SCALE);
+    assert_eq!(
+        s.vault.get_protocol_accounting().total_redeemed,
+        500 * SCALE
+    );
+}
+
+#[test]
+fn test_subscription_fee_routed_to_collector() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    let collector = Address::generate(&s.env);
+
+    assert_eq!(
+        s.vault
+            .try_set_subscription_fee_bps(&501, &nonce(&s, &s.admin), &AUTH_EXPIRY),
+        Err(Ok(Error::InvalidAmount))
+    );
+    s.vault
+        .set_subscription_fee_bps(&50, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    assert_eq!(s.vault.get_fee_collector(), s.treasury);
+    s.vault
+        .set_fee_collector(&collector, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+
+    // 0.5% of 1,000 goes to the collector; 995 buys bT-Bills at 0.98
+    let before = stablecoin.balance(&s.user);
+    s.vault.subscribe(&s.user, &1, &(1_000 * SCALE), &None);
+    assert_eq!(before - stablecoin.balance(&s.user), 1_000 * SCALE);
+    assert_eq!(stablecoin.balance(&collector), 5 * SCALE);
+    assert_eq!(stablecoin.balance(&s.vault.address), 995 * SCALE);
+    assert_eq!(
+        s.vault.get_user_position(&1, &s.user).subscribed_par,
+        995 * SCALE * SCALE / (98 * SCALE / 100)
+    );
+
+    let accounting = s.vault.get_protocol_accounting();
+    assert_eq!(accounting.total_subscription_fees, 5 * SCALE);
+    assert_eq!(accounting.total_subscriptions_collected, 995 * SCALE);
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_previews_match_execution() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    s.vault
+        .set_subscription_fee_bps(&50, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    s.vault
+        .set_redemption_fee_bps(&25, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+
+    // A third of the way through the tenor, at a price that does not divide evenly
+    s.env.ledger().set_timestamp(ISSUE_DATE + 333);
+    let quote = s.vault.preview_subscribe(&1, &(1_000 * SCALE + 7));
+    assert_eq!(quote.fee, 5 * SCALE);
+    assert_eq!(quote.price, s.vault.current_price(&1));
+    s.vault.subscribe(&s.user, &1, &(1_000 * SCALE + 7), &None);
+    assert_eq!(
+        s.vault.get_user_position(&1, &s.user).subscribed_par,
+        quote.minted_par
+    );
+    assert_eq!(
+        s.vault.try_preview_subscribe(&2, &(1_000 * SCALE)),
+        Err(Ok(Error::SeriesNotFound))
+    );
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.deposit_settlement(&s.treasury, &(40 * SCALE));
+    let minted_par = quote.minted_par;
+    let quote = s.vault.preview_redeem(&1, &minted_par);
+    assert_eq!(quote.payout, quote.gross - quote.fee);
+    let before = stablecoin.balance(&s.user);
+    s.vault.redeem(&s.user, &1, &minted_par, &None);
+    assert_eq!(stablecoin.balance(&s.user) - before, quote.payout);
+    assert_eq!(
+        s.vault.try_preview_redeem(&1, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_issuer_fee_split_claimable_by_issuer() {
+    let s = setup();
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    let issuer = Address::generate(&s.env);
+    let split = IssuerFeeSplit {
+        subscription_share_bps: 4_000,
+        spread_share_bps: 2_500,
+    };
+
+    assert_eq!(
+        s.vault.try_set_issuer_fee_split(&issuer, &split),
+        Err(Ok(Error::Unauthorized))
+    );
+    s.vault
+        .approve_issuer(&issuer, &issuer_limits(&s, 1_000_000 * SCALE));
+    assert_eq!(
+        s.vault.try_set_issuer_fee_split(
+            &issuer,
+            &IssuerFeeSplit {
+                subscription_share_bps: 10_001,
+                spread_share_bps: 0,
+            }
+        ),
+        Err(Ok(Error::InvalidAmount))
+    );
+    s.vault.set_issuer_fee_split(&issuer, &split);
+    assert_eq!(s.vault.get_issuer_fee_split(&issuer), Some(split));
+
+    s.vault.create_series(
+        &1,
+        &ISSUE_DATE,
+        &MATURITY_DATE,
+        &ISSUE_PRICE,
+        &(1_000_000 * SCALE),
+        &(100_000 * SCALE),
+        &Some(issuer.clone()),
+    );
+    s.vault.activate_series(&1);
+    s.vault
+        .set_subscription_fee_bps(&50, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+
+    // 5 fee: 2 held for the issuer, 3 to the collector
+    let collector_before = stablecoin.balance(&s.treasury);
+    s.vault.subscribe(&s.user, &1, &(1_000 * SCALE), &None);
+    assert_eq!(
+        stablecoin.balance(&s.treasury) - collector_before,
+        3 * SCALE
+    );
+    assert_eq!(stablecoin.balance(&s.vault.address), 997 * SCALE);
+
+    // 100 spread: 25 to the issuer, 75 accrued for collect_fees
+    let repo_market = Address::generate(&s.env);
+    s.stablecoin.mint(&repo_market, &(100 * SCALE));
+    assert_eq!(
+        s.vault.try_book_repo_spread(&repo_market, &1, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    s.vault.book_repo_spread(&repo_market, &1, &(100 * SCALE));
+    assert_eq!(s.vault.get_accrued_fees(), 75 * SCALE);
+    assert_eq!(
+        s.vault.get_issuer_fees(&issuer),
+        IssuerFeeAccount {
+            issuer_subscription_fees: 2 * SCALE,
+            issuer_spread_fees: 25 * SCALE,
+            protocol_subscription_fees: 3 * SCALE,
+            protocol_spread_fees: 75 * SCALE,
+            claimable: 27 * SCALE,
+            claimed: 0,
+        }
+    );
+    assert_eq!(s.vault.reconcile().delta, 0);
+
+    assert_eq!(s.vault.claim_issuer_fees(&issuer), 27 * SCALE);
+    assert_eq!(stablecoin.balance(&issuer), 27 * SCALE);
+    assert_eq!(s.vault.get_issuer_fees(&issuer).claimed, 27 * SCALE);
+    assert_eq!(
+        s.vault.try_claim_issuer_fees(&issuer),
+        Err(Ok(Error::NothingToSweep))
+    );
+    s.vault.collect_fees(&nonce(&s, &s.treasury), &AUTH_EXPIRY);
+
+    let accounting = s.vault.get_protocol_accounting();
+    assert_eq!(accounting.total_subscription_fees, 3 * SCALE);
+    assert_eq!(accounting.total_issuer_fees_received, 2 * SCALE);
+    assert_eq!(accounting.total_issuer_fees_claimed, 27 * SCALE);
+    assert_eq!(accounting.total_repo_revenue, 100 * SCALE);
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_repo_market_books_loans_repayments_and_defaults() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    assert_eq!(
+        s.vault.try_record_loan(&1, &(500 * SCALE)),
+        Err(Ok(Error::RepoMarketNotSet))
+    );
+
+    let repo_market = Address::generate(&s.env);
+    s.vault.set_repo_market(&repo_market);
+    let available = s.vault.calculate_available_for_lending();
+    let profit = s.vault.calculate_protocol_profit();
+
+    s.vault.record_loan(&1, &(500 * SCALE));
+    assert_eq!(s.env.auths()[0].0, repo_market);
+    s.vault.record_loan(&2, &(300 * SCALE));
+    assert_eq!(
+        s.vault.try_record_loan(&3, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(s.vault.get_protocol_accounting().total_lent, 800 * SCALE);
+    assert_eq!(
+        s.vault.calculate_available_for_lending(),
+        available - 800 * SCALE
+    );
+
+    // 500 back with 5 interest; 300 written off
+    s.vault.record_repayment(&1, &(500 * SCALE), &(5 * SCALE));
+    s.vault.record_default(&2, &(300 * SCALE));
+    let accounting = s.vault.get_protocol_accounting();
+    assert_eq!(accounting.total_lent, 0);
+    assert_eq!(accounting.total_repo_revenue, 5 * SCALE);
+    assert_eq!(accounting.total_defaults, 1);
+    assert_eq!(s.vault.get_reported_repo_revenue(), 5 * SCALE);
+    assert_eq!(s.vault.calculate_protocol_profit(), profit + 5 * SCALE);
+    assert_eq!(
+        s.vault.calculate_available_for_lending(),
+        available + 5 * SCALE
+    );
+
+    // Loans and interest move treasury cash, not the vault's
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_schema_guard_until_migrated() {
+    let s = setup();
+    create_active_series(&s, 1);
+
+    let schema = s.vault.get_storage_schema().unwrap();
+    assert_eq!(schema.version, STORAGE_SCHEMA_VERSION);
+    assert_eq!(schema.layouts.len(), STORAGE_LAYOUTS.len() as u32);
+    s.vault.assert_schema(&STORAGE_SCHEMA_VERSION);
+    assert_eq!(
+        s.vault.try_assert_schema(&(STORAGE_SCHEMA_VERSION + 1)),
+        Err(Ok(Error::NotInitialized))
+    );
+
+    // Storage written before the registry existed, as seen after an upgrade
+    s.env.as_contract(&s.vault.address, || {
+        s.env.storage().instance().remove(&DataKey::StorageSchema);
+        s.env.storage().persistent().remove(&DataKey::StorageSchema);
+    });
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(980 * SCALE), &None),
+        Err(Ok(Error::NotInitialized))
+    );
+    s.vault.pause();
+    assert_eq!(s.vault.try_unpause(), Err(Ok(Error::NotInitialized)));
+
+    s.vault.migrate_schema();
+    assert_eq!(s.vault.get_storage_schema(), Some(schema));
+    s.vault.unpause();
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+}
+
+#[test]
+fn test_redemption_fee_withheld_until_collected() {
+    let s = setup();
+    create_active_series(&s, 1);
+    create_active_series(&s, 2);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.subscribe(&s.user, &2, &(980 * SCALE), &None);
+    s.vault.deposit_settlement(&s.treasury, &(40 * SCALE));
+
+    s.vault
+        .set_redemption_fee_bps(&25, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    s.vault
+        .set_series_redemption_fee_bps(&2, &Some(0), &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    assert_eq!(s.vault.get_redemption_fee_bps(&1), 25);
+    assert_eq!(s.vault.get_redemption_fee_bps(&2), 0);
+    assert_eq!(
+        s.vault
+            .try_set_redemption_fee_bps(&101, &nonce(&s, &s.admin), &AUTH_EXPIRY),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    // 1,000 PAR less 0.25% = 997.5 paid, 2.5 withheld
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    let balance_before = stablecoin.balance(&s.user);
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+
+    // Read the event before the balance query replaces the event log
+    let redeemed = Symbol::new(&s.env, "redeemed");
+    let (_, _, data) = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics
+                .get(2)
+                .is_some_and(|topic| Symbol::try_from_val(&s.env, &topic) == Ok(redeemed.clone()))
+        })
+        .unwrap();
+    let (_, event) = <(u32, RedeemedEvent)>::try_from_val(&s.env, &data).unwrap();
+
+    let payout = stablecoin.balance(&s.user) - balance_before;
+    assert_eq!(payout, 9_975 * SCALE / 10);
+    assert_eq!(event.payout, payout);
+    assert_eq!(event.fee, 25 * SCALE / 10);
+
+    s.vault.redeem(&s.user, &2, &(1_000 * SCALE), &None);
+    assert_eq!(s.vault.get_accrued_fees(), 25 * SCALE / 10);
+    assert_eq!(s.vault.reconcile().delta, 0);
+
+    let collector_before = stablecoin.balance(&s.treasury);
+    assert_eq!(
+        s.vault.collect_fees(&nonce(&s, &s.treasury), &AUTH_EXPIRY),
+        25 * SCALE / 10
+    );
+    let collected = stablecoin.balance(&s.treasury) - collector_before;
+    assert_eq!(collected, 25 * SCALE / 10);
+    assert_eq!(s.vault.get_accrued_fees(), 0);
+    assert_eq!(
+        s.vault
+            .try_collect_fees(&nonce(&s, &s.treasury), &AUTH_EXPIRY),
+        Err(Ok(Error::NothingToSweep))
+    );
+
+    let accounting = s.vault.get_protocol_accounting();
+    assert_eq!(accounting.total_redemption_fees, 25 * SCALE / 10);
+    assert_eq!(accounting.total_fees_collected, 25 * SCALE / 10);
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_reconcile_flags_unexpected_inflow() {
+    let s = setup();
+    create_active_series(&s, 1);
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+
+    let clean = s.vault.reconcile();
+    assert_eq!(clean.expected_balance, 980 * SCALE);
+    assert_eq!(clean.delta, 0);
+
+    // Stray transfer straight to the vault, bypassing subscribe
+    s.stablecoin.mint(&s.vault.address, &(5 * SCALE));
+
+    let report = s.vault.reconcile();
+    assert_eq!(report.actual_balance, 985 * SCALE);
+    assert_eq!(report.delta, 5 * SCALE);
+}
+
+#[test]
+fn test_redeem_tracked_in_accounting() {
+    let s = setup();
+    create_active_series(&s, 1);
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.redeem(&s.user, &1, &(500 * SCALE), &None);
+
+    let accounting = s.vault.get_protocol_accounting();
+    assert_eq!(accounting.total_redeemed, 500 * SCALE);
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_six_decimal_settlement_asset() {
+    let s = setup();
+    const USDC: i128 = 1_000_000; // 6 decimals
+    s.vault.set_asset_decimals(&6);
+
+    create_active_series(&s, 1);
+    assert_eq!(
+        s.vault.try_set_asset_decimals(&7),
+        Err(Ok(Error::InvalidConfig))
+    );
+
+    s.vault.subscribe(&s.user, &1, &(980 * USDC), &None);
+    assert_eq!(
+        s.vault.get_user_position(&1, &s.user).subscribed_par,
+        1_000 * SCALE
+    );
+    assert_eq!(s.vault.collateral_value(&1, &(1_000 * SCALE)), 980 * USDC);
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.stablecoin.mint(&s.vault.address, &(20 * USDC)); // settlement shortfall topped up
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+
+    assert_eq!(
+        s.vault.get_protocol_accounting().total_redeemed,
+        1_000 * USDC
+    );
+}
+
+#[test]
+fn test_subscription_receipts_by_id_and_user() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let other = Address::generate(&s.env);
+    s.stablecoin.mint(&other, &(1_000 * SCALE));
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.subscribe(&other, &1, &(490 * SCALE), &None);
+    s.vault.subscribe(&s.user, &1, &(98 * SCALE), &None);
+
+    let receipt = s.vault.get_receipt(&2);
+    assert_eq!(receipt.user, other);
+    assert_eq!(receipt.series_id, 1);
+    assert_eq!(receipt.pay_amount, 490 * SCALE);
+    assert_eq!(receipt.minted_par, 500 * SCALE);
+    assert_eq!(receipt.price, ISSUE_PRICE);
+    assert_eq!(receipt.timestamp, ISSUE_DATE);
+
+    let receipts = s.vault.get_user_receipts(&s.user, &0, &10);
+    assert_eq!(receipts.len(), 2);
+    assert_eq!(receipts.get(0).unwrap().id, 1);
+    assert_eq!(receipts.get(1).unwrap().id, 3);
+    assert_eq!(s.vault.get_user_receipts(&s.user, &1, &10).len(), 1);
+
+    assert_eq!(s.vault.try_get_receipt(&4), Err(Ok(Error::ReceiptNotFound)));
+}
+
+#[test]
+fn test_statement_lists_user_activity() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let custodian = Address::generate(&s.env);
+    s.stablecoin.mint(&custodian, &(490 * SCALE));
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.env.ledger().set_timestamp(ISSUE_DATE + 100);
+    s.vault
+        .subscribe_on_behalf(&custodian, &s.user, &1, &(490 * SCALE));
+    s.vault.deposit_settlement(&s.treasury, &(30 * SCALE));
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.redeem(&s.user, &1, &(500 * SCALE), &None);
+
+    let statement = s.vault.get_statement(&s.user, &0, &u64::MAX, &0, &10);
+    assert_eq!(statement.len(), 3);
+    let subscription = statement.get(0).unwrap();
+    assert_eq!(subscription.kind, ActivityKind::Subscription);
+    assert_eq!(subscription.par_amount, 1_000 * SCALE);
+    assert_eq!(subscription.cash_amount, 980 * SCALE);
+    assert_eq!(subscription.counterparty, None);
+    assert_eq!(subscription.reference, 1);
+    assert_eq!(subscription.timestamp, ISSUE_DATE);
+    let on_behalf = statement.get(1).unwrap();
+    assert_eq!(on_behalf.counterparty, Some(custodian));
+    assert_eq!(on_behalf.reference, 2);
+    let redemption = statement.get(2).unwrap();
+    assert_eq!(redemption.kind, ActivityKind::Redemption);
+    assert_eq!(redemption.par_amount, 500 * SCALE);
+    assert_eq!(redemption.cash_amount, 500 * SCALE);
+    assert_eq!(redemption.timestamp, MATURITY_DATE);
+
+    // Time window, then paging inside it
+    let window = s
+        .vault
+        .get_statement(&s.user, &(ISSUE_DATE + 1), &(MATURITY_DATE - 1), &0, &10);
+    assert_eq!(window, vec![&s.env, on_behalf]);
+    let tail = s.vault.get_statement(&s.user, &0, &u64::MAX, &2, &10);
+    assert_eq!(tail, vec![&s.env, redemption]);
+    assert!(s
+        .vault
+        .get_statement(&s.user, &(MATURITY_DATE + 1), &u64::MAX, &0, &10)
+        .is_empty());
+
+    // Only the registered repo market may report repo activity
+    assert_eq!(
+        s.vault.try_record_repo_activity(
+            &s.user,
+            &RepoActivity::Opened,
+            &1,
+            &1,
+            &(500 * SCALE),
+            &(400 * SCALE)
+        ),
+        Err(Ok(Error::RepoMarketNotSet))
+    );
+}
+
+#[test]
+fn test_dust_policy_buckets_remainders() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.set_dust_policy(
+        &DustPolicy {
+            threshold: SCALE,
+            mode: DustMode::Bucket,
+        },
+        &nonce(&s, &s.admin),
+        &AUTH_EXPIRY,
+    );
+
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(SCALE / 2), &None),
+        Err(Ok(Error::BelowDustThreshold))
+    );
+
+    // 100 at 0.98 → 102.0408163 PAR costing 99.9999999
+    s.vault.subscribe(&s.user, &1, &(100 * SCALE), &None);
+    assert_eq!(s.vault.get_dust_bucket(), 1);
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.stablecoin.mint(&s.vault.address, &(3 * SCALE)); // accretion funded off-book
+
+    // Leaves 0.5408163 PAR behind, which is swept into the bucket
+    s.vault
+        .redeem(&s.user, &1, &(101 * SCALE + SCALE / 2), &None);
+    let series = s.vault.get_series(&1);
+    assert_eq!(series.redeemed_par, series.minted_par);
+    assert_eq!(s.vault.get_dust_bucket(), 1 + 5_408_163);
+
+    assert_eq!(
+        s.vault
+            .claim_dust(&s.treasury, &nonce(&s, &s.treasury), &AUTH_EXPIRY),
+        5_408_164
+    );
+    assert_eq!(
+        s.vault.get_protocol_accounting().total_dust_claimed,
+        5_408_164
+    );
+}
+
+#[test]
+fn test_dust_policy_rejects_leftovers() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.set_dust_policy(
+        &DustPolicy {
+            threshold: SCALE,
+            mode: DustMode::Reject,
+        },
+        &nonce(&s, &s.admin),
+        &AUTH_EXPIRY,
+    );
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.stablecoin.mint(&s.vault.address, &(20 * SCALE));
+
+    assert_eq!(
+        s.vault
+            .try_redeem(&s.user, &1, &(999 * SCALE + SCALE / 2), &None),
+        Err(Ok(Error::BelowDustThreshold))
+    );
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+    assert_eq!(s.vault.get_dust_bucket(), 0);
+}
+
+#[test]
+fn test_batched_settlement_pays_holders_per_batch() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let second = Address::generate(&s.env);
+    let third = Address::generate(&s.env);
+    s.stablecoin.mint(&second, &(980 * SCALE));
+    s.stablecoin.mint(&third, &(980 * SCALE));
+    for holder in [&s.user, &second, &third] {
+        s.vault.subscribe(holder, &1, &(980 * SCALE), &None);
+    }
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.mature_series(&1);
+    s.vault.begin_batched_settlement(&1);
+    assert_eq!(
+        s.vault.try_begin_batched_settlement(&1),
+        Err(Ok(Error::InvalidStatus))
+    );
+
+    assert_eq!(s.vault.announce_settlement_batch(&1, &2), 0);
+    assert_eq!(
+        s.vault.try_announce_settlement_batch(&1, &4),
+        Err(Ok(Error::InvalidBatchRange))
+    );
+    assert_eq!(s.vault.announce_settlement_batch(&1, &3), 1);
+
+    assert_eq!(
+        s.vault.try_redeem(&third, &1, &(1_000 * SCALE), &None),
+        Err(Ok(Error::SeriesSettling))
+    );
+
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    assert_eq!(s.vault.process_settlement_batch(&1), 2_000 * SCALE);
+    assert_eq!(stablecoin.balance(&second), 1_000 * SCALE);
+    assert_eq!(stablecoin.balance(&third), 0);
+    assert!(s.vault.get_settlement_batch(&1, &0).unwrap().processed);
+
+    assert_eq!(s.vault.process_settlement_batch(&1), 1_000 * SCALE);
+    assert_eq!(stablecoin.balance(&third), 1_000 * SCALE);
+    assert_eq!(s.vault.get_settlement_plan(&1), None);
+
+    let series = s.vault.get_series(&1);
+    assert_eq!(series.redeemed_par, series.minted_par);
+    assert_eq!(
+        s.vault.get_protocol_accounting().total_settlement_deposits,
+        3_000 * SCALE
+    );
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_redemption_fast_lane_queues_large_payouts() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let whale = Address::generate(&s.env);
+    s.stablecoin.mint(&whale, &(980 * SCALE));
+    s.vault.set_redemption_lane(&RedemptionLane {
+        threshold: 100 * SCALE,
+        retail_reserve: 500 * SCALE,
+    });
+
+    s.vault.subscribe(&s.user, &1, &(98 * SCALE), &None); // 100 PAR
+    s.vault.subscribe(&whale, &1, &(980 * SCALE), &None); // 1,000 PAR
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.deposit_settlement(&s.treasury, &(22 * SCALE)); // vault holds 1,100
+
+    // Would leave 100 < 500 reserve → queued
+    s.vault.redeem(&whale, &1, &(1_000 * SCALE), &None);
+    let queue = s.vault.get_redemption_queue();
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue.get(0).unwrap().amount, 1_000 * SCALE);
+
+    // Small holders are never queued
+    s.vault.redeem(&s.user, &1, &(100 * SCALE), &None);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    assert_eq!(stablecoin.balance(&whale), 0);
+    assert_eq!(s.vault.reconcile().delta, 0);
+
+    assert_eq!(s.vault.process_redemption_queue(&10), 0);
+    s.vault.deposit_settlement(&s.treasury, &(500 * SCALE));
+    assert_eq!(s.vault.process_redemption_queue(&10), 1);
+    assert_eq!(stablecoin.balance(&whale), 1_000 * SCALE);
+    assert_eq!(s.vault.get_redemption_queue().len(), 0);
+    assert_eq!(s.vault.get_protocol_accounting().queued_payouts, 0);
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_emergency_withdraw_respects_timelock_and_liability() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None); // 1,000 PAR owed at maturity
+    s.stablecoin.mint(&s.vault.address, &(1_020 * SCALE)); // vault holds 2,000
+
+    let to = Address::generate(&s.env);
+    let stablecoin = s.stablecoin.address.clone();
+
+    s.vault
+        .queue_emergency_withdraw(&stablecoin, &(1_500 * SCALE), &to);
+    assert_eq!(
+        s.vault.try_emergency_withdraw(
+            &stablecoin,
+            &(1_500 * SCALE),
+            &to,
+            &nonce(&s, &s.admin),
+            &AUTH_EXPIRY
+        ),
+        Err(Ok(Error::TimelockNotElapsed))
+    );
+
+    // Past maturity and timelock: 1,000 PAR of matured liability must stay
+    s.env
+        .ledger()
+        .set_timestamp(MATURITY_DATE + EMERGENCY_WITHDRAW_DELAY);
+    s.vault.mature_series(&1);
+    assert_eq!(
+        s.vault.try_emergency_withdraw(
+            &stablecoin,
+            &(1_500 * SCALE),
+            &to,
+            &nonce(&s, &s.admin),
+            &AUTH_EXPIRY
+        ),
+        Err(Ok(Error::BreachesRedemptionLiability))
+    );
+
+    s.vault
+        .queue_emergency_withdraw(&stablecoin, &(1_000 * SCALE), &to);
+    s.env
+        .ledger()
+        .set_timestamp(MATURITY_DATE + 2 * EMERGENCY_WITHDRAW_DELAY);
+    s.vault.emergency_withdraw(
+        &stablecoin,
+        &(1_000 * SCALE),
+        &to,
+        &nonce(&s, &s.admin),
+        &AUTH_EXPIRY,
+    );
+
+    let balance = token::Client::new(&s.env, &stablecoin).balance(&to);
+    assert_eq!(balance, 1_000 * SCALE);
+}
+
+#[test]
+fn test_emergency_withdraw_is_booked_for_reconciliation() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.deposit_settlement(&s.treasury, &(520 * SCALE)); // vault holds 1,500
+
+    let to = Address::generate(&s.env);
+    let stablecoin = s.stablecoin.address.clone();
+    s.vault
+        .queue_emergency_withdraw(&stablecoin, &(500 * SCALE), &to);
+    s.env
+        .ledger()
+        .set_timestamp(MATURITY_DATE + EMERGENCY_WITHDRAW_DELAY);
+    s.vault.emergency_withdraw(
+        &stablecoin,
+        &(500 * SCALE),
+        &to,
+        &nonce(&s, &s.admin),
+        &AUTH_EXPIRY,
+    );
+
+    assert_eq!(
+        s.vault
+            .get_protocol_accounting()
+            .total_emergency_withdrawals,
+        500 * SCALE
+    );
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_settlement_allowlist_and_sweep() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    s.stablecoin.mint(&stranger, &(100 * SCALE));
+
+    s.vault.set_settlement_allowlist(&true);
+    s.vault.set_settlement_sender(&s.treasury, &true);
+
+    assert_eq!(
+        s.vault.try_deposit_settlement(&stranger, &(100 * SCALE)),
+        Err(Ok(Error::UnregisteredSettlementSender))
+    );
+    s.vault.deposit_settlement(&s.treasury, &(500 * SCALE));
+    assert_eq!(s.vault.reconcile().delta, 0);
+
+    // Unexpected inflow is tracked separately and swept back out
+    s.stablecoin.mint(&s.vault.address, &(7 * SCALE));
+    let sink = Address::generate(&s.env);
+    assert_eq!(
+        s.vault
+            .sweep_unexpected_inflows(&sink, &nonce(&s, &s.admin), &AUTH_EXPIRY),
+        7 * SCALE
+    );
+    assert_eq!(s.vault.get_unexpected_inflows_swept(), 7 * SCALE);
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_referral_first_touch_accrual_and_claim() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault
+        .set_subscription_fee_bps(&50, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    s.vault.set_referral_reward_bps(&5_000); // half the protocol fee
+
+    let referrer = Address::generate(&s.env);
+    let latecomer = Address::generate(&s.env);
+
+    s.vault
+        .subscribe(&s.user, &1, &(1_000 * SCALE), &Some(referrer.clone()));
+    // Later referrer does not displace the first-touch attribution
+    s.vault
+        .subscribe(&s.user, &1, &(1_000 * SCALE), &Some(latecomer.clone()));
+
+    assert_eq!(s.vault.get_referrer(&s.user), Some(referrer.clone()));
+    assert_eq!(s.vault.get_referral_rewards(&latecomer), 0);
+    // 2.5 of each 5 fee is withheld for the referrer
+    assert_eq!(s.vault.get_referral_rewards(&referrer), 5 * SCALE);
+    s.env.as_contract(&s.vault.address, || {
+        let storage = s.env.storage();
+        assert!(storage.persistent().has(&DataKey::Referrer(s.user.clone())));
+        assert!(storage
+            .persistent()
+            .has(&DataKey::ReferralRewards(referrer.clone())));
+        assert!(!storage.instance().has(&DataKey::Referrer(s.user.clone())));
+    });
+
+    assert_eq!(s.vault.claim_referral_rewards(&referrer), 5 * SCALE);
+    let balance = token::Client::new(&s.env, &s.stablecoin.address).balance(&referrer);
+    assert_eq!(balance, 5 * SCALE);
+    assert_eq!(
+        s.vault.try_claim_referral_rewards(&referrer),
+        Err(Ok(Error::NoReferralRewards))
+    );
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_referred_subscription_keeps_vault_solvent() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    let collector = Address::generate(&s.env);
+    let referrer = Address::generate(&s.env);
+    s.vault
+        .set_subscription_fee_bps(&50, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    s.vault
+        .set_fee_collector(&collector, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    assert_eq!(
+        s.vault.try_set_referral_reward_bps(&10_001),
+        Err(Ok(Error::InvalidConfig))
+    );
+    // Even the maximum rate only redirects the fee, never principal
+    s.vault.set_referral_reward_bps(&10_000);
+
+    s.vault
+        .subscribe(&s.user, &1, &(1_000 * SCALE), &Some(referrer.clone()));
+    assert_eq!(stablecoin.balance(&collector), 0);
+    assert_eq!(s.vault.get_referral_rewards(&referrer), 5 * SCALE);
+    assert_eq!(stablecoin.balance(&s.vault.address), 1_000 * SCALE);
+    assert_eq!(s.vault.reconcile().delta, 0);
+
+    s.vault.claim_referral_rewards(&referrer);
+    // The 995 that bought bT-Bills is still fully held for the holder
+    let accounting = s.vault.get_protocol_accounting();
+    assert_eq!(accounting.total_subscriptions_collected, 995 * SCALE);
+    assert_eq!(accounting.total_referral_rewards_funded, 5 * SCALE);
+    assert_eq!(accounting.total_referral_rewards_paid, 5 * SCALE);
+    assert_eq!(
+        s.vault.get_solvency().actual_balance,
+        accounting.total_subscriptions_collected
+    );
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_loyalty_tier_boosts_user_cap() {
+    let s = setup();
+    create_active_series(&s, 1);
+
+    // Fill the 100,000 PAR base user cap
+    s.vault.subscribe(&s.user, &1, &(98_000 * SCALE), &None);
+    assert_eq!(s.vault.get_remaining_capacity(&1, &s.user), 0);
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(980 * SCALE), &None),
+        Err(Ok(Error::ExceedsUserCap))
+    );
+
+    s.vault.set_loyalty_tiers(&vec![
+        &s.env,
+        LoyaltyTier {
+            min_volume: 100_000 * SCALE,
+            cap_multiplier_bps: 20_000, // 2×
+        },
+    ]);
+
+    let tier = s.vault.get_user_tier(&s.user);
+    assert_eq!(tier.volume, 100_000 * SCALE);
+    assert_eq!(tier.tier, 1);
+    s.env.as_contract(&s.vault.address, || {
+        let key = DataKey::UserVolume(s.user.clone());
+        assert!(s.env.storage().persistent().has(&key));
+        assert!(!s.env.storage().instance().has(&key));
+    });
+    assert_eq!(s.vault.get_remaining_capacity(&1, &s.user), 100_000 * SCALE);
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    assert_eq!(s.vault.get_remaining_capacity(&1, &s.user), 99_000 * SCALE);
+}
+
+#[test]
+fn test_roles_split_admin_powers() {
+    let s = setup();
+    let pauser = Address::generate(&s.env);
+    let fee_manager = Address::generate(&s.env);
+
+    // Ungranted roles stay with the admin
+    assert_eq!(s.vault.get_role_holder(&Role::Upgrader), s.admin);
+
+    s.vault
+        .grant_role(&Role::Pauser, &pauser, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    assert_eq!(s.env.auths()[0].0, s.admin);
+    s.vault.pause();
+    assert_eq!(s.env.auths()[0].0, pauser);
+    s.vault.unpause();
+
+    // Fee changes carry the fee manager's own nonce
+    s.vault.grant_role(
+        &Role::FeeManager,
+        &fee_manager,
+        &nonce(&s, &s.admin),
+        &AUTH_EXPIRY,
+    );
+    s.vault
+        .set_subscription_fee_bps(&50, &nonce(&s, &fee_manager), &AUTH_EXPIRY);
+    assert_eq!(s.env.auths()[0].0, fee_manager);
+    assert_eq!(nonce(&s, &fee_manager), 1);
+    assert_eq!(nonce(&s, &s.admin), 2);
+
+    s.vault
+        .revoke_role(&Role::Pauser, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    assert_eq!(s.vault.get_role_holder(&Role::Pauser), s.admin);
+    s.vault.pause();
+    assert_eq!(s.env.auths()[0].0, s.admin);
+}
+
+#[test]
+fn test_admin_timelock_queues_sensitive_changes() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let custody = Address::generate(&s.env);
+    s.vault.set_admin_delay(&86_400);
+    assert_eq!(s.vault.get_admin_delay(), 86_400);
+
+    // Direct changes are closed while a delay is in force
+    assert_eq!(
+        s.vault
+            .try_set_treasury(&custody, &nonce(&s, &s.admin), &AUTH_EXPIRY),
+        Err(Ok(Error::ActionTimelocked))
+    );
+    assert_eq!(
+        s.vault.try_set_admin_delay(&0),
+        Err(Ok(Error::ActionTimelocked))
+    );
+    assert_eq!(
+        s.vault.try_set_series_redemption_fee_bps(
+            &1,
+            &Some(25),
+            &nonce(&s, &s.admin),
+            &AUTH_EXPIRY
+        ),
+        Err(Ok(Error::ActionTimelocked))
+    );
+    assert_eq!(
+        s.vault
+            .try_set_fee_collector(&custody, &nonce(&s, &s.admin), &AUTH_EXPIRY),
+        Err(Ok(Error::ActionTimelocked))
+    );
+    assert_eq!(
+        s.vault
+            .try_set_keeper_fee_bps(&10, &nonce(&s, &s.admin), &AUTH_EXPIRY),
+        Err(Ok(Error::ActionTimelocked))
+    );
+
+    let start = s.env.ledger().timestamp();
+    let treasury_id = s
+        .vault
+        .queue_admin_action(&AdminAction::SetTreasury(custody.clone()));
+    assert_eq!(
+        s.vault
+            .get_admin_action(&treasury_id)
+            .unwrap()
+            .executable_at,
+        start + 86_400
+    );
+    let fee_id = s
+        .vault
+        .queue_admin_action(&AdminAction::SetSubscriptionFee(50));
+    assert_eq!(
+        s.vault.try_execute_admin_action(&treasury_id),
+        Err(Ok(Error::ActionTimelocked))
+    );
+
+    s.vault.cancel_admin_action(&fee_id);
+    assert_eq!(s.vault.get_admin_action(&fee_id), None);
+
+    let series_fee_id = s
+        .vault
+        .queue_admin_action(&AdminAction::SetSeriesRedemptionFee(1, Some(25)));
+    let collector_id = s
+        .vault
+        .queue_admin_action(&AdminAction::SetFeeCollector(custody.clone()));
+    let keeper_fee_id = s.vault.queue_admin_action(&AdminAction::SetKeeperFee(10));
+
+    s.env.ledger().set_timestamp(start + 86_400);
+    s.vault.execute_admin_action(&series_fee_id);
+    s.vault.execute_admin_action(&collector_id);
+    s.vault.execute_admin_action(&keeper_fee_id);
+    assert_eq!(s.vault.get_redemption_fee_bps(&1), 25);
+    assert_eq!(s.vault.get_fee_collector(), custody);
+    assert_eq!(s.vault.get_keeper_fee_bps(), 10);
+
+    s.vault.execute_admin_action(&treasury_id);
+    assert_eq!(s.env.auths()[0].0, s.admin);
+    assert_eq!(s.vault.get_treasury(), custody);
+    assert_eq!(
+        s.vault.try_execute_admin_action(&fee_id),
+        Err(Ok(Error::NoPendingAction))
+    );
+    assert_eq!(s.vault.get_subscription_fee_bps(), 0);
+}
+
+#[test]
+fn test_cap_exempt_account_skips_user_cap() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let market_maker = Address::generate(&s.env);
+    s.stablecoin.mint(&market_maker, &(500_000 * SCALE));
+
+    assert!(!s.vault.is_cap_exempt(&market_maker));
+    s.vault.set_cap_exempt(&market_maker, &true);
+    assert_eq!(s.env.auths()[0].0, s.admin);
+    assert!(s.vault.is_cap_exempt(&market_maker));
+    s.env.as_contract(&s.vault.address, || {
+        let key = DataKeyExt::CapExempt(market_maker.clone());
+        assert!(s.env.storage().persistent().has(&key));
+        assert!(!s.env.storage().instance().has(&key));
+    });
+
+    // 3× the 100,000 PAR user cap
+    s.vault
+        .subscribe(&market_maker, &1, &(294_000 * SCALE), &None);
+    assert_eq!(
+        s.vault.get_remaining_capacity(&1, &market_maker),
+        700_000 * SCALE
+    );
+
+    // Everyone else is still capped
+    s.vault.subscribe(&s.user, &1, &(98_000 * SCALE), &None);
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(980 * SCALE), &None),
+        Err(Ok(Error::ExceedsUserCap))
+    );
+
+    s.vault.set_cap_exempt(&market_maker, &false);
+    assert!(!s.vault.is_cap_exempt(&market_maker));
+    assert_eq!(
+        s.vault
+            .try_subscribe(&market_maker, &1, &(980 * SCALE), &None),
+        Err(Ok(Error::ExceedsUserCap))
+    );
+}
+
+#[test]
+fn test_kyc_gated_series() {
+    let s = setup();
+    create_active_series(&s, 1);
+    create_active_series(&s, 2);
+
+    s.vault.set_series_kyc_required(&1, &true);
+    assert_eq!(s.env.auths()[0].0, s.treasury);
+    assert!(s.vault.is_series_kyc_required(&1));
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(980 * SCALE), &None),
+        Err(Ok(Error::KYCRequired))
+    );
+    // Ungated series stay open
+    s.vault.subscribe(&s.user, &2, &(980 * SCALE), &None);
+
+    // Internal allowlist
+    s.vault.set_kyc_verified(&s.user, &true);
+    assert!(s.vault.is_kyc_verified(&s.user));
+    s.env.as_contract(&s.vault.address, || {
+        let key = DataKeyExt::KycVerified(s.user.clone());
+        assert!(s.env.storage().persistent().has(&key));
+        assert!(!s.env.storage().instance().has(&key));
+    });
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+
+    // External registry
+    let registry_id = s.env.register(MockKycRegistry, ());
+    let registry = MockKycRegistryClient::new(&s.env, &registry_id);
+    s.vault.set_kyc_registry(&Some(registry_id.clone()));
+    let institution = Address::generate(&s.env);
+    s.stablecoin.mint(&institution, &(980 * SCALE));
+    assert!(!s.vault.is_kyc_verified(&institution));
+    registry.set_verified(&institution, &true);
+    s.vault.subscribe(&institution, &1, &(980 * SCALE), &None);
+
+    // Redemptions are gated too
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.set_kyc_verified(&s.user, &false);
+    assert_eq!(
+        s.vault.try_redeem(&s.user, &1, &(1_000 * SCALE), &None),
+        Err(Ok(Error::KYCRequired))
+    );
+    s.vault.redeem(&institution, &1, &(1_000 * SCALE), &None);
+    s.vault.set_series_kyc_required(&1, &false);
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+}
+
+#[test]
+fn test_series_risk_flags() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let series_manager = Address::generate(&s.env);
+    s.vault.grant_role(
+        &Role::SeriesManager,
+        &series_manager,
+        &nonce(&s, &s.admin),
+        &AUTH_EXPIRY,
+    );
+
+    assert!(s.vault.get_series_risk_flags(&1).is_empty());
+    assert_eq!(
+        s.vault
+            .try_set_series_risk_flags(&2, &vec![&s.env, RiskFlag::UnderReview]),
+        Err(Ok(Error::SeriesNotFound))
+    );
+
+    // Duplicates collapse; the alert carries the flags now in force
+    s.vault.set_series_risk_flags(
+        &1,
+        &vec![
+            &s.env,
+            RiskFlag::UnderReview,
+            RiskFlag::ImpairedIssuer,
+            RiskFlag::UnderReview,
+        ],
+    );
+    assert_eq!(s.env.auths()[0].0, series_manager);
+    let alert = Symbol::new(&s.env, "risk_alert");
+    let (_, _, data) = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics
+                .get(2)
+                .is_some_and(|topic| Symbol::try_from_val(&s.env, &topic) == Ok(alert.clone()))
+        })
+        .unwrap();
+    let event = <(u32, RiskAlertEvent)>::try_from_val(&s.env, &data)
+        .unwrap()
+        .1;
+    assert_eq!(event.series_id, 1);
+    assert_eq!(
+        event.flags,
+        vec![&s.env, RiskFlag::UnderReview, RiskFlag::ImpairedIssuer]
+    );
+    assert_eq!(s.vault.get_series_risk_flags(&1), event.flags);
+
+    s.vault.set_series_risk_flags(&1, &vec![&s.env]);
+    assert!(s.vault.get_series_risk_flags(&1).is_empty());
+}
+
+#[test]
+fn test_blocklist_rejects_subscribe_and_redeem() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+
+    s.vault.add_to_blocklist(&s.user);
+    assert_eq!(s.env.auths()[0].0, s.admin);
+    assert!(s.vault.is_blocked(&s.user));
+    let key = DataKeyExt::Blocked(s.user.clone());
+    s.env.as_contract(&s.vault.address, || {
+        assert!(s.env.storage().persistent().has(&key));
+        assert!(!s.env.storage().instance().has(&key));
+    });
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(980 * SCALE), &None),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.deposit_settlement(&s.treasury, &(20 * SCALE));
+    assert_eq!(
+        s.vault.try_redeem(&s.user, &1, &(1_000 * SCALE), &None),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    s.vault.remove_from_blocklist(&s.user);
+    assert!(!s.vault.is_blocked(&s.user));
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+
+    // A block written to instance storage before the move still applies
+    s.env.as_contract(&s.vault.address, || {
+        s.env.storage().instance().set(&key, &true);
+    });
+    assert!(s.vault.is_blocked(&s.user));
+    s.vault.remove_from_blocklist(&s.user);
+    s.env.as_contract(&s.vault.address, || {
+        assert!(!s.env.storage().instance().has(&key));
+    });
+    assert!(!s.vault.is_blocked(&s.user));
+}
+
+#[test]
+fn test_rewards_hook_notified_on_subscribe_and_redeem() {
+    let s = setup();
+    create_active_series(&s, 1);
+
+    let hook_id = s.env.register(MockRewardsHook, ());
+    let hook = MockRewardsHookClient::new(&s.env, &hook_id);
+    s.vault.set_rewards_hook(&Some(hook_id.clone()));
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.redeem(&s.user, &1, &(500 * SCALE), &None);
+
+    let actions = hook.actions();
+    assert_eq!(actions.len(), 2);
+    assert_eq!(
+        actions.get(0).unwrap(),
+        (
+            s.user.clone(),
+            Symbol::new(&s.env, "subscribe"),
+            980 * SCALE
+        )
+    );
+    assert_eq!(
+        actions.get(1).unwrap(),
+        (s.user.clone(), Symbol::new(&s.env, "redeem"), 500 * SCALE)
+    );
+
+    // A hook without on_action must not block core flows
+    let broken_hook = s.env.register(MockBTBillToken, ());
+    s.vault.set_rewards_hook(&Some(broken_hook));
+    create_active_series(&s, 2);
+    s.vault.subscribe(&s.user, &2, &(980 * SCALE), &None);
+}
+
+#[test]
+fn test_subscribe_for_binds_nonce_and_expiry() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let expiry = ISSUE_DATE + 600;
+
+    s.vault
+        .subscribe_for(&s.user, &1, &(980 * SCALE), &0, &expiry);
+    assert_eq!(s.vault.get_meta_nonce(&s.user), 1);
+    s.env.as_contract(&s.vault.address, || {
+        let key = DataKey::MetaNonce(s.user.clone());
+        assert!(s.env.storage().persistent().has(&key));
+        assert!(!s.env.storage().instance().has(&key));
+    });
+    assert_eq!(
+        s.vault.get_user_position(&1, &s.user).subscribed_par,
+        1_000 * SCALE
+    );
+
+    // Replaying the same signed parameters is rejected
+    assert_eq!(
+        s.vault
+            .try_subscribe_for(&s.user, &1, &(980 * SCALE), &0, &expiry),
+        Err(Ok(Error::InvalidNonce))
+    );
+
+    s.env.ledger().set_timestamp(expiry + 1);
+    assert_eq!(
+        s.vault
+            .try_subscribe_for(&s.user, &1, &(980 * SCALE), &1, &expiry),
+        Err(Ok(Error::MetaTxExpired))
+    );
+}
+
+#[test]
+fn test_redeem_pays_designated_recipient() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.deposit_settlement(&s.treasury, &(20 * SCALE));
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+
+    let settlement_account = Address::generate(&s.env);
+    s.vault.add_to_blocklist(&settlement_account);
+    assert_eq!(
+        s.vault.try_redeem(
+            &s.user,
+            &1,
+            &(500 * SCALE),
+            &Some(settlement_account.clone())
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    s.vault.remove_from_blocklist(&settlement_account);
+
+    s.vault.redeem(
+        &s.user,
+        &1,
+        &(500 * SCALE),
+        &Some(settlement_account.clone()),
+    );
+    assert_eq!(s.env.auths()[0].0, s.user);
+    let redirected = Symbol::new(&s.env, "redemption_redirected");
+    assert!(s.env.events().all().iter().any(|(_, topics, _)| {
+        topics
+            .get(2)
+            .is_some_and(|topic| Symbol::try_from_val(&s.env, &topic) == Ok(redirected.clone()))
+    }));
+
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    assert_eq!(stablecoin.balance(&settlement_account), 500 * SCALE);
+    assert_eq!(stablecoin.balance(&s.user), 999_020 * SCALE);
+}
+
+#[test]
+fn test_first_redemption_matures_series() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.deposit_settlement(&s.treasury, &(20 * SCALE));
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::Active);
+    s.vault.redeem(&s.user, &1, &(400 * SCALE), &None);
+    let matured = Symbol::new(&s.env, "series_matured");
+    assert!(s.env.events().all().iter().any(|(_, topics, _)| {
+        topics
+            .get(2)
+            .is_some_and(|topic| Symbol::try_from_val(&s.env, &topic) == Ok(matured.clone()))
+    }));
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::Matured);
+
+    // Later redemptions leave the status alone
+    s.vault.redeem(&s.user, &1, &(600 * SCALE), &None);
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::Matured);
+    s.vault.close_series(&1);
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::Closed);
+}
+
+#[test]
+fn test_redeem_all_burns_full_balance() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.subscribe(&s.user, &1, &(490 * SCALE), &None);
+    s.vault.deposit_settlement(&s.treasury, &(30 * SCALE));
+
+    assert_eq!(
+        s.vault.try_redeem_all(&s.user, &1),
+        Err(Ok(Error::SeriesNotMatured))
+    );
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    assert_eq!(s.vault.redeem_all(&s.user, &1), 1_500 * SCALE);
+    assert_eq!(s.env.auths()[0].0, s.user);
+    assert_eq!(s.vault.get_series(&1).redeemed_par, 1_500 * SCALE);
+    assert_eq!(
+        s.vault.try_redeem_all(&s.user, &1),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_subscribe_on_behalf_pays_for_beneficiary() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let beneficiary = Address::generate(&s.env);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+
+    s.vault
+        .subscribe_on_behalf(&s.user, &beneficiary, &1, &(980 * SCALE));
+    assert_eq!(s.env.auths()[0].0, s.user);
+
+    let subscribed = Symbol::new(&s.env, "subscribed");
+    let (_, _, data) = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics
+                .get(2)
+                .is_some_and(|topic| Symbol::try_from_val(&s.env, &topic) == Ok(subscribed.clone()))
+        })
+        .unwrap();
+    let (_, event) = <(u32, SubscribedEvent)>::try_from_val(&s.env, &data).unwrap();
+    assert_eq!(event.payer, s.user);
+    assert_eq!(event.user, beneficiary);
+
+    // Position and cap usage accrue to the beneficiary; the payer holds nothing
+    assert_eq!(
+        s.vault.get_user_position(&1, &beneficiary).subscribed_par,
+        1_000 * SCALE
+    );
+    assert_eq!(s.vault.get_user_position(&1, &s.user).subscribed_par, 0);
+    assert_eq!(stablecoin.balance(&s.user), 999_020 * SCALE);
+    assert_eq!(stablecoin.balance(&beneficiary), 0);
+
+    s.vault.add_to_blocklist(&s.user);
+    let other = Address::generate(&s.env);
+    assert_eq!(
+        s.vault
+            .try_subscribe_on_behalf(&s.user, &other, &1, &(980 * SCALE)),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_delegate_scoped_to_actions_limit_and_expiry() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+
+    let hot_key = Address::generate(&s.env);
+    s.vault.grant_delegation(
+        &s.user,
+        &hot_key,
+        &vec![&s.env, DelegatedAction::Roll],
+        &(600 * SCALE),
+        &(MATURITY_DATE + 100),
+    );
+
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    assert_eq!(
+        s.vault
+            .try_redeem_as_delegate(&hot_key, &s.user, &1, &(100 * SCALE)),
+        Err(Ok(Error::ActionNotDelegated))
+    );
+
+    // Roll 500 PAR of matured series 1 into series 2 (priced at 0.98)
+    s.vault.create_series(
+        &2,
+        &MATURITY_DATE,
+        &(MATURITY_DATE + 1_000),
+        &ISSUE_PRICE,
+        &(1_000_000 * SCALE),
+        &(100_000 * SCALE),
+        &None,
+    );
+    s.vault.activate_series(&2);
+    s.vault
+        .roll_as_delegate(&hot_key, &s.user, &1, &2, &(490 * SCALE));
+    assert_eq!(
+        s.vault.get_user_position(&2, &s.user).subscribed_par,
+        500 * SCALE
+    );
+    assert_eq!(s.vault.reconcile().delta, 0);
+
+    assert_eq!(
+        s.vault
+            .try_roll_as_delegate(&hot_key, &s.user, &1, &2, &(200 * SCALE)),
+        Err(Ok(Error::ExceedsDelegationLimit))
+    );
+
+    s.env.ledger().set_timestamp(MATURITY_DATE + 101);
+    assert_eq!(
+        s.vault
+            .try_roll_as_delegate(&hot_key, &s.user, &1, &2, &(10 * SCALE)),
+        Err(Ok(Error::DelegationExpired))
+    );
+
+    let key = DataKey::Delegation(s.user.clone(), hot_key.clone());
+    s.env.as_contract(&s.vault.address, || {
+        assert!(s.env.storage().persistent().has(&key));
+        assert!(!s.env.storage().instance().has(&key));
+    });
+    s.vault.revoke_delegation(&s.user, &hot_key);
+    assert!(s.vault.get_delegation(&s.user, &hot_key).is_none());
+    assert_eq!(
+        s.vault.try_revoke_delegation(&s.user, &hot_key),
+        Err(Ok(Error::DelegationNotFound))
+    );
+}
+
+#[test]
+fn test_keeper_executes_opted_in_auto_redeem() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+
+    assert_eq!(
+        s.vault
+            .try_execute_user_automation(&s.user, &AutomationAction::AutoRedeem, &1),
+        Err(Ok(Error::AutomationNotEnabled))
+    );
+
+    // Allowance caps how much the keeper may redeem
+    s.vault
+        .set_automation(&s.user, &AutomationAction::AutoRedeem, &(600 * SCALE));
+    let key = DataKey::Automation(s.user.clone(), AutomationAction::AutoRedeem);
+    s.env.as_contract(&s.vault.address, || {
+        assert!(s.env.storage().persistent().has(&key));
+        assert!(!s.env.storage().instance().has(&key));
+    });
+    let redeemed = s
+        .vault
+        .execute_user_automation(&s.user, &AutomationAction::AutoRedeem, &1);
+    assert_eq!(redeemed, 600 * SCALE);
+    assert_eq!(
+        s.vault
+            .get_automation_allowance(&s.user, &AutomationAction::AutoRedeem),
+        0
+    );
+    s.env.as_contract(&s.vault.address, || {
+        assert!(!s.env.storage().persistent().has(&key));
+    });
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_auto_redeem_pays_opted_in_users_minus_keeper_fee() {
+    let s = setup();
+    create_active_series(&s, 1);
+    let passive = Address::generate(&s.env);
+    s.stablecoin.mint(&passive, &(980 * SCALE));
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.subscribe(&passive, &1, &(980 * SCALE), &None);
+    s.vault
+        .set_automation(&passive, &AutomationAction::AutoRedeem, &(1_000 * SCALE));
+    s.vault
+        .set_keeper_fee_bps(&10, &nonce(&s, &s.admin), &AUTH_EXPIRY); // 0.1%
+
+    let keeper = Address::generate(&s.env);
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+
+    // s.user never opted in and is skipped
+    let users = vec![&s.env, s.user.clone(), passive.clone()];
+    assert_eq!(s.vault.auto_redeem(&keeper, &1, &users), 1_000 * SCALE);
+
+    // Read the event before the balance query replaces the event log
+    let redeemed = Symbol::new(&s.env, "redeemed");
+    let (_, _, data) = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics
+                .get(2)
+                .is_some_and(|topic| Symbol::try_from_val(&s.env, &topic) == Ok(redeemed.clone()))
+        })
+        .unwrap();
+    let (_, event) = <(u32, RedeemedEvent)>::try_from_val(&s.env, &data).unwrap();
+    assert_eq!(event.user, passive);
+    assert_eq!(event.payout, 999 * SCALE);
+    assert_eq!(event.keeper_fee, SCALE);
+
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+    assert_eq!(stablecoin.balance(&passive), 999 * SCALE);
+    assert_eq!(stablecoin.balance(&keeper), SCALE);
+    assert_eq!(s.vault.reconcile().delta, 0);
+}
+
+#[test]
+fn test_issuance_freeze_blocks_only_new_series() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.freeze_issuance();
+
+    assert_eq!(
+        s.vault.try_create_series(
+            &2,
+            &ISSUE_DATE,
+            &MATURITY_DATE,
+            &ISSUE_PRICE,
+            &(1_000_000 * SCALE),
+            &(100_000 * SCALE),
+            &None,
+        ),
+        Err(Ok(Error::IssuanceFrozen))
+    );
+
+    // Existing series keep working
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.redeem(&s.user, &1, &(500 * SCALE), &None);
+
+    s.vault.unfreeze_issuance();
+    assert!(!s.vault.is_issuance_frozen());
+}
+
+#[test]
+fn test_out_of_envelope_series_requires_admin_co_approval() {
+    let s = setup();
+    s.vault.set_issuance_envelope(&IssuanceEnvelope {
+        max_discount_bps: 500,
+        min_tenor: 500,
+        max_tenor: 5_000,
+        max_cap_par: 1_000_000 * SCALE,
+    });
+    // Inside the envelope: treasury alone
+    s.vault.create_series(
+        &1,
+        &ISSUE_DATE,
+        &MATURITY_DATE,
+        &ISSUE_PRICE,
+        &(1_000_000 * SCALE),
+        &(100_000 * SCALE),
+        &None,
+    );
+    assert!(!s.env.auths().iter().any(|(addr, _)| *addr == s.admin));
+
+    // 10% discount is outside the envelope: admin must co-sign
+    s.vault.create_series(
+        &2,
+        &ISSUE_DATE,
+        &MATURITY_DATE,
+        &(90 * SCALE / 100),
+        &(1_000_000 * SCALE),
+        &(100_000 * SCALE),
+        &None,
+    );
+    assert!(s.env.auths().iter().any(|(addr, _)| *addr == s.admin));
+}
+
+#[test]
+fn test_series_lifecycle_transitions() {
+    let s = setup();
+    create_active_series(&s, 1);
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.stablecoin.mint(&s.vault.address, &(20 * SCALE)); // fund full PAR payout
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.mature_series(&1);
+
+    // Matured series cannot be cancelled, only failed
+    assert_eq!(s.vault.try_cancel_series(&1), Err(Ok(Error::InvalidStatus)));
+
+    assert_eq!(
+        s.vault.try_close_series(&1),
+        Err(Ok(Error::SeriesHasOutstandingPar))
+    );
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+    s.vault.close_series(&1);
+    assert_eq!(s.vault.get_series_status(&1), SeriesStatus::Closed);
+
+    // Closed is terminal
+    assert_eq!(s.vault.try_fail_series(&1), Err(Ok(Error::InvalidStatus)));
+}
+
+#[test]
+fn test_prune_only_closed_series_and_tombstone_id() {
+    let s = setup();
+    create_active_series(&s, 1);
+    create_active_series(&s, 2);
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.stablecoin.mint(&s.vault.address, &(20 * SCALE)); // fund full PAR payout
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.mature_series(&1);
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+    s.vault.close_series(&1);
+
+    let keeper = Address::generate(&s.env);
+    let ids = vec![&s.env, 1u32, 2u32, 99u32];
+    assert_eq!(s.vault.prune_series(&keeper, &ids), 1);
+
+    assert_eq!(
+        s.vault.try_get_series_status(&1),
+        Err(Ok(Error::SeriesNotFound))
+    );
+    assert_eq!(s.vault.get_user_position(&1, &s.user).subscribed_par, 0);
+    assert!(s.vault.get_archived_series_hash(&1).is_some());
+    assert_eq!(s.vault.export_series(&0, &10).len(), 1);
+
+    // Pruned IDs stay reserved
+    assert_eq!(
+        s.vault.try_create_series(
+            &1,
+            &ISSUE_DATE,
+            &MATURITY_DATE,
+            &ISSUE_PRICE,
+            &(1_000_000 * SCALE),
+            &(100_000 * SCALE),
+            &None,
+        ),
+        Err(Ok(Error::SeriesAlreadyExists))
+    );
+}
+
+#[test]
+fn test_observers_notified_on_lifecycle_and_large_subscription() {
+    let s = setup();
+    let observer_id = s.env.register(MockObserver, ());
+    let observer = MockObserverClient::new(&s.env, &observer_id);
+    s.vault.add_observer(&observer_id);
+    assert_eq!(
+        s.vault.try_add_observer(&observer_id),
+        Err(Ok(Error::ObserverAlreadyRegistered))
+    );
+    s.vault.set_large_subscription_threshold(&(1_000 * SCALE));
+
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(490 * SCALE), &None); // 500 PAR, below threshold
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None); // 1,000 PAR
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.mature_series(&1);
+
+    let events = observer.events();
+    assert_eq!(events.len(), 3);
+    assert_eq!(
+        events.get(0).unwrap(),
+        (Symbol::new(&s.env, "series_activated"), 1, 0)
+    );
+    assert_eq!(
+        events.get(1).unwrap(),
+        (Symbol::new(&s.env, "large_subscription"), 1, 1_000 * SCALE)
+    );
+    assert_eq!(
+        events.get(2).unwrap(),
+        (Symbol::new(&s.env, "series_matured"), 1, 1_500 * SCALE)
+    );
+
+    s.vault.remove_observer(&observer_id);
+    assert_eq!(s.vault.get_observers().len(), 0);
+}
+
+#[test]
+fn test_circuit_breaker_halts_subscriptions_after_mint_storm() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.env.ledger().set_sequence_number(100);
+
+    s.vault.set_circuit_breaker(
+        &1,
+        &CircuitBreaker {
+            window_ledgers: 10,
+            max_window_par: 1_500 * SCALE,
+            pause_ledgers: 50,
+        },
+    );
+
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None); // 1,000 PAR
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None); // 2,000 PAR → trips
+    assert_eq!(s.vault.get_breaker_state(&1).unwrap().tripped_until, 150);
+
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(98 * SCALE), &None),
+        Err(Ok(Error::SubscriptionsHalted))
+    );
+
+    s.env.ledger().set_sequence_number(150);
+    s.vault.subscribe(&s.user, &1, &(98 * SCALE), &None);
+    assert_eq!(
+        s.vault.get_user_position(&1, &s.user).subscribed_par,
+        2_100 * SCALE
+    );
+
+    assert_eq!(
+        s.vault.try_set_circuit_breaker(
+            &1,
+            &CircuitBreaker {
+                window_ledgers: 10,
+                max_window_par: 0,
+                pause_ledgers: 50,
+            },
+        ),
+        Err(Ok(Error::InvalidConfig))
+    );
+}
+
+#[test]
+fn test_shadow_mode_reports_without_enforcing() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.env.ledger().set_sequence_number(100);
+    let stablecoin = token::Client::new(&s.env, &s.stablecoin.address);
+
+    s.vault
+        .set_subscription_fee_bps(&50, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    s.vault.set_circuit_breaker(
+        &1,
+        &CircuitBreaker {
+            window_ledgers: 10,
+            max_window_par: 500 * SCALE,
+            pause_ledgers: 50,
+        },
+    );
+    for feature in [
+        ShadowFeature::SubscriptionFee,
+        ShadowFeature::CircuitBreaker,
+    ] {
+        s.vault
+            .set_shadow_mode(&feature, &true, &nonce(&s, &s.admin), &AUTH_EXPIRY);
+    }
+    assert!(s.vault.is_shadow_mode(&ShadowFeature::SubscriptionFee));
+    assert!(!s.vault.is_shadow_mode(&ShadowFeature::RedemptionFee));
+
+    // The fee is reported but not skimmed: all 980 buys bT-Bills
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    let shadow_outcome = |s: &Setup, feature: ShadowFeature| {
+        let outcome = Symbol::new(&s.env, "shadow_outcome");
+        s.env
+            .events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| {
+                topics.get(2).is_some_and(|topic| {
+                    Symbol::try_from_val(&s.env, &topic) == Ok(outcome.clone())
+                })
+            })
+            .map(|(_, _, data)| {
+                <(u32, ShadowOutcomeEvent)>::try_from_val(&s.env, &data)
+                    .unwrap()
+                    .1
+            })
+            .find(|event| event.feature == feature)
+    };
+    let fee = shadow_outcome(&s, ShadowFeature::SubscriptionFee).unwrap();
+    assert_eq!(fee.amount, 49 * SCALE / 10);
+    assert!(shadow_outcome(&s, ShadowFeature::CircuitBreaker).is_none());
+    assert_eq!(stablecoin.balance(&s.vault.address), 980 * SCALE);
+
+    // 1,000 PAR tripped the breaker, yet the next subscription still mints
+    s.vault.subscribe(&s.user, &1, &(98 * SCALE), &None);
+    let halted = shadow_outcome(&s, ShadowFeature::CircuitBreaker).unwrap();
+    assert_eq!(halted.amount, 100 * SCALE);
+    assert_eq!(
+        s.vault.get_user_position(&1, &s.user).subscribed_par,
+        1_100 * SCALE
+    );
+
+    // Back in enforcement, the tracked trip halts subscriptions
+    s.vault.set_shadow_mode(
+        &ShadowFeature::CircuitBreaker,
+        &false,
+        &nonce(&s, &s.admin),
+        &AUTH_EXPIRY,
+    );
+    assert_eq!(
+        s.vault.try_subscribe(&s.user, &1, &(98 * SCALE), &None),
+        Err(Ok(Error::SubscriptionsHalted))
+    );
+}
+
+#[test]
+fn test_series_index_pages_in_creation_order() {
+    let s = setup();
+    assert!(s.vault.get_series_ids().is_empty());
+    for series_id in [7, 3, 9] {
+        create_active_series(&s, series_id);
+    }
+
+    assert_eq!(s.vault.get_series_ids(), vec![&s.env, 7, 3, 9]);
+    let first = s.vault.get_series_page(&0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap().series_id, 7);
+    assert_eq!(first.get(1).unwrap().series_id, 3);
+    let rest = s.vault.get_series_page(&2, &2);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest.get(0).unwrap().series_id, 9);
+    assert!(s.vault.get_series_page(&3, &2).is_empty());
+}
+
+#[test]
+fn test_user_series_tracks_holdings() {
+    let s = setup();
+    create_active_series(&s, 1);
+    create_active_series(&s, 2);
+    assert!(s.vault.get_user_series(&s.user).is_empty());
+
+    s.vault.subscribe(&s.user, &2, &(490 * SCALE), &None);
+    s.vault.subscribe(&s.user, &1, &(980 * SCALE), &None);
+    s.vault.subscribe(&s.user, &2, &(490 * SCALE), &None);
+    assert_eq!(s.vault.get_user_series(&s.user), vec![&s.env, 2, 1]);
+
+    s.vault.deposit_settlement(&s.treasury, &(40 * SCALE));
+    s.env.ledger().set_timestamp(MATURITY_DATE);
+    s.vault.redeem(&s.user, &2, &(400 * SCALE), &None);
+    assert_eq!(s.vault.get_user_series(&s.user), vec![&s.env, 2, 1]);
+    s.vault.redeem(&s.user, &2, &(600 * SCALE), &None);
+    assert_eq!(s.vault.get_user_series(&s.user), vec![&s.env, 1]);
+    s.vault.redeem(&s.user, &1, &(1_000 * SCALE), &None);
+    assert!(s.vault.get_user_series(&s.user).is_empty());
+}
+
+#[test]
+fn test_export_series_and_positions_paginate() {
+    let s = setup();
+    create_active_series(&s, 1);
+    create_active_series(&s, 2);
+
+    let other = Address::generate(&s.env);
+    s.stablecoin.mint(&other, &(980 * SCALE));
+    s.vault.subscribe(&s.user, &1, &(490 * SCALE), &None);
+    s.vault.subscribe(&other, &1, &(980 * SCALE), &None);
+    s.vault.subscribe(&s.user, &1, &(490 * SCALE), &None); // not re-indexed
+
+    let series = s.vault.export_series(&1, &10);
+    assert_eq!(series.len(), 1);
+    assert_eq!(series.get(0).unwrap().series_id, 2);
+
+    let first = s.vault.export_positions(&1, &0, &1);
+    assert_eq!(first.len(), 1);
+    assert_eq!(first.get(0).unwrap().user, s.user);
+    assert_eq!(first.get(0).unwrap().subscribed_par, 1_000 * SCALE);
+
+    let rest = s.vault.export_positions(&1, &1, &10);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest.get(0).unwrap().user, other);
+    assert_eq!(s.vault.export_positions(&1, &2, &10).len(), 0);
+}
+
+#[test]
+fn test_derived_series_id_is_deterministic() {
+    let s = setup();
+    let cap = 1_000_000 * SCALE;
+    let user_cap = 100_000 * SCALE;
+
+    let expected = s
+        .vault
+        .derive_series_id(&ISSUE_DATE, &MATURITY_DATE, &ISSUE_PRICE, &0);
+    let series_id = s.vault.create_series_derived(
+        &ISSUE_DATE,
+        &MATURITY_DATE,
+        &ISSUE_PRICE,
+        &0,
+        &cap,
+        &user_cap,
+        &None,
+    );
+    assert_eq!(series_id, expected);
+    assert_eq!(s.vault.get_series(&series_id).issue_price, ISSUE_PRICE);
+
+    // Same terms collide unless the nonce is bumped
+    assert_eq!(
+        s.vault.try_create_series_derived(
+            &ISSUE_DATE,
+            &MATURITY_DATE,
+            &ISSUE_PRICE,
+            &0,
+            &cap,
+            &user_cap,
+            &None
+        ),
+        Err(Ok(Error::SeriesAlreadyExists))
+    );
+    let second = s.vault.create_series_derived(
+        &ISSUE_DATE,
+        &MATURITY_DATE,
+        &ISSUE_PRICE,
+        &1,
+        &cap,
+        &user_cap,
+        &None,
+    );
+    assert_ne!(second, series_id);
+}
+
+#[test]
+fn test_subscribe_footprint_budget() {
+    let s = setup();
+    create_active_series(&s, 1);
+    s.vault.subscribe(&s.user, &1, &(98 * SCALE), &None);
+
+    // A repeat subscription: config read once, totals booked on the series tally,
+    // volume kept in its own persistent entry, one statement line appended,
+    // the running liability total bumped
+    s.env.cost_estimate().budget().reset_default();
+    s.vault.subscribe(&s.user, &1, &(98 * SCALE), &None);
+    let resources = s.env.cost_estimate().resources();
+    assert!(resources.instructions < 925_000);
+    assert!(resources.read_bytes < 3_550);
+    assert!(resources.write_bytes < 3_800);
+
+    let accounting = s.vault.get_protocol_accounting();
+    assert_eq!(accounting.total_subscriptions_collected, 196 * SCALE);
+    assert_eq!(accounting.total_par_minted, 200 * SCALE);
+}
@@ -15,4 +15,8 @@ pub enum Error {
     // Balance errors
     InsufficientBalance = 4,
     InvalidAmount = 5,
+    BelowDustThreshold = 8,
+
+    // Hook errors
+    TransferRejected = 7,
 }
@@ -24,3 +24,17 @@ pub struct BurnEvent {
     pub from: Address,
     pub amount: i128,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferHookUpdatedEvent {
+    pub series_id: u32,
+    pub hook: Option<Address>,
+    pub blocking: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DustThresholdUpdatedEvent {
+    pub threshold: i128,
+}
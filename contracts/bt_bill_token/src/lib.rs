@@ -4,11 +4,14 @@ mod error;
 mod events;
 mod storage;
 
-use error::Error;
-use events::{BurnEvent, MintEvent, TransferEvent};
-use storage::{Balance, DataKey};
+pub use error::Error;
+use events::{
+    BurnEvent, DustThresholdUpdatedEvent, MintEvent, TransferEvent, TransferHookUpdatedEvent,
+};
+use storage::{Balance, DataKey, TransferHook};
 
-use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+use bingo_events::{publish, publish_global, TOKEN};
+use soroban_sdk::{contract, contractimpl, vec, Address, Env, IntoVal, Symbol};
 
 #[contract]
 pub struct BTBillToken;
@@ -72,6 +75,117 @@ impl BTBillToken {
         Ok(())
     }
 
+    /// Set the transfer hook for a series
+    ///
+    /// A blocking hook that fails or panics reverts the transfer; a
+    /// non-blocking hook is called best-effort and its failures are ignored.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is not admin
+    pub fn set_transfer_hook(
+        env: Env,
+        series_id: u32,
+        hook: Address,
+        blocking: bool,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        env.storage().instance().set(
+            &DataKey::TransferHook(series_id),
+            &TransferHook {
+                hook: hook.clone(),
+                blocking,
+            },
+        );
+
+        publish(
+            &env,
+            TOKEN,
+            "transfer_hook_updated",
+            series_id,
+            TransferHookUpdatedEvent {
+                series_id,
+                hook: Some(hook),
+                blocking,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Remove the transfer hook for a series
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is not admin
+    pub fn remove_transfer_hook(env: Env, series_id: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::TransferHook(series_id));
+
+        publish(
+            &env,
+            TOKEN,
+            "transfer_hook_updated",
+            series_id,
+            TransferHookUpdatedEvent {
+                series_id,
+                hook: None,
+                blocking: false,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Set the minimum transfer amount, in PAR (0 disables)
+    ///
+    /// Transfers below the threshold, or that would leave the sender with a
+    /// positive balance below it, are rejected. Mint and burn are left to the
+    /// operators, which apply their own dust policy.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: Threshold is negative
+    pub fn set_dust_threshold(env: Env, threshold: i128) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        if threshold < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::DustThreshold, &threshold);
+
+        publish_global(
+            &env,
+            TOKEN,
+            "dust_threshold_updated",
+            DustThresholdUpdatedEvent { threshold },
+        );
+
+        Ok(())
+    }
+
     /// Mint tokens (only operators)
     /// 
     /// # Errors
@@ -104,9 +218,13 @@ impl BTBillToken {
                 amount: new_balance,
             },
         );
+        Self::adjust_total_supply(&env, series_id, amount)?;
 
-        env.events().publish(
-            (Symbol::new(&env, "mint"), series_id),
+        publish(
+            &env,
+            TOKEN,
+            "mint",
+            series_id,
             MintEvent {
                 series_id,
                 to: to.clone(),
@@ -155,9 +273,13 @@ impl BTBillToken {
                 },
             );
         }
+        Self::adjust_total_supply(&env, series_id, -amount)?;
 
-        env.events().publish(
-            (Symbol::new(&env, "burn"), series_id),
+        publish(
+            &env,
+            TOKEN,
+            "burn",
+            series_id,
             BurnEvent {
                 series_id,
                 from: from.clone(),
@@ -175,6 +297,8 @@ impl BTBillToken {
     /// - `InvalidAmount`: Amount <= 0
     /// - `Unauthorized`: From address did not authorize
     /// - `InsufficientBalance`: Not enough balance
+    /// - `BelowDustThreshold`: Amount or sender's leftover balance below the dust threshold
+    /// - `TransferRejected`: Blocking transfer hook failed
     pub fn transfer(
         env: Env,
         series_id: u32,
@@ -211,6 +335,12 @@ impl BTBillToken {
             .unwrap_or(Balance { amount: 0 });
 
         let new_from_balance = from_balance.amount - amount;
+
+        let dust_threshold = Self::get_dust_threshold(env.clone());
+        if amount < dust_threshold || (new_from_balance > 0 && new_from_balance < dust_threshold) {
+            return Err(Error::BelowDustThreshold);
+        }
+
         let new_to_balance = to_balance
             .amount
             .checked_add(amount)
@@ -234,8 +364,11 @@ impl BTBillToken {
             },
         );
 
-        env.events().publish(
-            (Symbol::new(&env, "transfer"), series_id),
+        publish(
+            &env,
+            TOKEN,
+            "transfer",
+            series_id,
             TransferEvent {
                 series_id,
                 from: from.clone(),
@@ -244,9 +377,25 @@ impl BTBillToken {
             },
         );
 
+        Self::notify_transfer_hook(&env, series_id, &from, &to, amount)?;
+
         Ok(())
     }
 
+    /// Get the transfer hook configured for a series
+    pub fn get_transfer_hook(env: Env, series_id: u32) -> Option<TransferHook> {
+        env.storage()
+            .instance()
+            .get(&DataKey::TransferHook(series_id))
+    }
+
+    pub fn get_dust_threshold(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::DustThreshold)
+            .unwrap_or(0)
+    }
+
     /// Get balance for a user in a series
     pub fn balance_of(env: Env, series_id: u32, user: Address) -> i128 {
         env.storage()
@@ -256,6 +405,14 @@ impl BTBillToken {
             .amount
     }
 
+    /// Total outstanding supply of a series (minted minus burned)
+    pub fn total_supply(env: Env, series_id: u32) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalSupply(series_id))
+            .unwrap_or(0)
+    }
+
     /// Check if address is an operator
     pub fn is_operator(env: Env, address: Address) -> bool {
         env.storage()
@@ -263,19 +420,79 @@ impl BTBillToken {
             .get::<DataKey, bool>(&DataKey::Operators(address))
             .unwrap_or(false)
     }
+
+    fn adjust_total_supply(env: &Env, series_id: u32, delta: i128) -> Result<(), Error> {
+        let supply = Self::total_supply(env.clone(), series_id)
+            .checked_add(delta)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalSupply(series_id), &supply);
+        Ok(())
+    }
+
+    /// Call the series transfer hook, if any, after balances have moved
+    fn notify_transfer_hook(
+        env: &Env,
+        series_id: u32,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let Some(config) = env
+            .storage()
+            .instance()
+            .get::<DataKey, TransferHook>(&DataKey::TransferHook(series_id))
+        else {
+            return Ok(());
+        };
+
+        let result = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &config.hook,
+            &Symbol::new(env, "on_transfer"),
+            vec![
+                env,
+                series_id.into_val(env),
+                from.into_val(env),
+                to.into_val(env),
+                amount.into_val(env),
+            ],
+        );
+
+        match result {
+            Ok(Ok(())) => Ok(()),
+            _ if config.blocking => Err(Error::TransferRejected),
+            _ => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Address, Env};
+    use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env};
 
     const SCALE: i128 = 10_000_000;
 
+    /// Compliance stand-in: rejects transfers above a fixed amount
+    #[contract]
+    pub struct MockTransferHook;
+
+    #[contractimpl]
+    impl MockTransferHook {
+        pub fn on_transfer(_env: Env, _series_id: u32, _from: Address, _to: Address, amount: i128) {
+            if amount > 100 * SCALE {
+                panic!("transfer above limit");
+            }
+        }
+    }
+
     #[test]
     fn test_initialize() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, BTBillToken);
+        env.mock_all_auths();
+
+        let contract_id = env.register(BTBillToken, ());
         let client = BTBillTokenClient::new(&env, &contract_id);
 
         let admin = Address::generate(&env);
@@ -288,7 +505,9 @@ mod test {
     #[test]
     fn test_mint_and_balance() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, BTBillToken);
+        env.mock_all_auths();
+
+        let contract_id = env.register(BTBillToken, ());
         let client = BTBillTokenClient::new(&env, &contract_id);
 
         let admin = Address::generate(&env);
@@ -310,7 +529,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register_contract(None, BTBillToken);
+        let contract_id = env.register(BTBillToken, ());
         let client = BTBillTokenClient::new(&env, &contract_id);
 
         let admin = Address::generate(&env);
@@ -332,7 +551,9 @@ mod test {
     #[test]
     fn test_burn() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, BTBillToken);
+        env.mock_all_auths();
+
+        let contract_id = env.register(BTBillToken, ());
         let client = BTBillTokenClient::new(&env, &contract_id);
 
         let admin = Address::generate(&env);
@@ -347,6 +568,7 @@ mod test {
         client.burn(&series_id, &user, &(400i128 * SCALE));
 
         assert_eq!(client.balance_of(&series_id, &user), 600i128 * SCALE);
+        assert_eq!(client.total_supply(&series_id), 600i128 * SCALE);
     }
 
     #[test]
@@ -354,7 +576,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register_contract(None, BTBillToken);
+        let contract_id = env.register(BTBillToken, ());
         let client = BTBillTokenClient::new(&env, &contract_id);
 
         let admin = Address::generate(&env);
@@ -371,4 +593,69 @@ mod test {
         let result = client.try_transfer(&series_id, &user1, &user2, &(1500i128 * SCALE));
         assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
     }
+
+    #[test]
+    fn test_transfer_hook_blocking_and_non_blocking() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BTBillToken, ());
+        let client = BTBillTokenClient::new(&env, &contract_id);
+        let hook = env.register(MockTransferHook, ());
+
+        let admin = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        let series_id = 1u32;
+        client.mint(&series_id, &user1, &(1000i128 * SCALE));
+
+        client.set_transfer_hook(&series_id, &hook, &true);
+        client.transfer(&series_id, &user1, &user2, &(100i128 * SCALE));
+        let result = client.try_transfer(&series_id, &user1, &user2, &(200i128 * SCALE));
+        assert_eq!(result, Err(Ok(Error::TransferRejected)));
+        assert_eq!(client.balance_of(&series_id, &user2), 100i128 * SCALE);
+
+        // Other series are unaffected
+        client.mint(&2, &user1, &(1000i128 * SCALE));
+        client.transfer(&2, &user1, &user2, &(200i128 * SCALE));
+
+        client.set_transfer_hook(&series_id, &hook, &false);
+        client.transfer(&series_id, &user1, &user2, &(200i128 * SCALE));
+        assert_eq!(client.balance_of(&series_id, &user2), 300i128 * SCALE);
+
+        client.remove_transfer_hook(&series_id);
+        assert_eq!(client.get_transfer_hook(&series_id), None);
+    }
+
+    #[test]
+    fn test_dust_threshold_on_transfer() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BTBillToken, ());
+        let client = BTBillTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.set_dust_threshold(&SCALE);
+
+        let series_id = 1u32;
+        client.mint(&series_id, &user1, &(10i128 * SCALE));
+
+        let result = client.try_transfer(&series_id, &user1, &user2, &(SCALE / 2));
+        assert_eq!(result, Err(Ok(Error::BelowDustThreshold)));
+
+        // Would strand 0.5 PAR with the sender
+        let result = client.try_transfer(&series_id, &user1, &user2, &(95i128 * SCALE / 10));
+        assert_eq!(result, Err(Ok(Error::BelowDustThreshold)));
+
+        client.transfer(&series_id, &user1, &user2, &(10i128 * SCALE));
+        assert_eq!(client.balance_of(&series_id, &user2), 10i128 * SCALE);
+    }
 }
@@ -6,6 +6,14 @@ pub struct Balance {
     pub amount: i128,
 }
 
+/// Contract notified via `on_transfer(series_id, from, to, amount)` after each transfer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferHook {
+    pub hook: Address,
+    pub blocking: bool, // A failing hook reverts the transfer
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -13,4 +21,7 @@ pub enum DataKey {
     Operators(Address),
     Balance(u32, Address), // (series_id, user)
     Initialized,
+    TransferHook(u32), // series_id → TransferHook
+    DustThreshold,     // Minimum transfer / leftover balance, in PAR
+    TotalSupply(u32),  // series_id → outstanding supply
 }
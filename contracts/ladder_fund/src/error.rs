@@ -0,0 +1,50 @@
+use bingo_shares::ShareError;
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // ============================================
+    // INITIALIZATION ERRORS (1-5)
+    // ============================================
+    /// Contract already initialized
+    AlreadyInitialized = 1,
+    /// Contract not initialized
+    NotInitialized = 2,
+
+    // ============================================
+    // LADDER ERRORS (20-29)
+    // ============================================
+    /// Ladder weights must be positive and sum to 10,000 bps, one rung per series
+    InvalidLadder = 20,
+    /// No ladder configured to deploy deposits into
+    LadderNotSet = 21,
+    /// Series is not a rung of the ladder
+    SeriesNotInLadder = 22,
+    /// Fund holds no bT-Bills of this series
+    SeriesNotHeld = 23,
+    /// Rung still holds bT-Bills; roll it before removing it from the ladder
+    RungNotEmpty = 24,
+
+    // ============================================
+    // AMOUNT/BALANCE ERRORS (30-39)
+    // ============================================
+    /// Amount must be positive
+    InvalidAmount = 30,
+    /// Not enough fund shares
+    InsufficientBalance = 31,
+    /// Deposit too small to mint a whole share
+    ZeroShares = 32,
+    /// Shares are outstanding against a zero NAV; a deposit would dilute them
+    NavDepleted = 33,
+}
+
+impl From<ShareError> for Error {
+    fn from(error: ShareError) -> Self {
+        match error {
+            ShareError::InvalidAmount => Error::InvalidAmount,
+            ShareError::InsufficientBalance => Error::InsufficientBalance,
+        }
+    }
+}
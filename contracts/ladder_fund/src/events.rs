@@ -0,0 +1,43 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+use crate::storage::LadderRung;
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LadderUpdatedEvent {
+    pub rungs: Vec<LadderRung>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundDepositEvent {
+    pub user: Address,
+    pub amount: i128,
+    pub shares: i128,
+    pub nav: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundWithdrawEvent {
+    pub user: Address,
+    pub shares: i128,
+    pub stablecoin_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundRolledEvent {
+    pub from_series_id: u32,
+    pub to_series_id: u32,
+    pub redeemed_par: i128,
+    pub minted_par: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundTransferEvent {
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+}
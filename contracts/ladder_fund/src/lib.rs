@@ -0,0 +1,479 @@
+#![no_std]
+
+mod error;
+mod events;
+mod nav;
+mod storage;
+
+pub use error::Error;
+use events::*;
+use nav::{split_by_weight, validate_ladder};
+use storage::DataKey;
+pub use storage::LadderRung;
+
+use bingo_events::{publish, publish_global, LADDER};
+use bingo_shares::{
+    authorize_subscription, burn_shares, calculate_pro_rata, calculate_share_price,
+    calculate_shares_for_deposit, idle_cash, mint_shares, move_shares, set_idle_cash,
+};
+use soroban_sdk::{contract, contractimpl, token, vec, Address, Env, IntoVal, Symbol, Vec};
+
+/// Maturity ladder fund: a tokenized money-market fund on top of the vault
+///
+/// Stablecoin deposits are subscribed straight into the configured ladder of
+/// series (one per tenor). Fund shares are priced at NAV, so they reflect the
+/// blended accretion of every rung. The admin keeper rolls each matured rung
+/// into the next series of the same tenor.
+#[contract]
+pub struct LadderFund;
+
+#[contractimpl]
+impl LadderFund {
+    // ============================================
+    // INITIALIZATION & ADMIN
+    // ============================================
+
+    /// Initialize the fund
+    ///
+    /// # Errors
+    /// - `AlreadyInitialized`: Contract already initialized
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        vault: Address,
+        bt_bill_token: Address,
+        stablecoin: Address,
+    ) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Initialized, &true);
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Vault, &vault);
+        env.storage()
+            .instance()
+            .set(&DataKey::BTBillToken, &bt_bill_token);
+        env.storage().instance().set(&DataKey::Stablecoin, &stablecoin);
+
+        Ok(())
+    }
+
+    /// Configure the ladder (series per tenor and deposit weights)
+    ///
+    /// A rung can only be dropped once the fund holds none of its series;
+    /// roll it first so its value stays in NAV.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidLadder`: Weights not positive or not summing to 10,000 bps, or a series repeats
+    /// - `RungNotEmpty`: A dropped rung still holds bT-Bills
+    pub fn set_ladder(env: Env, rungs: Vec<LadderRung>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        if !validate_ladder(&rungs) {
+            return Err(Error::InvalidLadder);
+        }
+
+        let bt_bill_token = Self::bt_bill_token(&env)?;
+        for old in Self::get_ladder(env.clone()).iter() {
+            let kept = rungs.iter().any(|rung| rung.series_id == old.series_id);
+            if !kept && Self::bt_balance(&env, &bt_bill_token, old.series_id) > 0 {
+                return Err(Error::RungNotEmpty);
+            }
+        }
+
+        env.storage().instance().set(&DataKey::Ladder, &rungs);
+
+        publish_global(&env, LADDER, "ladder_updated", LadderUpdatedEvent { rungs });
+
+        Ok(())
+    }
+
+    // ============================================
+    // DEPOSIT / WITHDRAW
+    // ============================================
+
+    /// Deposit stablecoin; it is subscribed across the ladder immediately
+    ///
+    /// Shares are minted for the NAV the deposit added, i.e. net of any vault
+    /// subscription fee, so fee-paying deposits don't dilute holders.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: amount must be positive
+    /// - `LadderNotSet`: No ladder configured
+    /// - `NavDepleted`: Shares outstanding against a zero NAV
+    /// - `ZeroShares`: Deposit too small to mint any shares
+    pub fn deposit(env: Env, user: Address, amount: i128) -> Result<i128, Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        user.require_auth();
+
+        let rungs = Self::get_ladder(env.clone());
+        if rungs.is_empty() {
+            return Err(Error::LadderNotSet);
+        }
+
+        let nav_before = Self::nav(env.clone())?;
+        let total_supply = Self::total_supply(env.clone());
+        if total_supply > 0 && nav_before <= 0 {
+            return Err(Error::NavDepleted);
+        }
+
+        let stablecoin_client = token::Client::new(&env, &Self::stablecoin(&env)?);
+        let this = env.current_contract_address();
+        let cash_before = stablecoin_client.balance(&this);
+        stablecoin_client.transfer(&user, &this, &amount);
+
+        let mut remaining = amount;
+        let last = rungs.len() - 1;
+        for (i, rung) in rungs.iter().enumerate() {
+            let slice = if i as u32 == last {
+                remaining
+            } else {
+                split_by_weight(amount, rung.weight_bps).ok_or(Error::InvalidAmount)?
+            };
+            if slice > 0 {
+                Self::subscribe_to_vault(&env, rung.series_id, slice)?;
+                remaining -= slice;
+            }
+        }
+        Self::book_leftover_cash(&env, cash_before)?;
+
+        let nav_after = Self::nav(env.clone())?;
+        let shares = calculate_shares_for_deposit(nav_after - nav_before, total_supply, nav_before)
+            .ok_or(Error::InvalidAmount)?;
+        if shares <= 0 {
+            return Err(Error::ZeroShares);
+        }
+        mint_shares(&env, &user, shares)?;
+
+        publish(
+            &env,
+            LADDER,
+            "fund_deposit",
+            user.clone(),
+            FundDepositEvent {
+                user,
+                amount,
+                shares,
+                nav: nav_after,
+            },
+        );
+
+        Ok(shares)
+    }
+
+    /// Burn shares for a pro-rata slice of every rung plus booked idle stablecoin
+    ///
+    /// Returns the stablecoin paid; bT-Bills are delivered in kind.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: shares must be positive
+    /// - `InsufficientBalance`: Not enough shares
+    pub fn withdraw(env: Env, user: Address, shares: i128) -> Result<i128, Error> {
+        if shares <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        user.require_auth();
+
+        let total_supply = Self::total_supply(env.clone());
+        let bt_bill_token = Self::bt_bill_token(&env)?;
+        let this = env.current_contract_address();
+
+        burn_shares(&env, &user, shares)?;
+
+        for rung in Self::get_ladder(env.clone()).iter() {
+            let held = Self::bt_balance(&env, &bt_bill_token, rung.series_id);
+            let out = calculate_pro_rata(held, shares, total_supply).ok_or(Error::InvalidAmount)?;
+            if out > 0 {
+                env.invoke_contract::<()>(
+                    &bt_bill_token,
+                    &Symbol::new(&env, "transfer"),
+                    vec![
+                        &env,
+                        rung.series_id.into(),
+                        this.to_val(),
+                        user.to_val(),
+                        out.into_val(&env),
+                    ],
+                );
+            }
+        }
+
+        let idle = idle_cash(&env);
+        let stablecoin_out =
+            calculate_pro_rata(idle, shares, total_supply).ok_or(Error::InvalidAmount)?;
+        if stablecoin_out > 0 {
+            set_idle_cash(&env, idle - stablecoin_out);
+            token::Client::new(&env, &Self::stablecoin(&env)?).transfer(
+                &this,
+                &user,
+                &stablecoin_out,
+            );
+        }
+
+        publish(
+            &env,
+            LADDER,
+            "fund_withdraw",
+            user.clone(),
+            FundWithdrawEvent {
+                user,
+                shares,
+                stablecoin_out,
+            },
+        );
+
+        Ok(stablecoin_out)
+    }
+
+    // ============================================
+    // MATURITY ROLLOVER
+    // ============================================
+
+    /// Redeem a matured rung at PAR and reinvest into the next series of that tenor
+    ///
+    /// The ladder rung is repointed to `to_series_id`, keeping its weight.
+    /// Returns the PAR minted in the new series.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotInLadder`: from_series_id is not a rung
+    /// - `InvalidLadder`: to_series_id is already a rung
+    /// - `SeriesNotHeld`: Fund holds nothing in from_series_id
+    pub fn roll(env: Env, from_series_id: u32, to_series_id: u32) -> Result<i128, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        let mut rungs = Self::get_ladder(env.clone());
+        let index = rungs
+            .iter()
+            .position(|rung| rung.series_id == from_series_id)
+            .ok_or(Error::SeriesNotInLadder)? as u32;
+        if rungs.iter().any(|rung| rung.series_id == to_series_id) {
+            return Err(Error::InvalidLadder);
+        }
+
+        let vault = Self::vault(&env)?;
+        let bt_bill_token = Self::bt_bill_token(&env)?;
+        let stablecoin_client = token::Client::new(&env, &Self::stablecoin(&env)?);
+        let this = env.current_contract_address();
+
+        let redeemed_par = Self::bt_balance(&env, &bt_bill_token, from_series_id);
+        if redeemed_par <= 0 {
+            return Err(Error::SeriesNotHeld);
+        }
+
+        let cash_before = stablecoin_client.balance(&this);
+        env.invoke_contract::<()>(
+            &vault,
+            &Symbol::new(&env, "redeem"),
+            vec![
+                &env,
+                this.to_val(),
+                from_series_id.into(),
+                redeemed_par.into_val(&env),
+                Option::<Address>::None.into_val(&env),
+            ],
+        );
+        let proceeds = stablecoin_client.balance(&this) - cash_before;
+
+        let minted_par = Self::subscribe_to_vault(&env, to_series_id, proceeds)?;
+        Self::book_leftover_cash(&env, cash_before)?;
+
+        let mut rung = rungs.get(index).ok_or(Error::SeriesNotInLadder)?;
+        rung.series_id = to_series_id;
+        rungs.set(index, rung);
+        env.storage().instance().set(&DataKey::Ladder, &rungs);
+
+        publish(
+            &env,
+            LADDER,
+            "fund_rolled",
+            from_series_id,
+            FundRolledEvent {
+                from_series_id,
+                to_series_id,
+                redeemed_par,
+                minted_par,
+            },
+        );
+
+        Ok(minted_par)
+    }
+
+    // ============================================
+    // SHARE TOKEN INTERFACE
+    // ============================================
+
+    /// Transfer fund shares
+    ///
+    /// # Errors
+    /// - `InvalidAmount`: amount must be positive
+    /// - `InsufficientBalance`: Not enough shares
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        from.require_auth();
+
+        move_shares(&env, &from, &to, amount)?;
+
+        publish_global(
+            &env,
+            LADDER,
+            "transfer",
+            FundTransferEvent { from, to, amount },
+        );
+
+        Ok(())
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        bingo_shares::balance(&env, &id)
+    }
+
+    pub fn total_supply(env: Env) -> i128 {
+        bingo_shares::total_supply(&env)
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    // ============================================
+    // VIEW FUNCTIONS
+    // ============================================
+
+    /// Net asset value: every rung at accreted price + booked idle stablecoin
+    ///
+    /// Stablecoin sent to the fund directly is not booked, so it cannot
+    /// move the share price.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn nav(env: Env) -> Result<i128, Error> {
+        let vault = Self::vault(&env)?;
+        let bt_bill_token = Self::bt_bill_token(&env)?;
+
+        let mut nav = idle_cash(&env);
+        for rung in Self::get_ladder(env.clone()).iter() {
+            let held = Self::bt_balance(&env, &bt_bill_token, rung.series_id);
+            if held > 0 {
+                let value: i128 = env.invoke_contract(
+                    &vault,
+                    &Symbol::new(&env, "collateral_value"),
+                    vec![&env, rung.series_id.into(), held.into_val(&env)],
+                );
+                nav = nav.checked_add(value).ok_or(Error::InvalidAmount)?;
+            }
+        }
+
+        Ok(nav)
+    }
+
+    /// NAV per share (scaled, 1.0 = SCALE)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn share_price(env: Env) -> Result<i128, Error> {
+        let nav = Self::nav(env.clone())?;
+        calculate_share_price(nav, Self::total_supply(env)).ok_or(Error::InvalidAmount)
+    }
+
+    pub fn get_ladder(env: Env) -> Vec<LadderRung> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Ladder)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ============================================
+    // INTERNAL HELPERS
+    // ============================================
+
+    fn vault(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Vault)
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn bt_bill_token(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::BTBillToken)
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn stablecoin(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Stablecoin)
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn bt_balance(env: &Env, bt_bill_token: &Address, series_id: u32) -> i128 {
+        env.invoke_contract(
+            bt_bill_token,
+            &Symbol::new(env, "balance_of"),
+            vec![
+                env,
+                series_id.into(),
+                env.current_contract_address().to_val(),
+            ],
+        )
+    }
+
+    /// Subscribe fund stablecoin into a vault series, returning PAR minted
+    fn subscribe_to_vault(env: &Env, series_id: u32, amount: i128) -> Result<i128, Error> {
+        let vault = Self::vault(env)?;
+        let bt_bill_token = Self::bt_bill_token(env)?;
+        let this = env.current_contract_address();
+
+        // The authorization covers only our next call, so it must be subscribe
+        let par_before = Self::bt_balance(env, &bt_bill_token, series_id);
+        authorize_subscription(env, &vault, &Self::stablecoin(env)?, series_id, amount);
+        env.invoke_contract::<()>(
+            &vault,
+            &Symbol::new(env, "subscribe"),
+            vec![
+                env,
+                this.to_val(),
+                series_id.into(),
+                amount.into_val(env),
+                Option::<Address>::None.into_val(env),
+            ],
+        );
+
+        Ok(Self::bt_balance(env, &bt_bill_token, series_id) - par_before)
+    }
+
+    /// Book stablecoin held beyond `cash_before` as idle cash
+    ///
+    /// The vault normally takes every subscription in full, leaving nothing.
+    fn book_leftover_cash(env: &Env, cash_before: i128) -> Result<(), Error> {
+        let held = token::Client::new(env, &Self::stablecoin(env)?)
+            .balance(&env.current_contract_address());
+        set_idle_cash(env, idle_cash(env) + held - cash_before);
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::storage::{LadderRung, BASIS_POINTS};
+use soroban_sdk::Vec;
+
+/// Validate a ladder: non-empty, distinct series, positive weights summing to 100%
+pub fn validate_ladder(rungs: &Vec<LadderRung>) -> bool {
+    if rungs.is_empty() {
+        return false;
+    }
+
+    let mut total: i128 = 0;
+    for (i, rung) in rungs.iter().enumerate() {
+        if rung.weight_bps <= 0 {
+            return false;
+        }
+        let repeated = rungs
+            .iter()
+            .skip(i + 1)
+            .any(|other| other.series_id == rung.series_id);
+        if repeated {
+            return false;
+        }
+        total += rung.weight_bps;
+    }
+    total == BASIS_POINTS
+}
+
+/// Split a deposit across ladder rungs by weight
+///
+/// Formula: rung_amount = amount × weight_bps / BASIS_POINTS
+///
+/// Callers send the rounding dust to the last rung so the whole deposit is deployed.
+pub fn split_by_weight(amount: i128, weight_bps: i128) -> Option<i128> {
+    amount.checked_mul(weight_bps)?.checked_div(BASIS_POINTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{vec, Env};
+
+    #[test]
+    fn test_validate_ladder() {
+        let env = Env::default();
+        let valid = vec![
+            &env,
+            LadderRung { series_id: 1, weight_bps: 5_000 },
+            LadderRung { series_id: 2, weight_bps: 5_000 },
+        ];
+        let short = vec![&env, LadderRung { series_id: 1, weight_bps: 9_000 }];
+        let duplicate = vec![
+            &env,
+            LadderRung { series_id: 1, weight_bps: 5_000 },
+            LadderRung { series_id: 1, weight_bps: 5_000 },
+        ];
+
+        assert!(validate_ladder(&valid));
+        assert!(!validate_ladder(&short));
+        assert!(!validate_ladder(&duplicate));
+        assert!(!validate_ladder(&Vec::new(&env)));
+    }
+
+    #[test]
+    fn test_split_by_weight_sends_dust_to_last_rung() {
+        let amount = 1_000;
+        let first = split_by_weight(amount, 3_333).unwrap();
+        let second = split_by_weight(amount, 3_333).unwrap();
+        let last = amount - first - second;
+
+        assert_eq!(first, 333);
+        assert_eq!(last, 334);
+    }
+}
@@ -0,0 +1,26 @@
+use soroban_sdk::contracttype;
+
+// Constants
+pub const BASIS_POINTS: i128 = 10_000; // 100% = 10,000 basis points
+
+/// One tenor of the maturity ladder
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LadderRung {
+    /// Vault series currently filling this tenor
+    pub series_id: u32,
+    /// Share of each deposit subscribed into this rung
+    pub weight_bps: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Vault,
+    BTBillToken,
+    Stablecoin,
+    Ladder, // Vec<LadderRung>
+    Initialized,
+    // Balances, supply and idle cash live under bingo_shares::ShareKey
+}
@@ -17,6 +17,8 @@ pub enum Error {
     // ============================================
     /// Caller not authorized (not treasury)
     Unauthorized = 10,
+    /// Borrower is on the vault's blocklist
+    AddressBlocked = 11,
     
     // ============================================
     // POSITION ERRORS (20-29)
@@ -25,6 +27,16 @@ pub enum Error {
     PositionNotFound = 20,
     /// Invalid position status for this operation
     InvalidStatus = 21,
+    /// No receipt yet (position still open or unknown)
+    ReceiptNotFound = 22,
+    /// Quote unknown or already used
+    QuoteNotFound = 23,
+    /// Borrower has no margin account
+    MarginAccountNotFound = 24,
+    /// Term auction not found
+    AuctionNotFound = 25,
+    /// Bidder has no bids in the auction
+    BidNotFound = 26,
     
     // ============================================
     // AMOUNT ERRORS (30-39)
@@ -33,6 +45,10 @@ pub enum Error {
     InvalidAmount = 30,
     /// Requested cash exceeds LTV limit (collateral × price × (1 - haircut))
     ExceedsMaxCash = 31,
+    /// Automated repayment would exceed the borrower's automation allowance
+    ExceedsAutomationAllowance = 32,
+    /// Cash out below the vault's dust threshold
+    BelowDustThreshold = 33,
     
     // ============================================
     // DEADLINE ERRORS (40-49)
@@ -43,10 +59,54 @@ pub enum Error {
     DeadlineNotPassed = 41,
     /// Cannot close repo: deadline already passed (defaulted)
     DeadlinePassed = 42,
+    /// Automated close attempted earlier than AUTO_CLOSE_WINDOW before deadline
+    OutsideAutoCloseWindow = 43,
+    /// Locked quote is past its expiry ledger
+    QuoteExpired = 44,
+    /// Auction bidding has ended, the auction is cleared or lapsed, or its bid book is full
+    AuctionClosed = 45,
+    /// Auction bidding is still running
+    AuctionNotEnded = 46,
+    /// Collateral series matures within the pre-maturity blackout window
+    MaturityBlackout = 47,
+    /// Auction can still be cleared; escrow is reclaimable once AUCTION_CLEARING_WINDOW has passed
+    ClearingWindowOpen = 48,
     
     // ============================================
     // OPERATIONAL ERRORS (50-59)
     // ============================================
     /// Contract is paused
     ContractPaused = 50,
+
+    // ============================================
+    // CONFIGURATION ERRORS (60-69)
+    // ============================================
+    /// Fee tiers must be ascending by volume with discounts in [0, 10,000] bps
+    InvalidFeeTiers = 60,
+    /// Mark override price must be in (0, SCALE] with an expiry in the future
+    InvalidMarkOverride = 61,
+    /// Default penalty above MAX_DEFAULT_PENALTY_BPS or insurance share outside [0, 10,000] bps
+    InvalidDefaultPenalty = 62,
+    /// Funding limits must be non-negative, with the weekly limit at least the daily one
+    InvalidFundingLimits = 63,
+    /// Default split must have 1 to MAX_DEFAULT_SHARES positive weights summing to 10,000 bps
+    InvalidDefaultSplit = 64,
+    /// Eligibility matrix must have at most MAX_ELIGIBILITY_RULES rows with haircuts in [0, 10,000] bps and ordered bounds
+    InvalidEligibilityRules = 65,
+    /// Capitalization interval must be 0 (disabled) or at least MIN_CAPITALIZATION_INTERVAL
+    InvalidCapitalizationInterval = 66,
+
+    // ============================================
+    // VAULT REGISTRY ERRORS (70-79)
+    // ============================================
+    /// Vault is already registered
+    VaultAlreadyRegistered = 70,
+    /// Vault is not registered
+    VaultNotRegistered = 71,
+    /// No registered vault has the requested series
+    SeriesNotFound = 72,
+    /// Series collateral is ineligible under the eligibility matrix
+    CollateralIneligible = 73,
+    /// Series belongs to a different vault than the margin account or auction
+    VaultMismatch = 74,
 }
@@ -1,15 +1,47 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
+
+use crate::storage::{
+    AnnotationKind, DefaultAllocation, DefaultPenalty, DefaultShare, EligibilityRule, FeeTier,
+    FundingLimits, ReleaseSchedule,
+};
 
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct RepoOpenedEvent {
     pub position_id: u64,
     pub borrower: Address,
+    pub vault: Address,
     pub series_id: u32,
     pub collateral_par: i128,
     pub cash_out: i128,
     pub repurchase_amount: i128,
     pub deadline: u64,
+    pub spread_bps: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct QuoteLockedEvent {
+    pub quote_id: u64,
+    pub borrower: Address,
+    pub series_id: u32,
+    pub collateral_par: i128,
+    pub cash_out: i128,
+    pub repurchase_amount: i128,
+    pub mark_price: i128,
+    pub expires_at_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct QuoteTtlUpdatedEvent {
+    pub ledgers: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MaturityBlackoutUpdatedEvent {
+    pub blackout: u64,
 }
 
 #[contracttype]
@@ -20,11 +52,227 @@ pub struct RepoClosedEvent {
     pub repayment: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InstallmentRepaidEvent {
+    pub position_id: u64,
+    pub borrower: Address,
+    pub amount: i128,
+    pub repaid: i128,
+    pub remaining: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CollateralReleasedEvent {
+    pub position_id: u64,
+    pub borrower: Address,
+    /// Collateral (PAR) returned by this release
+    pub collateral_par: i128,
+    /// Collateral (PAR) still locked
+    pub remaining_collateral: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReleaseScheduleUpdatedEvent {
+    pub schedule: ReleaseSchedule,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct RepoDefaultedEvent {
     pub position_id: u64,
     pub borrower: Address,
     pub treasury: Address,
+    /// Collateral (PAR) kept by the lender
     pub collateral_claimed: i128,
+    pub penalty: i128,
+    pub insurance_penalty: i128,
+    /// Collateral (PAR) routed to the insurance fund
+    pub insurance_collateral: i128,
+    /// How collateral_claimed was split across beneficiaries
+    pub allocations: Vec<DefaultAllocation>,
+    /// Treasury's share (PAR) redeemed at PAR through the vault, the series
+    /// having matured; the rest was delivered as bT-Bills
+    pub redeemed_par: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FeeTiersUpdatedEvent {
+    pub tiers: Vec<FeeTier>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RewardsHookUpdatedEvent {
+    pub hook: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct VaultRegisteredEvent {
+    pub vault: Address,
+    pub bt_bill_token: Address,
+    pub stablecoin: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct VaultDeregisteredEvent {
+    pub vault: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MarkOverrideSetEvent {
+    pub vault: Address,
+    pub series_id: u32,
+    pub price: i128,
+    pub expiry: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MarkOverrideClearedEvent {
+    pub vault: Address,
+    pub series_id: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DefaultPenaltyUpdatedEvent {
+    pub penalty: DefaultPenalty,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DefaultSplitUpdatedEvent {
+    pub shares: Vec<DefaultShare>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AnnotatedEvent {
+    pub entity_kind: AnnotationKind,
+    pub id: u64,
+    pub note_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EligibilityRulesUpdatedEvent {
+    pub rules: Vec<EligibilityRule>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PositionArchivedEvent {
+    pub position_id: u64,
+    /// sha256 of the final (position, valuation history) XDR
+    pub state_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundingLimitsUpdatedEvent {
+    pub limits: FundingLimits,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundingLimitCosignedEvent {
+    pub admin: Address,
+    pub amount: i128,
+    pub daily_used: i128,
+    pub weekly_used: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CapitalizationUpdatedEvent {
+    /// Seconds between capitalizations for new positions (0 = spread due at close)
+    pub interval: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InterestCapitalizedEvent {
+    pub position_id: u64,
+    /// Interest added to the outstanding balance
+    pub interest: i128,
+    /// Debt outstanding after capitalization
+    pub outstanding: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MarginCollateralEvent {
+    pub borrower: Address,
+    pub series_id: u32,
+    pub collateral_par: i128,
+    /// Credit line after the change
+    pub credit_line: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MarginDrawnEvent {
+    pub borrower: Address,
+    pub amount: i128,
+    /// Total drawn after the draw
+    pub drawn: i128,
+    pub credit_line: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MarginRepaidEvent {
+    pub borrower: Address,
+    pub amount: i128,
+    /// Total still drawn
+    pub drawn: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuctionOpenedEvent {
+    pub auction_id: u64,
+    pub vault: Address,
+    pub bucket_size: i128,
+    pub buckets: u32,
+    pub term: u64,
+    pub bidding_ends: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BidSubmittedEvent {
+    pub auction_id: u64,
+    pub bidder: Address,
+    pub series_id: u32,
+    pub collateral_par: i128,
+    pub buckets: u32,
+    pub rate_bps: i128,
+}
+
+/// Escrowed collateral returned for a whole bid, rejected at clearing or reclaimed after lapse
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BidRefundedEvent {
+    pub auction_id: u64,
+    pub bidder: Address,
+    pub series_id: u32,
+    pub collateral_par: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuctionClearedEvent {
+    pub auction_id: u64,
+    pub clearing_rate_bps: i128,
+    pub buckets_filled: u32,
+    /// Repo positions opened for accepted bids, highest rate first
+    pub positions: Vec<u64>,
 }
@@ -1,16 +1,44 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
 mod error;
 mod events;
 mod storage;
+mod test;
 mod validation;
 
-use error::Error;
+pub use error::Error;
 use events::*;
-use storage::{DataKey, RepoPosition, RepoStatus};
-use validation::{calculate_max_cash, calculate_repurchase};
-
-use soroban_sdk::{contract, contractimpl, token, vec, Address, Env, IntoVal, Symbol};
+use storage::{
+    Annotation, BorrowerTier, CollateralEligibility, CollateralValuation, Config, DataKey,
+    DefaultAllocation, DefaultPenalty, FeeTier, FundingLimits, FundingUsage, MarginAccount,
+    MarginMark, MarginValuation, MarkOverride, MarketAggregates, MarketStats, PositionReceipt,
+    RentBudget, RepoActivity, RepoPosition, RepoQuote, RiskFlag, TtlBucket, VaultConfig,
+    AUCTION_CLEARING_WINDOW, AUTO_CLOSE_WINDOW, BASIS_POINTS, DAILY_FUNDING_BUCKETS,
+    DEFAULT_QUOTE_TTL_LEDGERS, FUNDING_BUCKET_SECONDS, MAX_AUCTION_BIDS, MAX_MATURITY_BLACKOUT,
+    MAX_QUOTE_TTL_LEDGERS, MIN_CAPITALIZATION_INTERVAL, PRUNE_AFTER_LEDGERS, RECEIPT_TTL_LEDGERS,
+    TTL_BUCKET_LEDGERS, WEEKLY_FUNDING_BUCKETS,
+};
+pub use storage::{
+    AnnotationKind, AuctionBid, AuctionStatus, DefaultShare, EligibilityRule, MarkSource,
+    ReleaseSchedule, RepoStatus, TermAuction, ValuationKind,
+};
+use validation::{
+    apply_risk_flags, calculate_collateral_par_for, calculate_collateral_value,
+    calculate_compounded, calculate_default_penalty, calculate_default_split,
+    calculate_discounted_spread, calculate_max_cash, calculate_partial_collateral,
+    calculate_period_rate, calculate_releasable_collateral, calculate_repurchase,
+    calculate_weighted_spread, exceeds_funding_limit, from_asset_units, match_eligibility_rule,
+    resolve_fee_tier, rolling_window_total, to_asset_units, ttl_bucket_expires_within,
+    ttl_bucket_index, validate_default_penalty, validate_default_split, validate_eligibility_rules,
+    validate_fee_tiers, validate_funding_limits, validate_mark_override,
+};
+
+use bingo_events::{publish, publish_global, REPO};
+use soroban_sdk::{
+    contract, contractimpl, token, vec, xdr::ToXdr, Address, BytesN, Env, IntoVal, Map, Symbol,
+    Val, Vec,
+};
 
 #[contract]
 pub struct RepoMarket;
@@ -31,367 +59,3326 @@ impl RepoMarket {
         haircut_bps: i128,
         spread_bps: i128,
     ) -> Result<(), Error> {
-        if env.storage().instance().has(&DataKey::Initialized) {
+        if env.storage().instance().has(&DataKey::Config) {
             return Err(Error::AlreadyInitialized);
         }
 
         admin.require_auth();
 
-        env.storage().instance().set(&DataKey::Initialized, &true);
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::Treasury, &treasury);
-        env.storage().instance().set(&DataKey::Vault, &vault);
-        env.storage().instance().set(&DataKey::BTBillToken, &bt_bill_token);
-        env.storage().instance().set(&DataKey::Stablecoin, &stablecoin);
-        env.storage().instance().set(&DataKey::Haircut, &haircut_bps);
-        env.storage().instance().set(&DataKey::Spread, &spread_bps);
+        env.storage().instance().set(
+            &DataKey::Config,
+            &Config {
+                admin,
+                treasury,
+                haircut_bps,
+                spread_bps,
+                paused: false,
+            },
+        );
+        env.storage().instance().set(
+            &DataKey::VaultConfig(vault.clone()),
+            &VaultConfig {
+                bt_bill_token,
+                stablecoin,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::Vaults, &vec![&env, vault]);
         env.storage().instance().set(&DataKey::PositionCounter, &0u64);
-        env.storage().instance().set(&DataKey::Paused, &false);
 
         Ok(())
     }
 
     pub fn pause(env: Env) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        admin.require_auth();
+        let mut config = Self::load_config(&env)?;
+        config.admin.require_auth();
 
-        env.storage().instance().set(&DataKey::Paused, &true);
+        config.paused = true;
+        env.storage().instance().set(&DataKey::Config, &config);
         Ok(())
     }
 
     pub fn unpause(env: Env) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
+        let mut config = Self::load_config(&env)?;
+        config.admin.require_auth();
+
+        config.paused = false;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Configure volume-based spread discount tiers
+    ///
+    /// Passing an empty list disables discounts.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidFeeTiers`: Tiers not ascending or discount out of range
+    pub fn set_fee_tiers(env: Env, tiers: Vec<FeeTier>) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
         admin.require_auth();
 
-        env.storage().instance().set(&DataKey::Paused, &false);
+        if !validate_fee_tiers(&tiers) {
+            return Err(Error::InvalidFeeTiers);
+        }
+
+        env.storage().instance().set(&DataKey::FeeTiers, &tiers);
+
+        publish_global(
+            &env,
+            REPO,
+            "fee_tiers_updated",
+            FeeTiersUpdatedEvent { tiers },
+        );
+
         Ok(())
     }
 
     // ============================================
-    // FLOW 6: OPEN REPO
+    // VAULT REGISTRY
     // ============================================
 
-    pub fn open_repo(
+    /// Register an additional vault (e.g. one per stablecoin)
+    ///
+    /// Repos name their vault explicitly, so series IDs may repeat across vaults.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `VaultAlreadyRegistered`: Vault is already registered
+    pub fn register_vault(
         env: Env,
-        borrower: Address,
-        series_id: u32,
-        collateral_par: i128,
-        desired_cash_out: i128,
-        deadline: u64,
-    ) -> Result<u64, Error> {
-        Self::check_not_paused(&env)?;
+        vault: Address,
+        bt_bill_token: Address,
+        stablecoin: Address,
+    ) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
 
-        if collateral_par <= 0 || desired_cash_out <= 0 {
-            return Err(Error::InvalidAmount);
+        let mut vaults = Self::get_vaults(env.clone());
+        if vaults.contains(&vault) {
+            return Err(Error::VaultAlreadyRegistered);
         }
-
-        borrower.require_auth();
-
-        let vault: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Vault)
-            .ok_or(Error::NotInitialized)?;
-
-        let series: (u32, u64, u64, i128, i128, i128, i128, i128, u32) = env.invoke_contract(
-            &vault,
-            &Symbol::new(&env, "get_series"),
-            vec![&env, series_id.into()],
+        vaults.push_back(vault.clone());
+
+        env.storage().instance().set(&DataKey::Vaults, &vaults);
+        env.storage().instance().set(
+            &DataKey::VaultConfig(vault.clone()),
+            &VaultConfig {
+                bt_bill_token: bt_bill_token.clone(),
+                stablecoin: stablecoin.clone(),
+            },
         );
-        let maturity_date = series.2;
 
-        if deadline > maturity_date {
-            return Err(Error::InvalidDeadline);
-        }
-
-        let mark_price: i128 = env.invoke_contract(
-            &vault,
-            &Symbol::new(&env, "current_price"),
-            vec![&env, series_id.into()],
+        publish_global(
+            &env,
+            REPO,
+            "vault_registered",
+            VaultRegisteredEvent {
+                vault,
+                bt_bill_token,
+                stablecoin,
+            },
         );
 
-        let haircut_bps: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Haircut)
-            .unwrap_or(300);
-
-        let max_cash =
-            calculate_max_cash(collateral_par, mark_price, haircut_bps).ok_or(Error::InvalidAmount)?;
-
-        if desired_cash_out > max_cash {
-            return Err(Error::ExceedsMaxCash);
-        }
+        Ok(())
+    }
 
-        let spread_bps: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Spread)
-            .unwrap_or(200);
+    /// Stop opening new repos against a vault
+    ///
+    /// Open positions on the vault can still be closed or defaulted.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `VaultNotRegistered`: Vault is not registered
+    pub fn deregister_vault(env: Env, vault: Address) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
 
-        let repurchase_amount =
-            calculate_repurchase(desired_cash_out, spread_bps).ok_or(Error::InvalidAmount)?;
+        let mut vaults = Self::get_vaults(env.clone());
+        let index = vaults
+            .first_index_of(&vault)
+            .ok_or(Error::VaultNotRegistered)?;
+        vaults.remove(index);
 
-        let bt_bill_token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::BTBillToken)
-            .ok_or(Error::NotInitialized)?;
+        env.storage().instance().set(&DataKey::Vaults, &vaults);
 
-        env.invoke_contract::<()>(
-            &bt_bill_token,
-            &Symbol::new(&env, "transfer"),
-            vec![
-                &env,
-                series_id.into(),
-                borrower.to_val(),
-                env.current_contract_address().to_val(),
-                collateral_par.into_val(&env)
-            ],
+        publish_global(
+            &env,
+            REPO,
+            "vault_deregistered",
+            VaultDeregisteredEvent { vault },
         );
 
-        let stablecoin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Stablecoin)
-            .ok_or(Error::NotInitialized)?;
-        let treasury: Address = env
-            .storage()
+        Ok(())
+    }
+
+    pub fn get_vaults(env: Env) -> Vec<Address> {
+        env.storage()
             .instance()
-            .get(&DataKey::Treasury)
-            .ok_or(Error::NotInitialized)?;
+            .get(&DataKey::Vaults)
+            .unwrap_or(Vec::new(&env))
+    }
 
-        let stablecoin_client = token::Client::new(&env, &stablecoin);
-        stablecoin_client.transfer(&treasury, &borrower, &desired_cash_out);
+    pub fn get_vault_config(env: Env, vault: Address) -> Result<VaultConfig, Error> {
+        Self::load_vault_config(&env, &vault)
+    }
 
-        let position_id: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::PositionCounter)
-            .unwrap_or(0);
-        let new_position_id = position_id + 1;
+    /// Configure the penalty charged on claim_default
+    ///
+    /// Set `penalty_bps` to 0 to disable. See `DefaultPenalty` for routing.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidDefaultPenalty`: Penalty or insurance share out of range
+    pub fn set_default_penalty(
+        env: Env,
+        penalty_bps: i128,
+        insurance_share_bps: i128,
+        insurance_fund: Address,
+    ) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
 
-        let position = RepoPosition {
-            id: new_position_id,
-            borrower: borrower.clone(),
-            series_id,
-            collateral_par,
-            cash_out: desired_cash_out,
-            repurchase_amount,
-            start_time: env.ledger().timestamp(),
-            deadline,
-            status: RepoStatus::Open,
-        };
+        if !validate_default_penalty(penalty_bps, insurance_share_bps) {
+            return Err(Error::InvalidDefaultPenalty);
+        }
 
+        let penalty = DefaultPenalty {
+            penalty_bps,
+            insurance_share_bps,
+            insurance_fund,
+        };
         env.storage()
             .instance()
-            .set(&DataKey::Position(new_position_id), &position);
-        env.storage()
-            .instance()
-            .set(&DataKey::PositionCounter, &new_position_id);
+            .set(&DataKey::DefaultPenalty, &penalty);
 
-        env.events().publish(
-            (Symbol::new(&env, "repo_opened"), new_position_id),
-            RepoOpenedEvent {
-                position_id: new_position_id,
-                borrower: borrower.clone(),
-                series_id,
-                collateral_par,
-                cash_out: desired_cash_out,
-                repurchase_amount,
-                deadline,
-            },
+        publish_global(
+            &env,
+            REPO,
+            "default_penalty_updated",
+            DefaultPenaltyUpdatedEvent { penalty },
         );
 
-        Ok(new_position_id)
+        Ok(())
     }
 
-    // ============================================
-    // FLOW 7: CLOSE REPO (REPAY)
-    // ============================================
-
-    pub fn close_repo(env: Env, position_id: u64) -> Result<(), Error> {
-        Self::check_not_paused(&env)?;
+    pub fn get_default_penalty(env: Env) -> Option<DefaultPenalty> {
+        env.storage().instance().get(&DataKey::DefaultPenalty)
+    }
 
-        let mut position: RepoPosition = env
-            .storage()
-            .instance()
-            .get(&DataKey::Position(position_id))
-            .ok_or(Error::PositionNotFound)?;
+    /// Split seized collateral on claim_default across weighted beneficiaries
+    ///
+    /// Applies to collateral left after the insurance slice of the default
+    /// penalty. Pass an empty list to send it all to the treasury again.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidDefaultSplit`: Too many shares, or weights not positive and summing to 10,000
+    pub fn set_default_split(env: Env, shares: Vec<DefaultShare>) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
 
-        if position.status != RepoStatus::Open {
-            return Err(Error::InvalidStatus);
+        if shares.is_empty() {
+            env.storage().instance().remove(&DataKey::DefaultSplit);
+        } else if validate_default_split(&shares) {
+            env.storage()
+                .instance()
+                .set(&DataKey::DefaultSplit, &shares);
+        } else {
+            return Err(Error::InvalidDefaultSplit);
         }
 
-        position.borrower.require_auth();
+        publish_global(
+            &env,
+            REPO,
+            "default_split_updated",
+            DefaultSplitUpdatedEvent { shares },
+        );
 
-        let current_time = env.ledger().timestamp();
-        if current_time > position.deadline {
-            return Err(Error::DeadlinePassed);
+        Ok(())
+    }
+
+    /// Replace the collateral eligibility matrix
+    ///
+    /// Rows are checked in order when a repo is quoted or opened; the first
+    /// row matching the series' issuer, remaining tenor and outstanding PAR
+    /// decides eligibility and haircut. Series matching no row use the
+    /// global haircut. Pass an empty list to clear the matrix.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidEligibilityRules`: Too many rows, haircut out of range or inverted bounds
+    pub fn set_eligibility_rules(env: Env, rules: Vec<EligibilityRule>) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if !validate_eligibility_rules(&rules) {
+            return Err(Error::InvalidEligibilityRules);
+        }
+        if rules.is_empty() {
+            env.storage().instance().remove(&DataKey::EligibilityRules);
+        } else {
+            env.storage()
+                .instance()
+                .set(&DataKey::EligibilityRules, &rules);
         }
 
-        let stablecoin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Stablecoin)
-            .ok_or(Error::NotInitialized)?;
-        let treasury: Address = env
-            .storage()
+        publish_global(
+            &env,
+            REPO,
+            "eligibility_rules_updated",
+            EligibilityRulesUpdatedEvent { rules },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_eligibility_rules(env: Env) -> Vec<EligibilityRule> {
+        env.storage()
             .instance()
-            .get(&DataKey::Treasury)
-            .ok_or(Error::NotInitialized)?;
+            .get(&DataKey::EligibilityRules)
+            .unwrap_or(Vec::new(&env))
+    }
 
-        let stablecoin_client = token::Client::new(&env, &stablecoin);
-        stablecoin_client.transfer(&position.borrower, &treasury, &position.repurchase_amount);
+    /// Whether a series' bT-Bills are eligible collateral right now, and at which haircut
+    ///
+    /// Reflects both the eligibility matrix and the vault's risk flags on the series.
+    ///
+    /// # Errors
+    /// - `VaultNotRegistered`: Vault is not registered
+    /// - `SeriesNotFound`: Vault has no such series
+    pub fn get_collateral_eligibility(
+        env: Env,
+        vault: Address,
+        series_id: u32,
+    ) -> Result<CollateralEligibility, Error> {
+        let config = Self::load_config(&env)?;
+        let maturity_date = Self::resolve_vault(&env, &vault, series_id)?;
+        Self::collateral_eligibility(&env, &config, &vault, series_id, maturity_date)
+    }
 
-        let bt_bill_token: Address = env
-            .storage()
+    pub fn get_default_split(env: Env) -> Vec<DefaultShare> {
+        env.storage()
             .instance()
-            .get(&DataKey::BTBillToken)
-            .ok_or(Error::NotInitialized)?;
+            .get(&DataKey::DefaultSplit)
+            .unwrap_or(Vec::new(&env))
+    }
 
-        env.invoke_contract::<()>(
-            &bt_bill_token,
-            &Symbol::new(&env, "transfer"),
-            vec![
-                &env,
-                position.series_id.into(),
-                env.current_contract_address().to_val(),
-                position.borrower.to_val(),
-                position.collateral_par.into_val(&env)
-            ],
-        );
+    /// Set the collateral release schedule for positions opened from now on
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_release_schedule(env: Env, schedule: ReleaseSchedule) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
 
-        position.status = RepoStatus::Closed;
         env.storage()
             .instance()
-            .set(&DataKey::Position(position_id), &position);
+            .set(&DataKey::ReleaseSchedule, &schedule);
 
-        env.events().publish(
-            (Symbol::new(&env, "repo_closed"), position_id),
-            RepoClosedEvent {
-                position_id,
-                borrower: position.borrower.clone(),
-                repayment: position.repurchase_amount,
-            },
+        publish_global(
+            &env,
+            REPO,
+            "release_schedule_updated",
+            ReleaseScheduleUpdatedEvent { schedule },
         );
 
         Ok(())
     }
 
-    // ============================================
-    // FLOW 8: CLAIM DEFAULT
-    // ============================================
-
-    pub fn claim_default(env: Env, position_id: u64) -> Result<(), Error> {
-        Self::check_not_paused(&env)?;
-
-        let treasury: Address = env
-            .storage()
+    pub fn get_release_schedule(env: Env) -> ReleaseSchedule {
+        env.storage()
             .instance()
-            .get(&DataKey::Treasury)
-            .ok_or(Error::NotInitialized)?;
-
-        treasury.require_auth();
+            .get(&DataKey::ReleaseSchedule)
+            .unwrap_or(ReleaseSchedule::Proportional)
+    }
 
-        let mut position: RepoPosition = env
-            .storage()
-            .instance()
-            .get(&DataKey::Position(position_id))
-            .ok_or(Error::PositionNotFound)?;
+    /// Capitalize interest on positions opened from now on every `interval` seconds
+    ///
+    /// The spread is spread evenly over the term and compounded into the
+    /// outstanding balance at each interval instead of falling due at close;
+    /// a part interval before a close or the deadline accrues pro rata.
+    /// An interval of 0 restores the flat spread.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidCapitalizationInterval`: Non-zero interval below MIN_CAPITALIZATION_INTERVAL
+    pub fn set_capitalization_interval(env: Env, interval: u64) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
 
-        if position.status != RepoStatus::Open {
-            return Err(Error::InvalidStatus);
+        if interval != 0 && interval < MIN_CAPITALIZATION_INTERVAL {
+            return Err(Error::InvalidCapitalizationInterval);
         }
 
-        let current_time = env.ledger().timestamp();
-        if current_time <= position.deadline {
-            return Err(Error::DeadlineNotPassed);
+        if interval == 0 {
+            env.storage()
+                .instance()
+                .remove(&DataKey::CapitalizationInterval);
+        } else {
+            env.storage()
+                .instance()
+                .set(&DataKey::CapitalizationInterval, &interval);
         }
 
-        let bt_bill_token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::BTBillToken)
-            .ok_or(Error::NotInitialized)?;
-
-        env.invoke_contract::<()>(
-            &bt_bill_token,
-            &Symbol::new(&env, "transfer"),
-            vec![
-                &env,
-                position.series_id.into(),
-                env.current_contract_address().to_val(),
-                treasury.to_val(),
-                position.collateral_par.into_val(&env)
-            ],
+        publish_global(
+            &env,
+            REPO,
+            "capitalization_updated",
+            CapitalizationUpdatedEvent { interval },
         );
 
-        position.status = RepoStatus::Defaulted;
+        Ok(())
+    }
+
+    pub fn get_capitalization_interval(env: Env) -> u64 {
         env.storage()
             .instance()
-            .set(&DataKey::Position(position_id), &position);
+            .get(&DataKey::CapitalizationInterval)
+            .unwrap_or(0)
+    }
 
-        env.events().publish(
-            (Symbol::new(&env, "repo_defaulted"), position_id),
-            RepoDefaultedEvent {
-                position_id,
-                borrower: position.borrower.clone(),
-                treasury: treasury.clone(),
-                collateral_claimed: position.collateral_par,
-            },
+    /// Refuse new repos on series maturing within `blackout` seconds (0 = off)
+    ///
+    /// Keeps fresh positions away from the maturity edge, where collateral
+    /// stops accreting and turns into a redemption claim on the vault.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidDeadline`: blackout above MAX_MATURITY_BLACKOUT
+    pub fn set_maturity_blackout(env: Env, blackout: u64) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if blackout > MAX_MATURITY_BLACKOUT {
+            return Err(Error::InvalidDeadline);
+        }
+
+        if blackout == 0 {
+            env.storage().instance().remove(&DataKey::MaturityBlackout);
+        } else {
+            env.storage()
+                .instance()
+                .set(&DataKey::MaturityBlackout, &blackout);
+        }
+
+        publish_global(
+            &env,
+            REPO,
+            "maturity_blackout_updated",
+            MaturityBlackoutUpdatedEvent { blackout },
         );
 
         Ok(())
     }
 
-    // ============================================
-    // VIEW FUNCTIONS
-    // ============================================
-
-    pub fn get_position(env: Env, position_id: u64) -> Result<RepoPosition, Error> {
+    pub fn get_maturity_blackout(env: Env) -> u64 {
         env.storage()
             .instance()
-            .get(&DataKey::Position(position_id))
-            .ok_or(Error::PositionNotFound)
+            .get(&DataKey::MaturityBlackout)
+            .unwrap_or(0)
     }
 
-    pub fn get_haircut(env: Env) -> i128 {
+    /// Cap treasury cash paid out over rolling windows
+    ///
+    /// Covers repo opens (including cleared auctions) and margin draws.
+    /// Outflows beyond either cap still succeed, but only with the admin's
+    /// co-authorization, limiting what a compromised treasury key can drain.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidFundingLimits`: Negative limit or weekly below daily
+    pub fn set_funding_limits(env: Env, limits: FundingLimits) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if !validate_funding_limits(&limits) {
+            return Err(Error::InvalidFundingLimits);
+        }
+
         env.storage()
             .instance()
-            .get(&DataKey::Haircut)
-            .unwrap_or(300)
+            .set(&DataKey::FundingLimits, &limits);
+
+        publish_global(
+            &env,
+            REPO,
+            "funding_limits_updated",
+            FundingLimitsUpdatedEvent { limits },
+        );
+
+        Ok(())
     }
 
-    pub fn get_spread(env: Env) -> i128 {
+    pub fn get_funding_limits(env: Env) -> FundingLimits {
         env.storage()
             .instance()
-            .get(&DataKey::Spread)
-            .unwrap_or(200)
+            .get(&DataKey::FundingLimits)
+            .unwrap_or(FundingLimits {
+                daily_limit: 0,
+                weekly_limit: 0,
+            })
+    }
+
+    /// Treasury funding used in the trailing day and week
+    pub fn get_funding_usage(env: Env) -> FundingUsage {
+        let outflows = Self::load_funding_outflows(&env);
+        let now_bucket = env.ledger().timestamp() / FUNDING_BUCKET_SECONDS;
+
+        FundingUsage {
+            daily_used: rolling_window_total(&outflows, now_bucket, DAILY_FUNDING_BUCKETS),
+            weekly_used: rolling_window_total(&outflows, now_bucket, WEEKLY_FUNDING_BUCKETS),
+            limits: Self::get_funding_limits(env.clone()),
+        }
     }
 
     // ============================================
-    // INTERNAL HELPERS
+    // MARK OVERRIDE
+    // ============================================
+
+    /// Override a vault series' mark during disorderly markets
+    ///
+    /// Until `expiry`, opens, closes and defaults value the series' collateral
+    /// at `price` instead of the vault's model price, as do margin credit
+    /// lines, and the override is recorded in the valuation histories. After expiry the model price is
+    /// used again without further action.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `VaultNotRegistered`: Vault is not registered
+    /// - `InvalidMarkOverride`: Price outside (0, SCALE] or expiry not in the future
+    pub fn set_mark_override(
+        env: Env,
+        vault: Address,
+        series_id: u32,
+        price: i128,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        let treasury = Self::load_config(&env)?.treasury;
+        treasury.require_auth();
+
+        Self::load_vault_config(&env, &vault)?;
+        if !validate_mark_override(price, expiry, env.ledger().timestamp()) {
+            return Err(Error::InvalidMarkOverride);
+        }
+
+        env.storage().instance().set(
+            &DataKey::MarkOverride(vault.clone(), series_id),
+            &MarkOverride { price, expiry },
+        );
+
+        publish(
+            &env,
+            REPO,
+            "mark_override_set",
+            series_id,
+            MarkOverrideSetEvent {
+                vault,
+                series_id,
+                price,
+                expiry,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Drop a vault series' mark override before it expires
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn clear_mark_override(env: Env, vault: Address, series_id: u32) -> Result<(), Error> {
+        let treasury = Self::load_config(&env)?.treasury;
+        treasury.require_auth();
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::MarkOverride(vault.clone(), series_id));
+
+        publish(
+            &env,
+            REPO,
+            "mark_override_cleared",
+            series_id,
+            MarkOverrideClearedEvent { vault, series_id },
+        );
+
+        Ok(())
+    }
+
+    /// Get the override in force for a vault series (None once expired)
+    pub fn get_mark_override(env: Env, vault: Address, series_id: u32) -> Option<MarkOverride> {
+        Self::active_mark_override(&env, &vault, series_id)
+    }
+
+    // ============================================
+    // REWARDS HOOK
+    // ============================================
+
+    /// Set (or clear) the external incentives contract notified on user actions
+    ///
+    /// The hook must expose `on_action(user: Address, action: Symbol, notional: i128)`.
+    /// Hook failures are swallowed so a broken campaign cannot block core flows.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_rewards_hook(env: Env, hook: Option<Address>) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        match &hook {
+            Some(hook) => env.storage().instance().set(&DataKey::RewardsHook, hook),
+            None => env.storage().instance().remove(&DataKey::RewardsHook),
+        }
+
+        publish_global(
+            &env,
+            REPO,
+            "rewards_hook_updated",
+            RewardsHookUpdatedEvent { hook },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_rewards_hook(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::RewardsHook)
+    }
+
     // ============================================
+    // FLOW 6: OPEN REPO
+    // ============================================
+
+    pub fn open_repo(
+        env: Env,
+        borrower: Address,
+        vault: Address,
+        series_id: u32,
+        collateral_par: i128,
+        desired_cash_out: i128,
+        deadline: u64,
+    ) -> Result<u64, Error> {
+        let config = Self::check_not_paused(&env)?;
 
-    fn check_not_paused(env: &Env) -> Result<(), Error> {
-        let paused = env
+        if collateral_par <= 0 || desired_cash_out <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        borrower.require_auth();
+
+        let quote = Self::quote_repo(
+            &env,
+            &config,
+            &borrower,
+            &vault,
+            series_id,
+            collateral_par,
+            desired_cash_out,
+            deadline,
+            env.ledger().sequence(),
+        )?;
+        Self::escrow_collateral(&env, &quote)?;
+        Self::execute_open_repo(&env, &config, quote)
+    }
+
+    /// Freeze repo terms at the current mark for `get_quote_ttl` ledgers
+    ///
+    /// Max cash and spread are checked now; funding limits are still
+    /// enforced when the quote is executed via `open_repo_with_quote`.
+    ///
+    /// # Errors
+    /// - `ContractPaused`: Contract is paused
+    /// - `InvalidAmount`: collateral_par and cash_out must be positive
+    /// - `VaultNotRegistered`: Vault is not registered
+    /// - `SeriesNotFound`: Vault has no such series
+    /// - `InvalidDeadline`: Deadline after series maturity
+    /// - `CollateralIneligible`: Series excluded by the eligibility matrix
+    /// - `ExceedsMaxCash`: cash_out above the LTV limit at the current mark
+    /// - `BelowDustThreshold`: cash_out below the vault's dust threshold
+    pub fn lock_quote(
+        env: Env,
+        borrower: Address,
+        vault: Address,
+        series_id: u32,
+        collateral_par: i128,
+        cash_out: i128,
+        deadline: u64,
+    ) -> Result<u64, Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        if collateral_par <= 0 || cash_out <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        borrower.require_auth();
+
+        let ttl = Self::get_quote_ttl(env.clone());
+        let expires_at_ledger = env.ledger().sequence().saturating_add(ttl);
+        let quote = Self::quote_repo(
+            &env,
+            &config,
+            &borrower,
+            &vault,
+            series_id,
+            collateral_par,
+            cash_out,
+            deadline,
+            expires_at_ledger,
+        )?;
+
+        let quote_id: u64 = env
             .storage()
             .instance()
-            .get::<DataKey, bool>(&DataKey::Paused)
-            .unwrap_or(false);
+            .get(&DataKey::QuoteCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::QuoteCounter, &quote_id);
 
-        if paused {
-            return Err(Error::ContractPaused);
+        let key = DataKey::Quote(quote_id);
+        env.storage().temporary().set(&key, &quote);
+        // Outlive the expiry so late callers get QuoteExpired, not QuoteNotFound
+        let live_for = ttl.saturating_mul(2);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, live_for, live_for);
+
+        publish(
+            &env,
+            REPO,
+            "quote_locked",
+            quote_id,
+            QuoteLockedEvent {
+                quote_id,
+                borrower,
+                series_id,
+                collateral_par,
+                cash_out,
+                repurchase_amount: quote.repurchase_amount,
+                mark_price: quote.mark_price,
+                expires_at_ledger,
+            },
+        );
+
+        Ok(quote_id)
+    }
+
+    /// Open a repo on the terms frozen by `lock_quote`
+    ///
+    /// Each quote can be executed once.
+    ///
+    /// # Errors
+    /// - `ContractPaused`: Contract is paused
+    /// - `QuoteNotFound`: Unknown or already used quote
+    /// - `QuoteExpired`: Quote is past its expiry ledger
+    /// - `VaultNotRegistered`: Vault deregistered since the quote
+    /// - `AddressBlocked`: Borrower blocked by the vault since the quote
+    pub fn open_repo_with_quote(env: Env, quote_id: u64) -> Result<u64, Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        let key = DataKey::Quote(quote_id);
+        let quote: RepoQuote = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::QuoteNotFound)?;
+
+        quote.borrower.require_auth();
+
+        if env.ledger().sequence() > quote.expires_at_ledger {
+            return Err(Error::QuoteExpired);
+        }
+
+        env.storage().temporary().remove(&key);
+        Self::escrow_collateral(&env, &quote)?;
+        Self::execute_open_repo(&env, &config, quote)
+    }
+
+    pub fn get_quote(env: Env, quote_id: u64) -> Result<RepoQuote, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::Quote(quote_id))
+            .ok_or(Error::QuoteNotFound)
+    }
+
+    /// Set how many ledgers a locked quote stays valid
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidAmount`: ledgers must be in [1, MAX_QUOTE_TTL_LEDGERS]
+    pub fn set_quote_ttl(env: Env, ledgers: u32) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        if ledgers == 0 || ledgers > MAX_QUOTE_TTL_LEDGERS {
+            return Err(Error::InvalidAmount);
         }
+
+        env.storage().instance().set(&DataKey::QuoteTtl, &ledgers);
+
+        publish_global(
+            &env,
+            REPO,
+            "quote_ttl_updated",
+            QuoteTtlUpdatedEvent { ledgers },
+        );
+
         Ok(())
     }
+
+    pub fn get_quote_ttl(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::QuoteTtl)
+            .unwrap_or(DEFAULT_QUOTE_TTL_LEDGERS)
+    }
+
+    // ============================================
+    // FLOW 7: CLOSE REPO (REPAY)
+    // ============================================
+
+    pub fn close_repo(env: Env, position_id: u64) -> Result<(), Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        let mut position: RepoPosition = env
+            .storage()
+            .instance()
+            .get(&DataKey::Position(position_id))
+            .ok_or(Error::PositionNotFound)?;
+
+        if position.status != RepoStatus::Open {
+            return Err(Error::InvalidStatus);
+        }
+
+        position.borrower.require_auth();
+
+        let current_time = env.ledger().timestamp();
+        if current_time > position.deadline {
+            return Err(Error::DeadlinePassed);
+        }
+
+        Self::capitalize_interest(&env, &mut position)?;
+
+        let vault_config = Self::load_vault_config(&env, &position.vault)?;
+        let treasury = config.treasury;
+
+        let remaining_debt = position.repurchase_amount.saturating_sub(position.repaid);
+        let stablecoin_client = token::Client::new(&env, &vault_config.stablecoin);
+        stablecoin_client.transfer(&position.borrower, &treasury, &remaining_debt);
+
+        Self::settle_close(&env, position_id, position)
+    }
+
+    /// Repay part of a repo's debt ahead of close
+    ///
+    /// The final payment goes through `close_repo`. Collateral freed by
+    /// installments is claimed separately with `release_collateral`.
+    /// Installments pay principal before interest, and each one is booked
+    /// in the vault's accounting. Returns the debt still outstanding.
+    ///
+    /// # Errors
+    /// - `PositionNotFound`: Position doesn't exist
+    /// - `InvalidStatus`: Position not open
+    /// - `DeadlinePassed`: Position already past deadline
+    /// - `InvalidAmount`: amount not positive, or would settle the whole debt
+    pub fn repay_installment(env: Env, position_id: u64, amount: i128) -> Result<i128, Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        let mut position: RepoPosition = env
+            .storage()
+            .instance()
+            .get(&DataKey::Position(position_id))
+            .ok_or(Error::PositionNotFound)?;
+
+        if position.status != RepoStatus::Open {
+            return Err(Error::InvalidStatus);
+        }
+
+        position.borrower.require_auth();
+
+        if env.ledger().timestamp() > position.deadline {
+            return Err(Error::DeadlinePassed);
+        }
+
+        Self::capitalize_interest(&env, &mut position)?;
+        let remaining_debt = position.repurchase_amount.saturating_sub(position.repaid);
+        if amount <= 0 || amount >= remaining_debt {
+            return Err(Error::InvalidAmount);
+        }
+
+        let vault_config = Self::load_vault_config(&env, &position.vault)?;
+        let treasury = config.treasury;
+
+        let stablecoin_client = token::Client::new(&env, &vault_config.stablecoin);
+        stablecoin_client.transfer(&position.borrower, &treasury, &amount);
+
+        let principal = amount.min(Self::outstanding_principal(&position));
+        position.repaid += amount;
+        env.storage()
+            .instance()
+            .set(&DataKey::Position(position_id), &position);
+
+        Self::report_vault_accounting(
+            &env,
+            &position.vault,
+            "record_repayment",
+            vec![
+                &env,
+                position_id.into_val(&env),
+                principal.into_val(&env),
+                (amount - principal).into_val(&env),
+            ],
+        );
+
+        let remaining = remaining_debt - amount;
+        publish(
+            &env,
+            REPO,
+            "installment_repaid",
+            position_id,
+            InstallmentRepaidEvent {
+                position_id,
+                borrower: position.borrower,
+                amount,
+                repaid: position.repaid,
+                remaining,
+            },
+        );
+
+        Ok(remaining)
+    }
+
+    /// Collateral (PAR) the borrower may reclaim now under the position's release schedule
+    ///
+    /// # Errors
+    /// - `PositionNotFound`: Position doesn't exist
+    /// - `InvalidStatus`: Position not open
+    pub fn releasable_collateral(env: Env, position_id: u64) -> Result<i128, Error> {
+        let mut position: RepoPosition = env
+            .storage()
+            .instance()
+            .get(&DataKey::Position(position_id))
+            .ok_or(Error::PositionNotFound)?;
+
+        if position.status != RepoStatus::Open {
+            return Err(Error::InvalidStatus);
+        }
+
+        Self::accrue_interest(&mut position, env.ledger().timestamp())
+            .ok_or(Error::InvalidAmount)?;
+
+        Self::calculate_releasable(&env, position_id, &position)
+    }
+
+    /// Return collateral freed by installment repayments to the borrower
+    ///
+    /// Returns the PAR released.
+    ///
+    /// # Errors
+    /// - `PositionNotFound`: Position doesn't exist
+    /// - `InvalidStatus`: Position not open
+    /// - `DeadlinePassed`: Position already past deadline
+    /// - `InvalidAmount`: Nothing releasable yet
+    pub fn release_collateral(env: Env, position_id: u64) -> Result<i128, Error> {
+        Self::check_not_paused(&env)?;
+
+        let mut position: RepoPosition = env
+            .storage()
+            .instance()
+            .get(&DataKey::Position(position_id))
+            .ok_or(Error::PositionNotFound)?;
+
+        if position.status != RepoStatus::Open {
+            return Err(Error::InvalidStatus);
+        }
+
+        position.borrower.require_auth();
+
+        if env.ledger().timestamp() > position.deadline {
+            return Err(Error::DeadlinePassed);
+        }
+
+        Self::capitalize_interest(&env, &mut position)?;
+        let releasable = Self::calculate_releasable(&env, position_id, &position)?;
+        if releasable <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let vault_config = Self::load_vault_config(&env, &position.vault)?;
+        env.invoke_contract::<()>(
+            &vault_config.bt_bill_token,
+            &Symbol::new(&env, "transfer"),
+            vec![
+                &env,
+                position.series_id.into(),
+                env.current_contract_address().to_val(),
+                position.borrower.to_val(),
+                releasable.into_val(&env),
+            ],
+        );
+
+        position.collateral_par -= releasable;
+        position.collateral_released += releasable;
+        env.storage()
+            .instance()
+            .set(&DataKey::Position(position_id), &position);
+
+        let mut aggregates = Self::load_market_aggregates(&env);
+        aggregates.collateral_locked = aggregates.collateral_locked.saturating_sub(releasable);
+        env.storage()
+            .instance()
+            .set(&DataKey::MarketAggregates, &aggregates);
+
+        publish(
+            &env,
+            REPO,
+            "collateral_released",
+            position_id,
+            CollateralReleasedEvent {
+                position_id,
+                borrower: position.borrower,
+                collateral_par: releasable,
+                remaining_collateral: position.collateral_par,
+            },
+        );
+
+        Ok(releasable)
+    }
+
+    /// Withdraw collateral (PAR) the position no longer needs
+    ///
+    /// The collateral left behind is revalidated at the current mark and the
+    /// series' current haircut: its max cash must still cover the remaining
+    /// debt (capitalized interest included). Returns the collateral still locked.
+    ///
+    /// # Errors
+    /// - `PositionNotFound`: Position doesn't exist
+    /// - `InvalidStatus`: Position not open
+    /// - `DeadlinePassed`: Position already past deadline
+    /// - `InvalidAmount`: par_amount not positive, or not below the locked collateral
+    /// - `CollateralIneligible`: Series no longer eligible as collateral
+    /// - `ExceedsMaxCash`: Remaining collateral would not cover the remaining debt
+    pub fn withdraw_excess_collateral(
+        env: Env,
+        position_id: u64,
+        par_amount: i128,
+    ) -> Result<i128, Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        let mut position: RepoPosition = env
+            .storage()
+            .instance()
+            .get(&DataKey::Position(position_id))
+            .ok_or(Error::PositionNotFound)?;
+
+        if position.status != RepoStatus::Open {
+            return Err(Error::InvalidStatus);
+        }
+
+        position.borrower.require_auth();
+
+        if env.ledger().timestamp() > position.deadline {
+            return Err(Error::DeadlinePassed);
+        }
+
+        if par_amount <= 0 || par_amount >= position.collateral_par {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::capitalize_interest(&env, &mut position)?;
+        let remaining_collateral = position.collateral_par - par_amount;
+        let remaining_debt = position.repurchase_amount.saturating_sub(position.repaid);
+
+        let maturity_date: u64 = env.invoke_contract(
+            &position.vault,
+            &Symbol::new(&env, "get_series_maturity"),
+            vec![&env, position.series_id.into()],
+        );
+        let eligibility = Self::collateral_eligibility(
+            &env,
+            &config,
+            &position.vault,
+            position.series_id,
+            maturity_date,
+        )?;
+        if !eligibility.eligible {
+            return Err(Error::CollateralIneligible);
+        }
+
+        let (mark_price, override_expiry) =
+            Self::fetch_mark_price(&env, &position.vault, position.series_id)?;
+        let asset_decimals = Self::fetch_asset_decimals(&env, &position.vault);
+        let max_cash =
+            calculate_max_cash(remaining_collateral, mark_price, eligibility.haircut_bps)
+                .and_then(|max_cash| to_asset_units(max_cash, asset_decimals))
+                .ok_or(Error::InvalidAmount)?;
+        if remaining_debt > max_cash {
+            return Err(Error::ExceedsMaxCash);
+        }
+
+        let vault_config = Self::load_vault_config(&env, &position.vault)?;
+        env.invoke_contract::<()>(
+            &vault_config.bt_bill_token,
+            &Symbol::new(&env, "transfer"),
+            vec![
+                &env,
+                position.series_id.into(),
+                env.current_contract_address().to_val(),
+                position.borrower.to_val(),
+                par_amount.into_val(&env),
+            ],
+        );
+
+        position.collateral_par = remaining_collateral;
+        position.collateral_released += par_amount;
+        env.storage()
+            .instance()
+            .set(&DataKey::Position(position_id), &position);
+
+        let mut aggregates = Self::load_market_aggregates(&env);
+        aggregates.collateral_locked = aggregates.collateral_locked.saturating_sub(par_amount);
+        env.storage()
+            .instance()
+            .set(&DataKey::MarketAggregates, &aggregates);
+
+        Self::record_valuation(
+            &env,
+            position_id,
+            ValuationKind::Withdrawal,
+            remaining_collateral,
+            mark_price,
+            override_expiry,
+            asset_decimals,
+        )?;
+
+        publish(
+            &env,
+            REPO,
+            "excess_collateral_withdrawn",
+            position_id,
+            CollateralReleasedEvent {
+                position_id,
+                borrower: position.borrower,
+                collateral_par: par_amount,
+                remaining_collateral,
+            },
+        );
+
+        Ok(remaining_collateral)
+    }
+
+    /// Close a repo on the borrower's behalf via the vault's automation registry
+    ///
+    /// Only callable by the position's vault (which enforces the borrower's opt-in and
+    /// allowance) within AUTO_CLOSE_WINDOW of the deadline. The repurchase is
+    /// pulled with `transfer_from`, so the borrower must have approved this
+    /// contract as a spender on the stablecoin. Returns the repayment.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: borrower does not own the position
+    /// - `OutsideAutoCloseWindow`: More than AUTO_CLOSE_WINDOW before deadline
+    /// - `DeadlinePassed`: Position already past deadline
+    /// - `ExceedsAutomationAllowance`: Repurchase above max_repayment
+    pub fn close_repo_automated(
+        env: Env,
+        position_id: u64,
+        borrower: Address,
+        max_repayment: i128,
+    ) -> Result<i128, Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        let mut position: RepoPosition = env
+            .storage()
+            .instance()
+            .get(&DataKey::Position(position_id))
+            .ok_or(Error::PositionNotFound)?;
+        position.vault.require_auth();
+
+        if position.status != RepoStatus::Open {
+            return Err(Error::InvalidStatus);
+        }
+        if position.borrower != borrower {
+            return Err(Error::Unauthorized);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time > position.deadline {
+            return Err(Error::DeadlinePassed);
+        }
+        if current_time.saturating_add(AUTO_CLOSE_WINDOW) < position.deadline {
+            return Err(Error::OutsideAutoCloseWindow);
+        }
+        Self::capitalize_interest(&env, &mut position)?;
+        let remaining_debt = position.repurchase_amount.saturating_sub(position.repaid);
+        if remaining_debt > max_repayment {
+            return Err(Error::ExceedsAutomationAllowance);
+        }
+
+        let vault_config = Self::load_vault_config(&env, &position.vault)?;
+        let treasury = config.treasury;
+
+        let stablecoin_client = token::Client::new(&env, &vault_config.stablecoin);
+        stablecoin_client.transfer_from(
+            &env.current_contract_address(),
+            &position.borrower,
+            &treasury,
+            &remaining_debt,
+        );
+
+        Self::settle_close(&env, position_id, position)?;
+        Ok(remaining_debt)
+    }
+
+    // ============================================
+    // FLOW 8: CLAIM DEFAULT
+    // ============================================
+
+    pub fn claim_default(env: Env, position_id: u64) -> Result<(), Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        let treasury = config.treasury;
+
+        treasury.require_auth();
+
+        let mut position: RepoPosition = env
+            .storage()
+            .instance()
+            .get(&DataKey::Position(position_id))
+            .ok_or(Error::PositionNotFound)?;
+
+        if position.status != RepoStatus::Open {
+            return Err(Error::InvalidStatus);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time <= position.deadline {
+            return Err(Error::DeadlineNotPassed);
+        }
+        Self::capitalize_interest(&env, &mut position)?;
+
+        let vault_config = Self::load_vault_config(&env, &position.vault)?;
+        let (mark_price, override_expiry) =
+            Self::fetch_mark_price(&env, &position.vault, position.series_id)?;
+        let asset_decimals = Self::fetch_asset_decimals(&env, &position.vault);
+
+        let mut insurance_collateral = 0;
+        if let Some(config) = Self::get_default_penalty(env.clone()) {
+            let (penalty, insurance_penalty) = calculate_default_penalty(
+                position.repurchase_amount.saturating_sub(position.repaid),
+                config.penalty_bps,
+                config.insurance_share_bps,
+            )
+            .ok_or(Error::InvalidAmount)?;
+
+            // A sub-dust insurance slice is rounded into the lender's share
+            let insurance_penalty =
+                if insurance_penalty < Self::fetch_dust_threshold(&env, &position.vault) {
+                    0
+                } else {
+                    insurance_penalty
+                };
+            insurance_collateral = from_asset_units(insurance_penalty, asset_decimals)
+                .and_then(|penalty_value| {
+                    calculate_collateral_par_for(penalty_value, mark_price, position.collateral_par)
+                })
+                .ok_or(Error::InvalidAmount)?;
+
+            position.penalty = penalty;
+            position.insurance_penalty = insurance_penalty;
+
+            if insurance_collateral > 0 {
+                env.invoke_contract::<()>(
+                    &vault_config.bt_bill_token,
+                    &Symbol::new(&env, "transfer"),
+                    vec![
+                        &env,
+                        position.series_id.into(),
+                        env.current_contract_address().to_val(),
+                        config.insurance_fund.to_val(),
+                        insurance_collateral.into_val(&env),
+                    ],
+                );
+            }
+        }
+
+        // Past maturity the treasury's share is redeemed at PAR instead,
+        // falling back to the bT-Bills if the vault can't pay it yet
+        let maturity_date: u64 = env.invoke_contract(
+            &position.vault,
+            &Symbol::new(&env, "get_series_maturity"),
+            vec![&env, position.series_id.into()],
+        );
+        let matured = current_time >= maturity_date;
+
+        let collateral_claimed = position.collateral_par - insurance_collateral;
+        let allocations = Self::split_default_collateral(&env, &treasury, collateral_claimed)?;
+        let mut redeemed_par: i128 = 0;
+        for allocation in allocations.iter() {
+            if matured
+                && allocation.recipient == treasury
+                && Self::redeem_matured_collateral(
+                    &env,
+                    &position,
+                    &treasury,
+                    allocation.collateral_par,
+                )
+            {
+                redeemed_par += allocation.collateral_par;
+            } else if allocation.collateral_par > 0 {
+                env.invoke_contract::<()>(
+                    &vault_config.bt_bill_token,
+                    &Symbol::new(&env, "transfer"),
+                    vec![
+                        &env,
+                        position.series_id.into(),
+                        env.current_contract_address().to_val(),
+                        allocation.recipient.to_val(),
+                        allocation.collateral_par.into_val(&env),
+                    ],
+                );
+            }
+        }
+
+        position.status = RepoStatus::Defaulted;
+        env.storage()
+            .instance()
+            .set(&DataKey::Position(position_id), &position);
+
+        Self::record_valuation(
+            &env,
+            position_id,
+            ValuationKind::Default,
+            position.collateral_par,
+            mark_price,
+            override_expiry,
+            asset_decimals,
+        )?;
+        Self::write_receipt(
+            &env,
+            &position,
+            position.repaid,
+            mark_price,
+            override_expiry,
+        );
+
+        let mut aggregates = Self::release_from_aggregates(&env, &position);
+        aggregates.default_count += 1;
+        aggregates.default_notional = aggregates
+            .default_notional
+            .checked_add(position.cash_out)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::MarketAggregates, &aggregates);
+        Self::report_statement_activity(&env, position_id, &position, RepoActivity::Defaulted, 0);
+        Self::report_vault_accounting(
+            &env,
+            &position.vault,
+            "record_default",
+            vec![
+                &env,
+                position_id.into_val(&env),
+                Self::outstanding_principal(&position).into_val(&env),
+            ],
+        );
+
+        publish(
+            &env,
+            REPO,
+            "repo_defaulted",
+            position_id,
+            RepoDefaultedEvent {
+                position_id,
+                borrower: position.borrower.clone(),
+                treasury: treasury.clone(),
+                collateral_claimed,
+                penalty: position.penalty,
+                insurance_penalty: position.insurance_penalty,
+                insurance_collateral,
+                allocations,
+                redeemed_par,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Redeem seized matured collateral through the vault, paying `recipient`
+    ///
+    /// Returns false (with nothing redeemed) when the vault refuses, e.g.
+    /// while its settlement funding is short.
+    fn redeem_matured_collateral(
+        env: &Env,
+        position: &RepoPosition,
+        recipient: &Address,
+        collateral_par: i128,
+    ) -> bool {
+        if collateral_par <= 0 {
+            return false;
+        }
+        matches!(
+            env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &position.vault,
+                &Symbol::new(env, "redeem"),
+                vec![
+                    env,
+                    env.current_contract_address().to_val(),
+                    position.series_id.into(),
+                    collateral_par.into_val(env),
+                    Some(recipient.clone()).into_val(env),
+                ],
+            ),
+            Ok(Ok(()))
+        )
+    }
+
+    /// Allocate seized collateral per the default split (all to treasury if unset)
+    fn split_default_collateral(
+        env: &Env,
+        treasury: &Address,
+        collateral: i128,
+    ) -> Result<Vec<DefaultAllocation>, Error> {
+        let shares = Self::get_default_split(env.clone());
+        if shares.is_empty() {
+            return Ok(vec![
+                env,
+                DefaultAllocation {
+                    recipient: treasury.clone(),
+                    collateral_par: collateral,
+                },
+            ]);
+        }
+
+        let amounts = calculate_default_split(collateral, &shares).ok_or(Error::InvalidAmount)?;
+        let mut allocations = Vec::new(env);
+        for (share, collateral_par) in shares.iter().zip(amounts.iter()) {
+            allocations.push_back(DefaultAllocation {
+                recipient: share.recipient,
+                collateral_par,
+            });
+        }
+        Ok(allocations)
+    }
+
+    // ============================================
+    // FLOW 9: PORTFOLIO MARGIN
+    // ============================================
+
+    /// Deposit bT-Bills of any eligible series into the borrower's margin account
+    ///
+    /// The first deposit opens the account and binds it to the series' vault;
+    /// later deposits must come from series of the same vault.
+    ///
+    /// # Errors
+    /// - `ContractPaused`: Contract is paused
+    /// - `InvalidAmount`: collateral_par must be positive
+    /// - `VaultNotRegistered`: Vault is not registered
+    /// - `SeriesNotFound`: Vault has no such series
+    /// - `VaultMismatch`: Account is held on another vault
+    /// - `CollateralIneligible`: Series excluded by the eligibility matrix
+    /// - `AddressBlocked`: Borrower is on the vault's blocklist
+    pub fn margin_deposit(
+        env: Env,
+        borrower: Address,
+        vault: Address,
+        series_id: u32,
+        collateral_par: i128,
+    ) -> Result<(), Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        if collateral_par <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        borrower.require_auth();
+
+        let maturity_date = Self::resolve_vault(&env, &vault, series_id)?;
+        let vault_config = Self::load_vault_config(&env, &vault)?;
+        let mut account = env
+            .storage()
+            .instance()
+            .get::<DataKey, MarginAccount>(&DataKey::MarginAccount(borrower.clone()))
+            .unwrap_or(MarginAccount {
+                borrower: borrower.clone(),
+                vault: vault.clone(),
+                collateral: Map::new(&env),
+                drawn: 0,
+            });
+        if account.vault != vault {
+            return Err(Error::VaultMismatch);
+        }
+        let eligibility =
+            Self::collateral_eligibility(&env, &config, &vault, series_id, maturity_date)?;
+        if !eligibility.eligible {
+            return Err(Error::CollateralIneligible);
+        }
+        Self::check_not_blocked(&env, &vault, &borrower)?;
+
+        env.invoke_contract::<()>(
+            &vault_config.bt_bill_token,
+            &Symbol::new(&env, "transfer"),
+            vec![
+                &env,
+                series_id.into(),
+                borrower.to_val(),
+                env.current_contract_address().to_val(),
+                collateral_par.into_val(&env)
+            ],
+        );
+
+        let held = account
+            .collateral
+            .get(series_id)
+            .unwrap_or(0)
+            .checked_add(collateral_par)
+            .ok_or(Error::InvalidAmount)?;
+        account.collateral.set(series_id, held);
+        Self::adjust_margin_collateral(&env, &vault, series_id, collateral_par)?;
+        let credit_line = Self::calculate_credit_line(&env, &config, &account)?;
+        Self::save_margin_account(&env, &account);
+        Self::record_margin_valuation(&env, ValuationKind::MarginDeposit, &account, credit_line)?;
+
+        publish(
+            &env,
+            REPO,
+            "margin_deposited",
+            borrower.clone(),
+            MarginCollateralEvent {
+                borrower,
+                series_id,
+                collateral_par,
+                credit_line,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw margin collateral, provided the remaining line still covers the draws
+    ///
+    /// # Errors
+    /// - `ContractPaused`: Contract is paused
+    /// - `MarginAccountNotFound`: Borrower has no margin account
+    /// - `InvalidAmount`: collateral_par not positive or above the series' deposit
+    /// - `ExceedsMaxCash`: Drawn cash would exceed the reduced credit line
+    pub fn margin_withdraw(
+        env: Env,
+        borrower: Address,
+        series_id: u32,
+        collateral_par: i128,
+    ) -> Result<(), Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        let mut account = Self::load_margin_account(&env, &borrower)?;
+        borrower.require_auth();
+
+        let held = account.collateral.get(series_id).unwrap_or(0);
+        if collateral_par <= 0 || collateral_par > held {
+            return Err(Error::InvalidAmount);
+        }
+        if held == collateral_par {
+            account.collateral.remove(series_id);
+        } else {
+            account.collateral.set(series_id, held - collateral_par);
+        }
+
+        let credit_line = Self::calculate_credit_line(&env, &config, &account)?;
+        if account.drawn > credit_line {
+            return Err(Error::ExceedsMaxCash);
+        }
+
+        let vault_config = Self::load_vault_config(&env, &account.vault)?;
+        env.invoke_contract::<()>(
+            &vault_config.bt_bill_token,
+            &Symbol::new(&env, "transfer"),
+            vec![
+                &env,
+                series_id.into(),
+                env.current_contract_address().to_val(),
+                borrower.to_val(),
+                collateral_par.into_val(&env)
+            ],
+        );
+
+        Self::adjust_margin_collateral(&env, &account.vault, series_id, -collateral_par)?;
+        Self::save_margin_account(&env, &account);
+        Self::record_margin_valuation(
+            &env,
+            ValuationKind::MarginWithdrawal,
+            &account,
+            credit_line,
+        )?;
+
+        publish(
+            &env,
+            REPO,
+            "margin_withdrawn",
+            borrower.clone(),
+            MarginCollateralEvent {
+                borrower,
+                series_id,
+                collateral_par,
+                credit_line,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Draw cash from the treasury against the account's credit line
+    ///
+    /// Returns the total drawn.
+    ///
+    /// # Errors
+    /// - `ContractPaused`: Contract is paused
+    /// - `MarginAccountNotFound`: Borrower has no margin account
+    /// - `InvalidAmount`: amount must be positive
+    /// - `BelowDustThreshold`: amount below the vault's dust threshold
+    /// - `ExceedsMaxCash`: Total drawn would exceed the credit line
+    pub fn margin_draw(env: Env, borrower: Address, amount: i128) -> Result<i128, Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        let mut account = Self::load_margin_account(&env, &borrower)?;
+        borrower.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if amount < Self::fetch_dust_threshold(&env, &account.vault) {
+            return Err(Error::BelowDustThreshold);
+        }
+
+        let credit_line = Self::calculate_credit_line(&env, &config, &account)?;
+        account.drawn = account
+            .drawn
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        if account.drawn > credit_line {
+            return Err(Error::ExceedsMaxCash);
+        }
+
+        let vault_config = Self::load_vault_config(&env, &account.vault)?;
+        let treasury = config.treasury;
+
+        Self::record_treasury_funding(&env, amount)?;
+        token::Client::new(&env, &vault_config.stablecoin).transfer(&treasury, &borrower, &amount);
+        Self::save_margin_account(&env, &account);
+        Self::record_margin_valuation(&env, ValuationKind::MarginDraw, &account, credit_line)?;
+
+        publish(
+            &env,
+            REPO,
+            "margin_drawn",
+            borrower.clone(),
+            MarginDrawnEvent {
+                borrower,
+                amount,
+                drawn: account.drawn,
+                credit_line,
+            },
+        );
+
+        Ok(account.drawn)
+    }
+
+    /// Repay drawn cash to the treasury
+    ///
+    /// Returns the amount still drawn.
+    ///
+    /// # Errors
+    /// - `ContractPaused`: Contract is paused
+    /// - `MarginAccountNotFound`: Borrower has no margin account
+    /// - `InvalidAmount`: amount not positive or above the amount drawn
+    pub fn margin_repay(env: Env, borrower: Address, amount: i128) -> Result<i128, Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        let mut account = Self::load_margin_account(&env, &borrower)?;
+        borrower.require_auth();
+
+        if amount <= 0 || amount > account.drawn {
+            return Err(Error::InvalidAmount);
+        }
+
+        let vault_config = Self::load_vault_config(&env, &account.vault)?;
+
+        token::Client::new(&env, &vault_config.stablecoin).transfer(
+            &borrower,
+            &config.treasury,
+            &amount,
+        );
+        account.drawn -= amount;
+        Self::save_margin_account(&env, &account);
+        let credit_line = Self::calculate_credit_line(&env, &config, &account)?;
+        Self::record_margin_valuation(&env, ValuationKind::MarginRepay, &account, credit_line)?;
+
+        publish(
+            &env,
+            REPO,
+            "margin_repaid",
+            borrower.clone(),
+            MarginRepaidEvent {
+                borrower,
+                amount,
+                drawn: account.drawn,
+            },
+        );
+
+        Ok(account.drawn)
+    }
+
+    pub fn get_margin_account(env: Env, borrower: Address) -> Result<MarginAccount, Error> {
+        Self::load_margin_account(&env, &borrower)
+    }
+
+    /// Get the marks recorded at each margin event of a borrower, oldest first
+    ///
+    /// Kept after the account is fully unwound.
+    pub fn get_margin_valuation_history(env: Env, borrower: Address) -> Vec<MarginValuation> {
+        env.storage()
+            .instance()
+            .get(&DataKey::MarginValuationHistory(borrower))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Haircut-weighted value of the account's collateral at current marks
+    ///
+    /// Series the eligibility matrix now excludes count for nothing.
+    ///
+    /// # Errors
+    /// - `MarginAccountNotFound`: Borrower has no margin account
+    pub fn margin_credit_line(env: Env, borrower: Address) -> Result<i128, Error> {
+        let config = Self::load_config(&env)?;
+        let account = Self::load_margin_account(&env, &borrower)?;
+        Self::calculate_credit_line(&env, &config, &account)
+    }
+
+    // ============================================
+    // FLOW 10: TERM AUCTIONS
+    // ============================================
+
+    /// Offer `buckets` funding buckets of `bucket_size` cash for bids until `bidding_ends`
+    ///
+    /// Accepted repos run until `bidding_ends + term`. Returns the auction ID.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `VaultNotRegistered`: Vault is not registered
+    /// - `InvalidAmount`: bucket_size, buckets and term must be positive
+    /// - `InvalidDeadline`: bidding_ends is not in the future
+    pub fn open_auction(
+        env: Env,
+        vault: Address,
+        bucket_size: i128,
+        buckets: u32,
+        term: u64,
+        bidding_ends: u64,
+    ) -> Result<u64, Error> {
+        let treasury = Self::load_config(&env)?.treasury;
+        treasury.require_auth();
+
+        Self::load_vault_config(&env, &vault)?;
+        if bucket_size <= 0 || buckets == 0 || term == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if bidding_ends <= env.ledger().timestamp() {
+            return Err(Error::InvalidDeadline);
+        }
+
+        let auction_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AuctionCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::AuctionCounter, &auction_id);
+
+        let auction = TermAuction {
+            id: auction_id,
+            vault: vault.clone(),
+            bucket_size,
+            buckets,
+            term,
+            bidding_ends,
+            status: AuctionStatus::Open,
+            clearing_rate_bps: 0,
+            buckets_filled: 0,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::Auction(auction_id), &auction);
+
+        publish(
+            &env,
+            REPO,
+            "auction_opened",
+            auction_id,
+            AuctionOpenedEvent {
+                auction_id,
+                vault,
+                bucket_size,
+                buckets,
+                term,
+                bidding_ends,
+            },
+        );
+
+        Ok(auction_id)
+    }
+
+    /// Bid for funding buckets at a maximum spread, escrowing the collateral
+    ///
+    /// The collateral must support the full request at the current mark;
+    /// it is returned at clearing for whatever part of the bid is not filled.
+    ///
+    /// # Errors
+    /// - `ContractPaused`: Contract is paused
+    /// - `AuctionNotFound`: Unknown auction
+    /// - `AuctionClosed`: Bidding has ended or the bid book is full
+    /// - `InvalidAmount`: collateral_par not positive, buckets outside [1, auction buckets],
+    ///   or rate_bps outside [1, 10,000]
+    /// - `SeriesNotFound`: The auction's vault has no such series
+    /// - `InvalidDeadline`: Repo deadline would fall after series maturity
+    /// - `CollateralIneligible`: Series excluded by the eligibility matrix
+    /// - `ExceedsMaxCash`: Requested cash above the LTV limit at the current mark
+    /// - `BelowDustThreshold`: Requested cash below the vault's dust threshold
+    /// - `AddressBlocked`: Borrower is on the vault's blocklist
+    pub fn submit_bid(
+        env: Env,
+        bidder: Address,
+        auction_id: u64,
+        series_id: u32,
+        collateral_par: i128,
+        buckets: u32,
+        rate_bps: i128,
+    ) -> Result<(), Error> {
+        let config = Self::check_not_paused(&env)?;
+
+        let auction = Self::get_auction(env.clone(), auction_id)?;
+        let mut bids = Self::get_auction_bids(env.clone(), auction_id);
+        if auction.status != AuctionStatus::Open
+            || env.ledger().timestamp() >= auction.bidding_ends
+            || bids.len() >= MAX_AUCTION_BIDS
+        {
+            return Err(Error::AuctionClosed);
+        }
+        if collateral_par <= 0
+            || buckets == 0
+            || buckets > auction.buckets
+            || rate_bps <= 0
+            || rate_bps > BASIS_POINTS
+        {
+            return Err(Error::InvalidAmount);
+        }
+
+        bidder.require_auth();
+
+        let cash_out = auction
+            .bucket_size
+            .checked_mul(buckets as i128)
+            .ok_or(Error::InvalidAmount)?;
+        let quote = Self::quote_repo(
+            &env,
+            &config,
+            &bidder,
+            &auction.vault,
+            series_id,
+            collateral_par,
+            cash_out,
+            auction.bidding_ends.saturating_add(auction.term),
+            env.ledger().sequence(),
+        )?;
+
+        Self::escrow_collateral(&env, &quote)?;
+        bids.push_back(AuctionBid {
+            quote,
+            buckets,
+            rate_bps,
+        });
+        env.storage()
+            .instance()
+            .set(&DataKey::AuctionBids(auction_id), &bids);
+
+        publish(
+            &env,
+            REPO,
+            "bid_submitted",
+            auction_id,
+            BidSubmittedEvent {
+                auction_id,
+                bidder,
+                series_id,
+                collateral_par,
+                buckets,
+                rate_bps,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Allocate buckets highest rate first and open repos at the uniform clearing rate
+    ///
+    /// The lowest accepted rate becomes the clearing rate for every accepted
+    /// bid; it prices only this auction's repos. Each bid is revalidated at
+    /// the current mark before it is filled, and bids that no longer meet
+    /// the LTV or eligibility limits are refunded whole, passing their
+    /// buckets down the book. The marginal bid may be filled in part,
+    /// keeping collateral pro rata; unfilled collateral is returned. An
+    /// auction cleared after its repo deadline fills nothing.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `AuctionNotFound`: Unknown auction
+    /// - `AuctionClosed`: Auction already cleared, or lapsed after AUCTION_CLEARING_WINDOW
+    /// - `AuctionNotEnded`: Bidding is still running
+    ///
+    /// Returns the clearing rate in basis points (0 if nothing filled).
+    pub fn clear_auction(env: Env, auction_id: u64) -> Result<i128, Error> {
+        let config = Self::load_config(&env)?;
+        config.treasury.require_auth();
+
+        let mut auction = Self::get_auction(env.clone(), auction_id)?;
+        if auction.status != AuctionStatus::Open {
+            return Err(Error::AuctionClosed);
+        }
+        let now = env.ledger().timestamp();
+        if now < auction.bidding_ends {
+            return Err(Error::AuctionNotEnded);
+        }
+        if now >= auction.bidding_ends.saturating_add(AUCTION_CLEARING_WINDOW) {
+            return Err(Error::AuctionClosed);
+        }
+
+        let bids = Self::get_auction_bids(env.clone(), auction_id);
+        let deadline = auction.bidding_ends.saturating_add(auction.term);
+        let mut remaining = if now >= deadline { 0 } else { auction.buckets };
+        let mut accepted: Vec<RepoQuote> = Vec::new(&env);
+        for bid in Self::bids_by_rate(&env, &bids).iter() {
+            let filled = bid.buckets.min(remaining);
+            let escrowed = bid.quote.collateral_par;
+            let kept = calculate_partial_collateral(escrowed, filled, bid.buckets)
+                .ok_or(Error::InvalidAmount)?;
+            let requote = if filled > 0 {
+                let cash_out = auction
+                    .bucket_size
+                    .checked_mul(filled as i128)
+                    .ok_or(Error::InvalidAmount)?;
+                Self::quote_repo(
+                    &env,
+                    &config,
+                    &bid.quote.borrower,
+                    &auction.vault,
+                    bid.quote.series_id,
+                    kept,
+                    cash_out,
+                    deadline,
+                    env.ledger().sequence(),
+                )
+                .ok()
+            } else {
+                None
+            };
+
+            match requote {
+                Some(quote) => {
+                    remaining -= filled;
+                    auction.clearing_rate_bps = bid.rate_bps;
+                    if kept < escrowed {
+                        Self::return_collateral(&env, &bid.quote, escrowed - kept)?;
+                    }
+                    accepted.push_back(quote);
+                }
+                None if filled > 0 => Self::refund_bid(&env, auction_id, &bid)?,
+                None => Self::return_collateral(&env, &bid.quote, escrowed)?,
+            }
+        }
+
+        let mut positions = Vec::new(&env);
+        for mut quote in accepted.iter() {
+            quote.spread_bps = auction.clearing_rate_bps;
+            quote.repurchase_amount = if quote.capitalization_interval > 0 {
+                quote.cash_out
+            } else {
+                calculate_repurchase(quote.cash_out, quote.spread_bps)
+                    .ok_or(Error::InvalidAmount)?
+            };
+            positions.push_back(Self::execute_open_repo(&env, &config, quote)?);
+        }
+
+        auction.buckets_filled = auction.buckets - remaining;
+        auction.status = AuctionStatus::Cleared;
+        env.storage()
+            .instance()
+            .set(&DataKey::Auction(auction_id), &auction);
+
+        publish(
+            &env,
+            REPO,
+            "auction_cleared",
+            auction_id,
+            AuctionClearedEvent {
+                auction_id,
+                clearing_rate_bps: auction.clearing_rate_bps,
+                buckets_filled: auction.buckets_filled,
+                positions,
+            },
+        );
+
+        Ok(auction.clearing_rate_bps)
+    }
+
+    /// Take back the collateral escrowed by every bid of `bidder` in a lapsed auction
+    ///
+    /// An auction not cleared within AUCTION_CLEARING_WINDOW of the end of
+    /// bidding can no longer be cleared, so its bidders recover their escrow.
+    ///
+    /// # Errors
+    /// - `AuctionNotFound`: Unknown auction
+    /// - `AuctionClosed`: Auction already cleared
+    /// - `ClearingWindowOpen`: Auction can still be cleared
+    /// - `BidNotFound`: Bidder has no bids left in the auction
+    ///
+    /// Returns the number of bids refunded.
+    pub fn reclaim_bids(env: Env, bidder: Address, auction_id: u64) -> Result<u32, Error> {
+        bidder.require_auth();
+
+        let auction = Self::get_auction(env.clone(), auction_id)?;
+        if auction.status != AuctionStatus::Open {
+            return Err(Error::AuctionClosed);
+        }
+        let lapses_at = auction.bidding_ends.saturating_add(AUCTION_CLEARING_WINDOW);
+        if env.ledger().timestamp() < lapses_at {
+            return Err(Error::ClearingWindowOpen);
+        }
+
+        let mut kept = Vec::new(&env);
+        let mut refunded = 0u32;
+        for bid in Self::get_auction_bids(env.clone(), auction_id).iter() {
+            if bid.quote.borrower == bidder {
+                Self::refund_bid(&env, auction_id, &bid)?;
+                refunded += 1;
+            } else {
+                kept.push_back(bid);
+            }
+        }
+        if refunded == 0 {
+            return Err(Error::BidNotFound);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AuctionBids(auction_id), &kept);
+
+        Ok(refunded)
+    }
+
+    pub fn get_auction(env: Env, auction_id: u64) -> Result<TermAuction, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Auction(auction_id))
+            .ok_or(Error::AuctionNotFound)
+    }
+
+    pub fn get_auction_bids(env: Env, auction_id: u64) -> Vec<AuctionBid> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AuctionBids(auction_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ============================================
+    // OPS ANNOTATIONS
+    // ============================================
+
+    /// Anchor the hash of an off-chain note to a series or repo position
+    ///
+    /// Only the hash is stored, so compliance notes and incident references
+    /// stay off-chain. Annotations accumulate oldest first.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `SeriesNotFound`: No registered vault has the series
+    /// - `PositionNotFound`: No position or receipt with this ID
+    pub fn annotate(
+        env: Env,
+        entity_kind: AnnotationKind,
+        id: u64,
+        note_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let admin = Self::load_config(&env)?.admin;
+        admin.require_auth();
+
+        match entity_kind {
+            AnnotationKind::Series => {
+                let series_id = u32::try_from(id).map_err(|_| Error::SeriesNotFound)?;
+                let known = Self::get_vaults(env.clone())
+                    .iter()
+                    .any(|vault| Self::resolve_vault(&env, &vault, series_id).is_ok());
+                if !known {
+                    return Err(Error::SeriesNotFound);
+                }
+            }
+            AnnotationKind::Position => {
+                let known = env.storage().instance().has(&DataKey::Position(id))
+                    || env.storage().persistent().has(&DataKey::Receipt(id));
+                if !known {
+                    return Err(Error::PositionNotFound);
+                }
+            }
+        }
+
+        let key = DataKey::Annotations(entity_kind, id);
+        let mut annotations = Self::get_annotations(env.clone(), entity_kind, id);
+        annotations.push_back(Annotation {
+            note_hash: note_hash.clone(),
+            annotated_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &annotations);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, RECEIPT_TTL_LEDGERS, RECEIPT_TTL_LEDGERS);
+
+        publish(
+            &env,
+            REPO,
+            "annotated",
+            id,
+            AnnotatedEvent {
+                entity_kind,
+                id,
+                note_hash,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Note hashes anchored to a series or position, oldest first
+    pub fn get_annotations(env: Env, entity_kind: AnnotationKind, id: u64) -> Vec<Annotation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Annotations(entity_kind, id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ============================================
+    // STORAGE PRUNING
+    // ============================================
+
+    /// Archive and remove positions settled more than PRUNE_AFTER_LEDGERS ago
+    ///
+    /// For each eligible position, emits `position_archived` with a hash of
+    /// its final state and valuation history, then drops both. The persistent
+    /// receipt is kept as the settlement record. Open, unknown or too-recent
+    /// positions are skipped. Returns the number of positions pruned.
+    pub fn prune_positions(env: Env, keeper: Address, position_ids: Vec<u64>) -> u32 {
+        keeper.require_auth();
+
+        let current_ledger = env.ledger().sequence();
+        let mut pruned = 0u32;
+
+        for position_id in position_ids.iter() {
+            let Some(position) = env
+                .storage()
+                .instance()
+                .get::<DataKey, RepoPosition>(&DataKey::Position(position_id))
+            else {
+                continue;
+            };
+            let Ok(receipt) = Self::get_receipt(env.clone(), position_id) else {
+                continue;
+            };
+            if current_ledger < receipt.settled_ledger.saturating_add(PRUNE_AFTER_LEDGERS) {
+                continue;
+            }
+
+            let history = Self::get_valuation_history(env.clone(), position_id);
+            let state_hash: BytesN<32> = env
+                .crypto()
+                .sha256(&(position, history).to_xdr(&env))
+                .into();
+
+            env.storage()
+                .instance()
+                .remove(&DataKey::Position(position_id));
+            env.storage()
+                .instance()
+                .remove(&DataKey::ValuationHistory(position_id));
+
+            publish(
+                &env,
+                REPO,
+                "position_archived",
+                position_id,
+                PositionArchivedEvent {
+                    position_id,
+                    state_hash,
+                },
+            );
+            pruned += 1;
+        }
+
+        pruned
+    }
+
+    // ============================================
+    // VIEW FUNCTIONS
+    // ============================================
+
+    /// Debt the borrower owes right now, including interest capitalized up to this moment
+    ///
+    /// # Errors
+    /// - `PositionNotFound`: Position doesn't exist
+    /// - `InvalidStatus`: Position not open
+    pub fn current_debt(env: Env, position_id: u64) -> Result<i128, Error> {
+        let mut position: RepoPosition = env
+            .storage()
+            .instance()
+            .get(&DataKey::Position(position_id))
+            .ok_or(Error::PositionNotFound)?;
+
+        if position.status != RepoStatus::Open {
+            return Err(Error::InvalidStatus);
+        }
+
+        Self::accrue_interest(&mut position, env.ledger().timestamp())
+            .ok_or(Error::InvalidAmount)?;
+        Ok(position.repurchase_amount.saturating_sub(position.repaid))
+    }
+
+    pub fn get_position(env: Env, position_id: u64) -> Result<RepoPosition, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Position(position_id))
+            .ok_or(Error::PositionNotFound)
+    }
+
+    /// Get the settlement receipt of a closed or defaulted position
+    pub fn get_receipt(env: Env, position_id: u64) -> Result<PositionReceipt, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Receipt(position_id))
+            .ok_or(Error::ReceiptNotFound)
+    }
+
+    /// Estimate upcoming TTL-extension work for persistent entries
+    ///
+    /// Counts live receipts and groups them into TTL_BUCKET_LEDGERS-wide
+    /// buckets by ledgers-to-expiry, so ops can budget rent ahead of time.
+    pub fn get_rent_budget(env: Env) -> RentBudget {
+        let now = env.ledger().sequence();
+        let expiries: Map<u32, u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReceiptExpiries)
+            .unwrap_or(Map::new(&env));
+
+        let mut receipts = 0u32;
+        let mut receipt_buckets = Vec::new(&env);
+        for (bucket, entries) in expiries.iter() {
+            let expires_within = ttl_bucket_expires_within(bucket, TTL_BUCKET_LEDGERS, now);
+            if expires_within == 0 {
+                continue;
+            }
+            receipts = receipts.saturating_add(entries);
+            receipt_buckets.push_back(TtlBucket {
+                expires_within,
+                entries,
+            });
+        }
+
+        RentBudget {
+            receipts,
+            receipt_buckets,
+        }
+    }
+
+    /// Get the collateral marks recorded at each decision point of a position
+    pub fn get_valuation_history(env: Env, position_id: u64) -> Vec<CollateralValuation> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ValuationHistory(position_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get market dashboard stats (maintained as running aggregates)
+    pub fn get_market_stats(env: Env) -> MarketStats {
+        let aggregates = Self::load_market_aggregates(&env);
+
+        MarketStats {
+            open_positions: aggregates.open_positions,
+            total_cash_outstanding: aggregates.cash_outstanding,
+            total_collateral_locked: aggregates.collateral_locked,
+            weighted_avg_spread_bps: calculate_weighted_spread(
+                aggregates.spread_weighted_cash,
+                aggregates.cash_outstanding,
+            ),
+            default_count: aggregates.default_count,
+            default_notional: aggregates.default_notional,
+        }
+    }
+
+    /// Repurchase still owed on `vault`'s open positions due between now and `until`
+    ///
+    /// Scans every position; meant for off-chain reads and the vault's
+    /// liquidity forecast, not for use inside state-changing calls.
+    pub fn repayments_due(env: Env, vault: Address, until: u64) -> i128 {
+        let now = env.ledger().timestamp();
+        let position_count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PositionCounter)
+            .unwrap_or(0);
+
+        let mut due: i128 = 0;
+        for position_id in 1..=position_count {
+            if let Some(position) = env
+                .storage()
+                .instance()
+                .get::<DataKey, RepoPosition>(&DataKey::Position(position_id))
+            {
+                if position.status == RepoStatus::Open
+                    && position.vault == vault
+                    && position.deadline >= now
+                    && position.deadline <= until
+                {
+                    let mut position = position;
+                    // Capitalized debt as it will stand at the deadline
+                    let deadline = position.deadline;
+                    Self::accrue_interest(&mut position, deadline);
+                    due = due
+                        .saturating_add(position.repurchase_amount.saturating_sub(position.repaid));
+                }
+            }
+        }
+        due
+    }
+
+    /// Collateral PAR held for `vault`'s open positions and margin accounts, by series
+    ///
+    /// Scans every position; meant for off-chain reads and invariant monitors.
+    pub fn collateral_by_series(env: Env, vault: Address) -> Map<u32, i128> {
+        let position_count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PositionCounter)
+            .unwrap_or(0);
+
+        let mut collateral: Map<u32, i128> = env
+            .storage()
+            .instance()
+            .get(&DataKey::MarginCollateral(vault.clone()))
+            .unwrap_or(Map::new(&env));
+        for position_id in 1..=position_count {
+            if let Some(position) = env
+                .storage()
+                .instance()
+                .get::<DataKey, RepoPosition>(&DataKey::Position(position_id))
+            {
+                if position.status == RepoStatus::Open && position.vault == vault {
+                    let held = collateral.get(position.series_id).unwrap_or(0);
+                    collateral.set(
+                        position.series_id,
+                        held.saturating_add(position.collateral_par),
+                    );
+                }
+            }
+        }
+        collateral
+    }
+
+    pub fn get_haircut(env: Env) -> i128 {
+        Self::load_config(&env).map_or(300, |config| config.haircut_bps)
+    }
+
+    pub fn get_spread(env: Env) -> i128 {
+        Self::load_config(&env).map_or(200, |config| config.spread_bps)
+    }
+
+    pub fn get_fee_tiers(env: Env) -> Vec<FeeTier> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeTiers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get borrower's lifetime volume and the fee tier applied at next open
+    pub fn get_borrower_tier(env: Env, borrower: Address) -> BorrowerTier {
+        let volume: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::BorrowerVolume(borrower))
+            .unwrap_or(0);
+
+        let tiers = Self::get_fee_tiers(env.clone());
+        let (tier, discount_bps) = resolve_fee_tier(&tiers, volume);
+
+        BorrowerTier {
+            volume,
+            tier,
+            discount_bps,
+        }
+    }
+
+    // ============================================
+    // INTERNAL HELPERS
+    // ============================================
+
+    fn load_config(env: &Env) -> Result<Config, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// Load the config, failing while the market is paused
+    fn check_not_paused(env: &Env) -> Result<Config, Error> {
+        let config = Self::load_config(env)?;
+        if config.paused {
+            return Err(Error::ContractPaused);
+        }
+        Ok(config)
+    }
+
+    fn load_market_aggregates(env: &Env) -> MarketAggregates {
+        env.storage()
+            .instance()
+            .get(&DataKey::MarketAggregates)
+            .unwrap_or(MarketAggregates {
+                open_positions: 0,
+                cash_outstanding: 0,
+                collateral_locked: 0,
+                spread_weighted_cash: 0,
+                default_count: 0,
+                default_notional: 0,
+            })
+    }
+
+    /// Remove a no-longer-open position from the open-book aggregates
+    fn release_from_aggregates(env: &Env, position: &RepoPosition) -> MarketAggregates {
+        let mut aggregates = Self::load_market_aggregates(env);
+        aggregates.open_positions = aggregates.open_positions.saturating_sub(1);
+        aggregates.cash_outstanding = aggregates.cash_outstanding.saturating_sub(position.cash_out);
+        aggregates.collateral_locked = aggregates
+            .collateral_locked
+            .saturating_sub(position.collateral_par);
+        aggregates.spread_weighted_cash = aggregates
+            .spread_weighted_cash
+            .saturating_sub(position.cash_out.saturating_mul(position.spread_bps));
+        aggregates
+    }
+
+    /// Notify the rewards hook, if configured, of a user action
+    fn load_funding_outflows(env: &Env) -> Map<u64, i128> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FundingOutflows)
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Book a treasury outflow against the rolling windows
+    ///
+    /// Requires admin co-auth when either window would exceed its limit.
+    /// Buckets older than the weekly window are dropped.
+    fn record_treasury_funding(env: &Env, amount: i128) -> Result<(), Error> {
+        let usage = Self::get_funding_usage(env.clone());
+        if exceeds_funding_limit(usage.daily_used, amount, usage.limits.daily_limit)
+            || exceeds_funding_limit(usage.weekly_used, amount, usage.limits.weekly_limit)
+        {
+            let admin = Self::load_config(env)?.admin;
+            admin.require_auth();
+
+            publish_global(
+                env,
+                REPO,
+                "funding_limit_cosigned",
+                FundingLimitCosignedEvent {
+                    admin,
+                    amount,
+                    daily_used: usage.daily_used,
+                    weekly_used: usage.weekly_used,
+                },
+            );
+        }
+
+        let now_bucket = env.ledger().timestamp() / FUNDING_BUCKET_SECONDS;
+        let oldest_bucket = (now_bucket + 1).saturating_sub(WEEKLY_FUNDING_BUCKETS);
+
+        let mut outflows = Self::load_funding_outflows(env);
+        for bucket in outflows.keys().iter() {
+            if bucket < oldest_bucket {
+                outflows.remove(bucket);
+            }
+        }
+        let booked = outflows
+            .get(now_bucket)
+            .unwrap_or(0)
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        outflows.set(now_bucket, booked);
+        env.storage()
+            .instance()
+            .set(&DataKey::FundingOutflows, &outflows);
+
+        Ok(())
+    }
+
+    /// Report a position event to its vault for the borrower's statement
+    ///
+    /// Best-effort: a vault that has not registered this market ignores it.
+    fn report_statement_activity(
+        env: &Env,
+        position_id: u64,
+        position: &RepoPosition,
+        activity: RepoActivity,
+        cash_amount: i128,
+    ) {
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &position.vault,
+            &Symbol::new(env, "record_repo_activity"),
+            vec![
+                env,
+                position.borrower.to_val(),
+                activity.into_val(env),
+                position.series_id.into(),
+                position_id.into_val(env),
+                position.collateral_par.into_val(env),
+                cash_amount.into_val(env),
+            ],
+        );
+    }
+
+    /// Book a position's cash flow in its vault's protocol accounting
+    ///
+    /// Best-effort, like statement reporting: `record_loan` at open,
+    /// `record_repayment` on each installment and at close, and
+    /// `record_default` on default.
+    fn report_vault_accounting(env: &Env, vault: &Address, function: &str, args: Vec<Val>) {
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            vault,
+            &Symbol::new(env, function),
+            args,
+        );
+    }
+
+    /// Cash lent that installments have not paid back yet
+    ///
+    /// Installments pay principal first, then interest.
+    fn outstanding_principal(position: &RepoPosition) -> i128 {
+        position.cash_out.saturating_sub(position.repaid).max(0)
+    }
+
+    fn notify_rewards_hook(env: &Env, user: &Address, action: &str, notional: i128) {
+        if let Some(hook) = Self::get_rewards_hook(env.clone()) {
+            let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &hook,
+                &Symbol::new(env, "on_action"),
+                vec![
+                    env,
+                    user.to_val(),
+                    Symbol::new(env, action).to_val(),
+                    notional.into_val(env),
+                ],
+            );
+        }
+    }
+
+    /// Releasable collateral at the current mark, against the opening mark
+    fn calculate_releasable(
+        env: &Env,
+        position_id: u64,
+        position: &RepoPosition,
+    ) -> Result<i128, Error> {
+        let (mark_price, _) = Self::fetch_mark_price(env, &position.vault, position.series_id)?;
+        let open_mark = Self::get_valuation_history(env.clone(), position_id)
+            .first()
+            .map(|valuation| valuation.mark_price)
+            .unwrap_or(mark_price);
+
+        calculate_releasable_collateral(
+            position.release_schedule,
+            position.collateral_par,
+            position.collateral_released,
+            position.repurchase_amount,
+            position.repaid,
+            open_mark,
+            mark_price,
+        )
+        .ok_or(Error::InvalidAmount)
+    }
+
+    fn load_margin_account(env: &Env, borrower: &Address) -> Result<MarginAccount, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::MarginAccount(borrower.clone()))
+            .ok_or(Error::MarginAccountNotFound)
+    }
+
+    /// Store a margin account, dropping it once it holds nothing and owes nothing
+    fn save_margin_account(env: &Env, account: &MarginAccount) {
+        let key = DataKey::MarginAccount(account.borrower.clone());
+        if account.collateral.is_empty() && account.drawn == 0 {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, account);
+        }
+    }
+
+    /// Track margin collateral per vault and series for `collateral_by_series`
+    fn adjust_margin_collateral(
+        env: &Env,
+        vault: &Address,
+        series_id: u32,
+        delta: i128,
+    ) -> Result<(), Error> {
+        let key = DataKey::MarginCollateral(vault.clone());
+        let mut collateral: Map<u32, i128> =
+            env.storage().instance().get(&key).unwrap_or(Map::new(env));
+        let held = collateral
+            .get(series_id)
+            .unwrap_or(0)
+            .checked_add(delta)
+            .ok_or(Error::InvalidAmount)?;
+        if held == 0 {
+            collateral.remove(series_id);
+        } else {
+            collateral.set(series_id, held);
+        }
+        env.storage().instance().set(&key, &collateral);
+        Ok(())
+    }
+
+    /// Sum of max cash across the account's series, each at its own haircut and mark
+    fn calculate_credit_line(
+        env: &Env,
+        config: &Config,
+        account: &MarginAccount,
+    ) -> Result<i128, Error> {
+        let asset_decimals = Self::fetch_asset_decimals(env, &account.vault);
+        let mut credit_line: i128 = 0;
+        for (series_id, collateral_par) in account.collateral.iter() {
+            let maturity_date: u64 = env.invoke_contract(
+                &account.vault,
+                &Symbol::new(env, "get_series_maturity"),
+                vec![env, series_id.into()],
+            );
+            let eligibility = Self::collateral_eligibility(
+                env,
+                config,
+                &account.vault,
+                series_id,
+                maturity_date,
+            )?;
+            if !eligibility.eligible {
+                continue;
+            }
+            let (mark_price, _) = Self::fetch_mark_price(env, &account.vault, series_id)?;
+            credit_line = calculate_max_cash(collateral_par, mark_price, eligibility.haircut_bps)
+                .and_then(|max_cash| to_asset_units(max_cash, asset_decimals))
+                .and_then(|max_cash| credit_line.checked_add(max_cash))
+                .ok_or(Error::InvalidAmount)?;
+        }
+        Ok(credit_line)
+    }
+
+    /// Compound the outstanding debt over the intervals elapsed up to `until`
+    ///
+    /// Whole intervals compound; a part interval accrues its pro-rata share
+    /// of the period rate, so a close before the first interval or a term
+    /// shorter than the interval still pays the spread. Accrual stops at the
+    /// deadline. Returns the interest added to repurchase_amount (0 for
+    /// positions with the spread due at close).
+    fn accrue_interest(position: &mut RepoPosition, until: u64) -> Option<i128> {
+        let interval = position.capitalization_interval;
+        let until = until.min(position.deadline);
+        if interval == 0 || until <= position.capitalized_at {
+            return Some(0);
+        }
+        let elapsed = until - position.capitalized_at;
+        let term = position.deadline.saturating_sub(position.start_time);
+
+        let outstanding = position.repurchase_amount.checked_sub(position.repaid)?;
+        let rate = calculate_period_rate(position.spread_bps, interval, term)?;
+        let mut balance = calculate_compounded(outstanding, rate, elapsed / interval)?;
+        let tail = elapsed % interval;
+        if tail > 0 {
+            let tail_rate = calculate_period_rate(position.spread_bps, tail, term)?;
+            balance = calculate_compounded(balance, tail_rate, 1)?;
+        }
+        let interest = balance.checked_sub(outstanding)?;
+
+        position.repurchase_amount = position.repurchase_amount.checked_add(interest)?;
+        position.capitalized_at = until;
+        Some(interest)
+    }
+
+    /// Capitalize accrued interest ahead of a state change (caller saves the position)
+    fn capitalize_interest(env: &Env, position: &mut RepoPosition) -> Result<(), Error> {
+        let interest = Self::accrue_interest(position, env.ledger().timestamp())
+            .ok_or(Error::InvalidAmount)?;
+        if interest > 0 {
+            publish(
+                env,
+                REPO,
+                "interest_capitalized",
+                position.id,
+                InterestCapitalizedEvent {
+                    position_id: position.id,
+                    interest,
+                    outstanding: position.repurchase_amount.saturating_sub(position.repaid),
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Return collateral and book a repaid position (repayment already collected)
+    fn settle_close(env: &Env, position_id: u64, mut position: RepoPosition) -> Result<(), Error> {
+        let vault_config = Self::load_vault_config(env, &position.vault)?;
+
+        env.invoke_contract::<()>(
+            &vault_config.bt_bill_token,
+            &Symbol::new(env, "transfer"),
+            vec![
+                env,
+                position.series_id.into(),
+                env.current_contract_address().to_val(),
+                position.borrower.to_val(),
+                position.collateral_par.into_val(env)
+            ],
+        );
+
+        position.status = RepoStatus::Closed;
+        env.storage()
+            .instance()
+            .set(&DataKey::Position(position_id), &position);
+
+        let (mark_price, override_expiry) =
+            Self::fetch_mark_price(env, &position.vault, position.series_id)?;
+        Self::record_valuation(
+            env,
+            position_id,
+            ValuationKind::Close,
+            position.collateral_par,
+            mark_price,
+            override_expiry,
+            Self::fetch_asset_decimals(env, &position.vault),
+        )?;
+        Self::write_receipt(
+            env,
+            &position,
+            position.repurchase_amount,
+            mark_price,
+            override_expiry,
+        );
+
+        let aggregates = Self::release_from_aggregates(env, &position);
+        env.storage()
+            .instance()
+            .set(&DataKey::MarketAggregates, &aggregates);
+
+        Self::notify_rewards_hook(
+            env,
+            &position.borrower,
+            "repo_close",
+            position.repurchase_amount,
+        );
+        Self::report_statement_activity(
+            env,
+            position_id,
+            &position,
+            RepoActivity::Closed,
+            position.repurchase_amount,
+        );
+        // Installments already booked their share; book what the close paid
+        let principal = Self::outstanding_principal(&position);
+        let interest = position
+            .repurchase_amount
+            .saturating_sub(position.repaid)
+            .saturating_sub(principal);
+        Self::report_vault_accounting(
+            env,
+            &position.vault,
+            "record_repayment",
+            vec![
+                env,
+                position_id.into_val(env),
+                principal.into_val(env),
+                interest.into_val(env),
+            ],
+        );
+
+        publish(
+            env,
+            REPO,
+            "repo_closed",
+            position_id,
+            RepoClosedEvent {
+                position_id,
+                borrower: position.borrower.clone(),
+                repayment: position.repurchase_amount,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Price a repo at the current mark, enforcing deadline, LTV and dust checks
+    fn quote_repo(
+        env: &Env,
+        config: &Config,
+        borrower: &Address,
+        vault: &Address,
+        series_id: u32,
+        collateral_par: i128,
+        cash_out: i128,
+        deadline: u64,
+        expires_at_ledger: u32,
+    ) -> Result<RepoQuote, Error> {
+        let maturity_date = Self::resolve_vault(env, vault, series_id)?;
+        Self::load_vault_config(env, vault)?;
+
+        if deadline > maturity_date {
+            return Err(Error::InvalidDeadline);
+        }
+        let blackout = Self::get_maturity_blackout(env.clone());
+        if maturity_date.saturating_sub(env.ledger().timestamp()) < blackout {
+            return Err(Error::MaturityBlackout);
+        }
+
+        let eligibility =
+            Self::collateral_eligibility(env, config, vault, series_id, maturity_date)?;
+        if !eligibility.eligible {
+            return Err(Error::CollateralIneligible);
+        }
+
+        let (mark_price, override_expiry) = Self::fetch_mark_price(env, vault, series_id)?;
+        let asset_decimals = Self::fetch_asset_decimals(env, vault);
+
+        let max_cash = calculate_max_cash(collateral_par, mark_price, eligibility.haircut_bps)
+            .and_then(|max_cash| to_asset_units(max_cash, asset_decimals))
+            .ok_or(Error::InvalidAmount)?;
+
+        if cash_out > max_cash {
+            return Err(Error::ExceedsMaxCash);
+        }
+
+        if cash_out < Self::fetch_dust_threshold(env, vault) {
+            return Err(Error::BelowDustThreshold);
+        }
+
+        let borrower_tier = Self::get_borrower_tier(env.clone(), borrower.clone());
+        let spread_bps = calculate_discounted_spread(config.spread_bps, borrower_tier.discount_bps);
+
+        // With capitalization the spread accrues over the term instead
+        let capitalization_interval = Self::get_capitalization_interval(env.clone());
+        let repurchase_amount = if capitalization_interval > 0 {
+            cash_out
+        } else {
+            calculate_repurchase(cash_out, spread_bps).ok_or(Error::InvalidAmount)?
+        };
+
+        Ok(RepoQuote {
+            borrower: borrower.clone(),
+            vault: vault.clone(),
+            series_id,
+            collateral_par,
+            cash_out,
+            repurchase_amount,
+            spread_bps,
+            mark_price,
+            override_expiry,
+            deadline,
+            capitalization_interval,
+            expires_at_ledger,
+        })
+    }
+
+    /// Refuse collateral from accounts on the vault's blocklist
+    fn check_not_blocked(env: &Env, vault: &Address, borrower: &Address) -> Result<(), Error> {
+        let blocked: bool = env.invoke_contract(
+            vault,
+            &Symbol::new(env, "is_blocked"),
+            vec![env, borrower.to_val()],
+        );
+        if blocked {
+            return Err(Error::AddressBlocked);
+        }
+        Ok(())
+    }
+
+    /// Auction bids ordered by rate, highest first (ties in submission order)
+    fn bids_by_rate(env: &Env, bids: &Vec<AuctionBid>) -> Vec<AuctionBid> {
+        let mut remaining = bids.clone();
+        let mut ordered = Vec::new(env);
+        while let Some(first) = remaining.first() {
+            let mut best_index = 0;
+            let mut best_rate = first.rate_bps;
+            for (index, bid) in (0u32..).zip(remaining.iter()) {
+                if bid.rate_bps > best_rate {
+                    best_index = index;
+                    best_rate = bid.rate_bps;
+                }
+            }
+            ordered.push_back(remaining.get_unchecked(best_index));
+            remaining.remove(best_index);
+        }
+        ordered
+    }
+
+    /// Return a bid's whole escrow to its bidder
+    fn refund_bid(env: &Env, auction_id: u64, bid: &AuctionBid) -> Result<(), Error> {
+        Self::return_collateral(env, &bid.quote, bid.quote.collateral_par)?;
+        publish(
+            env,
+            REPO,
+            "bid_refunded",
+            auction_id,
+            BidRefundedEvent {
+                auction_id,
+                bidder: bid.quote.borrower.clone(),
+                series_id: bid.quote.series_id,
+                collateral_par: bid.quote.collateral_par,
+            },
+        );
+        Ok(())
+    }
+
+    /// Send escrowed collateral back to the borrower named in a quote
+    fn return_collateral(env: &Env, quote: &RepoQuote, collateral_par: i128) -> Result<(), Error> {
+        let vault_config = Self::load_vault_config(env, &quote.vault)?;
+        env.invoke_contract::<()>(
+            &vault_config.bt_bill_token,
+            &Symbol::new(env, "transfer"),
+            vec![
+                env,
+                quote.series_id.into(),
+                env.current_contract_address().to_val(),
+                quote.borrower.to_val(),
+                collateral_par.into_val(env)
+            ],
+        );
+        Ok(())
+    }
+
+    /// Pull the quoted collateral from the borrower into the market
+    fn escrow_collateral(env: &Env, quote: &RepoQuote) -> Result<(), Error> {
+        let vault_config = Self::load_vault_config(env, &quote.vault)?;
+        Self::check_not_blocked(env, &quote.vault, &quote.borrower)?;
+        env.invoke_contract::<()>(
+            &vault_config.bt_bill_token,
+            &Symbol::new(env, "transfer"),
+            vec![
+                env,
+                quote.series_id.into(),
+                quote.borrower.to_val(),
+                env.current_contract_address().to_val(),
+                quote.collateral_par.into_val(env)
+            ],
+        );
+        Ok(())
+    }
+
+    /// Pay out cash and record a position on quoted terms (collateral already escrowed)
+    fn execute_open_repo(env: &Env, config: &Config, quote: RepoQuote) -> Result<u64, Error> {
+        let RepoQuote {
+            borrower,
+            vault,
+            series_id,
+            collateral_par,
+            cash_out,
+            repurchase_amount,
+            spread_bps,
+            mark_price,
+            override_expiry,
+            deadline,
+            capitalization_interval,
+            ..
+        } = quote;
+        let vault_config = Self::load_vault_config(env, &vault)?;
+        let asset_decimals = Self::fetch_asset_decimals(env, &vault);
+        let borrower_tier = Self::get_borrower_tier(env.clone(), borrower.clone());
+
+        Self::record_treasury_funding(env, cash_out)?;
+
+        let stablecoin_client = token::Client::new(env, &vault_config.stablecoin);
+        stablecoin_client.transfer(&config.treasury, &borrower, &cash_out);
+
+        let position_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PositionCounter)
+            .unwrap_or(0);
+        let new_position_id = position_id + 1;
+
+        let start_time = env.ledger().timestamp();
+        let position = RepoPosition {
+            id: new_position_id,
+            borrower: borrower.clone(),
+            vault: vault.clone(),
+            series_id,
+            collateral_par,
+            cash_out,
+            repurchase_amount,
+            spread_bps,
+            start_time,
+            deadline,
+            status: RepoStatus::Open,
+            penalty: 0,
+            insurance_penalty: 0,
+            repaid: 0,
+            collateral_released: 0,
+            release_schedule: Self::get_release_schedule(env.clone()),
+            capitalization_interval,
+            capitalized_at: start_time,
+        };
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Position(new_position_id), &position);
+        env.storage()
+            .instance()
+            .set(&DataKey::PositionCounter, &new_position_id);
+
+        Self::record_valuation(
+            env,
+            new_position_id,
+            ValuationKind::Open,
+            collateral_par,
+            mark_price,
+            override_expiry,
+            asset_decimals,
+        )?;
+
+        let mut aggregates = Self::load_market_aggregates(env);
+        aggregates.open_positions += 1;
+        aggregates.cash_outstanding = aggregates
+            .cash_outstanding
+            .checked_add(cash_out)
+            .ok_or(Error::InvalidAmount)?;
+        aggregates.collateral_locked = aggregates
+            .collateral_locked
+            .checked_add(collateral_par)
+            .ok_or(Error::InvalidAmount)?;
+        aggregates.spread_weighted_cash = cash_out
+            .checked_mul(spread_bps)
+            .and_then(|w| aggregates.spread_weighted_cash.checked_add(w))
+            .ok_or(Error::InvalidAmount)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::MarketAggregates, &aggregates);
+
+        let new_volume = borrower_tier
+            .volume
+            .checked_add(cash_out)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::BorrowerVolume(borrower.clone()), &new_volume);
+
+        Self::notify_rewards_hook(env, &borrower, "repo_open", cash_out);
+        Self::report_statement_activity(
+            env,
+            new_position_id,
+            &position,
+            RepoActivity::Opened,
+            cash_out,
+        );
+        Self::report_vault_accounting(
+            env,
+            &position.vault,
+            "record_loan",
+            vec![env, new_position_id.into_val(env), cash_out.into_val(env)],
+        );
+
+        publish(
+            env,
+            REPO,
+            "repo_opened",
+            new_position_id,
+            RepoOpenedEvent {
+                position_id: new_position_id,
+                borrower: borrower.clone(),
+                vault,
+                series_id,
+                collateral_par,
+                cash_out,
+                repurchase_amount,
+                deadline,
+                spread_bps,
+            },
+        );
+
+        Ok(new_position_id)
+    }
+
+    /// Maturity of `series_id` on a registered vault
+    fn resolve_vault(env: &Env, vault: &Address, series_id: u32) -> Result<u64, Error> {
+        let vaults: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Vaults)
+            .ok_or(Error::NotInitialized)?;
+        if !vaults.contains(vault) {
+            return Err(Error::VaultNotRegistered);
+        }
+
+        match env.try_invoke_contract::<u64, soroban_sdk::Error>(
+            vault,
+            &Symbol::new(env, "get_series_maturity"),
+            vec![env, series_id.into()],
+        ) {
+            Ok(Ok(maturity_date)) => Ok(maturity_date),
+            _ => Err(Error::SeriesNotFound),
+        }
+    }
+
+    /// Run a series through the eligibility matrix
+    ///
+    /// Only queries the vault for issuer and size when a matrix is set.
+    fn collateral_eligibility(
+        env: &Env,
+        config: &Config,
+        vault: &Address,
+        series_id: u32,
+        maturity_date: u64,
+    ) -> Result<CollateralEligibility, Error> {
+        let rules = Self::get_eligibility_rules(env.clone());
+        let unmatched = CollateralEligibility {
+            eligible: true,
+            haircut_bps: config.haircut_bps,
+            rule: None,
+        };
+        let risk_flags: Vec<RiskFlag> = env.invoke_contract(
+            vault,
+            &Symbol::new(env, "get_series_risk_flags"),
+            vec![env, series_id.into()],
+        );
+        if rules.is_empty() {
+            return Ok(apply_risk_flags(unmatched, &risk_flags));
+        }
+
+        let issuer: Address = env.invoke_contract(
+            vault,
+            &Symbol::new(env, "get_series_issuer"),
+            vec![env, series_id.into()],
+        );
+        let series_par: i128 = env.invoke_contract(
+            vault,
+            &Symbol::new(env, "outstanding_par"),
+            vec![env, series_id.into()],
+        );
+        let remaining_tenor = maturity_date.saturating_sub(env.ledger().timestamp());
+
+        let eligibility = match match_eligibility_rule(&rules, &issuer, remaining_tenor, series_par)
+        {
+            Some((index, rule)) => CollateralEligibility {
+                eligible: rule.eligible,
+                haircut_bps: rule.haircut_bps,
+                rule: Some(index),
+            },
+            None => unmatched,
+        };
+        Ok(apply_risk_flags(eligibility, &risk_flags))
+    }
+
+    fn load_vault_config(env: &Env, vault: &Address) -> Result<VaultConfig, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::VaultConfig(vault.clone()))
+            .ok_or(Error::VaultNotRegistered)
+    }
+
+    fn active_mark_override(env: &Env, vault: &Address, series_id: u32) -> Option<MarkOverride> {
+        env.storage()
+            .instance()
+            .get::<DataKey, MarkOverride>(&DataKey::MarkOverride(vault.clone(), series_id))
+            .filter(|mark| env.ledger().timestamp() < mark.expiry)
+    }
+
+    fn mark_source(override_expiry: Option<u64>) -> MarkSource {
+        if override_expiry.is_some() {
+            MarkSource::Override
+        } else {
+            MarkSource::Model
+        }
+    }
+
+    /// Mark price for a series, preferring an unexpired treasury override
+    ///
+    /// Returns (price, override expiry), with None for the vault's model price.
+    fn fetch_mark_price(
+        env: &Env,
+        vault: &Address,
+        series_id: u32,
+    ) -> Result<(i128, Option<u64>), Error> {
+        if let Some(mark) = Self::active_mark_override(env, vault, series_id) {
+            return Ok((mark.price, Some(mark.expiry)));
+        }
+
+        let price = env.invoke_contract(
+            vault,
+            &Symbol::new(env, "current_price"),
+            vec![env, series_id.into()],
+        );
+        Ok((price, None))
+    }
+
+    /// Settlement asset decimals of a vault; cash amounts are in these units
+    fn fetch_asset_decimals(env: &Env, vault: &Address) -> u32 {
+        env.invoke_contract(vault, &Symbol::new(env, "asset_decimals"), Vec::new(env))
+    }
+
+    /// Vault dust threshold in asset units (0 when no policy is set)
+    fn fetch_dust_threshold(env: &Env, vault: &Address) -> i128 {
+        env.invoke_contract(
+            vault,
+            &Symbol::new(env, "get_dust_threshold"),
+            Vec::new(env),
+        )
+    }
+
+    fn record_valuation(
+        env: &Env,
+        position_id: u64,
+        kind: ValuationKind,
+        collateral_par: i128,
+        mark_price: i128,
+        override_expiry: Option<u64>,
+        asset_decimals: u32,
+    ) -> Result<(), Error> {
+        let collateral_value = calculate_collateral_value(collateral_par, mark_price)
+            .and_then(|value| to_asset_units(value, asset_decimals))
+            .ok_or(Error::InvalidAmount)?;
+
+        let key = DataKey::ValuationHistory(position_id);
+        let mut history: Vec<CollateralValuation> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        history.push_back(CollateralValuation {
+            kind,
+            timestamp: env.ledger().timestamp(),
+            mark_price,
+            source: Self::mark_source(override_expiry),
+            override_expiry,
+            collateral_par,
+            collateral_value,
+        });
+
+        env.storage().instance().set(&key, &history);
+        Ok(())
+    }
+
+    /// Append the marks of every series a margin account holds to its history
+    fn record_margin_valuation(
+        env: &Env,
+        kind: ValuationKind,
+        account: &MarginAccount,
+        credit_line: i128,
+    ) -> Result<(), Error> {
+        let asset_decimals = Self::fetch_asset_decimals(env, &account.vault);
+        let mut marks = Vec::new(env);
+        for (series_id, collateral_par) in account.collateral.iter() {
+            let (mark_price, override_expiry) =
+                Self::fetch_mark_price(env, &account.vault, series_id)?;
+            let collateral_value = calculate_collateral_value(collateral_par, mark_price)
+                .and_then(|value| to_asset_units(value, asset_decimals))
+                .ok_or(Error::InvalidAmount)?;
+            marks.push_back(MarginMark {
+                series_id,
+                mark_price,
+                source: Self::mark_source(override_expiry),
+                override_expiry,
+                collateral_par,
+                collateral_value,
+            });
+        }
+
+        let key = DataKey::MarginValuationHistory(account.borrower.clone());
+        let mut history: Vec<MarginValuation> =
+            env.storage().instance().get(&key).unwrap_or(Vec::new(env));
+        history.push_back(MarginValuation {
+            kind,
+            timestamp: env.ledger().timestamp(),
+            drawn: account.drawn,
+            credit_line,
+            marks,
+        });
+
+        env.storage().instance().set(&key, &history);
+        Ok(())
+    }
+
+    /// Write the settlement receipt of a position that just closed or defaulted
+    fn write_receipt(
+        env: &Env,
+        position: &RepoPosition,
+        repayment: i128,
+        settle_mark: i128,
+        override_expiry: Option<u64>,
+    ) {
+        let open_mark = Self::get_valuation_history(env.clone(), position.id)
+            .first()
+            .map(|valuation| valuation.mark_price)
+            .unwrap_or(0);
+
+        let receipt = PositionReceipt {
+            position_id: position.id,
+            borrower: position.borrower.clone(),
+            vault: position.vault.clone(),
+            series_id: position.series_id,
+            outcome: position.status,
+            collateral_par: position.collateral_par,
+            cash_out: position.cash_out,
+            repayment,
+            penalty: position.penalty,
+            insurance_penalty: position.insurance_penalty,
+            opened_at: position.start_time,
+            settled_at: env.ledger().timestamp(),
+            settled_ledger: env.ledger().sequence(),
+            open_mark,
+            settle_mark,
+            settle_mark_source: Self::mark_source(override_expiry),
+        };
+
+        let key = DataKey::Receipt(position.id);
+        env.storage().persistent().set(&key, &receipt);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, RECEIPT_TTL_LEDGERS, RECEIPT_TTL_LEDGERS);
+
+        let live_until = receipt.settled_ledger.saturating_add(RECEIPT_TTL_LEDGERS);
+        Self::track_receipt_expiry(env, live_until);
+    }
+
+    /// Count a receipt in its expiry bucket, dropping buckets that have fully expired
+    fn track_receipt_expiry(env: &Env, live_until: u32) {
+        let now = env.ledger().sequence();
+        let mut expiries: Map<u32, u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReceiptExpiries)
+            .unwrap_or(Map::new(env));
+
+        for bucket in expiries.keys().iter() {
+            if ttl_bucket_expires_within(bucket, TTL_BUCKET_LEDGERS, now) > 0 {
+                break;
+            }
+            expiries.remove(bucket);
+        }
+
+        let bucket = ttl_bucket_index(live_until, TTL_BUCKET_LEDGERS);
+        let entries = expiries.get(bucket).unwrap_or(0);
+        expiries.set(bucket, entries.saturating_add(1));
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReceiptExpiries, &expiries);
+    }
 }
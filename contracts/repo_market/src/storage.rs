@@ -1,10 +1,29 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, Map, Vec};
 
 // Constants
+pub const SCALE: i128 = 10_000_000; // 7 decimals
+pub const SCALE_DECIMALS: u32 = 7; // Decimals of SCALE; PAR and marks always use this
 pub const BASIS_POINTS: i128 = 10_000; // 100% = 10,000 basis points
+pub const AUTO_CLOSE_WINDOW: u64 = 24 * 3600; // Keeper may auto-close within 1 day of deadline
+pub const MAX_DEFAULT_PENALTY_BPS: i128 = 2_000; // Default penalty capped at 20% of debt
+pub const PRUNE_AFTER_LEDGERS: u32 = 30 * 17_280; // ~30 days at 5s ledgers before a settled position can be pruned
+pub const RECEIPT_TTL_LEDGERS: u32 = 90 * 17_280; // Receipts are extended ~90 days at write
+pub const TTL_BUCKET_LEDGERS: u32 = 7 * 17_280; // Rent budget groups expiries by ~1 week
+pub const FUNDING_BUCKET_SECONDS: u64 = 3600; // Treasury outflows are tracked in hourly buckets
+pub const DAILY_FUNDING_BUCKETS: u64 = 24; // Rolling daily window
+pub const WEEKLY_FUNDING_BUCKETS: u64 = 7 * 24; // Rolling weekly window
+pub const MAX_DEFAULT_SHARES: u32 = 8; // Beneficiaries in a default split
+pub const DEFAULT_QUOTE_TTL_LEDGERS: u32 = 60; // Locked quotes hold ~5 minutes at 5s ledgers
+pub const MAX_QUOTE_TTL_LEDGERS: u32 = 720; // Quotes can be held at most ~1 hour
+pub const MAX_ELIGIBILITY_RULES: u32 = 16; // Rows in the collateral eligibility matrix
+pub const MIN_CAPITALIZATION_INTERVAL: u64 = 3600; // Interest capitalizes at most hourly
+pub const MAX_AUCTION_BIDS: u32 = 32; // Bids a term auction's book can hold
+pub const AUCTION_CLEARING_WINDOW: u64 = 3 * 86_400; // Auctions lapse and escrow is reclaimable 3 days after bidding ends
+pub const MAX_MATURITY_BLACKOUT: u64 = 30 * 86_400; // Pre-maturity blackout capped at 30 days
+pub const UNDER_REVIEW_HAIRCUT_ADDON_BPS: i128 = 1_000; // Extra haircut on series flagged UnderReview
 
 #[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum RepoStatus {
     /// Position is open, borrower can still repay
     Open = 0,
@@ -21,34 +40,529 @@ pub struct RepoPosition {
     pub id: u64,
     /// Borrower address
     pub borrower: Address,
+    /// Vault that issued the collateral series
+    pub vault: Address,
     /// Series ID of the collateral
     pub series_id: u32,
     /// Collateral amount in PAR units
     pub collateral_par: i128,
     /// Cash given to borrower
     pub cash_out: i128,
-    /// Amount borrower must repay (cash_out × (1 + spread))
+    /// Amount borrower must repay (cash_out × (1 + spread)); with capitalization,
+    /// cash_out plus the interest capitalized so far
     pub repurchase_amount: i128,
+    /// Spread applied at open, after any fee tier discount
+    pub spread_bps: i128,
     /// Timestamp when repo was opened
     pub start_time: u64,
     /// Deadline for repayment
     pub deadline: u64,
     /// Current position status
     pub status: RepoStatus,
+    /// Default penalty charged on claim_default (0 unless defaulted)
+    pub penalty: i128,
+    /// Portion of the penalty routed to the insurance fund
+    pub insurance_penalty: i128,
+    /// Installments repaid so far toward repurchase_amount
+    pub repaid: i128,
+    /// Collateral (PAR) already returned via release_collateral or withdraw_excess_collateral
+    pub collateral_released: i128,
+    /// How collateral is freed as installments land (fixed at open)
+    pub release_schedule: ReleaseSchedule,
+    /// Seconds between interest capitalizations (0 = spread due at close, fixed at open)
+    pub capitalization_interval: u64,
+    /// Time interest was last capitalized into repurchase_amount
+    pub capitalized_at: u64,
+}
+
+/// Portfolio-margin account: one credit line against bT-Bills from many series
+///
+/// The line is the haircut-weighted value of the whole deposit, so draws
+/// and repayments are tracked against the account rather than per position.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MarginAccount {
+    pub borrower: Address,
+    /// Vault whose series back the account; draws settle in its stablecoin
+    pub vault: Address,
+    /// Series ID → collateral PAR deposited
+    pub collateral: Map<u32, i128>,
+    /// Cash drawn and not yet repaid
+    pub drawn: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AuctionStatus {
+    /// Taking bids until bidding_ends
+    Open = 0,
+    /// Buckets allocated, rejected bids refunded
+    Cleared = 1,
+}
+
+/// Periodic auction of fixed-size funding buckets at a uniform clearing rate
+///
+/// Bids are filled highest rate first; every accepted bid pays the lowest
+/// accepted rate. Regular repos keep the configured spread.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TermAuction {
+    pub id: u64,
+    /// Vault whose series are accepted as collateral; cash settles in its stablecoin
+    pub vault: Address,
+    /// Cash per bucket, in asset units
+    pub bucket_size: i128,
+    /// Buckets on offer
+    pub buckets: u32,
+    /// Seconds from bidding_ends to the deadline of accepted repos
+    pub term: u64,
+    pub bidding_ends: u64,
+    pub status: AuctionStatus,
+    /// Uniform rate paid by accepted bids, in basis points (0 until cleared or if nothing filled)
+    pub clearing_rate_bps: i128,
+    pub buckets_filled: u32,
+}
+
+/// Sealed-rate bid for funding buckets, with its collateral held in escrow
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuctionBid {
+    /// Terms checked at submission (LTV, eligibility, deadline); revalidated and priced at clearing
+    pub quote: RepoQuote,
+    pub buckets: u32,
+    /// Highest spread the borrower accepts, in basis points
+    pub rate_bps: i128,
+}
+
+/// Repo terms frozen by lock_quote for open_repo_with_quote
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RepoQuote {
+    pub borrower: Address,
+    pub vault: Address,
+    pub series_id: u32,
+    pub collateral_par: i128,
+    pub cash_out: i128,
+    pub repurchase_amount: i128,
+    pub spread_bps: i128,
+    /// Mark the max cash was checked against
+    pub mark_price: i128,
+    /// Expiry of the mark override in force when quoted, if any
+    pub override_expiry: Option<u64>,
+    pub deadline: u64,
+    /// Capitalization interval the position will open with (0 = spread due at close)
+    pub capitalization_interval: u64,
+    /// Last ledger at which the quote can be executed
+    pub expires_at_ledger: u32,
+}
+
+/// How collateral is freed as installments are repaid
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReleaseSchedule {
+    /// Locked collateral shrinks in step with the remaining debt
+    Proportional = 0,
+    /// Locked collateral keeps remaining debt / value at the opening LTV,
+    /// so accretion of the mark also frees collateral
+    ConstantLtv = 1,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValuationKind {
+    /// Mark taken when the position was opened
+    Open = 0,
+    /// Mark taken when the borrower repaid
+    Close = 1,
+    /// Mark taken when the lender claimed default
+    Default = 2,
+    /// Mark the remaining collateral was revalidated at on withdraw_excess_collateral
+    Withdrawal = 3,
+    /// Margin account marks after a margin_deposit (opening or top-up)
+    MarginDeposit = 4,
+    /// Margin account marks the reduced line was checked at on margin_withdraw
+    MarginWithdrawal = 5,
+    /// Margin account marks the draw was checked at on margin_draw
+    MarginDraw = 6,
+    /// Margin account marks after a margin_repay
+    MarginRepay = 7,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MarkSource {
+    /// Vault's accreted model price
+    Model = 0,
+    /// Treasury override set during disorderly markets
+    Override = 1,
+}
+
+/// Treasury-set mark for a series, used in place of the model price until expiry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarkOverride {
+    /// Override price (SCALE = 1.0 PAR)
+    pub price: i128,
+    /// Override applies while ledger time < expiry
+    pub expiry: u64,
+}
+
+/// Timestamped collateral mark recorded at a position decision point
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralValuation {
+    /// Decision point that triggered the valuation
+    pub kind: ValuationKind,
+    /// Ledger timestamp of the valuation
+    pub timestamp: u64,
+    /// Price used as the mark
+    pub mark_price: i128,
+    /// Where the mark came from
+    pub source: MarkSource,
+    /// Expiry of the override in force (None for model marks)
+    pub override_expiry: Option<u64>,
+    /// Collateral amount in PAR units at the time
+    pub collateral_par: i128,
+    /// collateral_par × mark_price
+    pub collateral_value: i128,
+}
+
+/// Mark of one series held in a margin account
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarginMark {
+    pub series_id: u32,
+    /// Price used as the mark
+    pub mark_price: i128,
+    /// Where the mark came from
+    pub source: MarkSource,
+    /// Expiry of the override in force (None for model marks)
+    pub override_expiry: Option<u64>,
+    /// Collateral PAR of the series held at the time
+    pub collateral_par: i128,
+    /// collateral_par × mark_price
+    pub collateral_value: i128,
+}
+
+/// Timestamped valuation of a margin account at a margin event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarginValuation {
+    /// Margin event that triggered the valuation
+    pub kind: ValuationKind,
+    /// Ledger timestamp of the valuation
+    pub timestamp: u64,
+    /// Cash drawn after the event
+    pub drawn: i128,
+    /// Credit line at these marks
+    pub credit_line: i128,
+    /// Mark of each series held after the event, by series ID
+    pub marks: Vec<MarginMark>,
+}
+
+/// Immutable settlement record written once a position closes or defaults
+///
+/// Kept in persistent storage so counterparties retain a canonical record
+/// even if the mutable position is pruned.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionReceipt {
+    pub position_id: u64,
+    pub borrower: Address,
+    pub vault: Address,
+    pub series_id: u32,
+    /// Closed or Defaulted
+    pub outcome: RepoStatus,
+    pub collateral_par: i128,
+    pub cash_out: i128,
+    /// Stablecoin repaid by the borrower (installments only on default)
+    pub repayment: i128,
+    /// Default penalty charged (0 on close)
+    pub penalty: i128,
+    pub insurance_penalty: i128,
+    pub opened_at: u64,
+    pub settled_at: u64,
+    /// Ledger sequence at settlement (drives pruning eligibility)
+    pub settled_ledger: u32,
+    /// Mark recorded at open
+    pub open_mark: i128,
+    /// Mark recorded at settlement
+    pub settle_mark: i128,
+    pub settle_mark_source: MarkSource,
+}
+
+/// Persistent entries expiring within a window, for rent budgeting
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlBucket {
+    /// Entries in this bucket expire within this many ledgers from now
+    pub expires_within: u32,
+    /// Number of entries in the bucket
+    pub entries: u32,
+}
+
+/// On-chain object an ops annotation is anchored to
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AnnotationKind {
+    /// Series ID, resolved against the registered vaults
+    Series = 0,
+    /// Repo position ID (open, settled or pruned to a receipt)
+    Position = 1,
+}
+
+/// Hash of an off-chain note (compliance memo, incident report) anchored on-chain
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Annotation {
+    pub note_hash: BytesN<32>,
+    pub annotated_at: u64,
+}
+
+/// Estimated TTL-extension workload for the contract's receipts
+///
+/// Annotations, the only other persistent entries, are extended on every
+/// write and not counted. Expiries assume no external TTL extension since
+/// the write, so the estimate errs early.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RentBudget {
+    /// Live receipts
+    pub receipts: u32,
+    /// Receipts grouped by ledgers-to-expiry, soonest first
+    pub receipt_buckets: Vec<TtlBucket>,
+}
+
+/// Running market aggregates, updated on open/close/default
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketAggregates {
+    /// Number of positions in Open status
+    pub open_positions: u32,
+    /// Sum of cash_out across open positions
+    pub cash_outstanding: i128,
+    /// Sum of collateral_par across open positions
+    pub collateral_locked: i128,
+    /// Sum of cash_out × spread_bps across open positions
+    pub spread_weighted_cash: i128,
+    /// Number of positions ever defaulted
+    pub default_count: u32,
+    /// Sum of cash_out across defaulted positions
+    pub default_notional: i128,
+}
+
+/// Market dashboard snapshot
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketStats {
+    pub open_positions: u32,
+    pub total_cash_outstanding: i128,
+    pub total_collateral_locked: i128,
+    /// Cash-weighted average spread of open positions (bps)
+    pub weighted_avg_spread_bps: i128,
+    pub default_count: u32,
+    pub default_notional: i128,
+}
+
+/// Volume-based spread discount tier
+///
+/// A borrower whose lifetime repo volume (sum of cash_out) is at least
+/// `min_volume` gets `discount_bps` knocked off the base spread.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTier {
+    /// Lifetime cash_out volume required to reach this tier
+    pub min_volume: i128,
+    /// Spread reduction in basis points (e.g., 50 = 0.5%)
+    pub discount_bps: i128,
+}
+
+/// Penalty charged on default, as bps of the outstanding repurchase amount
+///
+/// The penalty is carved out of the seized collateral at the default mark:
+/// the insurance share goes to `insurance_fund`, the lender keeps the rest.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultPenalty {
+    /// Penalty in basis points of repurchase_amount (max MAX_DEFAULT_PENALTY_BPS)
+    pub penalty_bps: i128,
+    /// Share of the penalty routed to the insurance fund, in basis points
+    pub insurance_share_bps: i128,
+    /// Recipient of the insurance share
+    pub insurance_fund: Address,
+}
+
+/// One beneficiary's weight in the default split
+///
+/// Seized collateral left after the insurance slice is divided across the
+/// configured shares (e.g. lender, insurance fund, a series holder pool).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultShare {
+    /// Recipient of this slice of seized collateral
+    pub recipient: Address,
+    /// Weight in basis points; all weights sum to BASIS_POINTS
+    pub share_bps: i128,
+}
+
+/// Collateral (PAR) actually routed to one beneficiary on default
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultAllocation {
+    pub recipient: Address,
+    pub collateral_par: i128,
+}
+
+/// Rolling-window caps on treasury cash paid out by open_repo
+///
+/// A limit of 0 disables that window. An open that would push either
+/// window over its cap needs the admin's co-authorization.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundingLimits {
+    /// Max treasury outflow over the trailing 24 hours
+    pub daily_limit: i128,
+    /// Max treasury outflow over the trailing 7 days
+    pub weekly_limit: i128,
+}
+
+/// Treasury funding already used in each rolling window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundingUsage {
+    pub daily_used: i128,
+    pub weekly_used: i128,
+    pub limits: FundingLimits,
+}
+
+/// Market-wide configuration, packed into one entry so a call reads it once
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub admin: Address,
+    pub treasury: Address,
+    /// Haircut for series the eligibility matrix doesn't match, in basis points
+    pub haircut_bps: i128,
+    /// Base spread when no auction has cleared, in basis points
+    pub spread_bps: i128,
+    pub paused: bool,
+}
+
+/// Token pair backing a registered vault
+///
+/// Positions settle against the config of the vault they were opened on,
+/// so a deregistered vault's config is kept until its positions wind down.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaultConfig {
+    /// bT-Bill token the vault mints
+    pub bt_bill_token: Address,
+    /// Stablecoin the vault's series are priced in
+    pub stablecoin: Address,
+}
+
+/// One row of the collateral eligibility matrix
+///
+/// A series matches when every criterion holds; the first matching row
+/// decides whether its bT-Bills are accepted and at which haircut.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EligibilityRule {
+    /// Series issuer the row applies to (None = any issuer)
+    pub issuer: Option<Address>,
+    /// Seconds to maturity, lower bound
+    pub min_remaining_tenor: u64,
+    /// Seconds to maturity, upper bound (0 = unbounded)
+    pub max_remaining_tenor: u64,
+    /// Series outstanding PAR, lower bound
+    pub min_series_par: i128,
+    /// Series outstanding PAR, upper bound (0 = unbounded)
+    pub max_series_par: i128,
+    /// Whether matching collateral can back a repo
+    pub eligible: bool,
+    /// Haircut bucket for matching collateral, in basis points
+    pub haircut_bps: i128,
+}
+
+/// Vault-side risk assessment of a series
+///
+/// Mirrors bingo_vault's `RiskFlag`; discriminants must match.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RiskFlag {
+    /// Haircut raised by `UNDER_REVIEW_HAIRCUT_ADDON_BPS`
+    UnderReview = 0,
+    /// No new borrowing against the series
+    ImpairedIssuer = 1,
+}
+
+/// Position event reported to the vault for the borrower's statement
+///
+/// Mirrors bingo_vault's `RepoActivity`; discriminants must match.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RepoActivity {
+    Opened = 0,
+    Closed = 1,
+    Defaulted = 2,
+}
+
+/// How the eligibility matrix treats a series today
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralEligibility {
+    pub eligible: bool,
+    /// Haircut applied at open (the global haircut when no row matched),
+    /// after any vault risk flags
+    pub haircut_bps: i128,
+    /// Index of the matching row, if any
+    pub rule: Option<u32>,
+}
+
+/// Borrower's current standing in the fee tier schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BorrowerTier {
+    /// Lifetime cash_out volume
+    pub volume: i128,
+    /// 1-based tier index (0 = no tier reached)
+    pub tier: u32,
+    /// Spread discount currently applied at open
+    pub discount_bps: i128,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
-    Admin,
-    Treasury,
-    Vault,
-    BTBillToken,
-    Stablecoin,
-    Haircut,      // In basis points (e.g., 300 = 3%)
-    Spread,       // In basis points (e.g., 200 = 2%)
+    Config,                  // Config (absent = not initialized)
+    Vaults,                  // Vec<Address>, registered vaults in registration order
+    VaultConfig(Address),    // Vault → VaultConfig
     Position(u64), // Position ID → RepoPosition
     PositionCounter,
-    Initialized,
-    Paused,
+    FeeTiers,                // Vec<FeeTier>, ascending by min_volume
+    BorrowerVolume(Address), // Borrower → lifetime cash_out volume
+    ValuationHistory(u64),   // Position ID → Vec<CollateralValuation>
+    MarketAggregates,
+    RewardsHook,             // External incentives contract notified on user actions
+    MarkOverride(Address, u32), // (Vault, series ID) → MarkOverride
+    DefaultPenalty,          // DefaultPenalty applied on claim_default
+    DefaultSplit,            // Vec<DefaultShare> (absent = all seized collateral to treasury)
+    ReleaseSchedule,         // ReleaseSchedule for new positions (absent = Proportional)
+    Receipt(u64),            // Position ID → PositionReceipt (persistent)
+    ReceiptExpiries,         // Map<u32, u32>: TTL bucket index → receipts expiring in it
+    FundingLimits,           // FundingLimits on treasury cash_out
+    FundingOutflows,         // Map<u64, i128>: hourly bucket → treasury cash_out
+    QuoteTtl,                // Ledgers a locked quote stays valid
+    QuoteCounter,
+    Quote(u64),              // Quote ID → RepoQuote (temporary storage)
+    EligibilityRules,        // Vec<EligibilityRule>, first match wins (absent = all eligible)
+    Annotations(AnnotationKind, u64), // (kind, ID) → Vec<Annotation>, oldest first (persistent)
+    CapitalizationInterval,  // Seconds between interest capitalizations for new positions (absent = spread due at close)
+    MarginAccount(Address),  // Borrower → MarginAccount
+    MarginCollateral(Address), // Vault → Map<u32, i128>: margin collateral PAR by series
+    AuctionCounter,
+    Auction(u64),            // Auction ID → TermAuction
+    AuctionBids(u64),        // Auction ID → Vec<AuctionBid>, in submission order
+    MaturityBlackout,        // Seconds before series maturity in which new repos can't open (absent = 0)
+    MarginValuationHistory(Address), // Borrower → Vec<MarginValuation>
 }
@@ -0,0 +1,463 @@
+#![cfg(test)]
+
+use super::*;
+use crate::storage::SCALE;
+
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token::StellarAssetClient,
+    TryFromVal,
+};
+
+/// Minimal stand-in for bt_bill_token (keeps repo unit tests self-contained)
+#[contract]
+pub struct MockBTBillToken;
+
+#[contractimpl]
+impl MockBTBillToken {
+    pub fn mint(env: Env, series_id: u32, to: Address, amount: i128) {
+        let balance = Self::balance_of(env.clone(), series_id, to.clone());
+        env.storage()
+            .instance()
+            .set(&(series_id, to), &(balance + amount));
+    }
+
+    pub fn transfer(env: Env, series_id: u32, from: Address, to: Address, amount: i128) {
+        let balance = Self::balance_of(env.clone(), series_id, from.clone());
+        if balance < amount {
+            panic!("insufficient balance");
+        }
+        env.storage()
+            .instance()
+            .set(&(series_id, from), &(balance - amount));
+        Self::mint(env, series_id, to, amount);
+    }
+
+    pub fn balance_of(env: Env, series_id: u32, user: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&(series_id, user))
+            .unwrap_or(0)
+    }
+}
+
+/// Vault stand-in serving one series at a fixed mark
+#[contract]
+pub struct MockVault;
+
+#[contractimpl]
+impl MockVault {
+    pub fn get_series_maturity(_env: Env, _series_id: u32) -> u64 {
+        MATURITY_DATE
+    }
+
+    pub fn get_series_risk_flags(env: Env, _series_id: u32) -> Vec<RiskFlag> {
+        Vec::new(&env)
+    }
+
+    pub fn current_price(_env: Env, _series_id: u32) -> i128 {
+        MARK_PRICE
+    }
+
+    pub fn asset_decimals(_env: Env) -> u32 {
+        7
+    }
+
+    pub fn get_dust_threshold(_env: Env) -> i128 {
+        0
+    }
+
+    pub fn is_blocked(_env: Env, _account: Address) -> bool {
+        false
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    treasury: Address,
+    borrower: Address,
+    vault: Address,
+    stablecoin: token::Client<'a>,
+    bt_bill: MockBTBillTokenClient<'a>,
+    repo: RepoMarketClient<'a>,
+}
+
+const SERIES_ID: u32 = 1;
+const START_TIME: u64 = 1_000;
+const DEADLINE: u64 = 5_000;
+const MATURITY_DATE: u64 = 10_000;
+const MARK_PRICE: i128 = 98 * SCALE / 100; // 0.98
+const COLLATERAL_PAR: i128 = 1_000 * SCALE;
+const CASH_OUT: i128 = 900 * SCALE;
+const REPURCHASE: i128 = 918 * SCALE; // CASH_OUT at a 2% spread
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    // The treasury's cash moves one call below the repo entry points
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(START_TIME);
+    // Keep every entry live across the pruning and rent windows
+    env.ledger()
+        .set_min_persistent_entry_ttl(RECEIPT_TTL_LEDGERS);
+    env.ledger().set_max_entry_ttl(2 * RECEIPT_TTL_LEDGERS);
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let borrower = Address::generate(&env);
+
+    let stablecoin_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let stablecoin_admin = StellarAssetClient::new(&env, &stablecoin_id);
+    stablecoin_admin.mint(&borrower, &(1_000 * SCALE));
+    stablecoin_admin.mint(&treasury, &(1_000_000 * SCALE));
+
+    let bt_bill = MockBTBillTokenClient::new(&env, &env.register(MockBTBillToken, ()));
+    bt_bill.mint(&SERIES_ID, &borrower, &(10_000 * SCALE));
+    let vault = env.register(MockVault, ());
+
+    let repo = RepoMarketClient::new(&env, &env.register(RepoMarket, ()));
+    repo.initialize(
+        &admin,
+        &treasury,
+        &vault,
+        &bt_bill.address,
+        &stablecoin_id,
+        &300, // 3% haircut
+        &200, // 2% spread
+    );
+
+    Setup {
+        stablecoin: token::Client::new(&env, &stablecoin_id),
+        env,
+        admin,
+        treasury,
+        borrower,
+        vault,
+        bt_bill,
+        repo,
+    }
+}
+
+fn open(s: &Setup) -> u64 {
+    s.repo.open_repo(
+        &s.borrower,
+        &s.vault,
+        &SERIES_ID,
+        &COLLATERAL_PAR,
+        &CASH_OUT,
+        &DEADLINE,
+    )
+}
+
+#[test]
+fn test_open_repo_escrows_collateral_and_pays_cash() {
+    let s = setup();
+
+    assert_eq!(
+        s.repo.try_open_repo(
+            &s.borrower,
+            &s.vault,
+            &SERIES_ID,
+            &COLLATERAL_PAR,
+            &(951 * SCALE), // above 1,000 × 0.98 × 97%
+            &DEADLINE,
+        ),
+        Err(Ok(Error::ExceedsMaxCash))
+    );
+
+    assert_eq!(open(&s), 1);
+    let position = s.repo.get_position(&1);
+    assert_eq!(position.status, RepoStatus::Open);
+    assert_eq!(position.repurchase_amount, REPURCHASE);
+    assert_eq!(
+        s.bt_bill.balance_of(&SERIES_ID, &s.repo.address),
+        COLLATERAL_PAR
+    );
+    assert_eq!(s.stablecoin.balance(&s.borrower), 1_000 * SCALE + CASH_OUT);
+
+    let stats = s.repo.get_market_stats();
+    assert_eq!(stats.open_positions, 1);
+    assert_eq!(stats.total_cash_outstanding, CASH_OUT);
+    assert_eq!(stats.total_collateral_locked, COLLATERAL_PAR);
+}
+
+#[test]
+fn test_close_repo_repays_and_returns_collateral() {
+    let s = setup();
+    open(&s);
+    let treasury_before = s.stablecoin.balance(&s.treasury);
+
+    s.repo.close_repo(&1);
+    assert_eq!(s.repo.get_position(&1).status, RepoStatus::Closed);
+    assert_eq!(
+        s.stablecoin.balance(&s.treasury) - treasury_before,
+        REPURCHASE
+    );
+    assert_eq!(
+        s.bt_bill.balance_of(&SERIES_ID, &s.borrower),
+        10_000 * SCALE
+    );
+    assert_eq!(s.bt_bill.balance_of(&SERIES_ID, &s.repo.address), 0);
+    assert_eq!(s.repo.get_market_stats().open_positions, 0);
+
+    assert_eq!(s.repo.try_close_repo(&1), Err(Ok(Error::InvalidStatus)));
+    assert_eq!(s.repo.try_claim_default(&1), Err(Ok(Error::InvalidStatus)));
+}
+
+#[test]
+fn test_close_repo_after_deadline_fails() {
+    let s = setup();
+    open(&s);
+
+    s.env.ledger().set_timestamp(DEADLINE + 1);
+    assert_eq!(s.repo.try_close_repo(&1), Err(Ok(Error::DeadlinePassed)));
+}
+
+#[test]
+fn test_claim_default_seizes_collateral() {
+    let s = setup();
+    open(&s);
+    assert_eq!(
+        s.repo.try_claim_default(&1),
+        Err(Ok(Error::DeadlineNotPassed))
+    );
+
+    s.env.ledger().set_timestamp(DEADLINE + 1);
+    s.repo.claim_default(&1);
+
+    let position = s.repo.get_position(&1);
+    assert_eq!(position.status, RepoStatus::Defaulted);
+    assert_eq!(position.penalty, 0);
+    assert_eq!(
+        s.bt_bill.balance_of(&SERIES_ID, &s.treasury),
+        COLLATERAL_PAR
+    );
+    assert_eq!(s.bt_bill.balance_of(&SERIES_ID, &s.repo.address), 0);
+
+    let stats = s.repo.get_market_stats();
+    assert_eq!(stats.open_positions, 0);
+    assert_eq!(stats.default_count, 1);
+    assert_eq!(stats.default_notional, CASH_OUT);
+}
+
+#[test]
+fn test_claim_default_charges_late_penalty() {
+    let s = setup();
+    let insurance_fund = Address::generate(&s.env);
+    assert_eq!(
+        s.repo
+            .try_set_default_penalty(&2_001, &5_000, &insurance_fund),
+        Err(Ok(Error::InvalidDefaultPenalty))
+    );
+    // 10% of the debt, half of it owed to the insurance fund
+    s.repo.set_default_penalty(&1_000, &5_000, &insurance_fund);
+    open(&s);
+
+    s.env.ledger().set_timestamp(DEADLINE + 1);
+    s.repo.claim_default(&1);
+
+    let position = s.repo.get_position(&1);
+    assert_eq!(position.penalty, REPURCHASE / 10);
+    assert_eq!(position.insurance_penalty, REPURCHASE / 20);
+
+    // 45.9 of penalty at the 0.98 mark, taken out of the seized collateral
+    let insurance_collateral = (REPURCHASE / 20) * SCALE / MARK_PRICE;
+    assert_eq!(
+        s.bt_bill.balance_of(&SERIES_ID, &insurance_fund),
+        insurance_collateral
+    );
+    assert_eq!(
+        s.bt_bill.balance_of(&SERIES_ID, &s.treasury),
+        COLLATERAL_PAR - insurance_collateral
+    );
+}
+
+#[test]
+fn test_receipt_written_on_repay() {
+    let s = setup();
+    open(&s);
+    assert_eq!(s.repo.try_get_receipt(&1), Err(Ok(Error::ReceiptNotFound)));
+
+    s.env.ledger().set_timestamp(2_000);
+    s.repo.close_repo(&1);
+
+    let receipt = s.repo.get_receipt(&1);
+    assert_eq!(receipt.outcome, RepoStatus::Closed);
+    assert_eq!(receipt.borrower, s.borrower);
+    assert_eq!(receipt.collateral_par, COLLATERAL_PAR);
+    assert_eq!(receipt.cash_out, CASH_OUT);
+    assert_eq!(receipt.repayment, REPURCHASE);
+    assert_eq!(receipt.penalty, 0);
+    assert_eq!(receipt.opened_at, START_TIME);
+    assert_eq!(receipt.settled_at, 2_000);
+    assert_eq!(receipt.open_mark, MARK_PRICE);
+    assert_eq!(receipt.settle_mark, MARK_PRICE);
+    assert_eq!(receipt.settle_mark_source, MarkSource::Model);
+}
+
+#[test]
+fn test_receipt_written_on_default() {
+    let s = setup();
+    let insurance_fund = Address::generate(&s.env);
+    s.repo.set_default_penalty(&1_000, &5_000, &insurance_fund);
+    open(&s);
+
+    s.repo.repay_installment(&1, &(100 * SCALE));
+    s.env.ledger().set_timestamp(DEADLINE + 1);
+    s.repo.claim_default(&1);
+
+    // Only the installment was repaid; the penalty is on what was left
+    let receipt = s.repo.get_receipt(&1);
+    assert_eq!(receipt.outcome, RepoStatus::Defaulted);
+    assert_eq!(receipt.repayment, 100 * SCALE);
+    assert_eq!(receipt.penalty, (REPURCHASE - 100 * SCALE) / 10);
+    assert_eq!(receipt.insurance_penalty, (REPURCHASE - 100 * SCALE) / 20);
+    assert_eq!(receipt.settled_at, DEADLINE + 1);
+    assert_eq!(receipt.settle_mark_source, MarkSource::Model);
+}
+
+#[test]
+fn test_prune_positions_drops_settled_keeps_open() {
+    let s = setup();
+    let keeper = Address::generate(&s.env);
+    open(&s);
+    open(&s);
+    s.repo.close_repo(&1);
+    let ids = vec![&s.env, 1, 2, 99];
+
+    // Settled too recently
+    assert_eq!(s.repo.prune_positions(&keeper, &ids), 0);
+
+    let settled_ledger = s.repo.get_receipt(&1).settled_ledger;
+    s.env
+        .ledger()
+        .set_sequence_number(settled_ledger + PRUNE_AFTER_LEDGERS);
+    assert_eq!(s.repo.prune_positions(&keeper, &ids), 1);
+
+    assert_eq!(
+        s.repo.try_get_position(&1).err(),
+        Some(Ok(Error::PositionNotFound))
+    );
+    assert!(s.repo.get_valuation_history(&1).is_empty());
+    assert_eq!(s.repo.get_receipt(&1).outcome, RepoStatus::Closed);
+    assert_eq!(s.repo.get_position(&2).status, RepoStatus::Open);
+    assert_eq!(s.repo.get_valuation_history(&2).len(), 1);
+
+    // Already pruned
+    assert_eq!(s.repo.prune_positions(&keeper, &ids), 0);
+}
+
+#[test]
+fn test_rent_budget_tracks_settled_receipts() {
+    let s = setup();
+    open(&s);
+    open(&s);
+
+    // Open positions have no receipts yet
+    let budget = s.repo.get_rent_budget();
+    assert_eq!(budget.receipts, 0);
+    assert!(budget.receipt_buckets.is_empty());
+
+    // A receipt written at ledger 0 lives until RECEIPT_TTL_LEDGERS
+    s.repo.close_repo(&1);
+    let expires_within = (RECEIPT_TTL_LEDGERS / TTL_BUCKET_LEDGERS + 1) * TTL_BUCKET_LEDGERS;
+    let budget = s.repo.get_rent_budget();
+    assert_eq!(budget.receipts, 1);
+    assert_eq!(
+        budget.receipt_buckets,
+        vec![
+            &s.env,
+            TtlBucket {
+                expires_within,
+                entries: 1,
+            },
+        ]
+    );
+
+    // One bucket later the default lands in the next bucket
+    s.env.ledger().set_sequence_number(TTL_BUCKET_LEDGERS);
+    s.env.ledger().set_timestamp(DEADLINE + 1);
+    s.repo.claim_default(&2);
+    let budget = s.repo.get_rent_budget();
+    assert_eq!(budget.receipts, 2);
+    assert_eq!(
+        budget.receipt_buckets,
+        vec![
+            &s.env,
+            TtlBucket {
+                expires_within: expires_within - TTL_BUCKET_LEDGERS,
+                entries: 1,
+            },
+            TtlBucket {
+                expires_within,
+                entries: 1,
+            },
+        ]
+    );
+}
+
+fn admin_signed(s: &Setup) -> bool {
+    s.env.auths().iter().any(|(addr, _)| *addr == s.admin)
+}
+
+#[test]
+fn test_treasury_funding_over_limit_needs_admin() {
+    let s = setup();
+    assert_eq!(
+        s.repo.try_set_funding_limits(&FundingLimits {
+            daily_limit: 2_000 * SCALE,
+            weekly_limit: 1_000 * SCALE,
+        }),
+        Err(Ok(Error::InvalidFundingLimits))
+    );
+    s.repo.set_funding_limits(&FundingLimits {
+        daily_limit: 1_000 * SCALE,
+        weekly_limit: 5_000 * SCALE,
+    });
+
+    // Within the daily cap the treasury alone funds the open
+    open(&s);
+    assert!(!admin_signed(&s));
+    assert_eq!(s.repo.get_funding_usage().daily_used, CASH_OUT);
+
+    // Margin draws come out of the same windows
+    s.repo
+        .margin_deposit(&s.borrower, &s.vault, &SERIES_ID, &COLLATERAL_PAR);
+    s.repo.margin_draw(&s.borrower, &(100 * SCALE));
+    assert!(!admin_signed(&s));
+    s.repo.margin_draw(&s.borrower, &SCALE);
+    assert!(admin_signed(&s));
+
+    let usage = s.repo.get_funding_usage();
+    assert_eq!(usage.daily_used, 1_001 * SCALE);
+    assert_eq!(usage.weekly_used, 1_001 * SCALE);
+}
+
+#[test]
+fn test_treasury_funding_admin_cosign_overrides_limit() {
+    let s = setup();
+    s.repo.set_funding_limits(&FundingLimits {
+        daily_limit: 1_000 * SCALE,
+        weekly_limit: 5_000 * SCALE,
+    });
+    open(&s);
+
+    // The second open breaches the daily cap and goes through on the admin's co-signature
+    assert_eq!(open(&s), 2);
+    assert!(admin_signed(&s));
+    let cosigned = Symbol::new(&s.env, "funding_limit_cosigned");
+    assert!(s.env.events().all().iter().any(|(_, topics, _)| {
+        topics
+            .get(2)
+            .is_some_and(|topic| Symbol::try_from_val(&s.env, &topic) == Ok(cosigned.clone()))
+    }));
+    assert_eq!(s.repo.get_funding_usage().daily_used, 2 * CASH_OUT);
+
+    // A day later the daily window has rolled; the weekly one has not
+    s.env.ledger().set_timestamp(START_TIME + 86_400);
+    let usage = s.repo.get_funding_usage();
+    assert_eq!(usage.daily_used, 0);
+    assert_eq!(usage.weekly_used, 2 * CASH_OUT);
+}
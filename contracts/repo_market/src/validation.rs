@@ -1,4 +1,9 @@
-use crate::storage::BASIS_POINTS;
+use crate::storage::{
+    CollateralEligibility, DefaultShare, EligibilityRule, FeeTier, FundingLimits, ReleaseSchedule,
+    RiskFlag, BASIS_POINTS, MAX_DEFAULT_PENALTY_BPS, MAX_DEFAULT_SHARES, MAX_ELIGIBILITY_RULES,
+    SCALE, SCALE_DECIMALS, UNDER_REVIEW_HAIRCUT_ADDON_BPS,
+};
+use soroban_sdk::{Address, Map, Vec};
 
 /// Calculate maximum cash that can be borrowed
 ///
@@ -11,13 +16,20 @@ use crate::storage::BASIS_POINTS;
 /// - collateral_value: 10,000 × 0.99 = 9,900
 /// - max_cash: 9,900 × 97% = 9,603
 pub fn calculate_max_cash(collateral_par: i128, mark_price: i128, haircut_bps: i128) -> Option<i128> {
-    let collateral_value = collateral_par.checked_mul(mark_price)?.checked_div(10_000_000)?; // Divide by SCALE
+    let collateral_value = calculate_collateral_value(collateral_par, mark_price)?;
 
     let ltv_bps = BASIS_POINTS.checked_sub(haircut_bps)?; // 10,000 - 300 = 9,700 (97%)
     
     collateral_value.checked_mul(ltv_bps)?.checked_div(BASIS_POINTS)
 }
 
+/// Calculate collateral value at a mark price
+///
+/// Formula: collateral_value = collateral_par × mark_price / SCALE
+pub fn calculate_collateral_value(collateral_par: i128, mark_price: i128) -> Option<i128> {
+    collateral_par.checked_mul(mark_price)?.checked_div(SCALE)
+}
+
 /// Calculate repurchase amount
 ///
 /// Formula: repurchase = cash_out × (1 + spread)
@@ -32,9 +44,464 @@ pub fn calculate_repurchase(cash_out: i128, spread_bps: i128) -> Option<i128> {
     cash_out.checked_mul(multiplier)?.checked_div(BASIS_POINTS)
 }
 
+/// Per-period interest rate when a term's spread is capitalized in installments
+///
+/// Formula: rate = spread × interval / term (SCALE = 100%)
+///
+/// Example:
+/// - spread: 2% (200 basis points) over a 4-day term
+/// - interval: 1 day
+/// - rate: 0.5% per period
+pub fn calculate_period_rate(spread_bps: i128, interval: u64, term: u64) -> Option<i128> {
+    if term == 0 {
+        return None;
+    }
+    spread_bps
+        .checked_mul(SCALE)?
+        .checked_mul(interval as i128)?
+        .checked_div(BASIS_POINTS.checked_mul(term as i128)?)
+}
+
+/// Compound a balance over whole periods
+///
+/// Formula: balance × (1 + rate)^periods, with the growth factor kept at
+/// SCALE precision (rounded down at each step)
+///
+/// Example:
+/// - balance: 9,000, rate: 0.5%, periods: 4
+/// - factor: 1.0201505 → 9,181.3545
+pub fn calculate_compounded(balance: i128, period_rate: i128, periods: u64) -> Option<i128> {
+    let mut base = SCALE.checked_add(period_rate)?;
+    let mut factor = SCALE;
+    let mut remaining = periods;
+    while remaining > 0 {
+        if remaining & 1 == 1 {
+            factor = factor.checked_mul(base)?.checked_div(SCALE)?;
+        }
+        remaining >>= 1;
+        if remaining > 0 {
+            base = base.checked_mul(base)?.checked_div(SCALE)?;
+        }
+    }
+    balance.checked_mul(factor)?.checked_div(SCALE)
+}
+
+/// Collateral kept behind a partially filled auction bid, rounded up
+///
+/// Formula: kept = ceil(collateral × filled / requested)
+pub fn calculate_partial_collateral(
+    collateral_par: i128,
+    filled: u32,
+    requested: u32,
+) -> Option<i128> {
+    if requested == 0 {
+        return None;
+    }
+    let numerator = collateral_par.checked_mul(filled as i128)?;
+    let requested = requested as i128;
+    numerator.checked_add(requested - 1)?.checked_div(requested)
+}
+
+/// Calculate cash-weighted average spread
+///
+/// Formula: avg_spread = Σ(cash_out × spread_bps) / Σ cash_out
+pub fn calculate_weighted_spread(spread_weighted_cash: i128, cash_outstanding: i128) -> i128 {
+    if cash_outstanding <= 0 {
+        return 0;
+    }
+    spread_weighted_cash / cash_outstanding
+}
+
+/// Validate a fee tier schedule
+///
+/// Tiers must be strictly ascending by min_volume, with non-negative volumes
+/// and discounts in [0, BASIS_POINTS].
+pub fn validate_fee_tiers(tiers: &Vec<FeeTier>) -> bool {
+    let mut prev_volume: Option<i128> = None;
+    for tier in tiers.iter() {
+        if tier.min_volume < 0 || tier.discount_bps < 0 || tier.discount_bps > BASIS_POINTS {
+            return false;
+        }
+        if let Some(prev) = prev_volume {
+            if tier.min_volume <= prev {
+                return false;
+            }
+        }
+        prev_volume = Some(tier.min_volume);
+    }
+    true
+}
+
+/// Resolve the highest tier reached for a lifetime volume
+///
+/// Returns (1-based tier index, discount_bps), or (0, 0) if no tier is reached.
+///
+/// Example:
+/// - tiers: [1M → 25 bps, 10M → 50 bps]
+/// - volume: 2.5M
+/// - result: (1, 25)
+pub fn resolve_fee_tier(tiers: &Vec<FeeTier>, volume: i128) -> (u32, i128) {
+    let mut resolved = (0u32, 0i128);
+    for (i, tier) in tiers.iter().enumerate() {
+        if volume < tier.min_volume {
+            break;
+        }
+        resolved = (i as u32 + 1, tier.discount_bps);
+    }
+    resolved
+}
+
+/// Validate a treasury mark override
+///
+/// The price must be positive and at most PAR, and the override must not
+/// already be expired at `now`.
+pub fn validate_mark_override(price: i128, expiry: u64, now: u64) -> bool {
+    price > 0 && price <= SCALE && expiry > now
+}
+
+/// Validate a default penalty configuration
+pub fn validate_default_penalty(penalty_bps: i128, insurance_share_bps: i128) -> bool {
+    (0..=MAX_DEFAULT_PENALTY_BPS).contains(&penalty_bps)
+        && (0..=BASIS_POINTS).contains(&insurance_share_bps)
+}
+
+/// Calculate the default penalty and its insurance share
+///
+/// Formula:
+/// - penalty = debt × penalty_bps
+/// - insurance_penalty = penalty × insurance_share_bps
+///
+/// Example:
+/// - debt: 9,180
+/// - penalty: 5% (500 bps) → 459
+/// - insurance share: 40% (4,000 bps) → 183.6
+pub fn calculate_default_penalty(
+    debt: i128,
+    penalty_bps: i128,
+    insurance_share_bps: i128,
+) -> Option<(i128, i128)> {
+    let penalty = debt.checked_mul(penalty_bps)?.checked_div(BASIS_POINTS)?;
+    let insurance_penalty = penalty
+        .checked_mul(insurance_share_bps)?
+        .checked_div(BASIS_POINTS)?;
+    Some((penalty, insurance_penalty))
+}
+
+/// Validate a default split
+///
+/// Needs 1 to MAX_DEFAULT_SHARES shares, each with a positive weight, and
+/// weights summing to exactly BASIS_POINTS.
+pub fn validate_default_split(shares: &Vec<DefaultShare>) -> bool {
+    if shares.is_empty() || shares.len() > MAX_DEFAULT_SHARES {
+        return false;
+    }
+    let mut total: i128 = 0;
+    for share in shares.iter() {
+        if share.share_bps <= 0 {
+            return false;
+        }
+        total = total.saturating_add(share.share_bps);
+    }
+    total == BASIS_POINTS
+}
+
+/// Divide seized collateral across a default split
+///
+/// Formula: amount_i = collateral × share_bps_i / 10,000, with the rounding
+/// remainder added to the first share (the lender by convention).
+///
+/// Example:
+/// - collateral: 1,000 PAR
+/// - shares: [6,000, 2,500, 1,500] bps
+/// - amounts: [600, 250, 150]
+pub fn calculate_default_split(collateral: i128, shares: &Vec<DefaultShare>) -> Option<Vec<i128>> {
+    let mut amounts = Vec::new(shares.env());
+    let mut allocated: i128 = 0;
+    for share in shares.iter() {
+        let amount = collateral
+            .checked_mul(share.share_bps)?
+            .checked_div(BASIS_POINTS)?;
+        allocated = allocated.checked_add(amount)?;
+        amounts.push_back(amount);
+    }
+
+    let remainder = collateral.checked_sub(allocated)?;
+    let first = amounts.get(0)?.checked_add(remainder)?;
+    amounts.set(0, first);
+    Some(amounts)
+}
+
+/// Calculate collateral that may be released after installment repayments
+///
+/// Formula (required collateral, rounded up in the lender's favour):
+/// - Proportional: original × remaining_debt / repurchase
+/// - ConstantLtv: original × remaining_debt × open_mark / (repurchase × mark)
+///
+/// releasable = max(locked - min(required, original), 0),
+/// where original = locked + released.
+///
+/// Example (Proportional):
+/// - original: 10,000 PAR, repurchase: 9,180, repaid: 4,590
+/// - required: 5,000 PAR → releasable: 5,000 PAR
+pub fn calculate_releasable_collateral(
+    schedule: ReleaseSchedule,
+    locked_par: i128,
+    released_par: i128,
+    repurchase_amount: i128,
+    repaid: i128,
+    open_mark: i128,
+    mark_price: i128,
+) -> Option<i128> {
+    if repurchase_amount <= 0 {
+        return Some(0);
+    }
+    let original = locked_par.checked_add(released_par)?;
+    let remaining_debt = repurchase_amount.checked_sub(repaid)?.max(0);
+
+    let (numerator, denominator) = match schedule {
+        ReleaseSchedule::Proportional => (original.checked_mul(remaining_debt)?, repurchase_amount),
+        ReleaseSchedule::ConstantLtv => {
+            if mark_price <= 0 {
+                return Some(0);
+            }
+            (
+                original
+                    .checked_mul(remaining_debt)?
+                    .checked_mul(open_mark)?,
+                repurchase_amount.checked_mul(mark_price)?,
+            )
+        }
+    };
+    let required = numerator
+        .checked_add(denominator - 1)?
+        .checked_div(denominator)?
+        .min(original);
+
+    Some(locked_par.checked_sub(required)?.max(0))
+}
+
+/// Convert a stablecoin amount into collateral PAR at a mark price
+///
+/// Formula: par = min(amount × SCALE / mark_price, collateral_par)
+pub fn calculate_collateral_par_for(
+    amount: i128,
+    mark_price: i128,
+    collateral_par: i128,
+) -> Option<i128> {
+    if mark_price <= 0 {
+        return None;
+    }
+    let par = amount.checked_mul(SCALE)?.checked_div(mark_price)?;
+    Some(par.min(collateral_par))
+}
+
+/// Rescale a SCALE-denominated value into the vault's settlement asset units
+///
+/// Truncates when the asset has fewer decimals (e.g. 6 for USDC-style assets).
+pub fn to_asset_units(amount: i128, asset_decimals: u32) -> Option<i128> {
+    rescale(amount, SCALE_DECIMALS, asset_decimals)
+}
+
+/// Rescale settlement asset units into a SCALE-denominated value
+pub fn from_asset_units(amount: i128, asset_decimals: u32) -> Option<i128> {
+    rescale(amount, asset_decimals, SCALE_DECIMALS)
+}
+
+fn rescale(amount: i128, from_decimals: u32, to_decimals: u32) -> Option<i128> {
+    if to_decimals >= from_decimals {
+        amount.checked_mul(10i128.checked_pow(to_decimals - from_decimals)?)
+    } else {
+        amount.checked_div(10i128.checked_pow(from_decimals - to_decimals)?)
+    }
+}
+
+/// Bucket index holding an entry that lives until `live_until`
+pub fn ttl_bucket_index(live_until: u32, bucket_ledgers: u32) -> u32 {
+    live_until / bucket_ledgers
+}
+
+/// Ledgers from `now` until every entry in a bucket has expired (0 if already expired)
+pub fn ttl_bucket_expires_within(bucket: u32, bucket_ledgers: u32, now: u32) -> u32 {
+    bucket
+        .saturating_add(1)
+        .saturating_mul(bucket_ledgers)
+        .saturating_sub(now)
+}
+
+/// Apply a tier discount to the base spread
+///
+/// Formula: spread = max(0, base_spread - discount)
+pub fn calculate_discounted_spread(spread_bps: i128, discount_bps: i128) -> i128 {
+    spread_bps.saturating_sub(discount_bps).max(0)
+}
+
+/// Validate funding limits (0 disables a window)
+pub fn validate_funding_limits(limits: &FundingLimits) -> bool {
+    if limits.daily_limit < 0 || limits.weekly_limit < 0 {
+        return false;
+    }
+    limits.daily_limit == 0 || limits.weekly_limit == 0 || limits.weekly_limit >= limits.daily_limit
+}
+
+/// Validate the collateral eligibility matrix
+///
+/// At most MAX_ELIGIBILITY_RULES rows, haircuts in [0, BASIS_POINTS], and
+/// each set upper bound at or above its lower bound.
+pub fn validate_eligibility_rules(rules: &Vec<EligibilityRule>) -> bool {
+    if rules.len() > MAX_ELIGIBILITY_RULES {
+        return false;
+    }
+    rules.iter().all(|rule| {
+        (0..=BASIS_POINTS).contains(&rule.haircut_bps)
+            && rule.min_series_par >= 0
+            && rule.max_series_par >= 0
+            && (rule.max_remaining_tenor == 0
+                || rule.max_remaining_tenor >= rule.min_remaining_tenor)
+            && (rule.max_series_par == 0 || rule.max_series_par >= rule.min_series_par)
+    })
+}
+
+/// Find the first row matching a series' issuer, remaining tenor and outstanding PAR
+///
+/// Returns (row index, row), or None if no row matches.
+pub fn match_eligibility_rule(
+    rules: &Vec<EligibilityRule>,
+    issuer: &Address,
+    remaining_tenor: u64,
+    series_par: i128,
+) -> Option<(u32, EligibilityRule)> {
+    for (index, rule) in (0u32..).zip(rules.iter()) {
+        let issuer_matches = match &rule.issuer {
+            Some(rule_issuer) => rule_issuer == issuer,
+            None => true,
+        };
+        let tenor_matches = remaining_tenor >= rule.min_remaining_tenor
+            && (rule.max_remaining_tenor == 0 || remaining_tenor <= rule.max_remaining_tenor);
+        let size_matches = series_par >= rule.min_series_par
+            && (rule.max_series_par == 0 || series_par <= rule.max_series_par);
+        if issuer_matches && tenor_matches && size_matches {
+            return Some((index, rule));
+        }
+    }
+    None
+}
+
+/// Tighten an eligibility verdict by a series' vault risk flags
+///
+/// `ImpairedIssuer` makes the series ineligible; `UnderReview` adds
+/// `UNDER_REVIEW_HAIRCUT_ADDON_BPS` to the haircut, capped at 100%.
+pub fn apply_risk_flags(
+    mut eligibility: CollateralEligibility,
+    flags: &Vec<RiskFlag>,
+) -> CollateralEligibility {
+    if flags.contains(RiskFlag::ImpairedIssuer) {
+        eligibility.eligible = false;
+    }
+    if flags.contains(RiskFlag::UnderReview) {
+        eligibility.haircut_bps = eligibility
+            .haircut_bps
+            .saturating_add(UNDER_REVIEW_HAIRCUT_ADDON_BPS)
+            .min(BASIS_POINTS);
+    }
+    eligibility
+}
+
+/// Sum outflows in the `window` buckets ending at `now_bucket` (inclusive)
+pub fn rolling_window_total(outflows: &Map<u64, i128>, now_bucket: u64, window: u64) -> i128 {
+    let start = (now_bucket + 1).saturating_sub(window);
+    let mut total: i128 = 0;
+    for (bucket, amount) in outflows.iter() {
+        if bucket >= start && bucket <= now_bucket {
+            total = total.saturating_add(amount);
+        }
+    }
+    total
+}
+
+/// Whether adding `amount` to `used` breaches `limit` (0 = unlimited)
+pub fn exceeds_funding_limit(used: i128, amount: i128, limit: i128) -> bool {
+    limit > 0 && used.saturating_add(amount) > limit
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use soroban_sdk::{testutils::Address as _, vec, Address, Env};
+
+    #[test]
+    fn test_validate_mark_override() {
+        let now = 1_000;
+
+        assert!(validate_mark_override(97 * SCALE / 100, now + 1, now));
+        assert!(validate_mark_override(SCALE, now + 3600, now));
+        assert!(!validate_mark_override(0, now + 1, now));
+        assert!(!validate_mark_override(SCALE + 1, now + 1, now));
+        // Already expired
+        assert!(!validate_mark_override(SCALE / 2, now, now));
+    }
+
+    #[test]
+    fn test_default_penalty_split() {
+        let debt = 9_180 * SCALE;
+
+        assert!(validate_default_penalty(500, 4_000));
+        assert!(!validate_default_penalty(MAX_DEFAULT_PENALTY_BPS + 1, 0));
+        assert!(!validate_default_penalty(500, BASIS_POINTS + 1));
+
+        let (penalty, insurance_penalty) = calculate_default_penalty(debt, 500, 4_000).unwrap();
+        assert_eq!(penalty, 459 * SCALE);
+        assert_eq!(insurance_penalty, 1_836 * SCALE / 10);
+
+        // 183.6 at a 0.9 mark → 204 PAR of collateral
+        let par = calculate_collateral_par_for(insurance_penalty, 9 * SCALE / 10, 10_000 * SCALE);
+        assert_eq!(par, Some(204 * SCALE));
+
+        // Capped at the collateral actually held
+        let capped = calculate_collateral_par_for(insurance_penalty, 9 * SCALE / 10, 100 * SCALE);
+        assert_eq!(capped, Some(100 * SCALE));
+    }
+
+    #[test]
+    fn test_ttl_buckets() {
+        let bucket_ledgers = 100;
+
+        assert_eq!(ttl_bucket_index(0, bucket_ledgers), 0);
+        assert_eq!(ttl_bucket_index(250, bucket_ledgers), 2);
+
+        // Bucket 2 covers [200, 300)
+        assert_eq!(ttl_bucket_expires_within(2, bucket_ledgers, 120), 180);
+        assert_eq!(ttl_bucket_expires_within(2, bucket_ledgers, 299), 1);
+        assert_eq!(ttl_bucket_expires_within(2, bucket_ledgers, 300), 0);
+    }
+
+    #[test]
+    fn test_apply_risk_flags() {
+        let env = Env::default();
+        let base = CollateralEligibility {
+            eligible: true,
+            haircut_bps: 300,
+            rule: Some(0),
+        };
+
+        assert_eq!(apply_risk_flags(base.clone(), &Vec::new(&env)), base);
+
+        let review = apply_risk_flags(base.clone(), &vec![&env, RiskFlag::UnderReview]);
+        assert!(review.eligible);
+        assert_eq!(review.haircut_bps, 300 + UNDER_REVIEW_HAIRCUT_ADDON_BPS);
+        assert_eq!(review.rule, Some(0));
+
+        let impaired = apply_risk_flags(base.clone(), &vec![&env, RiskFlag::ImpairedIssuer]);
+        assert!(!impaired.eligible);
+        assert_eq!(impaired.haircut_bps, 300);
+
+        // Haircut never exceeds 100%
+        let steep = CollateralEligibility {
+            haircut_bps: BASIS_POINTS - 100,
+            ..base
+        };
+        let capped = apply_risk_flags(steep, &vec![&env, RiskFlag::UnderReview]);
+        assert_eq!(capped.haircut_bps, BASIS_POINTS);
+    }
 
     #[test]
     fn test_calculate_max_cash() {
@@ -48,6 +515,23 @@ mod tests {
         assert_eq!(max_cash, 9603 * 10_000_000);
     }
 
+    #[test]
+    fn test_compounded_interest() {
+        // 2% over a 4-day term, capitalized daily
+        let rate = calculate_period_rate(200, 86_400, 4 * 86_400).unwrap();
+        assert_eq!(rate, SCALE / 200);
+
+        let cash_out = 9_000 * SCALE;
+        assert_eq!(calculate_compounded(cash_out, rate, 0), Some(cash_out));
+        assert_eq!(calculate_compounded(cash_out, rate, 1), Some(9_045 * SCALE));
+        // Compounding beats the 9,180 flat repurchase
+        assert_eq!(
+            calculate_compounded(cash_out, rate, 4),
+            Some(91_813_545_000)
+        );
+        assert_eq!(calculate_period_rate(200, 86_400, 0), None);
+    }
+
     #[test]
     fn test_calculate_repurchase() {
         let cash_out = 9_000 * 10_000_000; // 9,000
@@ -82,4 +566,230 @@ mod tests {
         // Expected: 10,000 × 1.0 × 50% = 5,000
         assert_eq!(max_cash, 5_000 * 10_000_000);
     }
+
+    #[test]
+    fn test_calculate_collateral_value() {
+        let collateral_par = 10_000 * 10_000_000; // 10,000 PAR
+        let mark_price = 99 * 10_000_000 / 100; // 0.99
+
+        let value = calculate_collateral_value(collateral_par, mark_price).unwrap();
+
+        // Expected: 10,000 × 0.99 = 9,900
+        assert_eq!(value, 9_900 * 10_000_000);
+    }
+
+    #[test]
+    fn test_calculate_weighted_spread() {
+        // 1,000 @ 200 bps + 3,000 @ 100 bps → (200,000 + 300,000) / 4,000 = 125 bps
+        let weighted = 1_000 * 200 + 3_000 * 100;
+        assert_eq!(calculate_weighted_spread(weighted, 4_000), 125);
+        assert_eq!(calculate_weighted_spread(0, 0), 0);
+    }
+
+    #[test]
+    fn test_resolve_fee_tier() {
+        let env = Env::default();
+        let tiers = vec![
+            &env,
+            FeeTier { min_volume: 1_000_000 * 10_000_000, discount_bps: 25 },
+            FeeTier { min_volume: 10_000_000 * 10_000_000, discount_bps: 50 },
+        ];
+
+        assert_eq!(resolve_fee_tier(&tiers, 0), (0, 0));
+        assert_eq!(resolve_fee_tier(&tiers, 2_500_000 * 10_000_000), (1, 25));
+        assert_eq!(resolve_fee_tier(&tiers, 10_000_000 * 10_000_000), (2, 50));
+    }
+
+    #[test]
+    fn test_validate_fee_tiers() {
+        let env = Env::default();
+        let ascending = vec![
+            &env,
+            FeeTier { min_volume: 100, discount_bps: 25 },
+            FeeTier { min_volume: 200, discount_bps: 50 },
+        ];
+        let unsorted = vec![
+            &env,
+            FeeTier { min_volume: 200, discount_bps: 25 },
+            FeeTier { min_volume: 100, discount_bps: 50 },
+        ];
+
+        assert!(validate_fee_tiers(&ascending));
+        assert!(!validate_fee_tiers(&unsorted));
+    }
+
+    #[test]
+    fn test_funding_rolling_windows() {
+        let env = Env::default();
+        let mut outflows: Map<u64, i128> = Map::new(&env);
+        outflows.set(100, 1_000 * SCALE); // 3 days ago
+        outflows.set(149, 500 * SCALE); // just outside the daily window
+        outflows.set(172, 200 * SCALE);
+        outflows.set(173, 300 * SCALE);
+
+        assert_eq!(rolling_window_total(&outflows, 173, 24), 500 * SCALE);
+        assert_eq!(rolling_window_total(&outflows, 173, 7 * 24), 2_000 * SCALE);
+        // Window start saturates near bucket zero
+        assert_eq!(rolling_window_total(&outflows, 120, 7 * 24), 1_000 * SCALE);
+
+        let limit = 1_000 * SCALE;
+        assert!(!exceeds_funding_limit(500 * SCALE, 500 * SCALE, limit));
+        assert!(exceeds_funding_limit(500 * SCALE, 501 * SCALE, limit));
+        assert!(!exceeds_funding_limit(i128::MAX, 1, 0));
+
+        let limits = |daily_limit, weekly_limit| FundingLimits {
+            daily_limit,
+            weekly_limit,
+        };
+        assert!(validate_funding_limits(&limits(100, 500)));
+        assert!(validate_funding_limits(&limits(100, 0)));
+        assert!(!validate_funding_limits(&limits(500, 100)));
+        assert!(!validate_funding_limits(&limits(-1, 0)));
+    }
+
+    #[test]
+    fn test_max_cash_in_six_decimal_asset() {
+        // 10,000 PAR at 0.99 with a 3% haircut → 9,603 USDC at 6 decimals
+        let max_cash = calculate_max_cash(10_000 * SCALE, 99 * SCALE / 100, 300).unwrap();
+        assert_eq!(to_asset_units(max_cash, 6), Some(9_603_000_000));
+        assert_eq!(from_asset_units(9_603_000_000, 6), Some(max_cash));
+        assert_eq!(to_asset_units(max_cash, SCALE_DECIMALS), Some(max_cash));
+    }
+
+    #[test]
+    fn test_discounted_spread_floors_at_zero() {
+        assert_eq!(calculate_discounted_spread(200, 50), 150);
+        assert_eq!(calculate_discounted_spread(200, 500), 0);
+    }
+
+    #[test]
+    fn test_partial_collateral_rounds_up() {
+        let collateral = 5_000 * SCALE;
+        assert_eq!(calculate_partial_collateral(collateral, 3, 3), Some(collateral));
+        assert_eq!(calculate_partial_collateral(collateral, 2, 3), Some(33_333_333_334));
+        assert_eq!(calculate_partial_collateral(collateral, 0, 3), Some(0));
+        assert_eq!(calculate_partial_collateral(collateral, 1, 0), None);
+    }
+
+    #[test]
+    fn test_default_split() {
+        let env = Env::default();
+        let share = |share_bps| DefaultShare {
+            recipient: Address::generate(&env),
+            share_bps,
+        };
+
+        let shares = vec![&env, share(6_000), share(2_500), share(1_500)];
+        assert!(validate_default_split(&shares));
+        assert_eq!(
+            calculate_default_split(1_000 * SCALE, &shares),
+            Some(vec![&env, 600 * SCALE, 250 * SCALE, 150 * SCALE])
+        );
+
+        // Rounding dust lands on the first share
+        let thirds = vec![&env, share(3_334), share(3_333), share(3_333)];
+        assert!(validate_default_split(&thirds));
+        assert_eq!(
+            calculate_default_split(100, &thirds),
+            Some(vec![&env, 34, 33, 33])
+        );
+
+        let short = vec![&env, share(5_000), share(4_999)];
+        let negative = vec![&env, share(10_001), share(-1)];
+        assert!(!validate_default_split(&short));
+        assert!(!validate_default_split(&negative));
+        assert!(!validate_default_split(&Vec::new(&env)));
+    }
+
+    #[test]
+    fn test_eligibility_first_match_wins() {
+        let env = Env::default();
+        let issuer = Address::generate(&env);
+        let other = Address::generate(&env);
+        let six_months = 182 * 24 * 3600;
+
+        // No collateral from `issuer` beyond 6 months; small series at 10%
+        let long_dated = EligibilityRule {
+            issuer: Some(issuer.clone()),
+            min_remaining_tenor: six_months + 1,
+            max_remaining_tenor: 0,
+            min_series_par: 0,
+            max_series_par: 0,
+            eligible: false,
+            haircut_bps: 0,
+        };
+        let small = EligibilityRule {
+            issuer: None,
+            min_remaining_tenor: 0,
+            max_remaining_tenor: 0,
+            min_series_par: 0,
+            max_series_par: 100_000 * SCALE,
+            eligible: true,
+            haircut_bps: 1_000,
+        };
+        let rules = vec![&env, long_dated.clone(), small.clone()];
+        assert!(validate_eligibility_rules(&rules));
+
+        let matched = |issuer: &Address, tenor: u64, par: i128| {
+            match_eligibility_rule(&rules, issuer, tenor, par).map(|(index, _)| index)
+        };
+        assert_eq!(matched(&issuer, six_months + 1, 50_000 * SCALE), Some(0));
+        assert_eq!(matched(&issuer, six_months, 50_000 * SCALE), Some(1));
+        assert_eq!(matched(&other, six_months + 1, 50_000 * SCALE), Some(1));
+        assert_eq!(matched(&other, six_months, 500_000 * SCALE), None);
+
+        let mut inverted = small.clone();
+        inverted.min_series_par = 200_000 * SCALE;
+        let mut full_haircut = small;
+        full_haircut.haircut_bps = BASIS_POINTS + 1;
+        assert!(!validate_eligibility_rules(&vec![&env, inverted]));
+        assert!(!validate_eligibility_rules(&vec![&env, full_haircut]));
+    }
+
+    #[test]
+    fn test_releasable_collateral_schedules() {
+        let original = 10_000 * SCALE;
+        let repurchase = 9_180 * SCALE;
+        let open_mark = 98 * SCALE / 100;
+        let releasable = |schedule, locked, repaid, mark| {
+            calculate_releasable_collateral(
+                schedule,
+                locked,
+                original - locked,
+                repurchase,
+                repaid,
+                open_mark,
+                mark,
+            )
+            .unwrap()
+        };
+
+        // Half repaid frees half the collateral, once
+        let proportional = ReleaseSchedule::Proportional;
+        assert_eq!(releasable(proportional, original, 0, open_mark), 0);
+        assert_eq!(
+            releasable(proportional, original, repurchase / 2, open_mark),
+            5_000 * SCALE
+        );
+        assert_eq!(
+            releasable(proportional, 5_000 * SCALE, repurchase / 2, open_mark),
+            0
+        );
+        assert_eq!(
+            releasable(proportional, original, repurchase, open_mark),
+            original
+        );
+
+        // At the opening mark constant LTV matches proportional; accretion frees more
+        let constant_ltv = ReleaseSchedule::ConstantLtv;
+        assert_eq!(
+            releasable(constant_ltv, original, repurchase / 2, open_mark),
+            5_000 * SCALE
+        );
+        assert_eq!(
+            releasable(constant_ltv, original, repurchase / 2, SCALE),
+            5_100 * SCALE
+        );
+        assert_eq!(releasable(constant_ltv, original, 0, SCALE), 200 * SCALE);
+    }
 }
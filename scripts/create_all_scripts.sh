@@ -234,6 +234,7 @@ if [ -z "$COLLATERAL" ] || [ -z "$CASH_REQUEST" ] || [ -z "$DEADLINE" ]; then
 fi
 
 REPO_ID=$(jq -r '.contracts.repo_market' "$DEPLOYMENT_FILE")
+VAULT_ID=$(jq -r '.contracts.bingo_vault' "$DEPLOYMENT_FILE")
 RPC_URL=$(jq -r '.rpc_url' "$DEPLOYMENT_FILE")
 NETWORK=$(jq -r '.network' "$DEPLOYMENT_FILE")
 
@@ -261,6 +262,7 @@ POSITION_ID=$(soroban contract invoke \
   --rpc-url "$RPC_URL" \
   --network-passphrase "$NETWORK_PASSPHRASE" \
   -- open_repo \
+  --vault "$VAULT_ID" \
   --series_id "$SERIES_ID" \
   --collateral_par "$COLLATERAL" \
   --desired_cash_out "$CASH_REQUEST" \
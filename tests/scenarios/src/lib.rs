@@ -0,0 +1,340 @@
+#![cfg(not(target_family = "wasm"))]
+
+//! Composable lifecycle scenarios for cross-contract tests
+//!
+//! `Scenario::new()` deploys and wires the stablecoin, bt_bill_token,
+//! bingo_vault, repo_market and the bingo_invariants monitor natively. Steps borrow the scenario and
+//! return it, so a test reads as one chain:
+//!
+//! ```ignore
+//! let s = Scenario::new();
+//! let user = s.funded_user();
+//! s.create_series(1, ISSUE_DATE + 1_000, 98 * SCALE / 100)
+//!     .subscribe(&user, 1, 980 * SCALE)
+//!     .expect_event(&s.vault_id, VAULT, "subscribed")
+//!     .advance_time(1_000)
+//!     .redeem(&user, 1, 1_000 * SCALE);
+//! ```
+//!
+//! Steps panic on failure; use `expect_error` with the matching `try_`
+//! client call for negative paths, and `expect_state` to compare the
+//! protocol against a golden snapshot.
+
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token::{self, StellarAssetClient},
+    Address, Env, InvokeError, Symbol, TryFromVal,
+};
+
+pub use bbill_index::{BBillIndex, BBillIndexClient, Error as IndexError};
+pub use bingo_events::{INDEX, LADDER, REPO, TOKEN, VAULT};
+pub use bingo_invariants::{
+    BingoInvariants, BingoInvariantsClient, Error as InvariantsError, InvariantKind,
+};
+pub use bingo_vault::{ActivityKind, BingoVault, BingoVaultClient, Error as VaultError, RiskFlag};
+pub use bt_bill_token::{BTBillToken, BTBillTokenClient, Error as TokenError};
+pub use ladder_fund::{Error as LadderError, LadderFund, LadderFundClient, LadderRung};
+pub use repo_market::{
+    AnnotationKind, AuctionStatus, DefaultShare, EligibilityRule, Error as RepoError, MarkSource,
+    RepoMarket, RepoMarketClient, RepoStatus, ValuationKind,
+};
+
+pub const SCALE: i128 = 10_000_000;
+pub const PAR_UNIT: i128 = SCALE;
+
+/// Ledger timestamp every scenario starts at
+pub const ISSUE_DATE: u64 = 1_000;
+/// Default repo haircut and spread, in basis points
+pub const HAIRCUT_BPS: i128 = 300;
+pub const SPREAD_BPS: i128 = 200;
+
+/// Stablecoin minted to each funded user and to the treasury
+pub const USER_FUNDING: i128 = 1_000_000 * SCALE;
+pub const TREASURY_FUNDING: i128 = 10_000_000 * SCALE;
+
+/// Protocol state compared by `expect_state`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoldenState {
+    /// Stablecoin held by the vault
+    pub vault_balance: i128,
+    /// Stablecoin held by the treasury
+    pub treasury_balance: i128,
+    /// PAR minted / redeemed in the tracked series
+    pub minted_par: i128,
+    pub redeemed_par: i128,
+    /// Open repo positions across the market
+    pub open_positions: u32,
+    /// Vault reconciliation delta (0 = accounting matches balance)
+    pub reconcile_delta: i128,
+}
+
+pub struct Scenario {
+    pub env: Env,
+    pub admin: Address,
+    pub treasury: Address,
+    pub stablecoin_id: Address,
+    pub bt_bill_id: Address,
+    pub vault_id: Address,
+    pub repo_id: Address,
+    pub invariants_id: Address,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scenario {
+    /// Deploy and wire every contract, with the ledger at ISSUE_DATE
+    pub fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths_allowing_non_root_auth();
+        env.ledger().set_timestamp(ISSUE_DATE);
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+
+        let stablecoin_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        StellarAssetClient::new(&env, &stablecoin_id).mint(&treasury, &TREASURY_FUNDING);
+
+        let bt_bill_id = env.register(BTBillToken, ());
+        let vault_id = env.register(BingoVault, ());
+        let repo_id = env.register(RepoMarket, ());
+        let invariants_id = env.register(BingoInvariants, ());
+
+        let bt_bill = BTBillTokenClient::new(&env, &bt_bill_id);
+        bt_bill.initialize(&admin);
+        bt_bill.add_operator(&vault_id);
+        bt_bill.add_operator(&repo_id);
+
+        BingoVaultClient::new(&env, &vault_id).initialize(
+            &admin,
+            &treasury,
+            &stablecoin_id,
+            &bt_bill_id,
+        );
+        RepoMarketClient::new(&env, &repo_id).initialize(
+            &admin,
+            &treasury,
+            &vault_id,
+            &bt_bill_id,
+            &stablecoin_id,
+            &HAIRCUT_BPS,
+            &SPREAD_BPS,
+        );
+        BingoInvariantsClient::new(&env, &invariants_id).initialize(
+            &admin,
+            &vault_id,
+            &bt_bill_id,
+            &repo_id,
+            &stablecoin_id,
+        );
+
+        Scenario {
+            env,
+            admin,
+            treasury,
+            stablecoin_id,
+            bt_bill_id,
+            vault_id,
+            repo_id,
+            invariants_id,
+        }
+    }
+
+    pub fn vault(&self) -> BingoVaultClient<'_> {
+        BingoVaultClient::new(&self.env, &self.vault_id)
+    }
+
+    pub fn repo(&self) -> RepoMarketClient<'_> {
+        RepoMarketClient::new(&self.env, &self.repo_id)
+    }
+
+    pub fn bt_bill(&self) -> BTBillTokenClient<'_> {
+        BTBillTokenClient::new(&self.env, &self.bt_bill_id)
+    }
+
+    pub fn invariants(&self) -> BingoInvariantsClient<'_> {
+        BingoInvariantsClient::new(&self.env, &self.invariants_id)
+    }
+
+    pub fn stablecoin(&self) -> token::Client<'_> {
+        token::Client::new(&self.env, &self.stablecoin_id)
+    }
+
+    /// Generate a user holding USER_FUNDING of stablecoin
+    pub fn funded_user(&self) -> Address {
+        let user = Address::generate(&self.env);
+        StellarAssetClient::new(&self.env, &self.stablecoin_id).mint(&user, &USER_FUNDING);
+        user
+    }
+
+    // ============================================
+    // STEPS
+    // ============================================
+
+    /// Set the ledger timestamp
+    pub fn at(&self, timestamp: u64) -> &Self {
+        self.env.ledger().set_timestamp(timestamp);
+        self
+    }
+
+    /// Move the ledger timestamp forward
+    pub fn advance_time(&self, secs: u64) -> &Self {
+        let now = self.env.ledger().timestamp();
+        self.at(now + secs)
+    }
+
+    /// Create and activate a series issued now, with the default caps
+    pub fn create_series(&self, series_id: u32, maturity_date: u64, issue_price: i128) -> &Self {
+        self.create_capped_series(
+            series_id,
+            maturity_date,
+            issue_price,
+            1_000_000 * SCALE,
+            100_000 * SCALE,
+        )
+    }
+
+    /// Create and activate a series issued now, with explicit caps
+    pub fn create_capped_series(
+        &self,
+        series_id: u32,
+        maturity_date: u64,
+        issue_price: i128,
+        cap_par: i128,
+        user_cap_par: i128,
+    ) -> &Self {
+        let vault = self.vault();
+        vault.create_series(
+            &series_id,
+            &self.env.ledger().timestamp(),
+            &maturity_date,
+            &issue_price,
+            &cap_par,
+            &user_cap_par,
+            &None,
+        );
+        vault.activate_series(&series_id);
+        self
+    }
+
+    pub fn subscribe(&self, user: &Address, series_id: u32, pay_amount: i128) -> &Self {
+        self.vault().subscribe(user, &series_id, &pay_amount, &None);
+        self
+    }
+
+    pub fn redeem(&self, user: &Address, series_id: u32, bt_bill_amount: i128) -> &Self {
+        self.vault()
+            .redeem(user, &series_id, &bt_bill_amount, &None);
+        self
+    }
+
+    /// Treasury tops up the vault ahead of redemptions
+    pub fn deposit_settlement(&self, amount: i128) -> &Self {
+        self.vault().deposit_settlement(&self.treasury, &amount);
+        self
+    }
+
+    pub fn open_repo(
+        &self,
+        borrower: &Address,
+        series_id: u32,
+        collateral_par: i128,
+        cash_out: i128,
+        deadline: u64,
+    ) -> &Self {
+        self.repo().open_repo(
+            borrower,
+            &self.vault_id,
+            &series_id,
+            &collateral_par,
+            &cash_out,
+            &deadline,
+        );
+        self
+    }
+
+    pub fn close_repo(&self, position_id: u64) -> &Self {
+        self.repo().close_repo(&position_id);
+        self
+    }
+
+    pub fn claim_default(&self, position_id: u64) -> &Self {
+        self.repo().claim_default(&position_id);
+        self
+    }
+
+    // ============================================
+    // EXPECTATIONS
+    // ============================================
+
+    /// Assert the most recent step emitted `("bingo", namespace, name, ..)` from `contract`
+    pub fn expect_event(&self, contract: &Address, namespace: Symbol, name: &str) -> &Self {
+        let name = Symbol::new(&self.env, name);
+        let found = self.env.events().all().iter().any(|(source, topics, _)| {
+            let topic = |i: u32| {
+                topics
+                    .get(i)
+                    .and_then(|val| Symbol::try_from_val(&self.env, &val).ok())
+            };
+            source == *contract
+                && topic(1) == Some(namespace.clone())
+                && topic(2) == Some(name.clone())
+        });
+        assert!(found, "expected event {:?} was not emitted", name);
+        self
+    }
+
+    /// Assert a `try_` client call failed with `expected`
+    pub fn expect_error<T, E>(
+        &self,
+        result: Result<T, Result<E, InvokeError>>,
+        expected: E,
+    ) -> &Self
+    where
+        E: core::fmt::Debug + PartialEq,
+    {
+        match result {
+            Err(Ok(error)) => assert_eq!(error, expected),
+            Err(Err(invoke_error)) => panic!("expected {:?}, got {:?}", expected, invoke_error),
+            Ok(_) => panic!("expected {:?}, call succeeded", expected),
+        }
+        self
+    }
+
+    /// Capture the golden-state fields for `series_id`
+    pub fn snapshot(&self, series_id: u32) -> GoldenState {
+        let vault = self.vault();
+        let series = vault.get_series(&series_id);
+
+        GoldenState {
+            vault_balance: self.stablecoin().balance(&self.vault_id),
+            treasury_balance: self.stablecoin().balance(&self.treasury),
+            minted_par: series.minted_par,
+            redeemed_par: series.redeemed_par,
+            open_positions: self.repo().get_market_stats().open_positions,
+            reconcile_delta: vault.reconcile().delta,
+        }
+    }
+
+    /// Assert the protocol matches a golden snapshot
+    pub fn expect_state(&self, series_id: u32, expected: &GoldenState) -> &Self {
+        assert_eq!(&self.snapshot(series_id), expected);
+        self
+    }
+
+    /// Assert the invariants monitor reports no violations
+    pub fn expect_invariants_hold(&self) -> &Self {
+        let report = self.invariants().check();
+        assert!(
+            report.healthy,
+            "invariant violations: {:?}",
+            report.violations
+        );
+        self
+    }
+}
@@ -0,0 +1,1668 @@
+use bingo_scenarios::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _, MockAuth, MockAuthInvoke},
+    token::StellarAssetClient,
+    vec, Address, BytesN, IntoVal,
+};
+
+const MATURITY_DATE: u64 = 2_000;
+const ISSUE_PRICE: i128 = 98 * SCALE / 100; // 0.98
+
+#[test]
+fn test_full_series_lifecycle() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .expect_event(&s.vault_id, VAULT, "series_activated")
+        .subscribe(&user, 1, 980 * SCALE)
+        .expect_event(&s.vault_id, VAULT, "subscribed")
+        .at(MATURITY_DATE)
+        .deposit_settlement(20 * SCALE)
+        .expect_event(&s.vault_id, VAULT, "settlement_deposited")
+        .redeem(&user, 1, 1_000 * SCALE)
+        .expect_event(&s.vault_id, VAULT, "redeemed");
+
+    assert_eq!(s.bt_bill().balance_of(&1, &user), 0);
+    assert_eq!(
+        s.stablecoin().balance(&user),
+        USER_FUNDING - 980 * SCALE + 1_000 * SCALE
+    );
+}
+
+#[test]
+fn test_subscribe_respects_series_cap() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+
+    s.create_capped_series(1, MATURITY_DATE, ISSUE_PRICE, 1_000 * SCALE, 1_000 * SCALE)
+        .subscribe(&user, 1, 490 * SCALE)
+        .expect_error(
+            s.vault().try_subscribe(&user, &1, &(500 * SCALE), &None),
+            VaultError::ExceedsSeriesCap,
+        )
+        .subscribe(&user, 1, 490 * SCALE) // exactly cap_par
+        .expect_error(
+            s.vault().try_subscribe(&user, &1, &(100 * SCALE), &None),
+            VaultError::SeriesFullySubscribed,
+        );
+}
+
+#[test]
+fn test_subscribe_respects_user_cap() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+
+    s.create_capped_series(
+        1,
+        MATURITY_DATE,
+        ISSUE_PRICE,
+        100_000 * SCALE,
+        1_000 * SCALE,
+    )
+    .subscribe(&user, 1, 980 * SCALE)
+    .expect_error(
+        s.vault().try_subscribe(&user, &1, &(100 * SCALE), &None),
+        VaultError::ExceedsUserCap,
+    );
+}
+
+#[test]
+fn test_price_accretion() {
+    let s = Scenario::new();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE);
+
+    assert_eq!(s.vault().current_price(&1), ISSUE_PRICE);
+    s.at(1_500);
+    assert_eq!(
+        s.vault().current_price(&1),
+        ISSUE_PRICE + (PAR_UNIT - ISSUE_PRICE) / 2
+    );
+    s.at(MATURITY_DATE);
+    assert_eq!(s.vault().current_price(&1), PAR_UNIT);
+}
+
+#[test]
+fn test_redeem_only_after_maturity() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&user, 1, 980 * SCALE)
+        .expect_error(
+            s.vault().try_redeem(&user, &1, &(100 * SCALE), &None),
+            VaultError::SeriesNotMatured,
+        )
+        .at(MATURITY_DATE)
+        .redeem(&user, 1, 100 * SCALE);
+}
+
+#[test]
+fn test_cancelled_series_pays_each_subscription_once() {
+    let s = Scenario::new();
+    let alice = s.funded_user();
+    let bob = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&alice, 1, 980 * SCALE)
+        .subscribe(&bob, 1, 980 * SCALE);
+    s.vault().cancel_series(&1);
+
+    assert_eq!(s.vault().claim_refund(&alice, &1), 980 * SCALE);
+    assert_eq!(s.bt_bill().balance_of(&1, &alice), 0);
+    assert_eq!(s.vault().get_user_position(&1, &alice).subscribed_par, 0);
+
+    // bT-Bills moved onto a refunded position buy no second refund, and
+    // the series never opens for PAR redemption
+    s.bt_bill().transfer(&1, &bob, &alice, &(1_000 * SCALE));
+    s.expect_error(
+        s.vault().try_claim_refund(&alice, &1),
+        VaultError::InvalidAmount,
+    )
+    .at(MATURITY_DATE)
+    .deposit_settlement(1_000 * SCALE)
+    .expect_error(
+        s.vault().try_redeem(&alice, &1, &(1_000 * SCALE), &None),
+        VaultError::InvalidStatus,
+    );
+
+    // Returned to the subscriber, they refund exactly once more
+    s.bt_bill().transfer(&1, &alice, &bob, &(1_000 * SCALE));
+    assert_eq!(s.vault().claim_refund(&bob, &1), 980 * SCALE);
+    assert_eq!(s.stablecoin().balance(&alice), USER_FUNDING);
+    assert_eq!(s.stablecoin().balance(&bob), USER_FUNDING);
+    assert_eq!(s.vault().reconcile().delta, 0);
+}
+
+#[test]
+fn test_repo_open_and_close_happy_path() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    s.create_series(1, 3_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_500 * SCALE, 2_500)
+        .expect_event(&s.repo_id, REPO, "repo_opened")
+        .at(2_000)
+        .close_repo(1)
+        .expect_event(&s.repo_id, REPO, "repo_closed");
+
+    assert_eq!(s.repo().get_market_stats().open_positions, 0);
+    assert_eq!(s.bt_bill().balance_of(&1, &borrower), 10_000 * SCALE);
+}
+
+#[test]
+fn test_ops_annotations_anchor_note_hashes() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    let incident = BytesN::from_array(&s.env, &[1; 32]);
+    let memo = BytesN::from_array(&s.env, &[2; 32]);
+
+    s.create_series(1, 3_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_500 * SCALE, 2_500);
+
+    s.repo().annotate(&AnnotationKind::Series, &1, &incident);
+    s.expect_event(&s.repo_id, REPO, "annotated");
+    s.repo().annotate(&AnnotationKind::Series, &1, &memo);
+    s.repo().annotate(&AnnotationKind::Position, &1, &memo);
+
+    let notes = s.repo().get_annotations(&AnnotationKind::Series, &1);
+    assert_eq!(notes.len(), 2);
+    assert_eq!(notes.get(0).unwrap().note_hash, incident);
+    assert_eq!(notes.get(1).unwrap().note_hash, memo);
+    assert_eq!(
+        s.repo().get_annotations(&AnnotationKind::Position, &1).len(),
+        1
+    );
+
+    s.expect_error(
+        s.repo().try_annotate(&AnnotationKind::Series, &2, &memo),
+        RepoError::SeriesNotFound,
+    )
+    .expect_error(
+        s.repo().try_annotate(&AnnotationKind::Position, &2, &memo),
+        RepoError::PositionNotFound,
+    );
+}
+
+#[test]
+fn test_repo_installments_release_collateral() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    // 4,500 cash at 2% spread → 4,590 owed against 5,000 PAR
+    s.create_series(1, 3_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_500 * SCALE, 2_500);
+    assert_eq!(s.repo().releasable_collateral(&1), 0);
+    s.expect_error(
+        s.repo().try_release_collateral(&1),
+        RepoError::InvalidAmount,
+    )
+    .expect_error(
+        s.repo().try_repay_installment(&1, &(4_590 * SCALE)),
+        RepoError::InvalidAmount,
+    );
+
+    assert_eq!(
+        s.repo().repay_installment(&1, &(2_295 * SCALE)),
+        2_295 * SCALE
+    );
+    s.expect_event(&s.repo_id, REPO, "installment_repaid");
+    assert_eq!(s.repo().releasable_collateral(&1), 2_500 * SCALE);
+    assert_eq!(s.repo().release_collateral(&1), 2_500 * SCALE);
+    s.expect_event(&s.repo_id, REPO, "collateral_released");
+    assert_eq!(s.bt_bill().balance_of(&1, &borrower), 7_500 * SCALE);
+    assert_eq!(
+        s.repo().get_market_stats().total_collateral_locked,
+        2_500 * SCALE
+    );
+
+    let before = s.stablecoin().balance(&borrower);
+    s.close_repo(1);
+    assert_eq!(before - s.stablecoin().balance(&borrower), 2_295 * SCALE);
+    assert_eq!(s.bt_bill().balance_of(&1, &borrower), 10_000 * SCALE);
+    assert_eq!(s.repo().get_market_stats().total_collateral_locked, 0);
+}
+
+#[test]
+fn test_repo_withdraw_excess_collateral() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    // 4,590 owed against 10,000 PAR; 4,700 PAR × 0.98 × 97% = 4,467 would not cover it
+    s.create_series(1, 3_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 10_000 * SCALE, 4_500 * SCALE, 2_500)
+        .expect_error(
+            s.repo()
+                .try_withdraw_excess_collateral(&1, &(5_300 * SCALE)),
+            RepoError::ExceedsMaxCash,
+        )
+        .expect_error(
+            s.repo()
+                .try_withdraw_excess_collateral(&1, &(10_000 * SCALE)),
+            RepoError::InvalidAmount,
+        );
+
+    assert_eq!(
+        s.repo().withdraw_excess_collateral(&1, &(5_000 * SCALE)),
+        5_000 * SCALE
+    );
+    s.expect_event(&s.repo_id, REPO, "excess_collateral_withdrawn");
+    assert_eq!(s.bt_bill().balance_of(&1, &borrower), 5_000 * SCALE);
+    assert_eq!(
+        s.repo().get_market_stats().total_collateral_locked,
+        5_000 * SCALE
+    );
+
+    s.close_repo(1);
+    assert_eq!(s.bt_bill().balance_of(&1, &borrower), 10_000 * SCALE);
+}
+
+#[test]
+fn test_repo_interest_capitalization() {
+    const DAY: u64 = 86_400;
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    s.expect_error(
+        s.repo().try_set_capitalization_interval(&60),
+        RepoError::InvalidCapitalizationInterval,
+    );
+    s.repo().set_capitalization_interval(&DAY);
+    s.expect_event(&s.repo_id, REPO, "capitalization_updated");
+
+    // 2% over a 4-day term → 0.5% compounded daily
+    let deadline = ISSUE_DATE + 4 * DAY;
+    s.create_series(1, ISSUE_DATE + 5 * DAY, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_500 * SCALE, deadline);
+    assert_eq!(s.repo().current_debt(&1), 4_500 * SCALE);
+
+    s.advance_time(DAY);
+    assert_eq!(s.repo().current_debt(&1), 45_225 * SCALE / 10);
+    assert_eq!(
+        s.repo().repay_installment(&1, &(15_225 * SCALE / 10)),
+        3_000 * SCALE
+    );
+    s.expect_event(&s.repo_id, REPO, "interest_capitalized");
+    assert_eq!(
+        s.repo().get_position(&1).repurchase_amount,
+        45_225 * SCALE / 10
+    );
+
+    // Remaining 3,000 compounds for the last 3 days: × 1.0150751
+    s.at(deadline);
+    assert_eq!(s.repo().current_debt(&1), 30_452_253_000);
+    let before = s.stablecoin().balance(&borrower);
+    s.close_repo(1);
+    assert_eq!(before - s.stablecoin().balance(&borrower), 30_452_253_000);
+    s.expect_error(s.repo().try_current_debt(&1), RepoError::InvalidStatus);
+}
+
+#[test]
+fn test_repo_interest_accrues_pro_rata_within_interval() {
+    const DAY: u64 = 86_400;
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    s.repo().set_capitalization_interval(&DAY);
+
+    // Half a day of a 4-day term at 2% → 0.25%; a half-day term owes the full 2%
+    s.create_series(1, ISSUE_DATE + 5 * DAY, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 19_600 * SCALE)
+        .open_repo(
+            &borrower,
+            1,
+            5_000 * SCALE,
+            4_500 * SCALE,
+            ISSUE_DATE + 4 * DAY,
+        )
+        .open_repo(
+            &borrower,
+            1,
+            5_000 * SCALE,
+            4_500 * SCALE,
+            ISSUE_DATE + DAY / 2,
+        )
+        .advance_time(DAY / 2);
+    assert_eq!(s.repo().current_debt(&1), 45_112_500_000);
+    assert_eq!(s.repo().current_debt(&2), 4_590 * SCALE);
+
+    let before = s.stablecoin().balance(&borrower);
+    s.close_repo(1);
+    s.expect_event(&s.repo_id, REPO, "interest_capitalized");
+    assert_eq!(before - s.stablecoin().balance(&borrower), 45_112_500_000);
+    s.close_repo(2);
+    assert_eq!(s.repo().get_position(&2).repurchase_amount, 4_590 * SCALE);
+}
+
+#[test]
+fn test_repo_interest_accrues_partial_tail_period() {
+    const DAY: u64 = 86_400;
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    s.repo().set_capitalization_interval(&DAY);
+
+    // 3,000 over 2.5 days at 0.5% a day: × 1.005² × 1.0025
+    s.create_series(1, ISSUE_DATE + 5 * DAY, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(
+            &borrower,
+            1,
+            5_000 * SCALE,
+            3_000 * SCALE,
+            ISSUE_DATE + 4 * DAY,
+        )
+        .advance_time(2 * DAY + DAY / 2);
+    assert_eq!(s.repo().current_debt(&1), 30_376_501_875);
+
+    let before = s.stablecoin().balance(&borrower);
+    s.close_repo(1);
+    assert_eq!(before - s.stablecoin().balance(&borrower), 30_376_501_875);
+}
+
+#[test]
+fn test_portfolio_margin_credit_line() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .create_series(2, 2 * MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .subscribe(&borrower, 2, 9_800 * SCALE)
+        .expect_error(
+            s.repo().try_margin_draw(&borrower, &(100 * SCALE)),
+            RepoError::MarginAccountNotFound,
+        );
+
+    // 5,000 PAR of each series at 0.98 with a 3% haircut → 4,753 per series
+    s.repo()
+        .margin_deposit(&borrower, &s.vault_id, &1, &(5_000 * SCALE));
+    s.expect_event(&s.repo_id, REPO, "margin_deposited");
+    s.repo()
+        .margin_deposit(&borrower, &s.vault_id, &2, &(5_000 * SCALE));
+    assert_eq!(s.repo().margin_credit_line(&borrower), 9_506 * SCALE);
+
+    assert_eq!(
+        s.repo().margin_draw(&borrower, &(9_000 * SCALE)),
+        9_000 * SCALE
+    );
+    s.expect_event(&s.repo_id, REPO, "margin_drawn")
+        .expect_error(
+            s.repo().try_margin_draw(&borrower, &(600 * SCALE)),
+            RepoError::ExceedsMaxCash,
+        )
+        .expect_error(
+            s.repo()
+                .try_margin_withdraw(&borrower, &2, &(5_000 * SCALE)),
+            RepoError::ExceedsMaxCash,
+        )
+        .expect_invariants_hold();
+
+    assert_eq!(
+        s.repo().margin_repay(&borrower, &(5_000 * SCALE)),
+        4_000 * SCALE
+    );
+    s.expect_event(&s.repo_id, REPO, "margin_repaid");
+    s.repo().margin_withdraw(&borrower, &2, &(5_000 * SCALE));
+    s.expect_event(&s.repo_id, REPO, "margin_withdrawn");
+    assert_eq!(s.bt_bill().balance_of(&2, &borrower), 10_000 * SCALE);
+    assert_eq!(s.repo().get_margin_account(&borrower).drawn, 4_000 * SCALE);
+    s.expect_invariants_hold();
+
+    // Fully unwound accounts are dropped
+    s.repo().margin_repay(&borrower, &(4_000 * SCALE));
+    s.repo().margin_withdraw(&borrower, &1, &(5_000 * SCALE));
+    s.expect_error(
+        s.repo().try_get_margin_account(&borrower),
+        RepoError::MarginAccountNotFound,
+    );
+}
+
+#[test]
+fn test_portfolio_margin_records_valuations() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .create_series(2, 2 * MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .subscribe(&borrower, 2, 9_800 * SCALE);
+
+    s.repo()
+        .margin_deposit(&borrower, &s.vault_id, &1, &(5_000 * SCALE));
+    // Top-up of the same series, then a second series
+    s.repo()
+        .margin_deposit(&borrower, &s.vault_id, &1, &(1_000 * SCALE));
+    s.repo()
+        .margin_deposit(&borrower, &s.vault_id, &2, &(4_000 * SCALE));
+    s.repo().margin_draw(&borrower, &(9_000 * SCALE));
+
+    let mark = 95 * SCALE / 100;
+    s.repo().set_mark_override(&s.vault_id, &2, &mark, &1_500);
+    s.repo().margin_repay(&borrower, &(5_000 * SCALE));
+    s.repo().margin_withdraw(&borrower, &2, &(4_000 * SCALE));
+
+    let history = s.repo().get_margin_valuation_history(&borrower);
+    let kinds: std::vec::Vec<ValuationKind> = history.iter().map(|v| v.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ValuationKind::MarginDeposit,
+            ValuationKind::MarginDeposit,
+            ValuationKind::MarginDeposit,
+            ValuationKind::MarginDraw,
+            ValuationKind::MarginRepay,
+            ValuationKind::MarginWithdrawal,
+        ]
+    );
+
+    let top_up = history.get(1).unwrap();
+    assert_eq!(top_up.marks.len(), 1);
+    assert_eq!(top_up.marks.get(0).unwrap().collateral_par, 6_000 * SCALE);
+
+    let draw = history.get(3).unwrap();
+    assert_eq!(draw.drawn, 9_000 * SCALE);
+    assert_eq!(draw.credit_line, 9_506 * SCALE);
+    assert_eq!(draw.marks.len(), 2);
+    let series_2 = draw.marks.get(1).unwrap();
+    assert_eq!(series_2.series_id, 2);
+    assert_eq!(series_2.mark_price, ISSUE_PRICE);
+    assert_eq!(series_2.source, MarkSource::Model);
+    assert_eq!(series_2.collateral_value, 3_920 * SCALE);
+
+    // The repay is valued at the override in force
+    let repay = history.get(4).unwrap();
+    assert_eq!(repay.timestamp, ISSUE_DATE);
+    assert_eq!(repay.drawn, 4_000 * SCALE);
+    let series_2 = repay.marks.get(1).unwrap();
+    assert_eq!(series_2.mark_price, mark);
+    assert_eq!(series_2.source, MarkSource::Override);
+    assert_eq!(series_2.override_expiry, Some(1_500));
+
+    let withdrawal = history.get(5).unwrap();
+    assert_eq!(withdrawal.marks.len(), 1);
+    assert_eq!(withdrawal.marks.get(0).unwrap().series_id, 1);
+}
+
+#[test]
+fn test_term_auction_clears_at_uniform_rate() {
+    let s = Scenario::new();
+    let (alice, bob, carol) = (s.funded_user(), s.funded_user(), s.funded_user());
+
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&alice, 1, 9_800 * SCALE)
+        .subscribe(&bob, 1, 9_800 * SCALE)
+        .subscribe(&carol, 1, 9_800 * SCALE);
+
+    // Five 1,000 buckets, bidding until 1,200, repos due at 1,700
+    let auction_id = s
+        .repo()
+        .open_auction(&s.vault_id, &(1_000 * SCALE), &5, &500, &1_200);
+    s.expect_event(&s.repo_id, REPO, "auction_opened")
+        .expect_error(
+            s.repo()
+                .try_submit_bid(&alice, &auction_id, &1, &(5_000 * SCALE), &3, &0),
+            RepoError::InvalidAmount,
+        );
+
+    s.repo()
+        .submit_bid(&alice, &auction_id, &1, &(5_000 * SCALE), &3, &300);
+    s.expect_event(&s.repo_id, REPO, "bid_submitted");
+    s.repo()
+        .submit_bid(&bob, &auction_id, &1, &(5_000 * SCALE), &3, &250);
+    s.repo()
+        .submit_bid(&carol, &auction_id, &1, &(2_000 * SCALE), &1, &150);
+    assert_eq!(s.bt_bill().balance_of(&1, &bob), 5_000 * SCALE);
+    s.expect_error(
+        s.repo().try_clear_auction(&auction_id),
+        RepoError::AuctionNotEnded,
+    );
+
+    s.at(1_200).expect_error(
+        s.repo()
+            .try_submit_bid(&carol, &auction_id, &1, &(2_000 * SCALE), &1, &400),
+        RepoError::AuctionClosed,
+    );
+
+    // Alice fills 3 buckets, Bob the last 2 of his 3, Carol is priced out
+    assert_eq!(s.repo().clear_auction(&auction_id), 250);
+    s.expect_event(&s.repo_id, REPO, "auction_cleared")
+        .expect_invariants_hold();
+
+    let alice_repo = s.repo().get_position(&1);
+    assert_eq!(alice_repo.spread_bps, 250);
+    assert_eq!(alice_repo.cash_out, 3_000 * SCALE);
+    assert_eq!(alice_repo.repurchase_amount, 3_075 * SCALE);
+    assert_eq!(alice_repo.deadline, 1_700);
+
+    let bob_repo = s.repo().get_position(&2);
+    assert_eq!(bob_repo.cash_out, 2_000 * SCALE);
+    assert_eq!(bob_repo.collateral_par, 33_333_333_334);
+    assert_eq!(
+        s.bt_bill().balance_of(&1, &bob),
+        10_000 * SCALE - 33_333_333_334
+    );
+    assert_eq!(s.bt_bill().balance_of(&1, &carol), 10_000 * SCALE);
+
+    let auction = s.repo().get_auction(&auction_id);
+    assert_eq!(auction.status, AuctionStatus::Cleared);
+    assert_eq!(auction.buckets_filled, 5);
+    // The clearing rate prices only the auction's own repos
+    assert_eq!(s.repo().get_spread(), SPREAD_BPS);
+    s.expect_error(
+        s.repo().try_clear_auction(&auction_id),
+        RepoError::AuctionClosed,
+    );
+}
+
+#[test]
+fn test_term_auction_revalidates_bids_at_clearing() {
+    let s = Scenario::new();
+    let (alice, bob) = (s.funded_user(), s.funded_user());
+
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&alice, 1, 9_800 * SCALE)
+        .subscribe(&bob, 1, 9_800 * SCALE);
+
+    let auction_id = s
+        .repo()
+        .open_auction(&s.vault_id, &(1_000 * SCALE), &3, &500, &1_200);
+
+    // Alice's bid only just clears the LTV limit at the bid-time mark
+    s.repo()
+        .submit_bid(&alice, &auction_id, &1, &(3_200 * SCALE), &3, &300);
+    s.repo()
+        .submit_bid(&bob, &auction_id, &1, &(5_000 * SCALE), &3, &250);
+
+    // The mark falls before clearing, so Alice is refunded and Bob takes her buckets
+    s.at(1_200)
+        .repo()
+        .set_mark_override(&s.vault_id, &1, &(90 * SCALE / 100), &1_500);
+    assert_eq!(s.repo().clear_auction(&auction_id), 250);
+    s.expect_event(&s.repo_id, REPO, "auction_cleared")
+        .expect_invariants_hold();
+
+    assert_eq!(s.bt_bill().balance_of(&1, &alice), 10_000 * SCALE);
+    let bob_repo = s.repo().get_position(&1);
+    assert_eq!(bob_repo.borrower, bob);
+    assert_eq!(bob_repo.cash_out, 3_000 * SCALE);
+    assert_eq!(bob_repo.spread_bps, 250);
+    assert_eq!(s.repo().get_auction(&auction_id).buckets_filled, 3);
+}
+
+#[test]
+fn test_term_auction_lapses_and_refunds_escrow() {
+    let s = Scenario::new();
+    let (alice, bob) = (s.funded_user(), s.funded_user());
+    let lapses_at = 1_200 + 3 * 86_400;
+
+    s.create_series(1, 1_000_000, ISSUE_PRICE)
+        .subscribe(&alice, 1, 9_800 * SCALE);
+
+    let auction_id = s
+        .repo()
+        .open_auction(&s.vault_id, &(1_000 * SCALE), &3, &500, &1_200);
+    s.repo()
+        .submit_bid(&alice, &auction_id, &1, &(5_000 * SCALE), &3, &300);
+    s.repo()
+        .submit_bid(&alice, &auction_id, &1, &(2_000 * SCALE), &1, &200);
+    assert_eq!(s.bt_bill().balance_of(&1, &alice), 3_000 * SCALE);
+
+    s.at(lapses_at - 1).expect_error(
+        s.repo().try_reclaim_bids(&alice, &auction_id),
+        RepoError::ClearingWindowOpen,
+    );
+
+    // Never cleared: the auction lapses and Alice recovers all her escrow
+    s.at(lapses_at).expect_error(
+        s.repo().try_clear_auction(&auction_id),
+        RepoError::AuctionClosed,
+    );
+    s.expect_error(
+        s.repo().try_reclaim_bids(&bob, &auction_id),
+        RepoError::BidNotFound,
+    );
+    assert_eq!(s.repo().reclaim_bids(&alice, &auction_id), 2);
+    s.expect_event(&s.repo_id, REPO, "bid_refunded");
+    assert_eq!(s.bt_bill().balance_of(&1, &alice), 10_000 * SCALE);
+    assert_eq!(s.repo().get_auction_bids(&auction_id).len(), 0);
+    s.expect_error(
+        s.repo().try_reclaim_bids(&alice, &auction_id),
+        RepoError::BidNotFound,
+    );
+}
+
+#[test]
+fn test_blocklist_freezes_vault_and_repo_flows() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    let peer = s.funded_user();
+
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .subscribe(&peer, 1, 980 * SCALE);
+    s.bt_bill().set_transfer_hook(&1, &s.vault_id, &true);
+
+    s.vault().add_to_blocklist(&borrower);
+    s.expect_event(&s.vault_id, VAULT, "blocklist_updated")
+        .expect_error(
+            s.vault()
+                .try_subscribe(&borrower, &1, &(980 * SCALE), &None),
+            VaultError::Unauthorized,
+        )
+        .expect_error(
+            s.repo().try_open_repo(
+                &borrower,
+                &s.vault_id,
+                &1,
+                &(5_000 * SCALE),
+                &(4_500 * SCALE),
+                &1_500,
+            ),
+            RepoError::AddressBlocked,
+        )
+        .expect_error(
+            s.repo()
+                .try_margin_deposit(&borrower, &s.vault_id, &1, &(5_000 * SCALE)),
+            RepoError::AddressBlocked,
+        )
+        .expect_error(
+            s.bt_bill()
+                .try_transfer(&1, &borrower, &peer, &(1_000 * SCALE)),
+            TokenError::TransferRejected,
+        )
+        .expect_error(
+            s.bt_bill()
+                .try_transfer(&1, &peer, &borrower, &(1_000 * SCALE)),
+            TokenError::TransferRejected,
+        );
+    assert_eq!(s.bt_bill().balance_of(&1, &borrower), 10_000 * SCALE);
+    assert_eq!(s.bt_bill().balance_of(&1, &peer), 1_000 * SCALE);
+
+    s.vault().remove_from_blocklist(&borrower);
+    s.bt_bill().transfer(&1, &peer, &borrower, &(1_000 * SCALE));
+    assert!(!s.vault().is_blocked(&borrower));
+    s.open_repo(&borrower, 1, 5_000 * SCALE, 4_500 * SCALE, 1_500)
+        .expect_event(&s.repo_id, REPO, "repo_opened");
+}
+
+#[test]
+fn test_repo_default_path() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_500 * SCALE, 2_000)
+        .expect_error(s.repo().try_claim_default(&1), RepoError::DeadlineNotPassed)
+        .at(2_001)
+        .claim_default(1)
+        .expect_event(&s.repo_id, REPO, "repo_defaulted");
+
+    assert_eq!(s.repo().get_market_stats().default_count, 1);
+}
+
+#[test]
+fn test_repo_default_receipt_keeps_installments() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_500 * SCALE, 2_000);
+    s.repo().repay_installment(&1, &(2_295 * SCALE));
+    s.at(2_001).claim_default(1);
+
+    let receipt = s.repo().get_receipt(&1);
+    assert_eq!(receipt.outcome, RepoStatus::Defaulted);
+    assert_eq!(receipt.repayment, 2_295 * SCALE);
+}
+
+#[test]
+fn test_repo_default_redeems_matured_collateral() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    s.repo().set_maturity_blackout(&1_000);
+
+    // Deadline on the maturity edge; opens inside the blackout are refused
+    s.create_series(1, 3_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_500 * SCALE, 3_000)
+        .at(2_500)
+        .expect_error(
+            s.repo().try_open_repo(
+                &borrower,
+                &s.vault_id,
+                &1,
+                &(1_000 * SCALE),
+                &(900 * SCALE),
+                &3_000,
+            ),
+            RepoError::MaturityBlackout,
+        );
+
+    // Past maturity the lender's share comes back as PAR, not bT-Bills
+    let before = s.stablecoin().balance(&s.treasury);
+    s.at(3_001).claim_default(1);
+    assert_eq!(s.stablecoin().balance(&s.treasury) - before, 5_000 * SCALE);
+    assert_eq!(s.bt_bill().balance_of(&1, &s.treasury), 0);
+    assert_eq!(s.vault().get_series(&1).redeemed_par, 5_000 * SCALE);
+}
+
+#[test]
+fn test_repo_default_split_across_beneficiaries() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    let insurance_fund = Address::generate(&s.env);
+    let holder_pool = Address::generate(&s.env);
+
+    let share = |recipient: &Address, share_bps| DefaultShare {
+        recipient: recipient.clone(),
+        share_bps,
+    };
+    s.repo().set_default_split(&vec![
+        &s.env,
+        share(&s.treasury, 6_000),
+        share(&insurance_fund, 2_500),
+        share(&holder_pool, 1_500),
+    ]);
+
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_500 * SCALE, 2_000)
+        .at(2_001)
+        .claim_default(1)
+        .expect_event(&s.repo_id, REPO, "repo_defaulted");
+
+    let bt_bill = s.bt_bill();
+    assert_eq!(bt_bill.balance_of(&1, &s.treasury), 3_000 * SCALE);
+    assert_eq!(bt_bill.balance_of(&1, &insurance_fund), 1_250 * SCALE);
+    assert_eq!(bt_bill.balance_of(&1, &holder_pool), 750 * SCALE);
+}
+
+#[test]
+fn test_repo_respects_haircut() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    // 10,000 PAR × 0.98 × (1 - 3%) = 9,506 max cash
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 19_600 * SCALE)
+        .expect_error(
+            s.repo().try_open_repo(
+                &borrower,
+                &s.vault_id,
+                &1,
+                &(10_000 * SCALE),
+                &(9_507 * SCALE),
+                &3_000,
+            ),
+            RepoError::ExceedsMaxCash,
+        )
+        .open_repo(&borrower, 1, 10_000 * SCALE, 9_506 * SCALE, 3_000);
+}
+
+#[test]
+fn test_repo_eligibility_matrix() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 19_600 * SCALE);
+
+    // Nothing from the treasury's series beyond 3,000s; everything else at 10%
+    let any_series = EligibilityRule {
+        issuer: None,
+        min_remaining_tenor: 0,
+        max_remaining_tenor: 0,
+        min_series_par: 0,
+        max_series_par: 0,
+        eligible: true,
+        haircut_bps: 1_000,
+    };
+    let long_dated = EligibilityRule {
+        issuer: Some(s.treasury.clone()),
+        min_remaining_tenor: 3_000,
+        eligible: false,
+        ..any_series.clone()
+    };
+    s.repo()
+        .set_eligibility_rules(&vec![&s.env, long_dated, any_series]);
+    s.expect_event(&s.repo_id, REPO, "eligibility_rules_updated")
+        .expect_error(
+            s.repo().try_open_repo(
+                &borrower,
+                &s.vault_id,
+                &1,
+                &(10_000 * SCALE),
+                &(5_000 * SCALE),
+                &4_500,
+            ),
+            RepoError::CollateralIneligible,
+        );
+
+    // 2,999s left: 10,000 PAR × ~0.985 × (1 - 10%) ≈ 8,865 max cash
+    s.advance_time(1_001);
+    let eligibility = s.repo().get_collateral_eligibility(&s.vault_id, &1);
+    assert_eq!(eligibility.rule, Some(1));
+    assert_eq!(eligibility.haircut_bps, 1_000);
+    s.expect_error(
+        s.repo().try_open_repo(
+            &borrower,
+            &s.vault_id,
+            &1,
+            &(10_000 * SCALE),
+            &(9_000 * SCALE),
+            &4_500,
+        ),
+        RepoError::ExceedsMaxCash,
+    )
+    .open_repo(&borrower, 1, 10_000 * SCALE, 8_800 * SCALE, 4_500);
+
+    // Clearing the matrix restores the global haircut
+    s.repo().set_eligibility_rules(&vec![&s.env]);
+    assert_eq!(
+        s.repo()
+            .get_collateral_eligibility(&s.vault_id, &1)
+            .haircut_bps,
+        HAIRCUT_BPS
+    );
+}
+
+#[test]
+fn test_repo_honours_vault_risk_flags() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 19_600 * SCALE);
+
+    // Under review: 10,000 PAR × 0.98 × (1 - 3% - 10%) = 8,526 max cash
+    s.vault()
+        .set_series_risk_flags(&1, &vec![&s.env, RiskFlag::UnderReview]);
+    s.expect_event(&s.vault_id, VAULT, "risk_alert");
+    assert_eq!(
+        s.repo()
+            .get_collateral_eligibility(&s.vault_id, &1)
+            .haircut_bps,
+        HAIRCUT_BPS + 1_000
+    );
+    s.expect_error(
+        s.repo().try_open_repo(
+            &borrower,
+            &s.vault_id,
+            &1,
+            &(10_000 * SCALE),
+            &(9_000 * SCALE),
+            &4_500,
+        ),
+        RepoError::ExceedsMaxCash,
+    )
+    .open_repo(&borrower, 1, 5_000 * SCALE, 4_000 * SCALE, 4_500);
+
+    // An impaired issuer shuts off new borrowing
+    s.vault()
+        .set_series_risk_flags(&1, &vec![&s.env, RiskFlag::ImpairedIssuer]);
+    s.expect_error(
+        s.repo().try_open_repo(
+            &borrower,
+            &s.vault_id,
+            &1,
+            &(5_000 * SCALE),
+            &(1_000 * SCALE),
+            &4_500,
+        ),
+        RepoError::CollateralIneligible,
+    );
+
+    // Clearing the flags restores the global terms
+    s.vault().set_series_risk_flags(&1, &vec![&s.env]);
+    assert!(
+        s.repo()
+            .get_collateral_eligibility(&s.vault_id, &1)
+            .eligible
+    );
+    s.open_repo(&borrower, 1, 5_000 * SCALE, 4_700 * SCALE, 4_500);
+}
+
+#[test]
+fn test_statement_covers_transfers_and_repos() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    let peer = Address::generate(&s.env);
+    s.vault().set_repo_market(&s.repo_id);
+    s.create_series(1, 5_000, ISSUE_PRICE);
+    s.bt_bill().set_transfer_hook(&1, &s.vault_id, &false);
+
+    s.subscribe(&borrower, 1, 9_800 * SCALE);
+    s.bt_bill().transfer(&1, &borrower, &peer, &(1_000 * SCALE));
+    s.open_repo(&borrower, 1, 5_000 * SCALE, 4_000 * SCALE, 4_500)
+        .close_repo(1);
+
+    let statement = s.vault().get_statement(&borrower, &0, &u64::MAX, &0, &20);
+    let kinds: Vec<ActivityKind> = statement.iter().map(|line| line.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ActivityKind::Subscription,
+            ActivityKind::TransferOut,
+            ActivityKind::TransferOut,
+            ActivityKind::RepoOpened,
+            ActivityKind::TransferIn,
+            ActivityKind::RepoClosed,
+        ]
+    );
+    let transfer = statement.get(1).unwrap();
+    assert_eq!(transfer.counterparty, Some(peer.clone()));
+    assert_eq!(transfer.par_amount, 1_000 * SCALE);
+    let opened = statement.get(3).unwrap();
+    assert_eq!(opened.reference, 1);
+    assert_eq!(opened.par_amount, 5_000 * SCALE);
+    assert_eq!(opened.cash_amount, 4_000 * SCALE);
+    assert_eq!(opened.counterparty, Some(s.repo_id.clone()));
+
+    // The receiving side gets the mirror line
+    let received = s.vault().get_statement(&peer, &0, &u64::MAX, &0, &20);
+    assert_eq!(received.len(), 1);
+    assert_eq!(received.get(0).unwrap().kind, ActivityKind::TransferIn);
+    assert_eq!(received.get(0).unwrap().counterparty, Some(borrower));
+}
+
+#[test]
+fn test_repo_books_vault_accounting() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    s.vault().set_repo_market(&s.repo_id);
+
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_000 * SCALE, 4_500)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 3_000 * SCALE, 4_500);
+    assert_eq!(
+        s.vault().get_protocol_accounting().total_lent,
+        7_000 * SCALE
+    );
+
+    s.close_repo(1);
+    let interest = s.repo().get_position(&1).repurchase_amount - 4_000 * SCALE;
+    assert!(interest > 0);
+    let accounting = s.vault().get_protocol_accounting();
+    assert_eq!(accounting.total_lent, 3_000 * SCALE);
+    assert_eq!(accounting.total_repo_revenue, interest);
+
+    s.at(4_501).claim_default(2);
+    let accounting = s.vault().get_protocol_accounting();
+    assert_eq!(accounting.total_lent, 0);
+    assert_eq!(accounting.total_defaults, 1);
+    assert_eq!(s.vault().reconcile().delta, 0);
+}
+
+#[test]
+fn test_repo_installments_update_tvl_and_utilization() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    s.vault().set_repo_market(&s.repo_id);
+
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_000 * SCALE, 4_500)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 3_000 * SCALE, 4_500);
+    let held = s.stablecoin().balance(&s.vault_id);
+    assert_eq!(s.vault().tvl(), held + 7_000 * SCALE);
+    assert_eq!(
+        s.vault().utilization(),
+        7_000 * SCALE * 10_000 / (held + 7_000 * SCALE)
+    );
+
+    // Installments pay principal first, then interest
+    assert_eq!(s.repo().get_position(&1).repurchase_amount, 4_080 * SCALE);
+    s.repo().repay_installment(&1, &(1_000 * SCALE));
+    assert_eq!(
+        s.vault().get_protocol_accounting().total_lent,
+        6_000 * SCALE
+    );
+    s.repo().repay_installment(&1, &(3_050 * SCALE));
+    let accounting = s.vault().get_protocol_accounting();
+    assert_eq!(accounting.total_lent, 3_000 * SCALE);
+    assert_eq!(accounting.total_repo_revenue, 50 * SCALE);
+
+    s.close_repo(1);
+    let accounting = s.vault().get_protocol_accounting();
+    assert_eq!(accounting.total_lent, 3_000 * SCALE);
+    assert_eq!(accounting.total_repo_revenue, 80 * SCALE);
+
+    // A default writes off only the principal still outstanding
+    s.repo().repay_installment(&2, &(1_000 * SCALE));
+    assert_eq!(
+        s.vault().get_protocol_accounting().total_lent,
+        2_000 * SCALE
+    );
+    s.at(4_501).claim_default(2);
+    let accounting = s.vault().get_protocol_accounting();
+    assert_eq!(accounting.total_lent, 0);
+    assert_eq!(accounting.total_defaults, 1);
+    assert_eq!(s.vault().tvl(), held);
+    assert_eq!(s.vault().utilization(), 0);
+}
+
+#[test]
+fn test_repo_quote_freezes_terms() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    // Quoted at 0.98: 10,000 PAR × 0.98 × (1 - 3%) = 9,506 max cash
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 19_600 * SCALE);
+    let quote_id = s.repo().lock_quote(
+        &borrower,
+        &s.vault_id,
+        &1,
+        &(10_000 * SCALE),
+        &(9_506 * SCALE),
+        &3_000,
+    );
+    s.expect_event(&s.repo_id, REPO, "quote_locked");
+    let quote = s.repo().get_quote(&quote_id);
+
+    // Accretion moves the live mark, but the quote executes at its own
+    s.advance_time(1_000);
+    let position_id = s.repo().open_repo_with_quote(&quote_id);
+    s.expect_event(&s.repo_id, REPO, "repo_opened");
+    let position = s.repo().get_position(&position_id);
+    assert_eq!(position.cash_out, 9_506 * SCALE);
+    assert_eq!(position.repurchase_amount, quote.repurchase_amount);
+    let opening = s.repo().get_valuation_history(&position_id).get(0).unwrap();
+    assert_eq!(opening.mark_price, quote.mark_price);
+    s.expect_error(
+        s.repo().try_open_repo_with_quote(&quote_id),
+        RepoError::QuoteNotFound,
+    );
+
+    // Stale quotes cannot be executed
+    let stale_id = s.repo().lock_quote(
+        &borrower,
+        &s.vault_id,
+        &1,
+        &(5_000 * SCALE),
+        &(4_000 * SCALE),
+        &3_000,
+    );
+    let stale = s.repo().get_quote(&stale_id);
+    s.env
+        .ledger()
+        .set_sequence_number(stale.expires_at_ledger + 1);
+    s.expect_error(
+        s.repo().try_open_repo_with_quote(&stale_id),
+        RepoError::QuoteExpired,
+    );
+}
+
+/// Deploy a second vault with its own bT-Bill token and register it with the repo market
+fn register_second_vault(s: &Scenario) -> (BingoVaultClient<'_>, BTBillTokenClient<'_>) {
+    let vault = BingoVaultClient::new(&s.env, &s.env.register(BingoVault, ()));
+    let bt_bill = BTBillTokenClient::new(&s.env, &s.env.register(BTBillToken, ()));
+    bt_bill.initialize(&s.admin);
+    bt_bill.add_operator(&vault.address);
+    bt_bill.add_operator(&s.repo_id);
+    vault.initialize(&s.admin, &s.treasury, &s.stablecoin_id, &bt_bill.address);
+    s.repo()
+        .register_vault(&vault.address, &bt_bill.address, &s.stablecoin_id);
+    (vault, bt_bill)
+}
+
+#[test]
+fn test_repo_vault_registry() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    s.expect_error(
+        s.repo()
+            .try_register_vault(&s.vault_id, &s.bt_bill_id, &s.stablecoin_id),
+        RepoError::VaultAlreadyRegistered,
+    )
+    .expect_error(
+        s.repo().try_deregister_vault(&Address::generate(&s.env)),
+        RepoError::VaultNotRegistered,
+    );
+
+    let (vault, _) = register_second_vault(&s);
+    s.expect_event(&s.repo_id, REPO, "vault_registered");
+    assert_eq!(
+        s.repo().get_vaults(),
+        vec![&s.env, s.vault_id.clone(), vault.address.clone()]
+    );
+
+    // The series only exists on the second vault
+    vault.create_series(
+        &1,
+        &ISSUE_DATE,
+        &5_000,
+        &ISSUE_PRICE,
+        &(1_000_000 * SCALE),
+        &(100_000 * SCALE),
+        &None,
+    );
+    vault.activate_series(&1);
+    vault.subscribe(&borrower, &1, &(9_800 * SCALE), &None);
+    s.expect_error(
+        s.repo().try_open_repo(
+            &borrower,
+            &s.vault_id,
+            &1,
+            &(5_000 * SCALE),
+            &(4_000 * SCALE),
+            &3_000,
+        ),
+        RepoError::SeriesNotFound,
+    );
+
+    s.repo().deregister_vault(&vault.address);
+    s.expect_event(&s.repo_id, REPO, "vault_deregistered")
+        .expect_error(
+            s.repo().try_open_repo(
+                &borrower,
+                &vault.address,
+                &1,
+                &(5_000 * SCALE),
+                &(4_000 * SCALE),
+                &3_000,
+            ),
+            RepoError::VaultNotRegistered,
+        );
+    assert_eq!(s.repo().get_vaults(), vec![&s.env, s.vault_id.clone()]);
+}
+
+#[test]
+fn test_repo_opens_against_named_vault() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    let (vault, bt_bill) = register_second_vault(&s);
+
+    // Same series ID on both vaults, priced differently
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 19_600 * SCALE);
+    vault.create_series(
+        &1,
+        &ISSUE_DATE,
+        &5_000,
+        &(95 * SCALE / 100),
+        &(1_000_000 * SCALE),
+        &(100_000 * SCALE),
+        &None,
+    );
+    vault.activate_series(&1);
+    vault.subscribe(&borrower, &1, &(19_000 * SCALE), &None);
+
+    // Second vault marks at 0.95: 10,000 PAR × 0.95 × (1 - 3%) = 9,215 max cash
+    s.expect_error(
+        s.repo().try_open_repo(
+            &borrower,
+            &vault.address,
+            &1,
+            &(10_000 * SCALE),
+            &(9_506 * SCALE),
+            &3_000,
+        ),
+        RepoError::ExceedsMaxCash,
+    );
+    let position_id = s.repo().open_repo(
+        &borrower,
+        &vault.address,
+        &1,
+        &(10_000 * SCALE),
+        &(9_215 * SCALE),
+        &3_000,
+    );
+    let position = s.repo().get_position(&position_id);
+    assert_eq!(position.vault, vault.address);
+
+    // Collateral comes from the second vault's token only
+    assert_eq!(bt_bill.balance_of(&1, &borrower), 10_000 * SCALE);
+    assert_eq!(s.bt_bill().balance_of(&1, &borrower), 20_000 * SCALE);
+
+    s.close_repo(position_id);
+    assert_eq!(bt_bill.balance_of(&1, &borrower), 20_000 * SCALE);
+}
+
+#[test]
+fn test_mark_override_revalues_until_expiry() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+    let (vault, _) = register_second_vault(&s);
+    let mark = 90 * SCALE / 100;
+
+    s.create_series(1, 5_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 19_600 * SCALE)
+        .expect_error(
+            s.repo()
+                .try_set_mark_override(&Address::generate(&s.env), &1, &mark, &1_500),
+            RepoError::VaultNotRegistered,
+        );
+    s.repo().set_mark_override(&s.vault_id, &1, &mark, &1_500);
+    s.expect_event(&s.repo_id, REPO, "mark_override_set");
+    assert_eq!(
+        s.repo().get_mark_override(&s.vault_id, &1).unwrap().price,
+        mark
+    );
+    // The same series ID on another vault keeps its model mark
+    assert_eq!(s.repo().get_mark_override(&vault.address, &1), None);
+
+    // Marked at 0.90: 10,000 PAR × 0.90 × (1 - 3%) = 8,730 max cash
+    s.expect_error(
+        s.repo().try_open_repo(
+            &borrower,
+            &s.vault_id,
+            &1,
+            &(10_000 * SCALE),
+            &(9_000 * SCALE),
+            &3_000,
+        ),
+        RepoError::ExceedsMaxCash,
+    );
+    let position_id = s.repo().open_repo(
+        &borrower,
+        &s.vault_id,
+        &1,
+        &(10_000 * SCALE),
+        &(8_730 * SCALE),
+        &3_000,
+    );
+    let opening = s.repo().get_valuation_history(&position_id).get(0).unwrap();
+    assert_eq!(opening.mark_price, mark);
+    assert_eq!(opening.source, MarkSource::Override);
+    assert_eq!(opening.override_expiry, Some(1_500));
+
+    // Past expiry the position is revalued at the vault's model price
+    s.at(1_500);
+    assert_eq!(s.repo().get_mark_override(&s.vault_id, &1), None);
+    s.close_repo(position_id);
+    let closing = s.repo().get_valuation_history(&position_id).get(1).unwrap();
+    assert_eq!(closing.kind, ValuationKind::Close);
+    assert_eq!(closing.mark_price, s.vault().current_price(&1));
+    assert_eq!(closing.source, MarkSource::Model);
+    assert_eq!(closing.override_expiry, None);
+
+    s.repo().set_mark_override(&s.vault_id, &1, &mark, &2_000);
+    s.repo().clear_mark_override(&s.vault_id, &1);
+    s.expect_event(&s.repo_id, REPO, "mark_override_cleared");
+    assert_eq!(s.repo().get_mark_override(&s.vault_id, &1), None);
+}
+
+/// Deploy a ladder fund wired to the scenario's vault
+fn ladder_fund(s: &Scenario) -> LadderFundClient<'_> {
+    let ladder = LadderFundClient::new(&s.env, &s.env.register(LadderFund, ()));
+    ladder.initialize(&s.admin, &s.vault_id, &s.bt_bill_id, &s.stablecoin_id);
+    ladder
+}
+
+fn rung(series_id: u32, weight_bps: i128) -> LadderRung {
+    LadderRung {
+        series_id,
+        weight_bps,
+    }
+}
+
+#[test]
+fn test_ladder_fund_deposit_nav_and_withdraw() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .create_series(2, 3_000, ISSUE_PRICE);
+    let ladder = ladder_fund(&s);
+
+    s.expect_error(
+        ladder.try_deposit(&user, &(1_960 * SCALE)),
+        LadderError::LadderNotSet,
+    );
+    ladder.set_ladder(&vec![&s.env, rung(1, 5_000), rung(2, 5_000)]);
+
+    // Each half buys 1,000 PAR at 0.98; shares start at 1.0
+    assert_eq!(ladder.deposit(&user, &(1_960 * SCALE)), 1_960 * SCALE);
+    assert_eq!(s.bt_bill().balance_of(&1, &ladder.address), 1_000 * SCALE);
+    assert_eq!(s.bt_bill().balance_of(&2, &ladder.address), 1_000 * SCALE);
+    assert_eq!(ladder.nav(), 1_960 * SCALE);
+    assert_eq!(ladder.share_price(), SCALE);
+
+    // Withdrawals are paid in kind, pro rata across the rungs
+    assert_eq!(ladder.withdraw(&user, &(980 * SCALE)), 0);
+    assert_eq!(ladder.balance(&user), 980 * SCALE);
+    assert_eq!(s.bt_bill().balance_of(&1, &user), 500 * SCALE);
+    assert_eq!(s.bt_bill().balance_of(&2, &user), 500 * SCALE);
+    assert_eq!(ladder.nav(), 980 * SCALE);
+    s.expect_error(
+        ladder.try_withdraw(&user, &(981 * SCALE)),
+        LadderError::InsufficientBalance,
+    );
+    assert_eq!(s.vault().reconcile().delta, 0);
+}
+
+#[test]
+fn test_ladder_fund_rejects_duplicate_and_held_rungs() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .create_series(2, 3_000, ISSUE_PRICE);
+    let ladder = ladder_fund(&s);
+
+    s.expect_error(
+        ladder.try_set_ladder(&vec![&s.env, rung(1, 5_000), rung(1, 5_000)]),
+        LadderError::InvalidLadder,
+    );
+    ladder.set_ladder(&vec![&s.env, rung(1, 5_000), rung(2, 5_000)]);
+    ladder.deposit(&user, &(1_960 * SCALE));
+
+    // Dropping a funded rung would strand its bT-Bills outside NAV
+    s.expect_error(
+        ladder.try_set_ladder(&vec![&s.env, rung(1, 10_000)]),
+        LadderError::RungNotEmpty,
+    );
+    ladder.set_ladder(&vec![&s.env, rung(1, 2_500), rung(2, 7_500)]);
+    assert_eq!(ladder.nav(), 1_960 * SCALE);
+}
+
+#[test]
+fn test_ladder_fund_rolls_matured_rung() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .create_series(2, 3_000, ISSUE_PRICE);
+    let ladder = ladder_fund(&s);
+    ladder.set_ladder(&vec![&s.env, rung(1, 5_000), rung(2, 5_000)]);
+    ladder.deposit(&user, &(1_960 * SCALE));
+
+    s.at(MATURITY_DATE)
+        .deposit_settlement(40 * SCALE)
+        .create_series(3, 4_000, ISSUE_PRICE);
+    s.expect_error(ladder.try_roll(&1, &2), LadderError::InvalidLadder)
+        .expect_error(ladder.try_roll(&3, &4), LadderError::SeriesNotInLadder);
+
+    // 1,000 PAR redeemed at maturity buys 1,000 / 0.98 PAR of the new series
+    let minted = ladder.roll(&1, &3);
+    assert_eq!(minted, 1_000 * SCALE * SCALE / ISSUE_PRICE);
+    s.expect_event(&ladder.address, LADDER, "fund_rolled");
+    assert_eq!(s.bt_bill().balance_of(&1, &ladder.address), 0);
+    assert_eq!(s.bt_bill().balance_of(&3, &ladder.address), minted);
+    let rungs = ladder.get_ladder();
+    assert_eq!(rungs.get(0).unwrap().series_id, 3);
+    assert_eq!(rungs.get(0).unwrap().weight_bps, 5_000);
+
+    // Series 1 has left the ladder; its value now sits in the new rung
+    s.expect_error(ladder.try_roll(&1, &3), LadderError::SeriesNotInLadder);
+    assert!(ladder.nav() > 1_960 * SCALE);
+    assert_eq!(s.vault().reconcile().delta, 0);
+}
+
+#[test]
+fn test_ladder_fund_deposits_net_of_subscription_fee() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+    let other = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .create_series(2, 3_000, ISSUE_PRICE);
+    let ladder = ladder_fund(&s);
+    ladder.set_ladder(&vec![&s.env, rung(1, 5_000), rung(2, 5_000)]);
+    let nonce = s.vault().get_auth_nonce(&s.admin);
+    s.vault()
+        .set_subscription_fee_bps(&50, &nonce, &(10 * MATURITY_DATE));
+
+    // Only the signing user's transfer is authorized; the fund authorizes the vault's pulls
+    let amount = 1_960 * SCALE;
+    let shares = ladder
+        .mock_auths(&[MockAuth {
+            address: &user,
+            invoke: &MockAuthInvoke {
+                contract: &ladder.address,
+                fn_name: "deposit",
+                args: (&user, amount).into_val(&s.env),
+                sub_invokes: &[MockAuthInvoke {
+                    contract: &s.stablecoin_id,
+                    fn_name: "transfer",
+                    args: (&user, &ladder.address, amount).into_val(&s.env),
+                    sub_invokes: &[],
+                }],
+            },
+        }])
+        .deposit(&user, &amount);
+    s.env.mock_all_auths_allowing_non_root_auth();
+
+    // 0.5% of each slice goes to the collector; shares track what the fund holds
+    assert_eq!(shares, 19_502 * SCALE / 10);
+    assert_eq!(ladder.nav(), shares);
+    assert_eq!(ladder.deposit(&other, &amount), shares);
+    assert_eq!(ladder.share_price(), SCALE);
+    assert_eq!(s.stablecoin().balance(&ladder.address), 0);
+    assert_eq!(s.vault().reconcile().delta, 0);
+}
+
+#[test]
+fn test_ladder_fund_ignores_stablecoin_donations() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE);
+    let ladder = ladder_fund(&s);
+    ladder.set_ladder(&vec![&s.env, rung(1, 10_000)]);
+    ladder.deposit(&user, &(980 * SCALE));
+
+    StellarAssetClient::new(&s.env, &s.stablecoin_id).mint(&ladder.address, &(500 * SCALE));
+    assert_eq!(ladder.nav(), 980 * SCALE);
+    assert_eq!(ladder.withdraw(&user, &(980 * SCALE)), 0);
+}
+
+fn bbill_index(s: &Scenario) -> BBillIndexClient<'_> {
+    let index = BBillIndexClient::new(&s.env, &s.env.register(BBillIndex, ()));
+    index.initialize(&s.admin, &s.vault_id, &s.bt_bill_id, &s.stablecoin_id);
+    index
+}
+
+#[test]
+fn test_bbill_index_deposit_nav_and_withdraw() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&user, 1, 980 * SCALE);
+    let index = bbill_index(&s);
+
+    s.expect_error(index.try_deposit(&user, &1, &0), IndexError::InvalidAmount);
+
+    // 1,000 PAR valued at 0.98; index units start at 1.0
+    assert_eq!(index.deposit(&user, &1, &(1_000 * SCALE)), 980 * SCALE);
+    s.expect_event(&index.address, INDEX, "index_deposit");
+    assert_eq!(s.bt_bill().balance_of(&1, &index.address), 1_000 * SCALE);
+    assert_eq!(index.get_held_series(), vec![&s.env, 1]);
+    assert_eq!(index.nav(), 980 * SCALE);
+    assert_eq!(index.share_price(), SCALE);
+
+    // Withdrawals are paid in kind; there is no idle stablecoin yet
+    assert_eq!(index.withdraw(&user, &(490 * SCALE)), 0);
+    assert_eq!(index.balance(&user), 490 * SCALE);
+    assert_eq!(index.total_supply(), 490 * SCALE);
+    assert_eq!(s.bt_bill().balance_of(&1, &user), 500 * SCALE);
+    assert_eq!(index.nav(), 490 * SCALE);
+    s.expect_error(
+        index.try_withdraw(&user, &(491 * SCALE)),
+        IndexError::InsufficientBalance,
+    );
+    assert_eq!(s.vault().reconcile().delta, 0);
+}
+
+#[test]
+fn test_bbill_index_rolls_matured_series() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+    let other = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&user, 1, 980 * SCALE);
+    let index = bbill_index(&s);
+    index.deposit(&user, &1, &(1_000 * SCALE));
+    s.expect_error(index.try_roll(&2, &1), IndexError::SeriesNotHeld);
+
+    // A filled series takes no new subscriptions, so the index cannot roll into it
+    s.at(MATURITY_DATE)
+        .deposit_settlement(20 * SCALE)
+        .create_series(2, 4_000, ISSUE_PRICE)
+        .create_capped_series(3, 4_000, ISSUE_PRICE, 1_000 * SCALE, 1_000 * SCALE)
+        .subscribe(&other, 3, 980 * SCALE);
+    s.expect_error(index.try_roll(&1, &3), IndexError::SeriesNotActive);
+
+    // 1,000 PAR redeemed at maturity buys 1,000 / 0.98 PAR of the new series
+    let minted = index.roll(&1, &2);
+    assert_eq!(minted, 1_000 * SCALE * SCALE / ISSUE_PRICE);
+    s.expect_event(&index.address, INDEX, "index_rolled");
+    assert_eq!(s.bt_bill().balance_of(&1, &index.address), 0);
+    assert_eq!(s.bt_bill().balance_of(&2, &index.address), minted);
+    assert_eq!(index.get_held_series(), vec![&s.env, 2]);
+    // Revalued at the new issue price, less a stroop of rounding
+    assert_eq!(index.nav(), 1_000 * SCALE - 1);
+    s.expect_error(index.try_roll(&1, &2), IndexError::SeriesNotHeld);
+    assert_eq!(s.vault().reconcile().delta, 0);
+}
+
+/// Roll with only the admin's signature; the index must authorize its own payments
+fn roll_with_admin_auth(s: &Scenario, index: &BBillIndexClient, from: u32, to: u32) -> i128 {
+    let minted = index
+        .mock_auths(&[MockAuth {
+            address: &s.admin,
+            invoke: &MockAuthInvoke {
+                contract: &index.address,
+                fn_name: "roll",
+                args: (from, to).into_val(&s.env),
+                sub_invokes: &[],
+            },
+        }])
+        .roll(&from, &to);
+    s.env.mock_all_auths_allowing_non_root_auth();
+    minted
+}
+
+#[test]
+fn test_bbill_index_rolls_with_fee_under_real_auth() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&user, 1, 980 * SCALE);
+    let index = bbill_index(&s);
+    index.deposit(&user, &1, &(1_000 * SCALE));
+
+    let nonce = s.vault().get_auth_nonce(&s.admin);
+    s.vault()
+        .set_subscription_fee_bps(&50, &nonce, &(10 * MATURITY_DATE));
+    s.at(MATURITY_DATE)
+        .deposit_settlement(20 * SCALE)
+        .create_series(2, 4_000, ISSUE_PRICE);
+    let quote = s.vault().preview_subscribe(&2, &(1_000 * SCALE));
+    assert_eq!(quote.fee, 5 * SCALE);
+
+    // The vault moves the payment and the collector's fee as two transfers
+    let collector_before = s.stablecoin().balance(&s.treasury);
+    assert_eq!(roll_with_admin_auth(&s, &index, 1, 2), quote.minted_par);
+    assert_eq!(
+        s.stablecoin().balance(&s.treasury) - collector_before,
+        5 * SCALE
+    );
+    assert_eq!(s.stablecoin().balance(&index.address), 0);
+    assert_eq!(s.vault().reconcile().delta, 0);
+}
+
+#[test]
+fn test_bbill_index_rolls_under_allowance_pull() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&user, 1, 980 * SCALE);
+    let index = bbill_index(&s);
+    index.deposit(&user, &1, &(1_000 * SCALE));
+
+    s.vault().set_allowance_pull(&true);
+    s.at(MATURITY_DATE)
+        .deposit_settlement(20 * SCALE)
+        .create_series(2, 4_000, ISSUE_PRICE);
+
+    let minted = roll_with_admin_auth(&s, &index, 1, 2);
+    assert_eq!(minted, 1_000 * SCALE * SCALE / ISSUE_PRICE);
+    assert_eq!(s.stablecoin().allowance(&index.address, &s.vault_id), 0);
+}
+
+#[test]
+fn test_bbill_index_ignores_stablecoin_donations() {
+    let s = Scenario::new();
+    let user = s.funded_user();
+    let other = s.funded_user();
+    s.create_series(1, MATURITY_DATE, ISSUE_PRICE)
+        .subscribe(&user, 1, 980 * SCALE)
+        .subscribe(&other, 1, 980 * SCALE);
+    let index = bbill_index(&s);
+    index.deposit(&user, &1, &(1_000 * SCALE));
+
+    // A donation neither lifts the share price nor dilutes the next depositor
+    StellarAssetClient::new(&s.env, &s.stablecoin_id).mint(&index.address, &(500 * SCALE));
+    assert_eq!(index.nav(), 980 * SCALE);
+    assert_eq!(index.share_price(), SCALE);
+    assert_eq!(index.deposit(&other, &1, &(1_000 * SCALE)), 980 * SCALE);
+
+    // Withdrawals pay only booked stablecoin
+    assert_eq!(index.withdraw(&user, &(980 * SCALE)), 0);
+    assert_eq!(s.stablecoin().balance(&index.address), 500 * SCALE);
+}
+
+#[test]
+fn test_invariants_monitor_flags_drift() {
+    let s = Scenario::new();
+    let borrower = s.funded_user();
+
+    s.create_series(1, 3_000, ISSUE_PRICE)
+        .subscribe(&borrower, 1, 9_800 * SCALE)
+        .open_repo(&borrower, 1, 5_000 * SCALE, 4_500 * SCALE, 2_500)
+        .expect_invariants_hold();
+    s.repo().repay_installment(&1, &(2_295 * SCALE));
+    s.repo().release_collateral(&1);
+    s.expect_invariants_hold();
+    assert_eq!(s.invariants().check().series_checked, 1);
+
+    // bT-Bills minted outside the vault break the supply invariant
+    s.bt_bill().mint(&1, &borrower, &SCALE);
+    let report = s.invariants().check_series(&1);
+    assert!(!report.healthy);
+    let violation = report.violations.get(0).unwrap();
+    assert_eq!(violation.kind, InvariantKind::TokenSupply);
+    assert_eq!(violation.expected, 10_000 * SCALE);
+    assert_eq!(violation.actual, 10_001 * SCALE);
+    s.expect_error(
+        s.invariants().try_assert_invariants(),
+        InvariantsError::InvariantViolated,
+    );
+}
+
+#[test]
+fn test_complete_integration_flow() {
+    let s = Scenario::new();
+    let early = s.funded_user();
+    let late = s.funded_user();
+
+    s.create_series(1, 10_000, 95 * SCALE / 100)
+        .subscribe(&early, 1, 9_500 * SCALE) // 10,000 PAR at 0.95
+        .open_repo(&early, 1, 5_000 * SCALE, 4_000 * SCALE, 5_000)
+        .at(6_000);
+
+    let mid_price = s.vault().current_price(&1);
+    assert!(mid_price > 95 * SCALE / 100 && mid_price < PAR_UNIT);
+
+    s.subscribe(&late, 1, 5_000 * SCALE);
+    assert!(s.bt_bill().balance_of(&1, &late) < 10_000 * SCALE);
+
+    // The default lands at maturity, so the seized 5,000 PAR is redeemed
+    let late_par = s.bt_bill().balance_of(&1, &late);
+    s.at(10_000)
+        .redeem(&late, 1, late_par)
+        .claim_default(1)
+        .expect_state(
+            1,
+            &GoldenState {
+                vault_balance: 9_500 * SCALE - late_par,
+                treasury_balance: TREASURY_FUNDING + 1_000 * SCALE,
+                minted_par: 10_000 * SCALE + late_par,
+                redeemed_par: late_par + 5_000 * SCALE,
+                open_positions: 0,
+                reconcile_delta: 0,
+            },
+        );
+}
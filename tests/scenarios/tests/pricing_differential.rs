@@ -0,0 +1,141 @@
+//! Differential pricing: bingo-mvp index model vs bingo_vault price model
+//!
+//! The MVP's `types.rs` and `yield_calc.rs` are compiled straight from
+//! `bingo-mvp/` so the comparison always runs against the current source.
+//! Each case drives the deployed vault through a `Scenario` and replays
+//! the same issuance, subscriptions and redemptions through
+//! `YieldCalculator`, asserting identical prices, minted amounts and
+//! payouts.
+//!
+//! The models are only comparable with `par_value == PAR_UNIT` (the MVP
+//! snaps its index to `SCALE` at maturity regardless of `par_value`) and
+//! from the issue date onwards (the MVP index underflows before issue).
+
+#[allow(dead_code)]
+#[path = "../../../bingo-mvp/contracts/series/src/types.rs"]
+mod types;
+#[allow(dead_code)]
+#[path = "../../../bingo-mvp/contracts/series/src/yield_calc.rs"]
+mod yield_calc;
+
+use bingo_scenarios::*;
+use soroban_sdk::{testutils::Address as _, Address};
+use types::{Series, SeriesStatus};
+use yield_calc::YieldCalculator;
+
+const DAY: u64 = 86_400;
+
+/// Issue prices covered by every case
+const ISSUE_PRICES: [i128; 3] = [95 * SCALE / 100, 98 * SCALE / 100, 9_987_654];
+
+/// Payments made at each subscription checkpoint
+const PAY_AMOUNTS: [i128; 3] = [1, 980 * SCALE, 12_345_678_901];
+
+/// The MVP series equivalent to a vault series issued at ISSUE_DATE
+fn mvp_series(s: &Scenario, issue_price: i128, maturity_date: u64) -> Series {
+    Series {
+        id: 0,
+        par_value: PAR_UNIT,
+        subscription_price: issue_price,
+        issue_time: ISSUE_DATE,
+        maturity_time: maturity_date,
+        max_cap: i128::MAX,
+        per_user_cap: i128::MAX,
+        total_subscribed: 0,
+        status: SeriesStatus::Active,
+        usdc_token: Address::generate(&s.env),
+    }
+}
+
+/// Run one tenor through both models at `checkpoints` (offsets from issue)
+fn assert_models_agree(tenor: u64, checkpoints: &[u64]) {
+    let s = Scenario::new();
+    let maturity_date = ISSUE_DATE + tenor;
+
+    // One holder per (series, payment size), accumulating lots across checkpoints
+    let mut mvp = Vec::new();
+    let mut holdings: Vec<(Address, u32, i128)> = Vec::new();
+    for (i, issue_price) in ISSUE_PRICES.iter().enumerate() {
+        let series_id = i as u32 + 1;
+        s.create_series(series_id, maturity_date, *issue_price);
+        mvp.push((series_id, mvp_series(&s, *issue_price, maturity_date)));
+        for _ in PAY_AMOUNTS {
+            holdings.push((s.funded_user(), series_id, 0));
+        }
+    }
+
+    for offset in checkpoints {
+        s.at(ISSUE_DATE + offset);
+
+        for (series_id, series) in &mvp {
+            let index = YieldCalculator::calculate_index(&s.env, series);
+            let price = s.vault().current_price(series_id);
+            assert_eq!(
+                price, index,
+                "series {} price drift at +{}s",
+                series_id, offset
+            );
+
+            if *offset >= tenor {
+                continue;
+            }
+            let lots = holdings.iter_mut().filter(|(_, id, _)| id == series_id);
+            for ((user, _, held), pay_amount) in lots.zip(PAY_AMOUNTS) {
+                let before = s.bt_bill().balance_of(series_id, user);
+                s.subscribe(user, *series_id, pay_amount);
+
+                let shares = YieldCalculator::calculate_shares(pay_amount, index);
+                let minted = s.bt_bill().balance_of(series_id, user) - before;
+                assert_eq!(
+                    minted, shares,
+                    "series {} mint drift for {} at +{}s",
+                    series_id, pay_amount, offset
+                );
+                assert_eq!(
+                    YieldCalculator::calculate_position_value(shares, index),
+                    s.vault().collateral_value(series_id, &minted),
+                    "series {} valuation drift at +{}s",
+                    series_id,
+                    offset
+                );
+                *held += shares;
+            }
+        }
+    }
+
+    s.at(maturity_date).deposit_settlement(TREASURY_FUNDING / 2);
+    for (user, series_id, shares) in &holdings {
+        let before = s.stablecoin().balance(user);
+        s.redeem(user, *series_id, *shares);
+
+        let payout = s.stablecoin().balance(user) - before;
+        assert_eq!(
+            payout,
+            YieldCalculator::calculate_redemption_value(*shares, PAR_UNIT),
+            "series {} redemption drift",
+            series_id
+        );
+    }
+}
+
+#[test]
+fn test_short_tenor_models_agree() {
+    let tenor = 91 * DAY;
+    assert_models_agree(
+        tenor,
+        &[0, 1, tenor / 3, tenor / 2, tenor - 1, tenor, tenor + DAY],
+    );
+}
+
+#[test]
+fn test_one_year_tenor_models_agree() {
+    let tenor = 365 * DAY;
+    assert_models_agree(tenor, &[0, 7 * DAY, 182 * DAY, 364 * DAY, tenor]);
+}
+
+#[test]
+fn test_odd_tenor_rounding_agrees() {
+    // Prime tenor so accretion never divides evenly
+    let tenor = 1_000_003;
+    assert_models_agree(tenor, &[0, 333_334, 999_999, tenor]);
+}